@@ -0,0 +1,54 @@
+use advent_of_code::year_2019::{IntcodeComputer, IntcodeProgram, IntcodeWord};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+/// Outputs a copy of itself (from 2019 day 9)
+const QUINE: &str = "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99";
+
+/// Outputs a 16 digit number (from 2019 day 9)
+const LARGE_NUMBER: &str = "1102,34915192,34915192,7,4,7,99,0";
+
+/// Reads n and outputs the sum of all primes below n, using trial division by repeated subtraction
+const SUM_OF_PRIMES: &str = "3,78,1101,0,0,81,1101,2,0,79,7,79,78,82,1006,82,75,1101,2,0,80,2,80,80,82,7,79,82,82,1005,82,64,1001,79,0,83,7,83,80,82,1005,82,54,1002,80,-1,82,1,83,82,83,1105,1,36,1006,83,68,1001,80,1,80,1105,1,21,1,81,79,81,1001,79,1,79,1105,1,10,4,81,99,0,0,0,0,0,0";
+
+fn run_program(program: &IntcodeProgram, inputs: &[IntcodeWord]) -> Vec<IntcodeWord> {
+    let mut computer = IntcodeComputer::from(program);
+    let input_tx = computer.create_input();
+    let output_rx = computer.create_output();
+
+    for input in inputs {
+        input_tx.send(*input).unwrap();
+    }
+
+    computer.run().unwrap();
+
+    output_rx.try_iter().collect()
+}
+
+fn bench_programs(c: &mut Criterion) {
+    let quine = IntcodeProgram::from(QUINE);
+    c.bench_function("quine", |b| b.iter(|| run_program(&quine, &[])));
+
+    let large_number = IntcodeProgram::from(LARGE_NUMBER);
+    c.bench_function("large number", |b| {
+        b.iter(|| run_program(&large_number, &[]))
+    });
+
+    let sum_of_primes = IntcodeProgram::from(SUM_OF_PRIMES);
+    c.bench_function("sum of primes below 1000", |b| {
+        b.iter(|| run_program(&sum_of_primes, &[1000]))
+    });
+}
+
+fn bench_load(c: &mut Criterion) {
+    let sum_of_primes = IntcodeProgram::from(SUM_OF_PRIMES);
+    c.bench_function("load program", |b| {
+        b.iter_batched(
+            || IntcodeComputer::from(QUINE),
+            |mut computer| computer.load(&sum_of_primes),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(benches, bench_programs, bench_load);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+use advent_of_code::generate::{self, Scale};
+use advent_of_code::params::Params;
+use advent_of_code::solutions::{self, INPUTS_DIRECTORY, SOLUTIONS};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::path::Path;
+
+/// Benchmarks every solution against its stored input, skipping days which have none
+fn bench_solutions(c: &mut Criterion) {
+    let mut group = c.benchmark_group("solutions");
+    let inputs = Path::new(INPUTS_DIRECTORY);
+    let params = Params::default();
+
+    for solution in SOLUTIONS {
+        let input = match solution.stored_input(inputs) {
+            Some(input) => input,
+            None => {
+                eprintln!(
+                    "Skipping {}, no input at {}",
+                    solution,
+                    solution.input_path(inputs).display()
+                );
+                continue;
+            }
+        };
+
+        let id = format!(
+            "{}/day{}/part{}",
+            solution.year, solution.day, solution.part
+        );
+        group.bench_function(id, |b| b.iter(|| solution.solve(&input, &params)));
+    }
+
+    group.finish();
+}
+
+/// Benchmarks the solutions of every day which can make up inputs against one the size of a real
+/// input, made from the same seed every time so that the timings compare between runs
+fn bench_generated(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generated");
+    let params = Params::default();
+
+    for generator in generate::generators() {
+        let input = generator.generate(Scale::REAL, 1);
+        let selected = solutions::select(SOLUTIONS, generator.year, generator.day, None)
+            .expect("every day with a generator has a solution");
+
+        for solution in selected {
+            let id = format!(
+                "{}/day{}/part{}",
+                solution.year, solution.day, solution.part
+            );
+            group.bench_function(id, |b| b.iter(|| solution.solve(&input, &params)));
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // some solutions take seconds per run, so criterion's default of 100 samples is far too many
+    config = Criterion::default().sample_size(10);
+    targets = bench_solutions, bench_generated
+}
+criterion_main!(benches);
@@ -0,0 +1,57 @@
+//! Collects the title of each day's puzzle from the first line of its source file, such as
+//! `//! --- Day 5: Alchemical Reduction ---`, so that `aoc list` can show them without a table
+//! of titles to keep up to date by hand
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=src");
+
+    let mut titles = Vec::new();
+    for year in fs::read_dir("src").expect("Failed to read src") {
+        let year = year.expect("Failed to read src").path();
+        let Some(year_number) = number_after(&year, "year_") else {
+            continue;
+        };
+
+        for day in fs::read_dir(&year).expect("Failed to read a year's directory") {
+            let day = day.expect("Failed to read a year's directory").path();
+            let Some(day_number) = number_after(&day, "day") else {
+                continue;
+            };
+
+            let source = fs::read_to_string(&day).expect("Failed to read a day's source");
+            let title = source
+                .lines()
+                .next()
+                .and_then(|line| line.strip_prefix("//! --- Day "))
+                .and_then(|line| line.strip_suffix(" ---"))
+                .and_then(|line| line.split_once(": "))
+                .map(|(_, title)| title.to_string());
+            if let Some(title) = title {
+                titles.push((year_number, day_number, title));
+            }
+        }
+    }
+    titles.sort();
+
+    let mut table = String::from("&[\n");
+    for (year, day, title) in titles {
+        table += &format!("    ({}, {}, {:?}),\n", year, day, title);
+    }
+    table += "]\n";
+
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    fs::write(Path::new(&out_dir).join("titles.rs"), table).expect("Failed to write the titles");
+}
+
+/// The number in a file name after a prefix, such as 5 in `day5.rs`
+fn number_after(path: &Path, prefix: &str) -> Option<u16> {
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(prefix)?
+        .parse()
+        .ok()
+}
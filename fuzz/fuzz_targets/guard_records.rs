@@ -0,0 +1,13 @@
+//! Parses the guards' records of 2018 day 4, whose timestamps are matched by `split_timestamp`
+//! and whose records are then sorted into shifts, which should refuse a malformed record with an
+//! error rather than a panic
+
+#![no_main]
+
+use advent_of_code::solutions::Solver;
+use advent_of_code::year_2018::day4::Day4;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|records: &str| {
+    let _ = Day4::parse(records);
+});
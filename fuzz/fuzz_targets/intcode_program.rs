@@ -0,0 +1,16 @@
+//! Parses Intcode programs, which should be refused with an error rather than a panic when they
+//! aren't comma separated integers
+
+#![no_main]
+
+use advent_of_code::year_2019::IntcodeProgram;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|program: &str| {
+    if let Ok(parsed) = IntcodeProgram::parse(program) {
+        // a parsed program is given back by parsing it once it's written out again
+        let serialized = parsed.data_serialized();
+        let reparsed = IntcodeProgram::parse(&serialized).expect("serialized programs parse");
+        assert_eq!(reparsed.data_serialized(), serialized);
+    }
+});
@@ -0,0 +1,14 @@
+//! Parses the input with the parser of every day. Only the parsing is fuzzed, as a part given
+//! an input which parses may still take forever to solve it.
+
+#![no_main]
+
+use advent_of_code::solutions::SOLUTIONS;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    // both parts of a day share its parser
+    for solution in SOLUTIONS.iter().filter(|solution| solution.part == 1) {
+        let _ = (solution.parse)(input);
+    }
+});
@@ -0,0 +1,71 @@
+/*
+ * C bindings for the Advent of Code solutions and the Intcode computer, built with the `ffi`
+ * feature (see the README). Strings returned are freed with aoc_string_free, and Intcode
+ * computers with aoc_intcode_free.
+ */
+
+#ifndef ADVENT_OF_CODE_H
+#define ADVENT_OF_CODE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Solves a part for a UTF-8 puzzle input of input_len bytes. Returns the answer, or NULL with
+ * *error set to why there is none (when error isn't NULL).
+ */
+char *aoc_solve(uint16_t year, uint8_t day, uint8_t part, const uint8_t *input, size_t input_len,
+                char **error);
+
+void aoc_string_free(char *string);
+
+/* An Intcode word, which is a __int128 when built with the `wide-intcode` feature */
+#ifdef AOC_WIDE_INTCODE
+typedef __int128 aoc_intcode_word;
+#else
+typedef int64_t aoc_intcode_word;
+#endif
+
+typedef enum {
+    /* the computer can execute its next instruction */
+    AOC_INTCODE_RUNNING = 0,
+    /* the next instruction is an input instruction, but no input has been pushed */
+    AOC_INTCODE_AWAITING_INPUT = 1,
+    AOC_INTCODE_HALTED = 2,
+    /* an instruction couldn't be executed, which aoc_intcode_error describes */
+    AOC_INTCODE_FAILED = 3,
+} aoc_intcode_status;
+
+typedef struct AocIntcode aoc_intcode;
+
+/* Loads a program from its comma separated text, returning NULL when it isn't a program */
+aoc_intcode *aoc_intcode_new(const uint8_t *program, size_t program_len);
+
+void aoc_intcode_free(aoc_intcode *intcode);
+
+void aoc_intcode_push_input(aoc_intcode *intcode, aoc_intcode_word input);
+
+/*
+ * Executes instructions until the computer halts, fails or awaits input, or max_steps have been
+ * executed, returning what it can do next
+ */
+aoc_intcode_status aoc_intcode_run(aoc_intcode *intcode, uint64_t max_steps);
+
+/* Takes the oldest output which hasn't been taken yet, returning whether there was one */
+bool aoc_intcode_poll_output(aoc_intcode *intcode, aoc_intcode_word *output);
+
+aoc_intcode_word aoc_intcode_peek(const aoc_intcode *intcode, size_t address);
+
+/* Why the computer failed, or NULL when it hasn't */
+char *aoc_intcode_error(const aoc_intcode *intcode);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,123 @@
+//! The answers the solutions give, kept as what they are (a number, some text, letters drawn in a
+//! grid or a position) until they're shown, recorded or entered on the website as text
+
+use crate::common::ocr;
+use std::fmt;
+
+/// The answer to a part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    /// A number, which most answers are
+    Int(i128),
+
+    /// Text, such as the order of some steps
+    String(String),
+
+    /// Letters drawn with pixels, a row to a string, which are read off by eye
+    Grid(Vec<String>),
+
+    /// A position, entered as `x,y`
+    Coordinates(i64, i64),
+}
+
+impl Answer {
+    /// The letters drawn in pixels, such as the image of 2019 day 8, as the text they read as, or
+    /// as the grid itself when they aren't letters the font knows
+    pub fn from_pixels(rows: Vec<String>) -> Self {
+        match ocr::recognize(&rows) {
+            Some(letters) => Self::String(letters),
+            None => Self::Grid(rows),
+        }
+    }
+
+    /// Whether the answer is entered as this text, such as an answer recorded earlier. Letters
+    /// drawn in pixels are the same answer as the text they read as.
+    pub fn is(&self, text: &str) -> bool {
+        if self.to_string() == text {
+            return true;
+        }
+
+        let drawn = match self {
+            Self::Grid(rows) => ocr::recognize(rows).is_some_and(|letters| letters == text),
+            _ => false,
+        };
+        drawn
+            || ocr::recognize(&text.lines().collect::<Vec<_>>())
+                .is_some_and(|letters| self.to_string() == letters)
+    }
+
+    /// Whether the answer takes more than one line to show, and is shown below what it answers
+    /// rather than beside it
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Self::Grid(rows) if rows.len() > 1)
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(int) => write!(f, "{}", int),
+            Self::String(string) => write!(f, "{}", string),
+            Self::Grid(rows) => write!(f, "{}", rows.join("\n")),
+            Self::Coordinates(x, y) => write!(f, "{},{}", x, y),
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(int: $int) -> Self {
+                    Self::Int(int as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(i32, i64, i128, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(string: String) -> Self {
+        Self::String(string)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(string: &str) -> Self {
+        Self::String(string.to_string())
+    }
+}
+
+impl From<(i64, i64)> for Answer {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::Coordinates(x, y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::from(-6_i64).to_string(), "-6");
+        assert_eq!(Answer::from("CABDFE").to_string(), "CABDFE");
+        assert_eq!(Answer::from((243, 17)).to_string(), "243,17");
+
+        let grid = Answer::Grid(vec![" █".to_string(), "█ ".to_string()]);
+        assert!(grid.is(" █\n█ "));
+        assert!(grid.is_multiline());
+        assert!(!Answer::from(10_usize).is_multiline());
+
+        let h: Vec<String> = ["█  █", "█  █", "████", "█  █", "█  █", "█  █"]
+            .iter()
+            .map(|row| row.to_string())
+            .collect();
+        assert_eq!(Answer::from_pixels(h.clone()), Answer::from("H"));
+        assert!(Answer::from("H").is(&h.join("\n")));
+        assert!(Answer::Grid(h).is("H"));
+        assert!(!Answer::from("I").is("#..#\n#..#\n####\n#..#\n#..#\n#..#"));
+    }
+}
@@ -0,0 +1,203 @@
+//! The recorded correct answers, which `aoc verify` checks every solution against.
+//!
+//! Answers are kept in a TOML file with a table per day, keyed by part:
+//!
+//! ```toml
+//! [2018.5]
+//! 1 = "10"
+//! 2 = "4"
+//! ```
+
+use crate::solutions::Solution;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// The answers are recorded in this file (relative to the working directory)
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+const HEADER: &str = "# The correct answers to the puzzles, for the inputs stored in inputs/
+# Checked by `aoc verify`, which adds any missing ones with `--record`
+";
+
+/// The correct answers to each part, by year, day and part
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u16, u8, u8), String>,
+}
+
+impl Answers {
+    /// Reads the answers recorded in a file, which has none when it doesn't exist
+    pub fn load(path: &Path) -> Result<Self, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(answers) => answers.parse(),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    /// The correct answer to a solution's part, if it has been recorded
+    pub fn get(&self, solution: &Solution) -> Option<&str> {
+        self.answers
+            .get(&(solution.year, solution.day, solution.part))
+            .map(String::as_str)
+    }
+
+    /// Records the correct answer to a solution's part, replacing any already recorded
+    pub fn insert(&mut self, solution: &Solution, answer: String) {
+        self.answers
+            .insert((solution.year, solution.day, solution.part), answer);
+    }
+
+    /// Every recorded answer, as the year, day and part it's the answer to, in order
+    pub fn iter(&self) -> impl Iterator<Item = (u16, u8, u8, &str)> {
+        self.answers
+            .iter()
+            .map(|(&(year, day, part), answer)| (year, day, part, answer.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table: toml::Table = s.parse().map_err(AnswersError::Parse)?;
+
+        let mut answers = BTreeMap::new();
+        for (year, days) in &table {
+            let year = parse_key(year)?;
+            for (day, parts) in as_table(days, year)? {
+                let day = parse_key(day)?;
+                for (part, answer) in as_table(parts, format!("{}.{}", year, day))? {
+                    let part = parse_key(part)?;
+                    let answer = match answer {
+                        toml::Value::String(answer) => answer.clone(),
+                        toml::Value::Integer(answer) => answer.to_string(),
+                        _ => {
+                            return Err(AnswersError::Invalid(format!(
+                                "{}.{}.{} should be a string or an integer",
+                                year, day, part
+                            )))
+                        }
+                    };
+                    answers.insert((year, day, part), answer);
+                }
+            }
+        }
+
+        Ok(Self { answers })
+    }
+}
+
+fn parse_key<T: std::str::FromStr>(key: &str) -> Result<T, AnswersError> {
+    key.parse()
+        .map_err(|_| AnswersError::Invalid(format!("{} is not a number", key)))
+}
+
+fn as_table(value: &toml::Value, key: impl fmt::Display) -> Result<&toml::Table, AnswersError> {
+    value
+        .as_table()
+        .ok_or_else(|| AnswersError::Invalid(format!("{} should be a table", key)))
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", HEADER)?;
+
+        let mut previous_day = None;
+        for (&(year, day, part), answer) in &self.answers {
+            if previous_day != Some((year, day)) {
+                write!(f, "\n[{}.{}]\n", year, day)?;
+                previous_day = Some((year, day));
+            }
+            writeln!(f, "{} = {}", part, toml::Value::String(answer.clone()))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum AnswersError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+
+    /// The file is valid TOML, but isn't laid out as a table per day
+    Invalid(String),
+}
+
+impl fmt::Display for AnswersError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read the answers: {}", error),
+            Self::Parse(error) => write!(f, "Failed to parse the answers: {}", error),
+            Self::Invalid(error) => write!(f, "Invalid answers: {}", error),
+        }
+    }
+}
+
+impl Error for AnswersError {}
+
+impl From<io::Error> for AnswersError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "year-2018", feature = "year-2019"))]
+    fn test_answers() {
+        use crate::solutions::{self, SOLUTIONS};
+
+        let answers: Answers = "[2018.5]\n1 = \"10\"\n2 = 4\n\n[2019.8]\n2 = \"#..\\n.#.\"\n"
+            .parse()
+            .unwrap();
+        assert_eq!(answers.len(), 3);
+
+        let day5 = solutions::select(SOLUTIONS, 2018, 5, None).unwrap();
+        assert_eq!(answers.get(day5[0]), Some("10"));
+        assert_eq!(answers.get(day5[1]), Some("4"));
+
+        let day8 = solutions::select(SOLUTIONS, 2019, 8, None).unwrap();
+        assert_eq!(answers.get(day8[0]), None);
+        assert_eq!(answers.get(day8[1]), Some("#..\n.#."));
+
+        // round trips through its own format
+        assert_eq!(answers.to_string().parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    fn test_invalid_answers() {
+        assert!(matches!(
+            "[2018.5]\n1 = [10]\n".parse::<Answers>(),
+            Err(AnswersError::Invalid(_))
+        ));
+        assert!(matches!(
+            "[2018]\nfive = \"10\"\n".parse::<Answers>(),
+            Err(AnswersError::Invalid(_))
+        ));
+        assert!(matches!(
+            "[2018.5\n".parse::<Answers>(),
+            Err(AnswersError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,182 @@
+//! Runs a day against every puzzle input in a directory, such as inputs collected from friends to
+//! find the edge cases a solution misses
+
+use crate::report::{self, Outcome, Unsolved};
+use advent_of_code::params::Params;
+use advent_of_code::solutions::Solution;
+use anstream::println;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::time::Duration;
+
+/// The results of the parts of a day for a single input
+pub struct Row {
+    /// The file name of the input
+    pub input: String,
+    pub results: Vec<Result<Outcome, Unsolved>>,
+}
+
+/// Solves each of the selected parts with every file in `dir`, in order of their names. A part
+/// which fails on an input, or even panics, doesn't stop the rest.
+pub fn run_batch(
+    selected: &[&'static Solution],
+    dir: &Path,
+    params: &Params,
+    timeout: Option<Duration>,
+) -> io::Result<Vec<Row>> {
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+
+    let mut rows = Vec::new();
+    for path in paths {
+        let input = fs::read_to_string(&path)?;
+        let results = selected
+            .iter()
+            .map(|solution| {
+                panic::catch_unwind(AssertUnwindSafe(|| {
+                    crate::solve(solution, &input, params, false, timeout)
+                }))
+                .unwrap_or(Err(Unsolved::Panicked))
+            })
+            .collect();
+
+        rows.push(Row {
+            input: path
+                .file_name()
+                .map_or_else(String::new, |name| name.to_string_lossy().into_owned()),
+            results,
+        });
+    }
+
+    Ok(rows)
+}
+
+/// Prints a row for each input, with the answer to each part and how long they took altogether.
+/// Answers which span several lines are cut to their first line.
+pub fn print_batch(selected: &[&Solution], rows: &[Row]) {
+    let answers: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| row.results.iter().map(first_line).collect())
+        .collect();
+
+    let input_width = rows
+        .iter()
+        .map(|row| row.input.chars().count())
+        .chain(Some("input".len()))
+        .max()
+        .unwrap_or(0);
+    let answer_widths: Vec<usize> = (0..selected.len())
+        .map(|i| {
+            answers
+                .iter()
+                .map(|answers| answers[i].chars().count())
+                .chain(Some("part 1".len()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let mut header = format!("{:<input_width$}", "input", input_width = input_width);
+    for (solution, width) in selected.iter().zip(&answer_widths) {
+        header += &format!(
+            "  {:<width$}",
+            format!("part {}", solution.part),
+            width = width
+        );
+    }
+    report::print_header(&format!("{}  {:>10}", header, "time"));
+
+    for (row, answers) in rows.iter().zip(&answers) {
+        let mut line = format!("{:<input_width$}", row.input, input_width = input_width);
+        for (answer, width) in answers.iter().zip(&answer_widths) {
+            line += &format!("  {:<width$}", answer, width = width);
+        }
+        let time: Duration = row
+            .results
+            .iter()
+            .filter_map(|result| Some(result.as_ref().ok()?.duration))
+            .sum();
+        println!("{}  {:>10}", line, report::format_duration(time));
+    }
+}
+
+/// The first line of an answer, marking that there's more to answers which span several lines,
+/// or why there's no answer
+fn first_line(result: &Result<Outcome, Unsolved>) -> String {
+    let outcome = match result {
+        Ok(outcome) => outcome,
+        Err(unsolved) => return format!("({})", unsolved),
+    };
+    let answer = outcome.answer.to_string();
+    let mut lines = answer.lines();
+    let first = lines.next().unwrap_or_default().to_string();
+
+    match lines.next() {
+        Some(_) => first + " …",
+        None => first,
+    }
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "year-2018", feature = "year-2019"))]
+mod tests {
+    use super::*;
+    use advent_of_code::solutions::{self, SOLUTIONS};
+    use advent_of_code::Error;
+
+    #[test]
+    fn test_run_batch() {
+        let dir = std::env::temp_dir().join(format!("batch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("b.txt"), "+1\n-1\n").unwrap();
+        fs::write(dir.join("a.txt"), "+3\n+3\n+4\n-2\n-4\n").unwrap();
+        fs::write(dir.join("c.txt"), "not a frequency change\n").unwrap();
+
+        let selected = solutions::select(SOLUTIONS, 2018, 1, None).unwrap();
+        let not_a_change = Unsolved::Failed(Error::line(
+            0,
+            "not a frequency change",
+            "isn't a frequency change like +7 or -2",
+        ));
+        let rows = run_batch(&selected, &dir, &Params::default(), None);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let answers: Vec<(String, Vec<Result<String, Unsolved>>)> = rows
+            .unwrap()
+            .into_iter()
+            .map(|row| {
+                let answers = row
+                    .results
+                    .into_iter()
+                    .map(|result| result.map(|outcome| outcome.answer.to_string()))
+                    .collect();
+                (row.input, answers)
+            })
+            .collect();
+        assert_eq!(
+            answers,
+            vec![
+                (
+                    "a.txt".to_string(),
+                    vec![Ok("4".to_string()), Ok("10".to_string())]
+                ),
+                (
+                    "b.txt".to_string(),
+                    vec![Ok("0".to_string()), Ok("0".to_string())]
+                ),
+                (
+                    "c.txt".to_string(),
+                    vec![Err(not_a_change.clone()), Err(not_a_change)]
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,393 @@
+//! Times solutions over several runs, and keeps a history of how long each takes, as rows appended
+//! to a CSV file, so that performance can be charted across commits, and solutions which have
+//! become slower since their last recorded timing are caught
+
+use crate::report::{self, Outcome, Unsolved};
+use advent_of_code::calendar;
+use advent_of_code::params::Params;
+use advent_of_code::solutions::Solution;
+use anstream::println;
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+const HEADER: &str = "timestamp,commit,year,day,part,nanoseconds,peak_bytes";
+
+/// Solutions which took less than this last time are too quick for their timings to be compared,
+/// as a little noise is a large part of them
+const MIN_COMPARED: Duration = Duration::from_millis(1);
+
+/// The timings of several runs of a part
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timings {
+    /// The answer of the first run, with the median of the runs as its duration
+    pub outcome: Outcome,
+    /// How long each run took, from the fastest to the slowest
+    pub durations: Vec<Duration>,
+}
+
+impl Timings {
+    /// The timings of runs which took `durations`, the first of which gave `outcome`
+    pub fn new(outcome: Outcome, mut durations: Vec<Duration>) -> Self {
+        durations.sort_unstable();
+        let mut timings = Self { outcome, durations };
+        timings.outcome.duration = timings.median();
+
+        timings
+    }
+
+    pub fn min(&self) -> Duration {
+        self.durations[0]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.durations[self.durations.len() - 1]
+    }
+
+    pub fn median(&self) -> Duration {
+        let middle = self.durations.len() / 2;
+        if self.durations.len().is_multiple_of(2) {
+            (self.durations[middle - 1] + self.durations[middle]) / 2
+        } else {
+            self.durations[middle]
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.durations.iter().sum::<Duration>() / self.durations.len() as u32
+    }
+
+    /// The sample standard deviation, which is 0 for a single run
+    pub fn std_dev(&self) -> Duration {
+        if self.durations.len() < 2 {
+            return Duration::default();
+        }
+
+        let mean = self.mean().as_secs_f64();
+        let variance = self
+            .durations
+            .iter()
+            .map(|duration| (duration.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / (self.durations.len() - 1) as f64;
+        Duration::from_secs_f64(variance.sqrt())
+    }
+}
+
+/// Solves a part `warmup` times without timing it, so that caches and the branch predictor are
+/// warm, then `runs` times (at least once) timing each. The memory is measured on the first timed
+/// run.
+pub fn time(
+    solution: &Solution,
+    input: &str,
+    warmup: usize,
+    runs: usize,
+) -> Result<Timings, Unsolved> {
+    for _ in 0..warmup {
+        let (answer, _) = solution.solve_timed(input, &Params::default());
+        answer.map_err(Unsolved::Failed)?;
+    }
+
+    let source = crate::Source::Input(input.into());
+    let outcome = crate::solve_measured(solution, &source, &Params::default(), true)?;
+    let mut durations = vec![outcome.duration];
+    durations.extend((1..runs).map(|_| solution.solve_timed(input, &Params::default()).1));
+
+    Ok(Timings::new(outcome, durations))
+}
+
+/// Prints a table of the spread of each part's timings, or why it wasn't timed
+pub fn print_timings(results: &[(&Solution, Result<Timings, Unsolved>)]) {
+    report::print_header(&format!(
+        "{:>4}  {:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}",
+        "year", "day", "part", "min", "median", "mean", "σ", "max"
+    ));
+    for (solution, result) in results {
+        let timings = match result {
+            Ok(timings) => timings,
+            Err(unsolved) => {
+                println!(
+                    "{:>4}  {:>3}  {:>4}  ({})",
+                    solution.year, solution.day, solution.part, unsolved
+                );
+                continue;
+            }
+        };
+
+        println!(
+            "{:>4}  {:>3}  {:>4}  {:>10}  {:>10}  {:>10}  {:>10}  {:>10}",
+            solution.year,
+            solution.day,
+            solution.part,
+            report::format_duration(timings.min()),
+            report::format_duration(timings.median()),
+            report::format_duration(timings.mean()),
+            report::format_duration(timings.std_dev()),
+            report::format_duration(timings.max()),
+        );
+    }
+}
+
+/// The last recorded timing of a part, and the commit it was recorded at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recorded {
+    pub commit: String,
+    pub duration: Duration,
+}
+
+/// A part which took longer than its last recorded timing allows
+#[derive(Debug, Clone)]
+pub struct Regression<'a> {
+    pub solution: &'a Solution,
+    pub recorded: Recorded,
+    pub duration: Duration,
+}
+
+/// Appends a row for each solution which was run, writing the header first when the file is new.
+/// The peak memory is left empty when it wasn't measured.
+pub fn append_csv(
+    path: &Path,
+    results: &[(&Solution, Result<Outcome, Unsolved>)],
+    timestamp: SystemTime,
+    commit: &str,
+) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if file.metadata()?.len() == 0 {
+        writeln!(file, "{}", HEADER)?;
+    }
+
+    let timestamp = calendar::format_timestamp(timestamp);
+    for (solution, result) in results {
+        if let Ok(outcome) = result {
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{}",
+                timestamp,
+                commit,
+                solution.year,
+                solution.day,
+                solution.part,
+                outcome.duration.as_nanos(),
+                outcome
+                    .peak_memory
+                    .map(|peak_memory| peak_memory.to_string())
+                    .unwrap_or_default()
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The last recorded timing of each part (by year, day and part) in a CSV file written by
+/// `append_csv`, which is empty when the file doesn't exist yet
+pub fn read_latest(path: &Path) -> io::Result<HashMap<(u16, u8, u8), Recorded>> {
+    let csv = match fs::read_to_string(path) {
+        Ok(csv) => csv,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(error) => return Err(error),
+    };
+
+    let mut latest = HashMap::new();
+    for line in csv.lines().skip(1) {
+        let columns: Vec<&str> = line.split(',').collect();
+        let parsed = match columns.as_slice() {
+            [_, commit, year, day, part, nanoseconds, ..] => (|| {
+                let key = (year.parse().ok()?, day.parse().ok()?, part.parse().ok()?);
+                let duration = Duration::from_nanos(nanoseconds.parse().ok()?);
+                Some((key, commit, duration))
+            })(),
+            _ => None,
+        };
+        let (key, commit, duration) = parsed.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{:?} isn't a row of timings", line),
+            )
+        })?;
+
+        // rows are appended, so later rows are newer
+        latest.insert(
+            key,
+            Recorded {
+                commit: commit.to_string(),
+                duration,
+            },
+        );
+    }
+
+    Ok(latest)
+}
+
+/// The parts which took more than `threshold` (such as 0.2 for 20%) longer than their last
+/// recorded timing
+pub fn find_regressions<'a>(
+    results: &[(&'a Solution, Result<Outcome, Unsolved>)],
+    latest: &HashMap<(u16, u8, u8), Recorded>,
+    threshold: f64,
+) -> Vec<Regression<'a>> {
+    results
+        .iter()
+        .filter_map(|(solution, result)| {
+            let duration = result.as_ref().ok()?.duration;
+            let recorded = latest.get(&(solution.year, solution.day, solution.part))?;
+
+            let regressed = recorded.duration >= MIN_COMPARED
+                && duration.as_secs_f64() > recorded.duration.as_secs_f64() * (1.0 + threshold);
+            regressed.then(|| Regression {
+                solution,
+                recorded: recorded.clone(),
+                duration,
+            })
+        })
+        .collect()
+}
+
+/// Prints each regression, or that there are none
+pub fn print_regressions(regressions: &[Regression]) {
+    if regressions.is_empty() {
+        println!("No solution is slower than its last recorded timing");
+        return;
+    }
+
+    report::print_header("Slower than their last recorded timing");
+    for regression in regressions {
+        println!(
+            "  {}: {} (was {} at {})",
+            regression.solution,
+            report::format_duration(regression.duration),
+            report::format_duration(regression.recorded.duration),
+            regression.recorded.commit
+        );
+    }
+}
+
+/// The commit the source at `root` is checked out at, marked as dirty when there are uncommitted
+/// changes, or nothing when it isn't a git repository
+pub fn current_commit(root: &Path) -> String {
+    Command::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "year-2018", feature = "year-2019"))]
+mod tests {
+    use super::*;
+    use advent_of_code::solutions::{self, SOLUTIONS};
+    use advent_of_code::Answer;
+    use std::time::{Duration, UNIX_EPOCH};
+    use std::{env, fs};
+
+    #[test]
+    fn test_append_csv() {
+        let path = env::temp_dir().join(format!("timings-{}.csv", std::process::id()));
+        let day5 = solutions::select(SOLUTIONS, 2018, 5, None).unwrap();
+        let outcome = |peak_memory| Outcome {
+            answer: Answer::Int(10),
+            duration: Duration::from_micros(15),
+            peak_memory,
+        };
+        let results = [
+            (day5[0], Ok(outcome(None))),
+            (day5[1], Ok(outcome(Some(2048)))),
+            (day5[1], Err(Unsolved::NoInput)),
+        ];
+
+        for _ in 0..2 {
+            append_csv(&path, &results, UNIX_EPOCH, "5fcf0c3").unwrap();
+        }
+        let csv = fs::read_to_string(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            csv.unwrap(),
+            "timestamp,commit,year,day,part,nanoseconds,peak_bytes
+1970-01-01T00:00:00Z,5fcf0c3,2018,5,1,15000,
+1970-01-01T00:00:00Z,5fcf0c3,2018,5,2,15000,2048
+1970-01-01T00:00:00Z,5fcf0c3,2018,5,1,15000,
+1970-01-01T00:00:00Z,5fcf0c3,2018,5,2,15000,2048
+"
+        );
+    }
+
+    #[test]
+    fn test_timings() {
+        let part1 = solutions::select(SOLUTIONS, 2018, 5, Some(1)).unwrap()[0];
+        let timings = time(part1, "dabAcCaCBAcCcaDA", 2, 5).unwrap();
+        assert_eq!(timings.outcome.answer, Answer::Int(10));
+        assert_eq!(timings.durations.len(), 5);
+        assert_eq!(timings.outcome.duration, timings.median());
+
+        let timings = Timings {
+            durations: [2, 4, 4, 4, 5, 5, 7, 9]
+                .iter()
+                .map(|&millis| Duration::from_millis(millis))
+                .collect(),
+            ..timings
+        };
+        assert_eq!(timings.min(), Duration::from_millis(2));
+        assert_eq!(timings.median(), Duration::from_micros(4500));
+        assert_eq!(timings.mean(), Duration::from_millis(5));
+        assert_eq!(timings.max(), Duration::from_millis(9));
+        // the sample standard deviation is sqrt(32 / 7) milliseconds
+        assert_eq!(timings.std_dev().as_micros(), 2138);
+    }
+
+    #[test]
+    fn test_find_regressions() {
+        let path = env::temp_dir().join(format!("history-{}.csv", std::process::id()));
+        fs::write(
+            &path,
+            "timestamp,commit,year,day,part,nanoseconds,peak_bytes
+2019-12-01T00:00:00Z,aaaaaaa,2018,5,1,1000000,
+2019-12-01T00:00:00Z,aaaaaaa,2018,5,2,50000000,
+2019-12-02T00:00:00Z,bbbbbbb,2018,5,1,2000000,
+2019-12-02T00:00:00Z,bbbbbbb,2018,5,2,900,
+",
+        )
+        .unwrap();
+        let latest = read_latest(&path);
+        fs::remove_file(&path).unwrap();
+        let latest = latest.unwrap();
+        assert_eq!(
+            latest[&(2018, 5, 1)],
+            Recorded {
+                commit: "bbbbbbb".to_string(),
+                duration: Duration::from_millis(2),
+            }
+        );
+
+        let day5 = solutions::select(SOLUTIONS, 2018, 5, None).unwrap();
+        let outcome = |micros| {
+            Ok(Outcome {
+                answer: Answer::Int(10),
+                duration: Duration::from_micros(micros),
+                peak_memory: None,
+            })
+        };
+        // part 2 is slower too, but too quick last time to tell
+        let regressions = find_regressions(
+            &[(day5[0], outcome(3000)), (day5[1], outcome(9))],
+            &latest,
+            0.2,
+        );
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].solution.part, 1);
+        assert_eq!(regressions[0].duration, Duration::from_millis(3));
+        assert!(find_regressions(&[(day5[0], outcome(2300))], &latest, 0.2).is_empty());
+
+        assert!(read_latest(Path::new("/nonexistent/history.csv"))
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+//! Shell completion scripts, which complete the years and days that have solutions as well as the
+//! commands and options
+
+use advent_of_code::solutions::SOLUTIONS;
+use clap::builder::PossibleValuesParser;
+use clap::Command;
+use clap_complete::Shell;
+use std::collections::BTreeSet;
+use std::io;
+
+/// Writes the completion script for a shell to stdout
+pub fn generate(command: Command, shell: Shell) {
+    let mut command = with_solution_values(command);
+    let name = command.get_name().to_string();
+
+    clap_complete::generate(shell, &mut command, name, &mut io::stdout());
+}
+
+/// Lists the years and days which have solutions as the possible values of the arguments which
+/// select them.
+///
+/// This is only done for completions: when parsing, any year or day is accepted so that
+/// `solutions::select` can explain what is available instead.
+fn with_solution_values(command: Command) -> Command {
+    let years: BTreeSet<_> = SOLUTIONS.iter().map(|s| s.year.to_string()).collect();
+    let days: BTreeSet<_> = SOLUTIONS.iter().map(|s| s.day).collect();
+    let days: Vec<_> = days.iter().map(u8::to_string).collect();
+
+    let years = PossibleValuesParser::new(years);
+    let days = PossibleValuesParser::new(days);
+
+    command
+        .mut_subcommand("run", |run| {
+            run.mut_arg("year", |arg| arg.value_parser(years.clone()))
+                .mut_arg("day", |arg| arg.value_parser(days.clone()))
+        })
+        .mut_subcommand("watch", |watch| {
+            watch
+                .mut_arg("year", |arg| arg.value_parser(years.clone()))
+                .mut_arg("day", |arg| arg.value_parser(days.clone()))
+        })
+        .mut_subcommand("run-all", |run_all| {
+            run_all.mut_arg("year", |arg| arg.value_parser(years.clone()))
+        })
+        .mut_subcommand("verify", |verify| {
+            verify.mut_arg("year", |arg| arg.value_parser(years.clone()))
+        })
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "year-2018", feature = "year-2019"))]
+mod tests {
+    use super::*;
+    use crate::Cli;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_solution_values() {
+        let command = with_solution_values(Cli::command());
+        let run = command.find_subcommand("run").unwrap();
+
+        let values = |name: &str| -> Vec<String> {
+            let arg = run.get_arguments().find(|arg| arg.get_id() == name);
+            arg.unwrap()
+                .get_possible_values()
+                .iter()
+                .map(|value| value.get_name().to_string())
+                .collect()
+        };
+        assert_eq!(values("year"), vec!["2018", "2019"]);
+        assert!(values("day").starts_with(&["1".to_string(), "2".to_string()]));
+    }
+}
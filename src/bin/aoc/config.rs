@@ -0,0 +1,203 @@
+//! Settings which would otherwise have to be given on every run, read from
+//! `~/.config/aoc/config.toml` or the file given with `--config`. Every setting is optional:
+//!
+//! ```toml
+//! # the session cookie of a logged in adventofcode.com account, used when AOC_SESSION isn't set
+//! session = "53616c7465645f5f..."
+//! # where puzzle inputs are stored and downloaded to
+//! inputs = "~/advent-of-code/inputs"
+//! # the year which run-all and verify run when no year is given
+//! year = 2019
+//! # whether to color the output: "auto", "always" or "never"
+//! color = "never"
+//!
+//! # solutions which live outside this crate, to compare with `aoc compare` (see plugin.rs)
+//! [plugins]
+//! alice = "~/src/alice-aoc/target/release/aoc {year} {day} {part}"
+//!
+//! # where to send the answers of runs which take longer than `after` (see notify.rs)
+//! [notify]
+//! after = "10m"
+//! command = "notify-send 'aoc' \"$AOC_MESSAGE\""
+//! ```
+
+use crate::notify::Notifier;
+use crate::plugin::Plugin;
+use advent_of_code::solutions::INPUTS_DIRECTORY;
+use anstream::ColorChoice;
+use std::convert::TryFrom;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    #[cfg_attr(not(feature = "download"), allow(dead_code))]
+    pub session: Option<String>,
+    pub inputs: PathBuf,
+    pub year: Option<u16>,
+    pub color: ColorChoice,
+    pub plugins: Vec<Plugin>,
+    pub notify: Option<Notifier>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            inputs: PathBuf::from(INPUTS_DIRECTORY),
+            year: None,
+            color: ColorChoice::Auto,
+            plugins: Vec::new(),
+            notify: None,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config file at `path`, or else the one in the user's config directory, which
+    /// needn't exist
+    pub fn load(path: Option<&Path>) -> Result<Self, ConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Self::default()),
+            },
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(config) => Self::parse(&config).map_err(|error| ConfigError::Invalid(path, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound && !required => {
+                Ok(Self::default())
+            }
+            Err(error) => Err(ConfigError::Io(path, error)),
+        }
+    }
+
+    fn parse(config: &str) -> Result<Self, String> {
+        let table: toml::Table = config.parse().map_err(|error| format!("{}", error))?;
+        let mut parsed = Self::default();
+
+        for (key, value) in &table {
+            match (key.as_str(), value) {
+                ("session", toml::Value::String(session)) => {
+                    parsed.session = Some(session.trim().to_string())
+                }
+                ("inputs", toml::Value::String(inputs)) => parsed.inputs = expand_home(inputs),
+                ("year", toml::Value::Integer(year)) => {
+                    parsed.year =
+                        Some(u16::try_from(*year).map_err(|_| format!("{} is not a year", year))?)
+                }
+                ("color", toml::Value::String(color)) => {
+                    parsed.color = match color.as_str() {
+                        "auto" => ColorChoice::Auto,
+                        "always" => ColorChoice::Always,
+                        "never" => ColorChoice::Never,
+                        _ => {
+                            return Err(format!(
+                                "color should be \"auto\", \"always\" or \"never\", not {:?}",
+                                color
+                            ))
+                        }
+                    }
+                }
+                ("plugins", toml::Value::Table(plugins)) => {
+                    for (name, command) in plugins {
+                        let command = command
+                            .as_str()
+                            .ok_or_else(|| format!("plugin {} isn't a command", name))?;
+                        parsed.plugins.push(Plugin {
+                            name: name.clone(),
+                            command: command.to_string(),
+                        });
+                    }
+                }
+                ("notify", toml::Value::Table(notify)) => {
+                    parsed.notify = Some(Notifier::parse(notify)?)
+                }
+                ("session" | "inputs" | "year" | "color" | "plugins" | "notify", _) => {
+                    return Err(format!("{} has the wrong type", key))
+                }
+                _ => return Err(format!("unknown setting {}", key)),
+            }
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// `$XDG_CONFIG_HOME/aoc/config.toml`, or `~/.config/aoc/config.toml`
+fn default_path() -> Option<PathBuf> {
+    let config_directory = env::var_os("XDG_CONFIG_HOME")
+        .filter(|directory| !directory.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(env::var_os("HOME")?).join(".config")))?;
+
+    Some(config_directory.join("aoc").join("config.toml"))
+}
+
+/// Replaces a leading `~` with the home directory
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(path), Some(home)) => PathBuf::from(home).join(path),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Invalid(PathBuf, String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(path, error) => write!(f, "Failed to read {}: {}", path.display(), error),
+            Self::Invalid(path, error) => write!(f, "Invalid config {}: {}", path.display(), error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            "session = \"cookie\"\ninputs = \"/tmp/inputs\"\nyear = 2019\ncolor = \"never\"\n\
+             [plugins]\nalice = \"./alice {year} {day} {part}\"\n\
+             [notify]\ncommand = \"cat\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            Config {
+                session: Some("cookie".to_string()),
+                inputs: PathBuf::from("/tmp/inputs"),
+                year: Some(2019),
+                color: ColorChoice::Never,
+                plugins: vec![Plugin {
+                    name: "alice".to_string(),
+                    command: "./alice {year} {day} {part}".to_string(),
+                }],
+                notify: Some(Notifier {
+                    after: std::time::Duration::from_secs(60),
+                    webhook: None,
+                    command: Some("cat".to_string()),
+                }),
+            }
+        );
+
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+        assert!(Config::parse("year = \"2019\"").is_err());
+        assert!(Config::parse("color = \"sometimes\"").is_err());
+        assert!(Config::parse("sesion = \"cookie\"").is_err());
+        assert!(Config::parse("[plugins]\nalice = 1").is_err());
+        assert!(Config::parse("notify = \"cat\"").is_err());
+    }
+}
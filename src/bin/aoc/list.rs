@@ -0,0 +1,75 @@
+//! Lists each day with a solution: its title, which parts are solved, and what is stored for it
+
+use crate::report;
+use advent_of_code::answers::Answers;
+use advent_of_code::solutions::{self, Solution, SOLUTIONS};
+use anstream::println;
+use std::path::Path;
+
+/// Prints a row for each day with a solution (or only those of `year`), with its title, whether
+/// each part has a solution, whether its input is stored in `inputs`, and which parts have a
+/// recorded answer
+pub fn print_list(inputs: &Path, answers: &Answers, year: Option<u16>) {
+    let mut days: Vec<(u16, u8)> = SOLUTIONS
+        .iter()
+        .filter(|solution| year.is_none_or(|year| solution.year == year))
+        .map(|solution| (solution.year, solution.day))
+        .collect();
+    days.dedup();
+
+    let title_width = days
+        .iter()
+        .filter_map(|&(year, day)| solutions::title(year, day))
+        .map(|title| title.chars().count())
+        .chain(Some("title".len()))
+        .max()
+        .unwrap_or(0);
+
+    report::print_header(&format!(
+        "{:>4}  {:>3}  {:<title_width$}  {:<6}  {:<6}  {:<5}  {}",
+        "year",
+        "day",
+        "title",
+        "part 1",
+        "part 2",
+        "input",
+        "answers",
+        title_width = title_width
+    ));
+    for (year, day) in days {
+        let parts: Vec<&Solution> = SOLUTIONS
+            .iter()
+            .filter(|solution| (solution.year, solution.day) == (year, day))
+            .collect();
+        let status = |part| {
+            if parts.iter().any(|solution| solution.part == part) {
+                "solved"
+            } else {
+                "-"
+            }
+        };
+
+        let stored = solutions::input_path(inputs, year, day).is_file();
+        let recorded: Vec<String> = answers
+            .iter()
+            .filter(|(answer_year, answer_day, _, _)| (*answer_year, *answer_day) == (year, day))
+            .map(|(_, _, part, _)| part.to_string())
+            .collect();
+
+        println!(
+            "{:>4}  {:>3}  {:<title_width$}  {:<6}  {:<6}  {:<5}  {}",
+            year,
+            day,
+            solutions::title(year, day).unwrap_or("?"),
+            status(1),
+            status(2),
+            if stored { "yes" } else { "no" },
+            if recorded.is_empty() {
+                "-".to_string()
+            } else {
+                recorded.join(", ")
+            },
+            title_width = title_width
+        );
+    }
+}
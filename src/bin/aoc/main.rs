@@ -0,0 +1,1288 @@
+use advent_of_code::answers::{self, Answers};
+use advent_of_code::common::InputSource;
+use advent_of_code::common::{artifacts, rng};
+#[cfg(feature = "download")]
+use advent_of_code::description;
+#[cfg(feature = "download")]
+use advent_of_code::download::{self, DownloadError};
+use advent_of_code::examples;
+use advent_of_code::generate::{self, Scale};
+use advent_of_code::memory;
+use advent_of_code::params::{self, Param, Params};
+use advent_of_code::progress;
+use advent_of_code::solutions::{self, Parsed, Solution, SOLUTIONS};
+use clap::{CommandFactory, Parser, Subcommand};
+use rayon::prelude::*;
+use rayon::ThreadPoolBuilder;
+use std::io::{self, BufRead, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+mod batch;
+mod bench;
+mod completions;
+mod config;
+mod list;
+mod next;
+mod notify;
+mod plugin;
+#[cfg(all(feature = "profile", unix))]
+mod profile;
+mod redact;
+mod report;
+mod scaffold;
+#[cfg(feature = "serve")]
+mod serve;
+mod stats;
+mod timeout;
+#[cfg(feature = "tui")]
+mod tui;
+mod watch;
+
+use config::Config;
+use report::{Outcome, Unsolved};
+
+/// The exit code when an answer doesn't match its recorded (or example's) answer. Other errors
+/// exit with 1.
+const EXIT_WRONG_ANSWER: i32 = 2;
+/// The exit code when a part is given up on for taking longer than `--timeout`
+const EXIT_TIMEOUT: i32 = 3;
+/// The exit code when the puzzle input can't be read
+const EXIT_MISSING_INPUT: i32 = 4;
+/// The exit code when `bench --compare` finds a solution which has become slower
+const EXIT_REGRESSION: i32 = 5;
+
+#[cfg(feature = "memory")]
+#[global_allocator]
+static ALLOCATOR: memory::TrackingAllocator = memory::TrackingAllocator;
+
+/// Solutions to the Advent of Code puzzles
+#[derive(Debug, Parser)]
+#[command(
+    name = "aoc",
+    after_help = "Exits with 2 when an answer is wrong, 3 when a part times out, 4 when the puzzle \
+                  input can't be read, 5 when bench --compare finds a slower solution, and 1 for \
+                  any other error."
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Prints without colors (which are also left out when the output isn't a terminal, or when
+    /// NO_COLOR is set)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Reads the settings from this file instead of ~/.config/aoc/config.toml
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Runs the solution of a day's puzzle, reading the puzzle input from the inputs directory
+    /// (or stdin when the day has no stored input) unless another input is given
+    Run {
+        year: u16,
+        day: u8,
+
+        /// Runs only this part (both parts are run by default)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Reads the puzzle input from this file, or from the clipboard when it's `clipboard`
+        #[arg(
+            short,
+            long,
+            value_name = "PATH",
+            value_parser = parse_input_source,
+            conflicts_with = "input_string"
+        )]
+        input: Option<InputSource>,
+
+        /// Uses this as the puzzle input
+        #[arg(long, value_name = "INPUT")]
+        input_string: Option<String>,
+
+        /// Runs the examples from the puzzle description instead, and checks their answers
+        #[arg(long, conflicts_with_all = ["input", "input_string"])]
+        example: bool,
+
+        /// Runs every puzzle input in this directory instead, printing a row for each
+        #[arg(
+            long,
+            value_name = "DIR",
+            conflicts_with_all = ["input", "input_string", "example"]
+        )]
+        inputs: Option<PathBuf>,
+
+        /// Watches the puzzle being solved, for the parts which can draw themselves
+        #[cfg(feature = "visualize")]
+        #[arg(long)]
+        visualize: bool,
+
+        /// The frame rate the visualization starts at (which + and - change as it plays)
+        #[cfg(feature = "visualize")]
+        #[arg(long, value_name = "FPS", default_value_t = 60, requires = "visualize")]
+        fps: u32,
+
+        /// Records the visualization into this file: an animated GIF for a .gif file, or a video
+        /// made by ffmpeg (which must be installed) for any other, such as out.mp4
+        #[cfg(feature = "visualize")]
+        #[arg(long, value_name = "FILE", requires = "visualize")]
+        record: Option<PathBuf>,
+
+        /// Saves each frame of the parts which can draw themselves as a numbered PNG in this
+        /// directory
+        #[cfg(feature = "image")]
+        #[arg(long, value_name = "DIR")]
+        frames: Option<PathBuf>,
+
+        /// Writes the intermediate data the solutions emit (such as grids and the state after
+        /// each round) into artifacts/{year}/{day}, emptying it first
+        #[arg(long)]
+        debug_artifacts: bool,
+
+        /// Gives up on a part which takes longer than this, such as 30s or 500ms
+        #[arg(long, value_name = "TIME", value_parser = timeout::parse_timeout)]
+        timeout: Option<Duration>,
+
+        /// Sets a setting of the puzzle, such as workers=2, in place of the puzzle's own value.
+        /// Can be given more than once.
+        #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_assignment)]
+        params: Vec<(String, i64)>,
+
+        /// Prints only the answers, one part after the other, for scripts
+        #[arg(short, long, conflicts_with_all = ["example", "inputs"])]
+        quiet: bool,
+
+        /// Copies the answer of the last part run to the clipboard, ready to be submitted
+        #[cfg(feature = "clipboard")]
+        #[arg(long, conflicts_with_all = ["example", "inputs"])]
+        copy: bool,
+    },
+
+    /// Runs the solution of a day's puzzle under a sampling profiler, and draws a flamegraph of
+    /// where it spent its time
+    #[cfg(all(feature = "profile", unix))]
+    Profile {
+        year: u16,
+        day: u8,
+
+        /// Profiles only this part (both parts are profiled by default)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Reads the puzzle input from this file
+        #[arg(short, long, value_name = "PATH")]
+        input: Option<PathBuf>,
+
+        /// Writes the flamegraph to this file (flamegraph-<year>-<day>.svg by default)
+        #[arg(short, long, value_name = "PATH")]
+        output: Option<PathBuf>,
+
+        /// How many times a second to sample the call stack
+        #[arg(long, default_value_t = 1000)]
+        frequency: i32,
+
+        /// Solves the puzzle over and over for at least this many seconds, so that quick
+        /// solutions are sampled enough
+        #[arg(long, default_value_t = 1.0)]
+        seconds: f64,
+    },
+
+    /// Runs every solution which has a stored input, and prints a table of the answers and how
+    /// long each took
+    RunAll {
+        /// Runs only the solutions of this year
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// How many solutions to run at once (one per core by default). Timings are most accurate
+        /// with 1.
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// Gives up on a part which takes longer than this, such as 30s or 500ms, and moves on to
+        /// the rest
+        #[arg(long, value_name = "TIME", value_parser = timeout::parse_timeout)]
+        timeout: Option<Duration>,
+    },
+
+    /// Times every solution which has a stored input one at a time, so that they don't compete
+    /// for the CPU, and prints a table of how long each took
+    Bench {
+        /// Times only the solutions of this year
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Times only the solutions of this day of the year
+        #[arg(short, long, requires = "year")]
+        day: Option<u8>,
+
+        /// Times each solution this many times, and prints the spread of the timings. The median
+        /// is what's recorded and compared.
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+
+        /// Solves each solution this many times before timing it
+        #[arg(short, long, default_value_t = 0)]
+        warmup: u32,
+
+        /// Appends the timings to this CSV file, along with the time and the current commit
+        #[arg(long, value_name = "PATH")]
+        csv: Option<PathBuf>,
+
+        /// Before appending, compares the timings with the last ones recorded in the CSV file, and
+        /// fails when a solution has become slower
+        #[arg(long, requires = "csv")]
+        compare: bool,
+
+        /// How much slower than its last timing (in percent) a solution has to be to fail
+        /// `--compare`
+        #[arg(long, value_name = "PERCENT", default_value_t = 20.0)]
+        threshold: f64,
+    },
+
+    /// Solves a day with each of the plugins in the config as well as with its own solution,
+    /// timing each and checking their answers against those recorded in answers.toml
+    Compare {
+        year: u16,
+        day: u8,
+
+        /// Compares only this part (both parts are compared by default)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Reads the puzzle input from this file
+        #[arg(short, long, value_name = "PATH")]
+        input: Option<PathBuf>,
+
+        /// Compares only with this plugin (every plugin by default). Can be given more than once.
+        #[arg(long = "plugin", value_name = "NAME")]
+        plugins: Vec<String>,
+
+        /// Times each solver this many times, and prints the median of the runs
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+    },
+
+    /// Runs every solution which has a stored input, and checks its answer against the correct
+    /// answer recorded in answers.toml
+    Verify {
+        /// Verifies only the solutions of this year
+        #[arg(short, long)]
+        year: Option<u16>,
+
+        /// Records the answers of solutions which have no recorded answer yet
+        #[arg(long)]
+        record: bool,
+
+        /// How many solutions to run at once (one per core by default)
+        #[arg(short, long)]
+        jobs: Option<usize>,
+    },
+
+    /// Runs a day, and runs it again (rebuilding through cargo) whenever the source or its input
+    /// changes
+    Watch {
+        year: u16,
+        day: u8,
+
+        /// Runs only this part (both parts are run by default)
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Reads the puzzle input from this file
+        #[arg(short, long, value_name = "PATH")]
+        input: Option<PathBuf>,
+
+        /// Builds with optimizations
+        #[arg(long)]
+        release: bool,
+    },
+
+    /// Prints the description of a puzzle, downloading it first (including part two once part
+    /// one is solved) unless it has been already
+    #[cfg(feature = "download")]
+    Desc {
+        year: u16,
+        day: u8,
+
+        /// Downloads the description again, such as to add part two
+        #[arg(long)]
+        refresh: bool,
+    },
+
+    /// Prints how long remains until the next puzzle unlocks
+    Next {
+        /// Keeps counting down until the puzzle unlocks
+        #[arg(long)]
+        live: bool,
+
+        /// Runs this shell command the moment the puzzle unlocks, with {year} and {day} replaced,
+        /// such as "aoc new {year} {day} --download"
+        #[arg(long, value_name = "COMMAND")]
+        then: Option<String>,
+    },
+
+    /// Prints how far along each year is: the stars earned going by the recorded answers, the
+    /// days with solutions, and the fastest and slowest solutions (timing each with a stored
+    /// input, one at a time)
+    Stats {
+        /// Prints only the statistics of this year
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+
+    /// Prints a puzzle input disguised so that it can be shared, with its lines shuffled and its
+    /// names and coordinates changed in ways that keep the puzzle the same kind of puzzle
+    Redact {
+        year: u16,
+        day: u8,
+
+        /// The puzzle input to disguise (the stored input by default)
+        input: Option<PathBuf>,
+
+        /// Disguises the input the same way as any other time with this seed
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+
+    /// Prints a made-up puzzle input for a day, of any size, to see how its solution copes with
+    /// inputs bigger than the real one
+    Gen {
+        year: u16,
+        day: u8,
+
+        /// How big an input to make, as a multiple of the size of a real one (such as 10x)
+        #[arg(long, default_value_t = Scale::REAL)]
+        scale: Scale,
+
+        /// Makes the same input as any other time with this seed
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+
+    /// Serves the solutions over HTTP: a POST to /{year}/day/{day}/part/{part} with the puzzle
+    /// input as the body is answered with the answer as JSON
+    #[cfg(feature = "serve")]
+    Serve {
+        /// The port to listen on
+        #[arg(short, long, default_value_t = 8080)]
+        port: u16,
+
+        /// The address to listen on, which only allows requests from this machine by default
+        #[arg(long, default_value = "127.0.0.1")]
+        host: String,
+
+        /// Gives up on a part which takes longer than this, such as 30s or 500ms
+        #[arg(long, value_name = "TIME", value_parser = timeout::parse_timeout)]
+        timeout: Option<Duration>,
+    },
+
+    /// Shows an interactive dashboard of every solution, with keys to run them, time them, and
+    /// watch their visualizations
+    #[cfg(feature = "tui")]
+    Tui,
+
+    /// Lists each day with a solution: its title, which parts are solved, whether its input is
+    /// stored, and which parts have a recorded answer
+    List {
+        /// Lists only the days of this year
+        #[arg(short, long)]
+        year: Option<u16>,
+    },
+
+    /// Prints the completion script for a shell, for example with
+    /// `aoc completions bash > /etc/bash_completion.d/aoc`
+    Completions { shell: clap_complete::Shell },
+
+    /// Creates the source file of a new day from a template, and registers both its parts
+    New {
+        year: u16,
+        day: u8,
+
+        /// Downloads the puzzle input too, using the session token in AOC_SESSION
+        #[cfg(feature = "download")]
+        #[arg(short, long)]
+        download: bool,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let config = match Config::load(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+    let inputs = config.inputs.as_path();
+
+    if cli.no_color {
+        anstream::ColorChoice::Never.write_global();
+    } else {
+        config.color.write_global();
+    }
+    if io::stderr().is_terminal() {
+        progress::enable();
+    }
+
+    match cli.command {
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            input_string,
+            example,
+            inputs: batch_inputs,
+            #[cfg(feature = "visualize")]
+            visualize,
+            #[cfg(feature = "visualize")]
+            fps,
+            #[cfg(feature = "visualize")]
+            record,
+            #[cfg(feature = "image")]
+            frames,
+            debug_artifacts,
+            timeout,
+            params: assignments,
+            quiet,
+            #[cfg(feature = "clipboard")]
+            copy,
+        } => {
+            #[allow(unused_mut)]
+            let mut selected = select(SOLUTIONS, year, day, part);
+            #[cfg(feature = "visualize")]
+            if visualize {
+                use advent_of_code::common::vis;
+
+                vis::set_enabled(true);
+                vis::set_frames_per_second(fps);
+                vis::set_recording(record);
+                // parts with a visualization of their own run it in place of the plain solution,
+                // and the others can check `vis::enabled()` to draw themselves
+                for solution in &mut selected {
+                    if let Some(visualization) = solutions::VISUALIZATIONS.iter().find(|v| {
+                        (v.year, v.day, v.part) == (solution.year, solution.day, solution.part)
+                    }) {
+                        *solution = visualization;
+                    }
+                }
+            }
+            #[cfg(feature = "image")]
+            advent_of_code::common::vis::set_frames_directory(frames);
+            if debug_artifacts {
+                let directory = Path::new(artifacts::ARTIFACTS_DIRECTORY)
+                    .join(year.to_string())
+                    .join(day.to_string());
+                if let Err(error) = artifacts::set_directory(Some(directory.clone())) {
+                    eprintln!("Failed to create {}: {}", directory.display(), error);
+                    process::exit(1);
+                }
+            }
+            let params = select_params(&selected, assignments);
+
+            if example {
+                report::print_header(&format!("{} day {} examples", year, day));
+                let mut failed = 0;
+                for solution in &selected {
+                    let examples = examples::select(year, day, solution.part);
+                    if examples.is_empty() {
+                        println!("  part {}: no examples", solution.part);
+                    }
+                    for (i, example) in examples.iter().enumerate() {
+                        // the settings given on the command line win over the example's own
+                        let mut example_params = example.params();
+                        for (name, value) in params.iter() {
+                            example_params.set(name, value);
+                        }
+                        artifacts::set_part(Some(solution.part));
+                        let result =
+                            solve(solution, example.input, &example_params, false, timeout);
+                        if !report::print_example(solution, i + 1, example, &result) {
+                            failed += 1;
+                        }
+                    }
+                }
+                if failed > 0 {
+                    process::exit(EXIT_WRONG_ANSWER);
+                }
+                return;
+            }
+
+            if let Some(dir) = batch_inputs {
+                let rows =
+                    batch::run_batch(&selected, &dir, &params, timeout).unwrap_or_else(|error| {
+                        eprintln!(
+                            "Failed to read puzzle inputs from {}: {}",
+                            dir.display(),
+                            error
+                        );
+                        process::exit(EXIT_MISSING_INPUT);
+                    });
+                report::print_header(&format!("{} day {}", year, day));
+                batch::print_batch(&selected, &rows);
+
+                let results = rows.iter().flat_map(|row| &row.results);
+                if results
+                    .clone()
+                    .any(|result| matches!(result, Err(Unsolved::TimedOut(_))))
+                {
+                    process::exit(EXIT_TIMEOUT);
+                }
+                if results.clone().any(Result::is_err) {
+                    process::exit(1);
+                }
+                return;
+            }
+
+            let input_source = match (input, input_string) {
+                (_, Some(input_string)) => InputSource::String(input_string),
+                (Some(input_source), None) => input_source,
+                (None, None) => default_input_source(selected[0], &config),
+            };
+            let mut input = open_input(&input_source);
+
+            if !quiet {
+                report::print_header(&format!("{} day {}", year, day));
+            }
+            // the parts of a day are solved from the same parsed input, so it's parsed only once,
+            // as it's read
+            let start = Instant::now();
+            let parsed = (selected[0].parse_reader)(&mut *input);
+            if let (Ok(_), false) = (&parsed, quiet) {
+                report::print_parse_time(start.elapsed());
+            }
+            let results: Vec<_> = selected
+                .iter()
+                .map(|&solution| {
+                    artifacts::set_part(Some(solution.part));
+                    let result = match &parsed {
+                        Ok(parsed) => solve_from(
+                            solution,
+                            &Source::Parsed(parsed.clone()),
+                            &params,
+                            true,
+                            timeout,
+                        ),
+                        Err(error) => Err(Unsolved::Failed(error.clone())),
+                    };
+                    match (&result, quiet) {
+                        (Ok(outcome), true) => println!("{}", outcome.answer),
+                        (Err(unsolved), true) => eprintln!("{}: {}", solution, unsolved),
+                        (_, false) => report::print_answer(solution, &result),
+                    }
+                    result
+                })
+                .collect();
+            if let Some(notifier) = &config.notify {
+                let results: Vec<_> = selected.into_iter().zip(results.iter().cloned()).collect();
+                notifier.notify(&format!("{} day {}", year, day), start.elapsed(), &results);
+            }
+            #[cfg(feature = "clipboard")]
+            if let (true, Some(Ok(outcome))) = (copy, results.last()) {
+                if let Err(error) = arboard::Clipboard::new()
+                    .and_then(|mut clipboard| clipboard.set_text(outcome.answer.to_string()))
+                {
+                    eprintln!("Failed to copy the answer to the clipboard: {}", error);
+                    process::exit(1);
+                }
+            }
+            if results
+                .iter()
+                .any(|result| matches!(result, Err(Unsolved::TimedOut(_))))
+            {
+                process::exit(EXIT_TIMEOUT);
+            }
+            if results.iter().any(Result::is_err) {
+                process::exit(1);
+            }
+        }
+
+        #[cfg(all(feature = "profile", unix))]
+        Command::Profile {
+            year,
+            day,
+            part,
+            input,
+            output,
+            frequency,
+            seconds,
+        } => {
+            let selected = select(SOLUTIONS, year, day, part);
+            let input_source = match input {
+                Some(path) => InputSource::File(path),
+                None => default_input_source(selected[0], &config),
+            };
+            let input = read_input(&input_source);
+            let output =
+                output.unwrap_or_else(|| PathBuf::from(format!("flamegraph-{}-{}.svg", year, day)));
+            let min_time = Duration::try_from_secs_f64(seconds).unwrap_or_else(|error| {
+                eprintln!("Invalid --seconds: {}", error);
+                process::exit(1);
+            });
+
+            report::print_header(&format!("{} day {}", year, day));
+            match profile::profile(&selected, &input, frequency, min_time, &output) {
+                Ok(runs) => println!(
+                    "Wrote the flamegraph of {} runs to {}",
+                    runs,
+                    output.display()
+                ),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            }
+        }
+
+        Command::RunAll {
+            year,
+            jobs,
+            timeout,
+        } => {
+            let start = Instant::now();
+            let results = solve_stored(select_year(year.or(config.year)), jobs, inputs, timeout);
+
+            report::print_table(&results, start.elapsed());
+            if let Some(notifier) = &config.notify {
+                let title = match year.or(config.year) {
+                    Some(year) => format!("run-all of {}", year),
+                    None => "run-all".to_string(),
+                };
+                notifier.notify(&title, start.elapsed(), &results);
+            }
+
+            let timed_out =
+                |(_, result): &(_, Result<_, _>)| matches!(result, Err(Unsolved::TimedOut(_)));
+            let no_input =
+                |(_, result): &(_, Result<_, _>)| matches!(result, Err(Unsolved::NoInput));
+            if results.iter().any(timed_out) {
+                process::exit(EXIT_TIMEOUT);
+            }
+            if results.iter().any(no_input) {
+                process::exit(EXIT_MISSING_INPUT);
+            }
+            if results.iter().any(|(_, result)| result.is_err()) {
+                process::exit(1);
+            }
+        }
+
+        Command::Bench {
+            year,
+            day,
+            runs,
+            warmup,
+            csv,
+            compare,
+            threshold,
+        } => {
+            let selected = match (year, day) {
+                (Some(year), Some(day)) => select(SOLUTIONS, year, day, None),
+                _ => select_year(year.or(config.year)),
+            };
+
+            let start = Instant::now();
+            let timings: Vec<_> = selected
+                .into_iter()
+                .map(|solution| {
+                    let result = match solution.stored_input(inputs) {
+                        Some(input) => {
+                            bench::time(solution, &input, warmup as usize, runs as usize)
+                        }
+                        None => Err(Unsolved::NoInput),
+                    };
+                    (solution, result)
+                })
+                .collect();
+            let elapsed = start.elapsed();
+            let results: Vec<_> = timings
+                .iter()
+                .map(|(solution, result)| {
+                    let outcome = result.as_ref().map(|timings| timings.outcome.clone());
+                    (*solution, outcome.map_err(Unsolved::clone))
+                })
+                .collect();
+
+            if runs > 1 {
+                bench::print_timings(&timings);
+            } else {
+                report::print_table(&results, elapsed);
+            }
+
+            let mut regressed = false;
+            if let (true, Some(csv)) = (compare, &csv) {
+                let latest = bench::read_latest(csv).unwrap_or_else(|error| {
+                    eprintln!("Failed to read the timings in {}: {}", csv.display(), error);
+                    process::exit(1);
+                });
+                let regressions = bench::find_regressions(&results, &latest, threshold / 100.0);
+                bench::print_regressions(&regressions);
+                regressed = !regressions.is_empty();
+            }
+
+            if let Some(csv) = csv {
+                let commit = bench::current_commit(Path::new(env!("CARGO_MANIFEST_DIR")));
+                if let Err(error) = bench::append_csv(&csv, &results, SystemTime::now(), &commit) {
+                    eprintln!(
+                        "Failed to write the timings to {}: {}",
+                        csv.display(),
+                        error
+                    );
+                    process::exit(1);
+                }
+            }
+            if regressed {
+                process::exit(EXIT_REGRESSION);
+            }
+        }
+
+        Command::Compare {
+            year,
+            day,
+            part,
+            input,
+            plugins: names,
+            runs,
+        } => {
+            let selected = select(SOLUTIONS, year, day, part);
+            let plugins = select_plugins(&config.plugins, &names);
+            let answers = match Answers::load(Path::new(answers::ANSWERS_FILE)) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            };
+            let input_source = match input {
+                Some(path) => InputSource::File(path),
+                None => default_input_source(selected[0], &config),
+            };
+            let input = read_input(&input_source);
+
+            report::print_header(&format!("{} day {}", year, day));
+            let mut failed = 0;
+            for solution in selected {
+                let mut results = vec![(
+                    "aoc".to_string(),
+                    bench::time(solution, &input, 0, runs as usize).map(|t| t.outcome),
+                )];
+                for plugin in &plugins {
+                    let timings = plugin.time(year, day, solution.part, &input, runs as usize);
+                    results.push((plugin.name.clone(), timings.map(|t| t.outcome)));
+                }
+                failed += report::print_comparison(solution.part, &results, answers.get(solution));
+            }
+
+            if failed > 0 {
+                process::exit(EXIT_WRONG_ANSWER);
+            }
+        }
+
+        Command::Verify { year, record, jobs } => {
+            let path = Path::new(answers::ANSWERS_FILE);
+            let mut answers = match Answers::load(path) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            };
+
+            let results = solve_stored(select_year(year.or(config.year)), jobs, inputs, None);
+            let failed = report::print_verification(&results, &answers);
+
+            if record {
+                let recorded = answers.len();
+                for (solution, result) in &results {
+                    if let (Ok(outcome), None) = (result, answers.get(solution)) {
+                        answers.insert(solution, outcome.answer.to_string());
+                    }
+                }
+
+                if let Err(error) = answers.save(path) {
+                    eprintln!("Failed to record the answers: {}", error);
+                    process::exit(1);
+                }
+                println!(
+                    "Recorded {} answers in {}",
+                    answers.len() - recorded,
+                    path.display()
+                );
+            }
+
+            if failed > 0 {
+                process::exit(EXIT_WRONG_ANSWER);
+            }
+        }
+
+        Command::Stats { year } => {
+            let answers = match Answers::load(Path::new(answers::ANSWERS_FILE)) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            };
+
+            let year = year.or(config.year);
+            let results = solve_stored(select_year(year), Some(1), inputs, None);
+            stats::print_stats(&results, &answers, year);
+        }
+
+        Command::Watch {
+            year,
+            day,
+            part,
+            input,
+            release,
+        } => {
+            if let Err(error) = solutions::select(SOLUTIONS, year, day, part) {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+
+            let mut arguments = vec![year.to_string(), day.to_string()];
+            if let Some(part) = part {
+                arguments.extend(vec!["--part".to_string(), part.to_string()]);
+            }
+            if let Some(input) = &input {
+                arguments.extend(vec!["--input".to_string(), input.display().to_string()]);
+            }
+            if cli.no_color {
+                arguments.push("--no-color".to_string());
+            }
+            if let Some(config) = &cli.config {
+                arguments.extend(vec!["--config".to_string(), config.display().to_string()]);
+            }
+
+            // the day's source, along with the code shared between days which it may use
+            let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+            let paths = [
+                root.join("src"),
+                input.unwrap_or_else(|| solutions::input_path(inputs, year, day)),
+            ];
+
+            watch::watch(root, &paths, &arguments, release);
+        }
+
+        #[cfg(feature = "download")]
+        Command::Desc { year, day, refresh } => {
+            let path = description::description_path(inputs, year, day);
+            let result = if path.is_file() && !refresh {
+                std::fs::read_to_string(&path).map_err(DownloadError::from)
+            } else {
+                session(&config).and_then(|session| {
+                    description::fetch_description(year, day, &session, &path, inputs, refresh)
+                })
+            };
+
+            match result {
+                Ok(description) => print!("{}", description),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            }
+        }
+
+        Command::Next { live, then } => match next::countdown(live, then.as_deref()) {
+            Ok(Some(status)) if !status.success() => process::exit(status.code().unwrap_or(1)),
+            Ok(_) => {}
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        },
+
+        Command::Redact {
+            year,
+            day,
+            input,
+            seed,
+        } => {
+            let input_source = InputSource::File(
+                input.unwrap_or_else(|| solutions::input_path(inputs, year, day)),
+            );
+            let input = read_input(&input_source);
+
+            match redact::redact(year, day, &input, seed.unwrap_or_else(rng::random_seed)) {
+                Ok(redacted) => print!("{}", redacted),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            }
+        }
+
+        Command::Gen {
+            year,
+            day,
+            scale,
+            seed,
+        } => match generate::select(year, day) {
+            Ok(generator) => print!(
+                "{}",
+                generator.generate(scale, seed.unwrap_or_else(rng::random_seed))
+            ),
+            Err(error) => {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        },
+
+        #[cfg(feature = "serve")]
+        Command::Serve {
+            port,
+            host,
+            timeout,
+        } => {
+            if let Err(error) = serve::serve(&format!("{}:{}", host, port), timeout) {
+                eprintln!("Failed to serve the solutions: {}", error);
+                process::exit(1);
+            }
+        }
+
+        #[cfg(feature = "tui")]
+        Command::Tui => {
+            if !io::stdout().is_terminal() {
+                eprintln!("The dashboard needs a terminal");
+                process::exit(1);
+            }
+            let answers = match Answers::load(Path::new(answers::ANSWERS_FILE)) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            };
+
+            if let Err(error) = tui::run(config.inputs.clone(), answers) {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+
+        Command::List { year } => {
+            let answers = match Answers::load(Path::new(answers::ANSWERS_FILE)) {
+                Ok(answers) => answers,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            };
+
+            list::print_list(inputs, &answers, year.or(config.year));
+        }
+
+        Command::Completions { shell } => completions::generate(Cli::command(), shell),
+
+        Command::New {
+            year,
+            day,
+            #[cfg(feature = "download")]
+            download,
+        } => {
+            match scaffold::new_day(Path::new(env!("CARGO_MANIFEST_DIR")), year, day) {
+                Ok(path) => println!("Created {}", path.display()),
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            }
+
+            #[cfg(feature = "download")]
+            {
+                if download {
+                    let path = solutions::input_path(inputs, year, day);
+                    let result = session(&config).and_then(|session| {
+                        download::fetch_input(year, day, &session, &path, inputs)
+                    });
+                    match result {
+                        Ok(_) => println!("Downloaded the input to {}", path.display()),
+                        Err(error) => {
+                            eprintln!("{}", error);
+                            process::exit(1);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The solutions of a day's parts, or of only one part
+fn select(
+    solutions: &'static [Solution],
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+) -> Vec<&'static Solution> {
+    match solutions::select(solutions, year, day, part) {
+        Ok(selected) => selected,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
+}
+
+/// The settings given with `--param`, which must each be a setting of one of the selected parts
+fn select_params(selected: &[&Solution], assignments: Vec<(String, i64)>) -> Params {
+    // parts of the same day often share their settings
+    let mut available: Vec<&Param> = Vec::new();
+    for param in selected.iter().flat_map(|solution| solution.params) {
+        if !available.contains(&param) {
+            available.push(param);
+        }
+    }
+
+    let mut params = Params::default();
+    for (name, value) in assignments {
+        if !available.iter().any(|param| param.name == name) {
+            let (year, day) = (selected[0].year, selected[0].day);
+            if available.is_empty() {
+                eprintln!("{} day {} has no settings", year, day);
+            } else {
+                eprintln!(
+                    "{} day {} has no setting {}, its settings are:",
+                    year, day, name
+                );
+                for param in &available {
+                    eprintln!("  {}", param);
+                }
+            }
+            process::exit(1);
+        }
+        params.set(&name, value);
+    }
+
+    params
+}
+
+/// The plugins named with `--plugin`, or every plugin in the config when none are
+fn select_plugins<'a>(plugins: &'a [plugin::Plugin], names: &[String]) -> Vec<&'a plugin::Plugin> {
+    if plugins.is_empty() {
+        eprintln!("There are no plugins to compare with, as the config has no [plugins]");
+        process::exit(1);
+    }
+    if names.is_empty() {
+        return plugins.iter().collect();
+    }
+
+    names
+        .iter()
+        .map(
+            |name| match plugins.iter().find(|plugin| plugin.name == *name) {
+                Some(plugin) => plugin,
+                None => {
+                    eprintln!("There's no plugin {} in the config", name);
+                    process::exit(1);
+                }
+            },
+        )
+        .collect()
+}
+
+/// The input source of `--input`: a file, or the clipboard when it's `clipboard`
+fn parse_input_source(input: &str) -> Result<InputSource, String> {
+    match input {
+        #[cfg(feature = "clipboard")]
+        "clipboard" => Ok(InputSource::Clipboard),
+        #[cfg(not(feature = "clipboard"))]
+        "clipboard" => Err("reading from the clipboard needs the clipboard feature".to_string()),
+        path => Ok(InputSource::File(PathBuf::from(path))),
+    }
+}
+
+/// Reads a puzzle input, which every part of a day shares
+fn read_input(input_source: &InputSource) -> String {
+    match input_source.read() {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!(
+                "Failed to read puzzle input from {}: {}",
+                input_source, error
+            );
+            process::exit(EXIT_MISSING_INPUT);
+        }
+    }
+}
+
+/// Opens a puzzle input to be parsed as it's read
+fn open_input(input_source: &InputSource) -> Box<dyn BufRead> {
+    match input_source.open() {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!(
+                "Failed to read puzzle input from {}: {}",
+                input_source, error
+            );
+            process::exit(EXIT_MISSING_INPUT);
+        }
+    }
+}
+
+/// Every solution, or those of a single year
+fn select_year(year: Option<u16>) -> Vec<&'static Solution> {
+    let selected: Vec<_> = SOLUTIONS
+        .iter()
+        .filter(|solution| year.is_none_or(|year| solution.year == year))
+        .collect();
+    if selected.is_empty() {
+        eprintln!("No solutions for {}", year.unwrap_or_default());
+        process::exit(1);
+    }
+
+    selected
+}
+
+/// Solves each solution which has a stored input, timing each. The solutions are run in parallel
+/// on `jobs` threads (one per core when not given), but the results keep their order. Memory is
+/// only measured when they run one at a time.
+fn solve_stored(
+    selected: Vec<&'static Solution>,
+    jobs: Option<usize>,
+    inputs: &Path,
+    timeout: Option<Duration>,
+) -> Vec<(&'static Solution, Result<Outcome, Unsolved>)> {
+    let pool = match ThreadPoolBuilder::new()
+        .num_threads(jobs.unwrap_or(0))
+        .build()
+    {
+        Ok(pool) => pool,
+        Err(error) => {
+            eprintln!("Failed to start the solution threads: {}", error);
+            process::exit(1);
+        }
+    };
+
+    pool.install(|| {
+        selected
+            .into_par_iter()
+            .map(|solution| {
+                let result = match solution.stored_input(inputs) {
+                    Some(input) => solve(
+                        solution,
+                        &input,
+                        &Params::default(),
+                        jobs == Some(1),
+                        timeout,
+                    ),
+                    None => Err(Unsolved::NoInput),
+                };
+                (solution, result)
+            })
+            .collect()
+    })
+}
+
+/// What a part is solved from: a puzzle input, or its day's input parsed already by another part
+#[derive(Clone)]
+enum Source {
+    Input(Arc<str>),
+    Parsed(Parsed),
+}
+
+/// Solves a part like `solve_measured`, giving up once it has taken longer than `timeout`
+fn solve(
+    solution: &'static Solution,
+    input: &str,
+    params: &Params,
+    measure_memory: bool,
+    timeout: Option<Duration>,
+) -> Result<Outcome, Unsolved> {
+    solve_from(
+        solution,
+        &Source::Input(input.into()),
+        params,
+        measure_memory,
+        timeout,
+    )
+}
+
+/// Solves a part like `solve`, from a source which may already be parsed
+fn solve_from(
+    solution: &'static Solution,
+    source: &Source,
+    params: &Params,
+    measure_memory: bool,
+    timeout: Option<Duration>,
+) -> Result<Outcome, Unsolved> {
+    match timeout {
+        Some(timeout) => {
+            let (source, params) = (source.clone(), params.clone());
+            timeout::run_with_timeout(timeout, move || {
+                solve_measured(solution, &source, &params, measure_memory)
+            })
+            .unwrap_or(Err(Unsolved::TimedOut(timeout)))
+        }
+        None => solve_measured(solution, source, params, measure_memory),
+    }
+}
+
+/// Solves a part, timing it (along with parsing its input, unless that's been parsed already),
+/// and measuring the most memory it had allocated at once too when `measure_memory` is set (and
+/// this is built with the `memory` feature)
+fn solve_measured(
+    solution: &Solution,
+    source: &Source,
+    params: &Params,
+    measure_memory: bool,
+) -> Result<Outcome, Unsolved> {
+    let solve_timed = || match source {
+        Source::Input(input) => solution.solve_timed(input, params),
+        Source::Parsed(parsed) => solution.solve_parsed_timed(parsed, params),
+    };
+
+    if cfg!(feature = "memory") && measure_memory {
+        let ((answer, duration), peak_memory) = memory::measure_peak(solve_timed);
+        Ok(Outcome {
+            answer: answer.map_err(Unsolved::Failed)?,
+            duration,
+            peak_memory: Some(peak_memory),
+        })
+    } else {
+        let (answer, duration) = solve_timed();
+        Ok(Outcome {
+            answer: answer.map_err(Unsolved::Failed)?,
+            duration,
+            peak_memory: None,
+        })
+    }
+}
+
+/// The stored input of a solution's day, downloading it first when a session token is set, or
+/// else stdin
+fn default_input_source(solution: &Solution, config: &Config) -> InputSource {
+    #[cfg(feature = "download")]
+    {
+        if let (false, Ok(session)) = (
+            solution.input_path(&config.inputs).is_file(),
+            session(config),
+        ) {
+            eprintln!(
+                "Downloading the input for {} {}",
+                solution.year, solution.day
+            );
+            if let Err(error) = solution.download_input(&session, &config.inputs) {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+    }
+
+    solution.default_input_source(&config.inputs)
+}
+
+/// The session token from AOC_SESSION, or else from the config
+#[cfg(feature = "download")]
+fn session(config: &Config) -> Result<String, DownloadError> {
+    download::session_from_env().or_else(|error| config.session.clone().ok_or(error))
+}
@@ -0,0 +1,111 @@
+//! Counts down to the next puzzle, and can run a command the moment it unlocks
+
+use advent_of_code::calendar;
+use std::io::{self, Write};
+use std::process::{Command, ExitStatus};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// Prints how long remains until the next puzzle unlocks. With `live`, or when there is a command
+/// to run, the countdown is updated every second until then.
+///
+/// `then` is run by the shell, with `{year}` and `{day}` replaced by those of the puzzle.
+pub fn countdown(live: bool, then: Option<&str>) -> io::Result<Option<ExitStatus>> {
+    let (year, day, unlock) = calendar::next_unlock(SystemTime::now());
+    let line = |remaining: Duration| {
+        format!(
+            "{} day {} unlocks in {} (at {})",
+            year,
+            day,
+            format_remaining(remaining),
+            calendar::format_timestamp(unlock)
+        )
+    };
+
+    if !live && then.is_none() {
+        let remaining = unlock.duration_since(SystemTime::now()).unwrap_or_default();
+        println!("{}", line(remaining));
+        return Ok(None);
+    }
+
+    let mut stdout = io::stdout();
+    while let Ok(remaining) = unlock.duration_since(SystemTime::now()) {
+        // the line is padded, as it may have shrunk since it was last printed
+        write!(stdout, "\r{:<60}", line(remaining))?;
+        stdout.flush()?;
+
+        // wake on the second, so that the countdown ticks evenly
+        let sleep = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        thread::sleep(if sleep.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            sleep
+        });
+    }
+    writeln!(
+        stdout,
+        "\r{:<60}",
+        format!("{} day {} is unlocked!", year, day)
+    )?;
+
+    match then {
+        Some(command) => {
+            let command = command
+                .replace("{year}", &year.to_string())
+                .replace("{day}", &day.to_string());
+            shell(&command).status().map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
+/// A command which runs `command` through the shell, so that it may use pipes and the like
+#[cfg(unix)]
+pub fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+pub fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+/// Formats a duration in days, hours, minutes and seconds, leaving out the days when there are
+/// none
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_secs();
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+
+    if days > 0 {
+        format!("{}d {:02}h {:02}m {:02}s", days, hours, minutes, seconds)
+    } else {
+        format!("{:02}h {:02}m {:02}s", hours, minutes, seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_remaining() {
+        assert_eq!(format_remaining(Duration::from_secs(59)), "00h 00m 59s");
+        assert_eq!(
+            format_remaining(Duration::from_millis(3_723_500)),
+            "01h 02m 03s"
+        );
+        assert_eq!(
+            format_remaining(Duration::from_secs(2 * 86_400 + 3600)),
+            "2d 01h 00m 00s"
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Pings when a run which took a while finishes, so that a slow day can be left to solve itself.
+//!
+//! The `[notify]` table of the config sets how long a run has to take (1 minute unless `after`
+//! says otherwise), and where to send the answers and timings once it finishes: a Slack or
+//! Discord webhook (which needs the `download` feature, for its HTTP client), a command, or both:
+//!
+//! ```toml
+//! [notify]
+//! after = "10m"
+//! webhook = "https://hooks.slack.com/services/..."
+//! command = "notify-send 'aoc' \"$AOC_MESSAGE\""
+//! ```
+//!
+//! The command is run through the shell with the message in `AOC_MESSAGE` and on its stdin, so
+//! that the answers needn't be quoted into it.
+
+use crate::next;
+use crate::report::{format_duration, Outcome, Unsolved};
+use crate::timeout::parse_timeout;
+use advent_of_code::solutions::Solution;
+use std::io::Write;
+use std::process::Stdio;
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notifier {
+    /// How long a run has to take to be notified about
+    pub after: Duration,
+    pub webhook: Option<String>,
+    pub command: Option<String>,
+}
+
+impl Notifier {
+    /// Reads the `[notify]` table of the config
+    pub fn parse(table: &toml::Table) -> Result<Self, String> {
+        let mut notifier = Notifier {
+            after: Duration::from_secs(60),
+            webhook: None,
+            command: None,
+        };
+
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("after", toml::Value::String(after)) => {
+                    notifier.after =
+                        parse_timeout(after).map_err(|error| format!("notify.after: {}", error))?
+                }
+                ("webhook", toml::Value::String(webhook)) => {
+                    notifier.webhook = Some(webhook.clone())
+                }
+                ("command", toml::Value::String(command)) => {
+                    notifier.command = Some(command.clone())
+                }
+                ("after" | "webhook" | "command", _) => {
+                    return Err(format!("notify.{} has the wrong type", key))
+                }
+                _ => return Err(format!("unknown setting notify.{}", key)),
+            }
+        }
+
+        if notifier.webhook.is_none() && notifier.command.is_none() {
+            return Err("notify has neither a webhook nor a command".to_string());
+        }
+        if cfg!(not(feature = "download")) && notifier.webhook.is_some() {
+            return Err("notify.webhook needs the download feature".to_string());
+        }
+        Ok(notifier)
+    }
+
+    /// Sends the answers of a run which took `elapsed` (`title` saying what was run) when that's
+    /// long enough, reporting on stderr when it couldn't be sent, which leaves the run as it was
+    pub fn notify(
+        &self,
+        title: &str,
+        elapsed: Duration,
+        results: &[(&Solution, Result<Outcome, Unsolved>)],
+    ) {
+        if elapsed < self.after {
+            return;
+        }
+
+        let message = message(title, elapsed, results);
+        #[cfg(feature = "download")]
+        if let Some(webhook) = &self.webhook {
+            if let Err(error) = post(webhook, &message) {
+                eprintln!("Failed to notify the webhook: {}", error);
+            }
+        }
+        if let Some(command) = &self.command {
+            if let Err(error) = run(command, &message) {
+                eprintln!("Failed to notify with {:?}: {}", command, error);
+            }
+        }
+    }
+}
+
+/// What was run and how long it took, followed by a line for each part: its answer and timing,
+/// or why it has none. Runs of more than one day's puzzle leave out the parts which were
+/// answered, to keep the message short enough for a chat.
+fn message(
+    title: &str,
+    elapsed: Duration,
+    results: &[(&Solution, Result<Outcome, Unsolved>)],
+) -> String {
+    let answered = results.iter().filter(|(_, result)| result.is_ok()).count();
+    let mut message = format!(
+        "{} finished after {}: {} of {} parts answered",
+        title,
+        format_duration(elapsed),
+        answered,
+        results.len()
+    );
+
+    let several_days = results
+        .windows(2)
+        .any(|pair| (pair[0].0.year, pair[0].0.day) != (pair[1].0.year, pair[1].0.day));
+    for (solution, result) in results {
+        match result {
+            Ok(_) if several_days => continue,
+            Ok(outcome) => message.push_str(&format!(
+                "\n{}: {} ({})",
+                solution,
+                outcome.answer,
+                format_duration(outcome.duration)
+            )),
+            Err(unsolved) => message.push_str(&format!("\n{}: {}", solution, unsolved)),
+        }
+    }
+
+    message
+}
+
+/// Posts the message to a webhook, under the key Slack reads it from (`text`) as well as the one
+/// Discord does (`content`)
+#[cfg(feature = "download")]
+fn post(webhook: &str, message: &str) -> Result<(), String> {
+    let message = crate::report::json_string(message);
+    ureq::post(webhook)
+        .header("Content-Type", "application/json")
+        .send(format!("{{\"text\":{0},\"content\":{0}}}", message))
+        .map(drop)
+        .map_err(|error| error.to_string())
+}
+
+fn run(command: &str, message: &str) -> Result<(), String> {
+    let mut child = next::shell(command)
+        .env("AOC_MESSAGE", message)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|error| error.to_string())?;
+
+    // a command which doesn't read the message may close stdin without reading it
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let _ = stdin.write_all(message.as_bytes());
+    drop(stdin);
+
+    let status = child.wait().map_err(|error| error.to_string())?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("failed with {}", status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "year-2018", feature = "year-2019"))]
+    fn test_message() {
+        use advent_of_code::solutions::{self, SOLUTIONS};
+        use advent_of_code::Answer;
+
+        let solutions = solutions::select(SOLUTIONS, 2018, 1, None).unwrap();
+        let outcome = Outcome {
+            answer: Answer::Int(587),
+            duration: Duration::from_millis(1500),
+            peak_memory: None,
+        };
+        let results = vec![
+            (solutions[0], Ok(outcome)),
+            (solutions[1], Err(Unsolved::Panicked)),
+        ];
+
+        assert_eq!(
+            message("2018 day 1", Duration::from_secs(90), &results),
+            "2018 day 1 finished after 90.00s: 1 of 2 parts answered\n\
+             2018 day 1 part 1: 587 (1.50s)\n\
+             2018 day 1 part 2: PANICKED"
+        );
+    }
+
+    #[test]
+    fn test_parse() {
+        let table = |notify: &str| notify.parse::<toml::Table>().unwrap();
+        assert_eq!(
+            Notifier::parse(&table("after = \"10m\"\ncommand = \"cat\"")),
+            Ok(Notifier {
+                after: Duration::from_secs(600),
+                webhook: None,
+                command: Some("cat".to_string()),
+            })
+        );
+        assert!(Notifier::parse(&table("after = \"10m\"")).is_err());
+        assert!(Notifier::parse(&table("command = \"cat\"\nafter = 10")).is_err());
+        assert!(Notifier::parse(&table("command = \"cat\"\nevery = \"1m\"")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run() {
+        let path = std::env::temp_dir().join(format!("notify-{}.txt", std::process::id()));
+        let command = format!("cat > {} && test \"$AOC_MESSAGE\" = done", path.display());
+
+        let result = run(&command, "done");
+        let written = std::fs::read_to_string(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(result, Ok(()));
+        assert_eq!(written.unwrap(), "done");
+        assert!(run("exit 3", "done").is_err());
+    }
+}
@@ -0,0 +1,155 @@
+//! Solutions which live outside this crate, such as a friend's solutions in another language, run
+//! as commands so that they can be timed and verified side by side with these ones.
+//!
+//! A plugin is a shell command from the `[plugins]` table of the config, in which `{year}`,
+//! `{day}` and `{part}` are replaced by the part to solve. The puzzle input is written to its
+//! stdin, and it prints the answer to stdout:
+//!
+//! ```toml
+//! [plugins]
+//! alice = "~/src/alice-aoc/target/release/aoc {year} {day} {part}"
+//! bob = "python3 ~/src/bob-aoc/day{day}.py --part {part}"
+//! ```
+//!
+//! An exit with any code other than 0 means that it couldn't solve the part, and whatever it
+//! printed to stderr is kept as the reason.
+
+use crate::bench::Timings;
+use crate::next;
+use crate::report::{Outcome, Unsolved};
+use advent_of_code::Answer;
+use std::io::{self, Write};
+use std::process::Stdio;
+use std::thread;
+use std::time::Instant;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plugin {
+    pub name: String,
+    pub command: String,
+}
+
+impl Plugin {
+    /// Solves a part with the plugin, timing how long the command takes altogether, which
+    /// includes starting it up
+    pub fn solve(&self, year: u16, day: u8, part: u8, input: &str) -> Result<Outcome, Unsolved> {
+        let failed = |reason: String| Unsolved::Plugin(format!("{} {}", self.name, reason));
+        let command = self
+            .command
+            .replace("{year}", &year.to_string())
+            .replace("{day}", &day.to_string())
+            .replace("{part}", &part.to_string());
+
+        let start = Instant::now();
+        let output =
+            run(&command, input).map_err(|error| failed(format!("couldn't be run: {}", error)))?;
+        let duration = start.elapsed();
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(failed(format!(
+                "failed with {}: {}",
+                output.status,
+                stderr.trim()
+            )));
+        }
+        let stdout = String::from_utf8(output.stdout)
+            .map_err(|_| failed("printed an answer which isn't UTF-8".to_string()))?;
+
+        Ok(Outcome {
+            answer: parse_answer(&stdout)
+                .ok_or_else(|| failed("didn't print an answer".to_string()))?,
+            duration,
+            peak_memory: None,
+        })
+    }
+
+    /// Solves a part with the plugin `runs` times (at least once), timing each like `bench::time`
+    pub fn time(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        input: &str,
+        runs: usize,
+    ) -> Result<Timings, Unsolved> {
+        let outcome = self.solve(year, day, part, input)?;
+        let mut durations = vec![outcome.duration];
+        for _ in 1..runs {
+            durations.push(self.solve(year, day, part, input)?.duration);
+        }
+
+        Ok(Timings::new(outcome, durations))
+    }
+}
+
+/// Runs a command with `input` as its stdin, collecting what it prints
+fn run(command: &str, input: &str) -> io::Result<std::process::Output> {
+    let mut child = next::shell(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // written from another thread, as the command may print more than a pipe holds before it has
+    // read all of its input
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = input.to_string();
+    let writer = thread::spawn(move || {
+        // a command which doesn't need the whole input may close stdin early
+        let _ = stdin.write_all(input.as_bytes());
+    });
+    let output = child.wait_with_output();
+    let _ = writer.join();
+
+    output
+}
+
+/// The answer a plugin printed, as a number when it is one, or letters drawn in a grid when it
+/// spans several lines (read as the letters, where they're ones the font knows)
+fn parse_answer(stdout: &str) -> Option<Answer> {
+    let answer = stdout.trim_end_matches(['\n', '\r']);
+    if answer.trim().is_empty() {
+        return None;
+    }
+
+    Some(if answer.contains('\n') {
+        Answer::from_pixels(answer.lines().map(str::to_string).collect())
+    } else if let Ok(int) = answer.trim().parse() {
+        Answer::Int(int)
+    } else {
+        Answer::String(answer.trim().to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answer() {
+        assert_eq!(parse_answer("42\n"), Some(Answer::Int(42)));
+        assert_eq!(parse_answer("CABDFE"), Some(Answer::from("CABDFE")));
+        assert_eq!(
+            parse_answer("#..\n.#.\n"),
+            Some(Answer::Grid(vec!["#..".to_string(), ".#.".to_string()]))
+        );
+        assert_eq!(parse_answer("\n"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_solve() {
+        let plugin = Plugin {
+            name: "wc".to_string(),
+            command: "test {year}-{day}-{part} = 2018-1-2 && wc -l".to_string(),
+        };
+        let outcome = plugin.solve(2018, 1, 2, "+1\n-1\n").unwrap();
+        assert_eq!(outcome.answer, Answer::Int(2));
+
+        assert!(matches!(
+            plugin.solve(2018, 1, 1, "+1\n-1\n"),
+            Err(Unsolved::Plugin(_))
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+//! Samples the call stacks of a solution while it runs, and draws them as a flamegraph
+
+use crate::report::{self, Outcome, Unsolved};
+use advent_of_code::params::Params;
+use advent_of_code::solutions::Solution;
+use pprof::ProfilerGuardBuilder;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Solves each part over and over until they have been running for at least `min_time`, sampling
+/// their call stacks `frequency` times a second. The samples are written as
+/// an SVG flamegraph to `path`.
+///
+/// A fast solution finishes before many samples are taken, so the parts are repeated to be sure
+/// there are enough to see where the time goes. Each answer is printed the first time.
+pub fn profile(
+    selected: &[&Solution],
+    input: &str,
+    frequency: i32,
+    min_time: Duration,
+    path: &Path,
+) -> Result<usize, ProfileError> {
+    let guard = ProfilerGuardBuilder::default()
+        .frequency(frequency)
+        // the sampler can't unwind through these without crashing
+        .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+        .build()
+        .map_err(ProfileError::Profiler)?;
+
+    let start = Instant::now();
+    let mut runs = 0;
+    while runs == 0 || start.elapsed() < min_time {
+        for solution in selected {
+            let (answer, duration) = solution.solve_timed(input, &Params::default());
+            if runs == 0 {
+                let outcome = answer.map_err(Unsolved::Failed).map(|answer| Outcome {
+                    answer,
+                    duration,
+                    peak_memory: None,
+                });
+                report::print_answer(solution, &outcome);
+            }
+        }
+        runs += 1;
+    }
+
+    let report = guard.report().build().map_err(ProfileError::Profiler)?;
+    let file = File::create(path).map_err(|error| ProfileError::Io(path.to_path_buf(), error))?;
+    report.flamegraph(file).map_err(ProfileError::Profiler)?;
+
+    Ok(runs)
+}
+
+#[derive(Debug)]
+pub enum ProfileError {
+    Profiler(pprof::Error),
+    Io(PathBuf, io::Error),
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Profiler(error) => write!(f, "Failed to profile the solution: {}", error),
+            Self::Io(path, error) => write!(f, "Failed to write {}: {}", path.display(), error),
+        }
+    }
+}
+
+impl Error for ProfileError {}
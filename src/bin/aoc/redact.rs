@@ -0,0 +1,274 @@
+//! Disguises puzzle inputs, so that an input which trips up a solution can be shared without
+//! sharing the puzzle input itself (which Advent of Code asks people not to redistribute).
+//!
+//! Each day's input has a structure its solution relies on, so the changes depend on the day:
+//! lines are shuffled, names are replaced, and coordinates are moved or mirrored, but only in
+//! ways that keep the puzzle the same kind of puzzle.
+
+use advent_of_code::common::rng::Rng;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// Disguises the input of a day's puzzle. The same seed always disguises an input the same way.
+pub fn redact(year: u16, day: u8, input: &str, seed: u64) -> Result<String, RedactError> {
+    let mut rng = Rng::new(seed);
+
+    let redacted = match (year, day) {
+        // the order of frequency changes and module masses is all there is to change
+        (2018, 1) | (2019, 1) => shuffle_lines(input, &mut rng),
+
+        // box ids and polymers: the same letter is always swapped for the same letter, so which
+        // letters match (and react) is kept
+        (2018, 2) | (2018, 5) => {
+            let substitution = Substitution::new(&mut rng);
+            input.chars().map(|c| substitution.apply(c)).collect()
+        }
+
+        // claims: the ids are handed out again, and the claims shuffled
+        (2018, 3) => {
+            let mut ids: Vec<&str> = input
+                .lines()
+                .filter_map(|line| line.split_whitespace().next())
+                .collect();
+            rng.shuffle(&mut ids);
+
+            let claims: String = input
+                .lines()
+                .zip(ids)
+                .map(|(line, id)| {
+                    let rest = line.split_once(' ').map_or("", |(_, rest)| rest);
+                    format!("{} {}\n", id, rest)
+                })
+                .collect();
+            shuffle_lines(&claims, &mut rng)
+        }
+
+        // guard records: each guard gets a new id, and the records are shuffled (they're sorted
+        // by their timestamps anyway)
+        (2018, 4) => {
+            let mut guards = HashMap::new();
+            let records: String = input
+                .lines()
+                .map(|line| {
+                    let mut words: Vec<String> = line.split(' ').map(str::to_string).collect();
+                    for word in words.iter_mut().filter(|word| word.starts_with('#')) {
+                        let count = guards.len();
+                        let id = guards
+                            .entry(word.clone())
+                            .or_insert_with(|| 100 * (count + 1) + rng.below(100));
+                        *word = format!("#{}", id);
+                    }
+                    words.join(" ") + "\n"
+                })
+                .collect();
+            shuffle_lines(&records, &mut rng)
+        }
+
+        // coordinates: all are moved by the same amount, which keeps the distances between them
+        (2018, 6) => {
+            let (dx, dy) = (rng.below(100), rng.below(100));
+            let mut coordinates = String::new();
+            for line in input.lines() {
+                let (x, y) = line
+                    .split_once(", ")
+                    .and_then(|(x, y)| Some((x.parse::<usize>().ok()?, y.parse::<usize>().ok()?)))
+                    .ok_or_else(|| RedactError::Malformed(line.to_string()))?;
+                coordinates += &format!("{}, {}\n", x + dx, y + dy);
+            }
+            coordinates
+        }
+
+        // instructions: the steps are renamed, which keeps the order they depend on each other
+        // (though not which of the available steps comes first alphabetically)
+        (2018, 7) => {
+            let substitution = Substitution::new(&mut rng);
+            input
+                .lines()
+                .map(|line| {
+                    let words: Vec<String> = line
+                        .split(' ')
+                        .map(|word| match word.len() {
+                            1 => word.chars().map(|c| substitution.apply(c)).collect(),
+                            _ => word.to_string(),
+                        })
+                        .collect();
+                    words.join(" ") + "\n"
+                })
+                .collect()
+        }
+
+        // wires: the same reflection of both, which keeps every distance
+        (2019, 3) => {
+            let (flip_horizontal, flip_vertical, transpose) =
+                (rng.below(2) == 1, rng.below(2) == 1, rng.below(2) == 1);
+            input
+                .chars()
+                .map(|c| {
+                    let c = match c {
+                        'L' if flip_horizontal => 'R',
+                        'R' if flip_horizontal => 'L',
+                        'U' if flip_vertical => 'D',
+                        'D' if flip_vertical => 'U',
+                        c => c,
+                    };
+                    match c {
+                        'L' if transpose => 'U',
+                        'U' if transpose => 'L',
+                        'R' if transpose => 'D',
+                        'D' if transpose => 'R',
+                        c => c,
+                    }
+                })
+                .collect()
+        }
+
+        // orbits: every object but the center of mass, you and santa gets a new name (of four
+        // characters, so that none clash with those three)
+        (2019, 6) => {
+            let mut names: Vec<&str> = input.lines().flat_map(|line| line.split(')')).collect();
+            names.retain(|name| !["", "COM", "YOU", "SAN"].contains(name));
+            names.sort_unstable();
+            names.dedup();
+            rng.shuffle(&mut names);
+
+            let renamed: HashMap<&str, String> = names
+                .iter()
+                .enumerate()
+                .map(|(i, name)| (*name, format!("{:04X}", i)))
+                .collect();
+            input
+                .lines()
+                .map(|line| {
+                    let objects: Vec<&str> = line
+                        .split(')')
+                        .map(|name| renamed.get(name).map_or(name, String::as_str))
+                        .collect();
+                    objects.join(")") + "\n"
+                })
+                .collect()
+        }
+
+        // asteroid map: mirrored, which keeps which asteroids see each other
+        (2019, 10) => {
+            let (reverse_columns, reverse_rows) = (rng.below(2) == 1, rng.below(2) == 1);
+            let mut rows: Vec<String> = input
+                .lines()
+                .map(|row| {
+                    if reverse_columns {
+                        row.chars().rev().collect()
+                    } else {
+                        row.to_string()
+                    }
+                })
+                .collect();
+            if reverse_rows {
+                rows.reverse();
+            }
+            rows.iter().map(|row| format!("{}\n", row)).collect()
+        }
+
+        (2019, 2) | (2019, 5) | (2019, 7) | (2019, 9) | (2019, 13) => {
+            return Err(RedactError::Intcode)
+        }
+        _ => return Err(RedactError::Unsupported { year, day }),
+    };
+
+    Ok(redacted)
+}
+
+fn shuffle_lines(input: &str, rng: &mut Rng) -> String {
+    let mut lines: Vec<&str> = input.lines().collect();
+    rng.shuffle(&mut lines);
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+/// Swaps each letter for another, keeping its case
+struct Substitution {
+    alphabet: Vec<u8>,
+}
+
+impl Substitution {
+    fn new(rng: &mut Rng) -> Self {
+        let mut alphabet: Vec<u8> = (b'a'..=b'z').collect();
+        rng.shuffle(&mut alphabet);
+        Self { alphabet }
+    }
+
+    fn apply(&self, c: char) -> char {
+        match c {
+            'a'..='z' => char::from(self.alphabet[usize::from(c as u8 - b'a')]),
+            'A'..='Z' => {
+                char::from(self.alphabet[usize::from(c as u8 - b'A')]).to_ascii_uppercase()
+            }
+            c => c,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RedactError {
+    /// Changing any value of an Intcode program would change what it does
+    Intcode,
+    Unsupported {
+        year: u16,
+        day: u8,
+    },
+    Malformed(String),
+}
+
+impl fmt::Display for RedactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Intcode => write!(
+                f,
+                "Intcode programs can't be disguised without changing what they do"
+            ),
+            Self::Unsupported { year, day } => {
+                write!(
+                    f,
+                    "There's no way to disguise the input of {} day {}",
+                    year, day
+                )
+            }
+            Self::Malformed(line) => write!(f, "Failed to parse the input line {:?}", line),
+        }
+    }
+}
+
+impl Error for RedactError {}
+
+#[cfg(test)]
+#[cfg(all(feature = "year-2018", feature = "year-2019"))]
+mod tests {
+    use super::*;
+    use advent_of_code::params::Params;
+    use advent_of_code::solutions::{self, SOLUTIONS};
+    use advent_of_code::Answer;
+
+    #[test]
+    fn test_redact() {
+        let solve = |year, day, input: &str| -> Vec<Answer> {
+            solutions::select(SOLUTIONS, year, day, None)
+                .unwrap()
+                .iter()
+                .map(|solution| solution.solve(input, &Params::default()).unwrap())
+                .collect()
+        };
+
+        let polymer = "dabAcCaCBAcCcaDA";
+        let redacted = redact(2018, 5, polymer, 7).unwrap();
+        assert_ne!(redacted, polymer);
+        assert_eq!(solve(2018, 5, &redacted), solve(2018, 5, polymer));
+
+        let wires = "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83\n";
+        let redacted = redact(2019, 3, wires, 7).unwrap();
+        assert_eq!(solve(2019, 3, &redacted), solve(2019, 3, wires));
+        assert_eq!(redact(2019, 3, wires, 7).unwrap(), redacted);
+
+        assert!(matches!(
+            redact(2019, 9, "99", 7),
+            Err(RedactError::Intcode)
+        ));
+    }
+}
@@ -0,0 +1,415 @@
+//! Formats answers and timings for the terminal.
+//!
+//! Everything is printed through anstream, which drops the colors when stdout isn't a terminal,
+//! when `NO_COLOR` is set, or when `--no-color` is given.
+
+use advent_of_code::answers::Answers;
+use advent_of_code::examples::Example;
+use advent_of_code::solutions::Solution;
+use advent_of_code::Answer;
+use advent_of_code::Error;
+use anstream::println;
+use anstyle::{AnsiColor, Style};
+use std::fmt;
+use std::time::Duration;
+
+const HEADER: Style = Style::new().bold();
+const TIME: Style = Style::new().dimmed();
+const PASS: Style = AnsiColor::Green.on_default();
+const FAIL: Style = AnsiColor::Red.on_default().bold();
+const UNVERIFIED: Style = AnsiColor::Yellow.on_default();
+
+/// The answer to a part, how long it took, and the most memory it had allocated at once when that
+/// was measured
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub answer: Answer,
+    pub duration: Duration,
+    pub peak_memory: Option<usize>,
+}
+
+/// Why a part has no answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Unsolved {
+    /// There's no stored input to solve it with
+    NoInput,
+
+    /// It was given up on after running for this long
+    TimedOut(Duration),
+
+    /// The input isn't what the solution expects
+    Failed(Error),
+
+    /// The solution panicked, which they may still do on an input they don't check
+    Panicked,
+
+    /// A plugin's command failed, or didn't print an answer
+    Plugin(String),
+}
+
+impl fmt::Display for Unsolved {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoInput => write!(f, "no stored input"),
+            Self::TimedOut(timeout) => write!(f, "TIMEOUT after {}", format_duration(*timeout)),
+            Self::Failed(error) => write!(f, "ERROR: {}", error),
+            Self::Panicked => write!(f, "PANICKED"),
+            Self::Plugin(reason) => write!(f, "ERROR: {}", reason),
+        }
+    }
+}
+
+impl Outcome {
+    /// How long it took, along with the memory used when that was measured
+    fn cost(&self) -> String {
+        match self.peak_memory {
+            Some(peak_memory) => format!(
+                "{}, {}",
+                format_duration(self.duration),
+                format_bytes(peak_memory)
+            ),
+            None => format_duration(self.duration),
+        }
+    }
+}
+
+/// Wraps text in a style, so that the style is reset after it
+fn styled(style: Style, text: impl fmt::Display) -> String {
+    format!("{}{}{:#}", style, text, style)
+}
+
+/// Prints a heading, such as the day whose answers follow
+pub fn print_header(header: &str) {
+    println!("{}", styled(HEADER, header));
+}
+
+/// Prints the answer to a single part, putting answers which span several lines (such as
+/// letters drawn in a grid) on lines of their own
+pub fn print_answer(solution: &Solution, result: &Result<Outcome, Unsolved>) {
+    let outcome = match result {
+        Ok(outcome) => outcome,
+        Err(unsolved) => {
+            println!("  part {}: {}", solution.part, styled(FAIL, unsolved));
+            return;
+        }
+    };
+    let cost = styled(TIME, format!("({})", outcome.cost()));
+
+    if outcome.answer.is_multiline() {
+        println!("  part {} {}:", solution.part, cost);
+        for line in outcome.answer.to_string().lines() {
+            println!("    {}", line);
+        }
+    } else {
+        println!("  part {}: {} {}", solution.part, outcome.answer, cost);
+    }
+}
+
+/// Prints how long parsing a day's input took, which the answers of its parts that follow don't
+/// include
+pub fn print_parse_time(duration: Duration) {
+    println!(
+        "  parsed {}",
+        styled(TIME, format!("({})", format_duration(duration)))
+    );
+}
+
+/// Prints whether a part gives the answer to one of its examples (the `number`th, from 1).
+/// Returns whether it does.
+pub fn print_example(
+    solution: &Solution,
+    number: usize,
+    example: &Example,
+    result: &Result<Outcome, Unsolved>,
+) -> bool {
+    let part = format!("  part {} example {}:", solution.part, number);
+
+    match result {
+        Ok(outcome) if outcome.answer.is(example.answer) => {
+            println!(
+                "{} {} {}",
+                part,
+                styled(PASS, "pass"),
+                styled(TIME, format!("({})", outcome.cost()))
+            );
+            true
+        }
+        Ok(outcome) => {
+            println!("{} {}", part, styled(FAIL, "FAIL"));
+            println!("    expected: {}", example.answer);
+            println!("    got:      {}", outcome.answer);
+            false
+        }
+        Err(unsolved) => {
+            println!("{} {}", part, styled(FAIL, unsolved));
+            false
+        }
+    }
+}
+
+/// Prints a table of every answer and how long it took, with the total time at the bottom, or why
+/// there is no answer. The peak memory of each is printed too when it was measured.
+///
+/// The solutions may have run in parallel, so the time they took altogether (`elapsed`) is
+/// printed too.
+pub fn print_table(results: &[(&Solution, Result<Outcome, Unsolved>)], elapsed: Duration) {
+    let show_memory = results
+        .iter()
+        .any(|(_, result)| result.as_ref().is_ok_and(|o| o.peak_memory.is_some()));
+    let answers: Vec<String> = results
+        .iter()
+        .map(|(_, result)| match result {
+            Ok(outcome) => outcome.answer.to_string(),
+            Err(unsolved) => format!("({})", unsolved),
+        })
+        .collect();
+    let memory_header = if show_memory {
+        format!("  {:>10}", "memory")
+    } else {
+        String::new()
+    };
+
+    let answer_width = answers
+        .iter()
+        .flat_map(|answer| answer.lines())
+        .map(|line| line.chars().count())
+        .chain(Some("answer".len()))
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{}",
+        styled(
+            HEADER,
+            format!(
+                "{:>4}  {:>3}  {:>4}  {:<answer_width$}  {:>10}{}",
+                "year",
+                "day",
+                "part",
+                "answer",
+                "time",
+                memory_header,
+                answer_width = answer_width
+            )
+        )
+    );
+
+    let mut total = Duration::default();
+    for ((solution, result), answer) in results.iter().zip(&answers) {
+        let time = match result {
+            Ok(outcome) => {
+                total += outcome.duration;
+                format_duration(outcome.duration)
+            }
+            Err(_) => String::new(),
+        };
+        let memory = match result.as_ref().ok().and_then(|outcome| outcome.peak_memory) {
+            Some(peak_memory) if show_memory => format!("  {:>10}", format_bytes(peak_memory)),
+            _ => String::new(),
+        };
+
+        let mut lines = answer.lines();
+        let first_line = format!(
+            "{:<answer_width$}",
+            lines.next().unwrap_or(""),
+            answer_width = answer_width
+        );
+        println!(
+            "{:>4}  {:>3}  {:>4}  {}  {}",
+            solution.year,
+            solution.day,
+            solution.part,
+            match result {
+                Ok(_) => first_line,
+                Err(Unsolved::NoInput) => styled(TIME, first_line),
+                Err(_) => styled(FAIL, first_line),
+            },
+            styled(TIME, format!("{:>10}{}", time, memory)),
+        );
+        for line in lines {
+            println!("{:>17}{}", "", line);
+        }
+    }
+
+    for (label, duration) in &[("total", total), ("elapsed", elapsed)] {
+        println!(
+            "{}",
+            styled(
+                HEADER,
+                format!(
+                    "{:<15}  {:<answer_width$}  {:>10}",
+                    label,
+                    "",
+                    format_duration(*duration),
+                    answer_width = answer_width
+                )
+            )
+        );
+    }
+}
+
+/// Prints whether each answer matches its recorded answer, followed by a summary. Returns how
+/// many didn't match.
+pub fn print_verification(
+    results: &[(&Solution, Result<Outcome, Unsolved>)],
+    answers: &Answers,
+) -> usize {
+    let (mut passed, mut failed, mut unverified) = (0, 0, 0);
+
+    let mut previous_year = None;
+    for (solution, result) in results {
+        if previous_year != Some(solution.year) {
+            print_header(&solution.year.to_string());
+            previous_year = Some(solution.year);
+        }
+        let part = format!("  day {:>2} part {}:", solution.day, solution.part);
+
+        match (result, answers.get(solution)) {
+            (Err(Unsolved::NoInput), _) => {
+                unverified += 1;
+                println!("{} {}", part, styled(TIME, Unsolved::NoInput));
+            }
+            (Err(unsolved), _) => {
+                failed += 1;
+                println!("{} {}", part, styled(FAIL, unsolved));
+            }
+            (Ok(outcome), None) => {
+                unverified += 1;
+                println!(
+                    "{} {} (got {})",
+                    part,
+                    styled(UNVERIFIED, "no recorded answer"),
+                    outcome.answer
+                );
+            }
+            (Ok(outcome), Some(expected)) if outcome.answer.is(expected) => {
+                passed += 1;
+                println!(
+                    "{} {} {}",
+                    part,
+                    styled(PASS, "pass"),
+                    styled(TIME, format!("({})", outcome.cost()))
+                );
+            }
+            (Ok(outcome), Some(expected)) => {
+                failed += 1;
+                println!("{} {}", part, styled(FAIL, "FAIL"));
+                println!("    expected: {}", expected.trim_end());
+                println!("    got:      {}", outcome.answer);
+            }
+        }
+    }
+
+    let count_style = |count: usize, style: Style| if count > 0 { style } else { Style::new() };
+    println!(
+        "{}, {}, {}",
+        styled(count_style(passed, PASS), format!("{} passed", passed)),
+        styled(count_style(failed, FAIL), format!("{} failed", failed)),
+        styled(
+            count_style(unverified, UNVERIFIED),
+            format!("{} unverified", unverified)
+        ),
+    );
+
+    failed
+}
+
+/// Prints the answer each solver (this crate's solution, or a plugin) gave to a part, whether it
+/// matches the recorded answer, and how long it took. Returns how many didn't give the recorded
+/// answer.
+pub fn print_comparison(
+    part: u8,
+    results: &[(String, Result<Outcome, Unsolved>)],
+    expected: Option<&str>,
+) -> usize {
+    println!("  part {}", part);
+    let name_width = results
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let mut failed = 0;
+    for (name, result) in results {
+        let name = format!("    {:<name_width$}", name, name_width = name_width);
+        let outcome = match result {
+            Ok(outcome) => outcome,
+            Err(unsolved) => {
+                failed += 1;
+                println!("{}  {}", name, styled(FAIL, unsolved));
+                continue;
+            }
+        };
+
+        let status = match expected {
+            Some(expected) if outcome.answer.is(expected) => styled(PASS, "pass"),
+            Some(_) => {
+                failed += 1;
+                styled(FAIL, "FAIL")
+            }
+            None => styled(UNVERIFIED, "unverified"),
+        };
+        let cost = styled(TIME, format!("({})", outcome.cost()));
+        if outcome.answer.is_multiline() {
+            println!("{}  {} {}:", name, status, cost);
+            for line in outcome.answer.to_string().lines() {
+                println!("      {}", line);
+            }
+        } else {
+            println!("{}  {} {} {}", name, status, outcome.answer, cost);
+        }
+    }
+
+    failed
+}
+
+/// Formats a number of bytes in the largest binary unit which keeps it above 1
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit + 1 < UNITS.len() {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+
+    if seconds < 0.001 {
+        format!("{:.1}µs", seconds * 1_000_000.0)
+    } else if seconds < 1.0 {
+        format!("{:.2}ms", seconds * 1_000.0)
+    } else {
+        format!("{:.2}s", seconds)
+    }
+}
+
+/// Quotes a string for JSON, escaping the characters which can't appear in a JSON string as they
+/// are
+#[cfg(any(feature = "download", feature = "serve"))]
+pub fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+
+    quoted
+}
@@ -0,0 +1,489 @@
+//! Creates the source file of a new day from a template, and registers it in its year's module.
+//! The first day of a new year creates the year's module too, and registers the year.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A new day, with the input parsed into its lines, both parts left to solve, and a test for the
+/// examples of each
+const TEMPLATE: &str = r#"//! --- Day {day}: TODO ---
+
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day{day};
+
+impl Solver for Day{day} {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    /// TODO: the puzzle description of part 1
+    fn part1(_lines: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Err(Error::NoAnswer("part 1 isn't solved yet".to_string()))
+    }
+
+    /// TODO: the puzzle description of part 2
+    fn part2(_lines: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Err(Error::NoAnswer("part 2 isn't solved yet".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1_examples() {
+        let examples = [("", "0")];
+
+        for (input, expected) in &examples {
+            let lines = Day{day}::parse(input).unwrap();
+            let answer = Day{day}::part1(&lines, &Params::default()).unwrap().to_string();
+            assert_eq!(answer, *expected);
+        }
+    }
+
+    #[test]
+    fn test_part2_examples() {
+        let examples = [("", "0")];
+
+        for (input, expected) in &examples {
+            let lines = Day{day}::parse(input).unwrap();
+            let answer = Day{day}::part2(&lines, &Params::default()).unwrap().to_string();
+            assert_eq!(answer, *expected);
+        }
+    }
+}
+"#;
+
+/// The module of a new year, with its first day
+const YEAR_TEMPLATE: &str = r#"//! The puzzles of {year}
+
+use crate::solutions::{solver, Solution};
+
+pub mod day{day};
+
+/// Every solution of the year, in order of day and part
+pub const SOLUTIONS: &[Solution] = &[
+    solver!({year}, {day}, 1, day{day}::Day{day}),
+    solver!({year}, {day}, 2, day{day}::Day{day}),
+];
+"#;
+
+/// Creates `src/year_{year}/day{day}.rs` in the crate at `root`, and registers both its parts in
+/// the module of its year. The first day of a year creates the year's module too, and registers it
+/// along with a feature for the year. Returns the path of the new file.
+pub fn new_day(root: &Path, year: u16, day: u8) -> Result<PathBuf, ScaffoldError> {
+    let year_directory = root.join("src").join(format!("year_{}", year));
+    let path = year_directory.join(format!("day{}.rs", day));
+    if path.exists() {
+        return Err(ScaffoldError::Exists(path));
+    }
+
+    let module_path = year_directory.join("mod.rs");
+    let mut writes = Vec::new();
+    if module_path.exists() {
+        let module = fs::read_to_string(&module_path)?;
+        let module = register_solutions(&register_module(&module, year, day)?, year, day)?;
+        writes.push((module_path, module));
+    } else {
+        let module = YEAR_TEMPLATE
+            .replace("{year}", &year.to_string())
+            .replace("{day}", &day.to_string());
+        writes.push((module_path, module));
+
+        let lib = root.join("src").join("lib.rs");
+        let registered = register_year_module(&fs::read_to_string(&lib)?, year)?;
+        writes.push((lib, registered));
+        let solutions = root.join("src").join("solutions.rs");
+        let registered = register_year(&fs::read_to_string(&solutions)?, year)?;
+        writes.push((solutions, registered));
+        let cargo = root.join("Cargo.toml");
+        let registered = register_feature(&fs::read_to_string(&cargo)?, year)?;
+        writes.push((cargo, registered));
+        fs::create_dir_all(&year_directory)?;
+    }
+
+    fs::write(&path, TEMPLATE.replace("{day}", &day.to_string()))?;
+    for (file, contents) in writes {
+        fs::write(file, contents)?;
+    }
+
+    Ok(path)
+}
+
+/// Adds `pub mod day{day};` to the module of its year, keeping the days in order
+fn register_module(module: &str, year: u16, day: u8) -> Result<String, ScaffoldError> {
+    insert_in_block(
+        module,
+        |line| line.starts_with("use crate::solutions"),
+        |line| line.starts_with("pub const SOLUTIONS"),
+        |line| {
+            line.strip_prefix("pub mod day")?
+                .strip_suffix(';')?
+                .parse::<u8>()
+                .ok()
+        },
+        day,
+        &[format!("pub mod day{};", day)],
+    )
+    .ok_or(ScaffoldError::Missing("use crate::solutions"))?
+    .map_err(|_| ScaffoldError::Registered(year, day))
+}
+
+/// Adds a `solver!` for both parts of the day to the `SOLUTIONS` of its year, keeping it in order
+fn register_solutions(module: &str, year: u16, day: u8) -> Result<String, ScaffoldError> {
+    let solver = format!("day{}::Day{}", day, day);
+
+    insert_in_block(
+        module,
+        |line| line.starts_with("pub const SOLUTIONS"),
+        |line| line == "];",
+        |line| {
+            let line = line.trim();
+            let mut arguments = line
+                .strip_prefix("solver!(")
+                .or_else(|| line.strip_prefix("solution!("))?
+                .split(',')
+                .map(str::trim);
+            let year = arguments.next()?.parse::<u16>().ok()?;
+            let day = arguments.next()?.parse::<u8>().ok()?;
+            Some((year, day))
+        },
+        (year, day),
+        &[
+            format!("    solver!({}, {}, 1, {}),", year, day, solver),
+            format!("    solver!({}, {}, 2, {}),", year, day, solver),
+        ],
+    )
+    .ok_or(ScaffoldError::Missing("pub const SOLUTIONS"))?
+    .map_err(|_| ScaffoldError::Registered(year, day))
+}
+
+/// The year of a line which gates something behind its feature (`#[cfg(feature = "year-2018")]`)
+/// or which is its module (`pub mod year_2018;` or `crate::year_2018::SOLUTIONS,`)
+fn year_of(line: &str) -> Option<u16> {
+    let line = line.trim();
+    let year = match line.strip_prefix("#[cfg(feature = \"year-") {
+        Some(rest) => rest.strip_suffix("\")]")?,
+        None => line
+            .strip_prefix("pub mod year_")
+            .or_else(|| line.strip_prefix("crate::year_"))?
+            .split(|c: char| !c.is_ascii_digit())
+            .next()?,
+    };
+
+    year.parse().ok()
+}
+
+/// Adds the module of a year to `lib.rs`, behind the year's feature
+fn register_year_module(lib: &str, year: u16) -> Result<String, ScaffoldError> {
+    insert_in_block(
+        lib,
+        |line| line.starts_with("// the solutions of each year"),
+        str::is_empty,
+        year_of,
+        year,
+        &[
+            format!("#[cfg(feature = \"year-{}\")]", year),
+            format!("pub mod year_{};", year),
+        ],
+    )
+    .ok_or(ScaffoldError::Missing("// the solutions of each year"))?
+    .map_err(|_| ScaffoldError::YearRegistered(year))
+}
+
+/// Adds the `SOLUTIONS` of a year's module to the `YEARS` of `solutions.rs`
+fn register_year(solutions: &str, year: u16) -> Result<String, ScaffoldError> {
+    insert_in_block(
+        solutions,
+        |line| line.starts_with("const YEARS"),
+        |line| line == "];",
+        year_of,
+        year,
+        &[
+            format!("    #[cfg(feature = \"year-{}\")]", year),
+            format!("    crate::year_{}::SOLUTIONS,", year),
+        ],
+    )
+    .ok_or(ScaffoldError::Missing("const YEARS"))?
+    .map_err(|_| ScaffoldError::YearRegistered(year))
+}
+
+/// Adds a feature for a year to `Cargo.toml`, which is enabled by default like those of the other
+/// years
+fn register_feature(cargo: &str, year: u16) -> Result<String, ScaffoldError> {
+    let feature = format!("year-{}", year);
+    let year_of_feature = |feature: &str| feature.strip_prefix("year-")?.parse::<u16>().ok();
+
+    let cargo = insert_in_block(
+        cargo,
+        |line| line == "[features]",
+        |line| line.starts_with("[["),
+        |line| year_of_feature(line.strip_suffix(" = []")?),
+        year,
+        &[format!("{} = []", feature)],
+    )
+    .ok_or(ScaffoldError::Missing("[features]"))?
+    .map_err(|_| ScaffoldError::YearRegistered(year))?;
+
+    let mut lines: Vec<String> = cargo.lines().map(str::to_string).collect();
+    let default = lines
+        .iter_mut()
+        .find(|line| line.starts_with("default = ["))
+        .ok_or(ScaffoldError::Missing("default = ["))?;
+    let mut features: Vec<String> = default
+        .trim_start_matches("default = [")
+        .trim_end_matches(']')
+        .split(',')
+        .map(|feature| feature.trim().to_string())
+        .filter(|feature| !feature.is_empty())
+        .collect();
+    // after the years before it, and before everything else
+    let position = features
+        .iter()
+        .position(|other| year_of_feature(other.trim_matches('"')).is_none_or(|other| other > year))
+        .unwrap_or(features.len());
+    features.insert(position, format!("{:?}", feature));
+    *default = format!("default = [{}]", features.join(", "));
+
+    let mut registered = lines.join("\n");
+    if cargo.ends_with('\n') {
+        registered.push('\n');
+    }
+    Ok(registered)
+}
+
+/// Inserts `lines` into the block between the line matching `is_start` and the next line matching
+/// `is_end` (or the end of the source), before the first line whose key (if it has one) is greater
+/// than `key`. Lines are inserted after the last keyed line when none is greater.
+///
+/// Returns `None` when there is no such block, and `Some(Err(()))` when a line already has `key`.
+fn insert_in_block<K: Ord>(
+    source: &str,
+    is_start: impl Fn(&str) -> bool,
+    is_end: impl Fn(&str) -> bool,
+    key_of: impl Fn(&str) -> Option<K>,
+    key: K,
+    lines: &[String],
+) -> Option<Result<String, ()>> {
+    let mut source_lines: Vec<&str> = source.lines().collect();
+
+    let start = source_lines.iter().position(|line| is_start(line))?;
+    let end = source_lines[start + 1..]
+        .iter()
+        .position(|line| is_end(line))
+        .map_or(source_lines.len(), |end| start + 1 + end);
+
+    let mut insert_at = None;
+    let mut last_keyed = None;
+    for (index, line) in source_lines.iter().enumerate().take(end).skip(start + 1) {
+        match key_of(line) {
+            Some(existing) if existing == key => return Some(Err(())),
+            Some(existing) if existing > key => {
+                insert_at = Some(index);
+                break;
+            }
+            Some(_) => last_keyed = Some(index),
+            None => {}
+        }
+    }
+    let insert_at = insert_at.unwrap_or_else(|| last_keyed.map_or(end, |index| index + 1));
+
+    source_lines.splice(insert_at..insert_at, lines.iter().map(String::as_str));
+
+    let mut inserted = source_lines.join("\n");
+    if source.ends_with('\n') {
+        inserted.push('\n');
+    }
+    Some(Ok(inserted))
+}
+
+#[derive(Debug)]
+pub enum ScaffoldError {
+    /// The source file of the day already exists
+    Exists(PathBuf),
+
+    /// A file to register the day in doesn't have the line it's registered beside
+    Missing(&'static str),
+
+    /// The day is already registered, though it has no source file
+    Registered(u16, u8),
+
+    /// The year is already registered, though it has no module
+    YearRegistered(u16),
+
+    Io(io::Error),
+}
+
+impl fmt::Display for ScaffoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exists(path) => write!(f, "{} already exists", path.display()),
+            Self::Missing(line) => write!(f, "There is no `{}` to register the day beside", line),
+            Self::Registered(year, day) => {
+                write!(f, "{} day {} is already registered", year, day)
+            }
+            Self::YearRegistered(year) => write!(
+                f,
+                "{} is already registered, though there is no src/year_{}/mod.rs",
+                year, year
+            ),
+            Self::Io(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl From<io::Error> for ScaffoldError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YEAR: &str = "//! The puzzles of 2019
+
+use crate::solutions::{solution, solver, Solution};
+
+pub mod intcode_computer;
+
+pub mod day1;
+pub mod day9;
+
+pub mod day10;
+pub mod day13;
+
+/// Every solution of the year, in order of day and part
+pub const SOLUTIONS: &[Solution] = &[
+    solver!(2019, 1, 1, day1::Day1),
+    solver!(2019, 9, 1, day9::Day9),
+    solution!(2019, 10, 1, day10::part1),
+    solver!(2019, 13, 1, day13::Day13),
+];
+";
+
+    const LIB: &str = "pub mod common;
+
+// the solutions of each year, which `aoc new` adds new years to
+#[cfg(feature = \"year-2018\")]
+pub mod year_2018;
+#[cfg(feature = \"year-2019\")]
+pub mod year_2019;
+";
+
+    const SOLUTIONS: &str = "const YEARS: &[&[Solution]] = &[
+    #[cfg(feature = \"year-2018\")]
+    crate::year_2018::SOLUTIONS,
+    #[cfg(feature = \"year-2019\")]
+    crate::year_2019::SOLUTIONS,
+];
+";
+
+    const CARGO: &str = "[features]
+default = [\"year-2018\", \"year-2019\", \"download\"]
+# the solutions of each year
+year-2018 = []
+year-2019 = []
+# downloads missing puzzle inputs
+download = [\"dep:ureq\"]
+
+[[bin]]
+name = \"aoc\"
+";
+
+    #[test]
+    fn test_register_module() {
+        let module = register_module(YEAR, 2019, 11).unwrap();
+        assert!(module.contains("pub mod day10;\npub mod day11;\npub mod day13;\n"));
+
+        let module = register_module(YEAR, 2019, 14).unwrap();
+        assert!(module.contains("pub mod day13;\npub mod day14;\n\n/// Every solution"));
+
+        let module = register_module(YEAR, 2019, 2).unwrap();
+        assert!(module.contains("pub mod day1;\npub mod day2;\npub mod day9;\n"));
+
+        assert!(matches!(
+            register_module(YEAR, 2019, 9),
+            Err(ScaffoldError::Registered(2019, 9))
+        ));
+        assert!(matches!(
+            register_module(LIB, 2019, 2),
+            Err(ScaffoldError::Missing(_))
+        ));
+    }
+
+    #[test]
+    fn test_register_solutions() {
+        let module = register_solutions(YEAR, 2019, 2).unwrap();
+        assert!(module.ends_with(
+            "pub const SOLUTIONS: &[Solution] = &[
+    solver!(2019, 1, 1, day1::Day1),
+    solver!(2019, 2, 1, day2::Day2),
+    solver!(2019, 2, 2, day2::Day2),
+    solver!(2019, 9, 1, day9::Day9),
+    solution!(2019, 10, 1, day10::part1),
+    solver!(2019, 13, 1, day13::Day13),
+];
+"
+        ));
+
+        let module = register_solutions(YEAR, 2019, 11).unwrap();
+        assert!(module.contains(
+            "    solution!(2019, 10, 1, day10::part1),
+    solver!(2019, 11, 1, day11::Day11),
+    solver!(2019, 11, 2, day11::Day11),
+"
+        ));
+
+        let module = register_solutions(YEAR, 2019, 14).unwrap();
+        assert!(module.ends_with("    solver!(2019, 14, 2, day14::Day14),\n];\n"));
+    }
+
+    #[test]
+    fn test_register_year() {
+        let lib = register_year_module(LIB, 2015).unwrap();
+        assert!(lib.contains(
+            "adds new years to\n#[cfg(feature = \"year-2015\")]\npub mod year_2015;\n#[cfg(feature = \"year-2018\")]\n"
+        ));
+        let lib = register_year_module(LIB, 2020).unwrap();
+        assert!(lib.ends_with(
+            "pub mod year_2019;\n#[cfg(feature = \"year-2020\")]\npub mod year_2020;\n"
+        ));
+        assert!(matches!(
+            register_year_module(LIB, 2018),
+            Err(ScaffoldError::YearRegistered(2018))
+        ));
+
+        let solutions = register_year(SOLUTIONS, 2020).unwrap();
+        assert!(solutions.ends_with(
+            "    crate::year_2019::SOLUTIONS,
+    #[cfg(feature = \"year-2020\")]
+    crate::year_2020::SOLUTIONS,
+];
+"
+        ));
+
+        let cargo = register_feature(CARGO, 2015).unwrap();
+        assert!(cargo.starts_with(
+            "[features]
+default = [\"year-2015\", \"year-2018\", \"year-2019\", \"download\"]
+# the solutions of each year
+year-2015 = []
+year-2018 = []
+"
+        ));
+        let cargo = register_feature(CARGO, 2020).unwrap();
+        assert!(cargo.contains("[\"year-2018\", \"year-2019\", \"year-2020\", \"download\"]"));
+        assert!(cargo.contains("year-2019 = []\nyear-2020 = []\n# downloads"));
+    }
+}
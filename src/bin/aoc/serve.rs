@@ -0,0 +1,129 @@
+//! Serves the solutions over HTTP, so that other tools can use them without running `aoc`.
+//!
+//! Each part is solved by a POST to `/{year}/day/{day}/part/{part}` with the puzzle input as the
+//! body, which is answered with JSON:
+//!
+//! ```text
+//! {"year":2019,"day":9,"part":1,"answer":"2682107844","nanoseconds":1523000}
+//! ```
+//!
+//! Errors are answered with a status code and `{"error":"..."}`, such as 422 with the line of the
+//! input the solution couldn't make sense of.
+
+use crate::report::{json_string, Unsolved};
+use advent_of_code::params::Params;
+use advent_of_code::solutions::{self, Solution, SOLUTIONS};
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::thread;
+use std::time::Duration;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Answers requests on `address` (such as `127.0.0.1:8080`) until the process is stopped. Each
+/// request is solved on a thread of its own, and given up on after `timeout`, if given.
+pub fn serve(address: &str, timeout: Option<Duration>) -> io::Result<()> {
+    let server = Server::http(address).map_err(io::Error::other)?;
+    println!("Listening on http://{}", server.server_addr());
+
+    for mut request in server.incoming_requests() {
+        thread::spawn(move || {
+            let (status, body) = handle(&mut request, timeout);
+            let content_type = Header::from_bytes("Content-Type", "application/json")
+                .expect("the header is valid");
+            let response = Response::from_string(body)
+                .with_status_code(status)
+                .with_header(content_type);
+
+            // the client may have gone away, and then there's nobody to tell
+            let _ = request.respond(response);
+        });
+    }
+
+    Ok(())
+}
+
+/// Solves the part a request is for, returning the status code and JSON body to answer with
+fn handle(request: &mut Request, timeout: Option<Duration>) -> (u16, String) {
+    let solution = match route(request.url()) {
+        Ok(solution) => solution,
+        Err(error) => return (404, error_json(&error)),
+    };
+    if *request.method() != Method::Post {
+        return (
+            405,
+            error_json("Solutions are run by a POST, with the puzzle input as the body"),
+        );
+    }
+
+    let mut input = String::new();
+    if let Err(error) = request.as_reader().read_to_string(&mut input) {
+        return (
+            400,
+            error_json(&format!("Failed to read the puzzle input: {}", error)),
+        );
+    }
+
+    // a solution may still panic on an input it doesn't check
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        crate::solve(solution, &input, &Params::default(), false, timeout)
+    }));
+    match result {
+        Ok(Ok(outcome)) => (
+            200,
+            format!(
+                "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{},\"nanoseconds\":{}}}",
+                solution.year,
+                solution.day,
+                solution.part,
+                json_string(&outcome.answer.to_string()),
+                outcome.duration.as_nanos()
+            ),
+        ),
+        Ok(Err(Unsolved::Failed(error))) => (422, error_json(&error.to_string())),
+        Ok(Err(unsolved)) => (504, error_json(&unsolved.to_string())),
+        Err(_) => (
+            422,
+            error_json("The solution failed, so the input may not be a puzzle input for this day"),
+        ),
+    }
+}
+
+/// The solution at a path of the form `/{year}/day/{day}/part/{part}`
+fn route(url: &str) -> Result<&'static Solution, String> {
+    let path = url.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+
+    let (year, day, part) = match segments.as_slice() {
+        [year, "day", day, "part", part] => (year, day, part),
+        _ => return Err("Solutions are at /{year}/day/{day}/part/{part}".to_string()),
+    };
+    let (Ok(year), Ok(day), Ok(part)) = (year.parse(), day.parse(), part.parse()) else {
+        return Err(format!("{} isn't a year, day and part", path));
+    };
+
+    solutions::select(SOLUTIONS, year, day, Some(part))
+        .map(|selected| selected[0])
+        .map_err(|error| error.to_string())
+}
+
+fn error_json(error: &str) -> String {
+    format!("{{\"error\":{}}}", json_string(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route() {
+        let solution = route("/2019/day/9/part/1").unwrap();
+        assert_eq!((solution.year, solution.day, solution.part), (2019, 9, 1));
+        assert_eq!(route("/2018/day/5/part/2/?verbose").unwrap().part, 2);
+
+        assert!(route("/2019/day/9").is_err());
+        assert!(route("/2019/day/nine/part/1").is_err());
+        assert!(route("/1999/day/9/part/1").is_err());
+
+        assert_eq!(json_string("#.\n\"a\"\\"), "\"#.\\n\\\"a\\\"\\\\\"");
+    }
+}
@@ -0,0 +1,110 @@
+//! How far along each year is: the stars earned (going by the recorded answers), the days which
+//! have solutions, and which solutions are the fastest and slowest
+
+use crate::report::{self, Outcome, Unsolved};
+use advent_of_code::answers::Answers;
+use advent_of_code::calendar::LAST_DAY;
+use advent_of_code::solutions::Solution;
+use anstream::println;
+use std::collections::BTreeSet;
+
+/// Prints the statistics of each year which has a solution or a recorded answer, or only those of
+/// `year`
+pub fn print_stats(
+    results: &[(&Solution, Result<Outcome, Unsolved>)],
+    answers: &Answers,
+    year: Option<u16>,
+) {
+    let years: BTreeSet<u16> = results
+        .iter()
+        .map(|(solution, _)| solution.year)
+        .chain(answers.iter().map(|(year, _, _, _)| year))
+        .filter(|answer_year| year.is_none_or(|year| *answer_year == year))
+        .collect();
+
+    for year in years {
+        let solutions: Vec<_> = results
+            .iter()
+            .filter(|(solution, _)| solution.year == year)
+            .collect();
+        let solved_days: BTreeSet<u8> =
+            solutions.iter().map(|(solution, _)| solution.day).collect();
+
+        let stars: Vec<_> = answers
+            .iter()
+            .filter(|(answer_year, _, _, _)| *answer_year == year)
+            .map(|(_, day, part, _)| (day, part))
+            .collect();
+        let starred_days = (1..=LAST_DAY)
+            .filter(|day| stars.contains(&(*day, 1)) && stars.contains(&(*day, 2)))
+            .count();
+
+        let timed = solutions
+            .iter()
+            .filter_map(|(solution, result)| Some((solution, result.as_ref().ok()?.duration)));
+        let fastest = timed.clone().min_by_key(|(_, duration)| *duration);
+        let slowest = timed.max_by_key(|(_, duration)| *duration);
+
+        let missing: Vec<_> = (1..=LAST_DAY)
+            .filter(|day| !solved_days.contains(day))
+            .collect();
+
+        report::print_header(&year.to_string());
+        println!("  stars      {} of {}", stars.len(), 2 * LAST_DAY);
+        println!("  complete   {} of {} days", starred_days, LAST_DAY);
+        println!(
+            "  solutions  {} parts of {} days",
+            solutions.len(),
+            solved_days.len()
+        );
+        for (label, timed) in &[("fastest", fastest), ("slowest", slowest)] {
+            if let Some((solution, duration)) = timed {
+                println!(
+                    "  {:<9}  day {} part {} ({})",
+                    label,
+                    solution.day,
+                    solution.part,
+                    report::format_duration(*duration)
+                );
+            }
+        }
+        if !missing.is_empty() {
+            println!("  missing    days {}", format_days(&missing));
+        }
+    }
+}
+
+/// Lists days in order, shortening runs of consecutive days to ranges, such as "8, 11-25"
+fn format_days(days: &[u8]) -> String {
+    let mut ranges: Vec<(u8, u8)> = Vec::new();
+    for &day in days {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == day => *last = day,
+            _ => ranges.push((day, day)),
+        }
+    }
+
+    ranges
+        .iter()
+        .map(|&(first, last)| {
+            if first == last {
+                first.to_string()
+            } else {
+                format!("{}-{}", first, last)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_days() {
+        assert_eq!(format_days(&[8, 11, 12, 13, 25]), "8, 11-13, 25");
+        assert_eq!(format_days(&[1, 2]), "1-2");
+        assert_eq!(format_days(&[]), "");
+    }
+}
@@ -0,0 +1,76 @@
+//! Gives up on solutions which take too long, so that one slow day doesn't hold up the rest
+
+use std::panic;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Parses a time limit such as `30s`, `500ms` or `2m` (a bare number is in seconds)
+pub fn parse_timeout(timeout: &str) -> Result<Duration, String> {
+    let split = timeout
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(timeout.len());
+    let (number, unit) = timeout.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("{} doesn't start with a number", timeout))?;
+
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 60.0 * 60.0,
+        _ => return Err(format!("{} isn't a unit of ms, s, m or h", unit)),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|error| error.to_string())
+}
+
+/// Runs `f` on a thread of its own, returning its result unless it takes longer than `timeout`.
+///
+/// A thread can't be stopped from outside, so when it takes too long it's left running in the
+/// background until it finishes or the program exits. A panic is passed on to the caller.
+pub fn run_with_timeout<T: Send + 'static>(
+    timeout: Duration,
+    f: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    let (sender, receiver) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        // the receiver is gone when this took too long, and then nobody wants the result
+        let _ = sender.send(f());
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => None,
+        Err(RecvTimeoutError::Disconnected) => match thread.join() {
+            Err(panic) => panic::resume_unwind(panic),
+            Ok(()) => unreachable!("the thread sends its result before finishing"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_timeout("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_timeout("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_timeout("1.5m"), Ok(Duration::from_secs(90)));
+        assert!(parse_timeout("30 seconds").is_err());
+        assert!(parse_timeout("s").is_err());
+    }
+
+    #[test]
+    fn test_run_with_timeout() {
+        assert_eq!(run_with_timeout(Duration::from_secs(10), || 4), Some(4));
+        assert_eq!(
+            run_with_timeout(Duration::from_millis(10), || thread::sleep(
+                Duration::from_secs(1)
+            )),
+            None
+        );
+    }
+}
@@ -0,0 +1,308 @@
+//! An interactive dashboard of every solution: whether its answer is correct, how long it last
+//! took, and keys to run it, time it, or watch its visualization.
+//!
+//! Solutions run on a thread of their own, so that the dashboard keeps responding while they do.
+
+use crate::report::{self, Outcome, Unsolved};
+use advent_of_code::answers::Answers;
+use advent_of_code::params::Params;
+#[cfg(feature = "visualize")]
+use advent_of_code::solutions::VISUALIZATIONS;
+use advent_of_code::solutions::{Solution, SOLUTIONS};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// How many times `b` solves a part to time it
+const BENCH_RUNS: usize = 10;
+
+const KEYS: &str = "↑/↓ select  r run  a run all  b bench  v visualize  q quit";
+
+/// Shows the dashboard until it's quit. The answers are checked against `answers`, and the stored
+/// inputs are read from `inputs`.
+pub fn run(inputs: PathBuf, answers: Answers) -> io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result = Dashboard::new(inputs, answers).run(&mut terminal);
+    ratatui::restore();
+
+    result
+}
+
+/// A finished run, sent back from the thread which ran it
+enum Finished {
+    Solved(usize, Result<Outcome, Unsolved>),
+    Benched(usize, Result<Vec<Duration>, Unsolved>),
+}
+
+struct Dashboard {
+    inputs: PathBuf,
+    answers: Answers,
+
+    /// The last result of each solution, in the order of `SOLUTIONS`
+    results: Vec<Option<Result<Outcome, Unsolved>>>,
+    /// Which solutions are waiting to be run, or are running
+    running: Vec<bool>,
+    table: TableState,
+    status: String,
+
+    sender: Sender<Finished>,
+    receiver: Receiver<Finished>,
+}
+
+impl Dashboard {
+    fn new(inputs: PathBuf, answers: Answers) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        Self {
+            inputs,
+            answers,
+            results: vec![None; SOLUTIONS.len()],
+            running: vec![false; SOLUTIONS.len()],
+            table: TableState::default().with_selected(Some(0)),
+            status: String::new(),
+            sender,
+            receiver,
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            while let Ok(finished) = self.receiver.try_recv() {
+                self.finish(finished);
+            }
+            terminal.draw(|frame| self.draw(frame))?;
+
+            // wake up now and then to pick up solutions which have finished
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            let key = match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => key,
+                _ => continue,
+            };
+            let selected = self.table.selected().unwrap_or(0);
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+                KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+                KeyCode::Home | KeyCode::Char('g') => self.table.select_first(),
+                KeyCode::End | KeyCode::Char('G') => self.table.select_last(),
+                KeyCode::Char('r') => self.solve(vec![selected]),
+                KeyCode::Char('a') => self.solve((0..SOLUTIONS.len()).collect()),
+                KeyCode::Char('b') => self.bench(selected),
+                KeyCode::Char('v') => {
+                    ratatui::restore();
+                    self.visualize(selected);
+                    *terminal = ratatui::init();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Solves solutions one after another in the background, so that their timings aren't
+    /// disturbed by each other
+    fn solve(&mut self, indices: Vec<usize>) {
+        if self.running.contains(&true) {
+            self.status = "Wait for the running solutions to finish".to_string();
+            return;
+        }
+        for &i in &indices {
+            self.running[i] = true;
+        }
+        self.status.clear();
+
+        let (inputs, sender) = (self.inputs.clone(), self.sender.clone());
+        thread::spawn(move || {
+            for i in indices {
+                let solution = &SOLUTIONS[i];
+                let result = match solution.stored_input(&inputs) {
+                    Some(input) => crate::solve(solution, &input, &Params::default(), true, None),
+                    None => Err(Unsolved::NoInput),
+                };
+                if sender.send(Finished::Solved(i, result)).is_err() {
+                    // the dashboard has been closed
+                    return;
+                }
+            }
+        });
+    }
+
+    /// Times a solution `BENCH_RUNS` times in the background
+    fn bench(&mut self, i: usize) {
+        if self.running.contains(&true) {
+            self.status = "Wait for the running solutions to finish".to_string();
+            return;
+        }
+        self.running[i] = true;
+        self.status = format!("Timing {} {} times", SOLUTIONS[i], BENCH_RUNS);
+
+        let (inputs, sender) = (self.inputs.clone(), self.sender.clone());
+        thread::spawn(move || {
+            let solution = &SOLUTIONS[i];
+            let durations = match solution.stored_input(&inputs) {
+                Some(input) => Ok((0..BENCH_RUNS)
+                    .map(|_| solution.solve_timed(&input, &Params::default()).1)
+                    .collect()),
+                None => Err(Unsolved::NoInput),
+            };
+            // nobody is left to tell when the dashboard has been closed
+            let _ = sender.send(Finished::Benched(i, durations));
+        });
+    }
+
+    fn finish(&mut self, finished: Finished) {
+        match finished {
+            Finished::Solved(i, result) => {
+                self.running[i] = false;
+                self.results[i] = Some(result);
+            }
+            Finished::Benched(i, Ok(durations)) => {
+                self.running[i] = false;
+
+                let mean = durations.iter().sum::<Duration>() / durations.len() as u32;
+                let fastest = durations.iter().min().copied().unwrap_or_default();
+                self.status = format!(
+                    "{}: {} on average over {} runs, {} at the fastest",
+                    SOLUTIONS[i],
+                    report::format_duration(mean),
+                    durations.len(),
+                    report::format_duration(fastest)
+                );
+            }
+            Finished::Benched(i, Err(unsolved)) => {
+                self.running[i] = false;
+                self.status = format!("{}: {}", SOLUTIONS[i], unsolved);
+            }
+        }
+    }
+
+    /// Runs the visualization of a solution in the terminal, then prints its answer and waits for
+    /// enter before going back to the dashboard
+    #[cfg(feature = "visualize")]
+    fn visualize(&mut self, i: usize) {
+        let solution = &SOLUTIONS[i];
+        let visualization = VISUALIZATIONS
+            .iter()
+            .find(|v| (v.year, v.day, v.part) == (solution.year, solution.day, solution.part));
+
+        self.status = match (visualization, solution.stored_input(&self.inputs)) {
+            (None, _) => format!("{} has no visualization", solution),
+            (Some(_), None) => format!("{}: {}", solution, Unsolved::NoInput),
+            (Some(visualization), Some(input)) => {
+                match visualization.solve(&input, &Params::default()) {
+                    Ok(answer) => println!("{}: {}", solution, answer),
+                    Err(error) => println!("{}: {}", solution, Unsolved::Failed(error)),
+                }
+                println!("Press enter to return to the dashboard");
+                // the dashboard is redrawn either way
+                let _ = io::stdin().read_line(&mut String::new());
+                String::new()
+            }
+        };
+    }
+
+    #[cfg(not(feature = "visualize"))]
+    fn visualize(&mut self, _: usize) {
+        self.status = "Visualizations need the visualize feature".to_string();
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, status_area, keys_area] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let rows = SOLUTIONS.iter().enumerate().map(|(i, solution)| {
+            let (status, style) = self.status_of(i, solution);
+            let (time, answer) = match &self.results[i] {
+                Some(Ok(outcome)) => (
+                    report::format_duration(outcome.duration),
+                    first_line(outcome),
+                ),
+                _ => (String::new(), String::new()),
+            };
+
+            Row::new(vec![
+                Cell::from(solution.year.to_string()),
+                Cell::from(solution.day.to_string()),
+                Cell::from(solution.part.to_string()),
+                Cell::from(status).style(style),
+                Cell::from(time),
+                Cell::from(answer),
+            ])
+        });
+        let header = Row::new(vec!["year", "day", "part", "status", "time", "answer"])
+            .style(Style::new().add_modifier(Modifier::BOLD));
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(3),
+            Constraint::Length(4),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Fill(1),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .block(Block::bordered().title(" Advent of Code "))
+            .column_spacing(2)
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+        frame.render_widget(Paragraph::new(self.status.as_str()), status_area);
+        frame.render_widget(
+            Paragraph::new(Line::from(KEYS)).style(Style::new().add_modifier(Modifier::DIM)),
+            keys_area,
+        );
+    }
+
+    /// Whether a solution's last answer was correct, or why there isn't one
+    fn status_of(&self, i: usize, solution: &Solution) -> (&'static str, Style) {
+        if self.running[i] {
+            return ("running", Style::new().fg(Color::Cyan));
+        }
+
+        match (&self.results[i], self.answers.get(solution)) {
+            (None, _) => ("not run", Style::new().add_modifier(Modifier::DIM)),
+            (Some(Err(Unsolved::NoInput)), _) => {
+                ("no input", Style::new().add_modifier(Modifier::DIM))
+            }
+            (Some(Err(Unsolved::TimedOut(_))), _) => ("timeout", Style::new().fg(Color::Red)),
+            (Some(Err(Unsolved::Failed(_) | Unsolved::Plugin(_))), _) => {
+                ("error", Style::new().fg(Color::Red))
+            }
+            (Some(Err(Unsolved::Panicked)), _) => ("panicked", Style::new().fg(Color::Red)),
+            (Some(Ok(_)), None) => ("unverified", Style::new().fg(Color::Yellow)),
+            (Some(Ok(outcome)), Some(expected)) if outcome.answer.is(expected) => {
+                ("pass", Style::new().fg(Color::Green))
+            }
+            (Some(Ok(_)), Some(_)) => (
+                "FAIL",
+                Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+        }
+    }
+}
+
+/// The first line of an answer, marking that there's more to answers which span several lines
+fn first_line(outcome: &Outcome) -> String {
+    let answer = outcome.answer.to_string();
+    let mut lines = answer.lines();
+    let first = lines.next().unwrap_or_default().to_string();
+
+    match lines.next() {
+        Some(_) => first + " …",
+        None => first,
+    }
+}
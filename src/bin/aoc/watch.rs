@@ -0,0 +1,113 @@
+//! Re-runs a day whenever its source or input changes.
+//!
+//! The source is compiled into this binary, so every run goes through `cargo run`, which rebuilds
+//! it first when needed. Files are polled rather than watched, which is plenty for a handful of
+//! source files.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often to check for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Runs `aoc run` with `arguments` through cargo each time a file in `paths` (or in one of the
+/// directories in `paths`) changes, until interrupted
+pub fn watch(root: &Path, paths: &[PathBuf], arguments: &[String], release: bool) -> ! {
+    let mut last_modified = None;
+
+    loop {
+        let modified = latest_modification(paths);
+        if modified != last_modified {
+            last_modified = modified;
+
+            match cargo_run(root, arguments, release) {
+                Ok(status) if !status.success() => eprintln!("aoc exited with {}", status),
+                Ok(_) => {}
+                Err(error) => eprintln!("Failed to start cargo: {}", error),
+            }
+            eprintln!("Waiting for changes...");
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn cargo_run(root: &Path, arguments: &[String], release: bool) -> io::Result<ExitStatus> {
+    let mut command = Command::new(option_env!("CARGO").unwrap_or("cargo"));
+    command
+        .arg("run")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(root.join("Cargo.toml"));
+    if release {
+        command.arg("--release");
+    }
+
+    // when this is itself run through cargo, the variables cargo sets to describe this package
+    // would otherwise leak into the build and needlessly rebuild dependencies which track them
+    for (variable, _) in env::vars_os() {
+        let name = variable.to_string_lossy();
+        if name.starts_with("CARGO_PKG_")
+            || name.starts_with("CARGO_MANIFEST_")
+            || [
+                "CARGO_CRATE_NAME",
+                "CARGO_BIN_NAME",
+                "CARGO_PRIMARY_PACKAGE",
+            ]
+            .contains(&&*name)
+        {
+            command.env_remove(&variable);
+        }
+    }
+
+    command.arg("--").arg("run").args(arguments).status()
+}
+
+/// The most recent modification time of the files at `paths`, searching directories recursively.
+/// Paths which don't exist are skipped, so that an input appearing counts as a change.
+fn latest_modification(paths: &[PathBuf]) -> Option<SystemTime> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(path).ok()?;
+            if metadata.is_dir() {
+                let entries = fs::read_dir(path).ok()?;
+                let children: Vec<_> = entries
+                    .filter_map(|entry| Some(entry.ok()?.path()))
+                    .collect();
+                latest_modification(&children)
+            } else {
+                metadata.modified().ok()
+            }
+        })
+        .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latest_modification() {
+        let directory = env::temp_dir().join(format!("watch-{}", std::process::id()));
+        fs::create_dir_all(directory.join("year_2019")).unwrap();
+        let day = directory.join("year_2019").join("day7.rs");
+        fs::write(&day, "").unwrap();
+
+        let paths = [directory.clone(), directory.join("input.txt")];
+        let before = latest_modification(&paths);
+        assert_eq!(before, fs::metadata(&day).unwrap().modified().ok());
+
+        thread::sleep(Duration::from_millis(20));
+        fs::write(directory.join("input.txt"), "3,0,4,0,99").unwrap();
+        let after = latest_modification(&paths);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(after > before);
+    }
+}
@@ -0,0 +1,134 @@
+//! When puzzles unlock, and the date arithmetic needed to work that out without pulling in a date
+//! library
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The last day of each event
+pub const LAST_DAY: u8 = 25;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Puzzles unlock at midnight EST (UTC-5) on each day of December
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_since_epoch(i64::from(year), 12, i64::from(day));
+    let seconds = days * SECONDS_PER_DAY as i64 + 5 * 60 * 60;
+
+    UNIX_EPOCH + Duration::from_secs(seconds.max(0) as u64)
+}
+
+/// The first puzzle to unlock after `now`, and when it unlocks
+pub fn next_unlock(now: SystemTime) -> (u16, u8, SystemTime) {
+    let (year, _, _) = civil_from_days(days_since_unix_epoch(now));
+    let year = year as u16;
+
+    (year..=year + 1)
+        .flat_map(|year| (1..=LAST_DAY).map(move |day| (year, day, unlock_time(year, day))))
+        .find(|(_, _, unlock)| *unlock > now)
+        .expect("next year's puzzles are all after now")
+}
+
+/// Formats a time as UTC in ISO 8601, such as `2019-12-01T05:00:00Z`
+pub fn format_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (year, month, day) = civil_from_days((seconds / SECONDS_PER_DAY) as i64);
+    let seconds_of_day = seconds % SECONDS_PER_DAY;
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60
+    )
+}
+
+fn days_since_unix_epoch(time: SystemTime) -> i64 {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    (seconds / SECONDS_PER_DAY) as i64
+}
+
+/// The number of days from 1970-01-01 to a date in the proleptic Gregorian calendar
+fn days_since_epoch(year: i64, month: i64, day: i64) -> i64 {
+    // Howard Hinnant's days_from_civil, which counts years from March so that the leap day is the
+    // last day of a year
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date which is a number of days after 1970-01-01, the inverse of `days_since_epoch`
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    // Howard Hinnant's civil_from_days, which counts years from March too
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlock_time() {
+        // 2018-12-01T05:00:00Z
+        assert_eq!(
+            unlock_time(2018, 1),
+            UNIX_EPOCH + Duration::from_secs(1_543_640_400)
+        );
+        // 2019-12-25T05:00:00Z
+        assert_eq!(
+            unlock_time(2019, 25),
+            UNIX_EPOCH + Duration::from_secs(1_577_250_000)
+        );
+    }
+
+    #[test]
+    fn test_next_unlock() {
+        // during an event, the next day
+        let (year, day, _) = next_unlock(unlock_time(2019, 7));
+        assert_eq!((year, day), (2019, 8));
+
+        // after the last day, the first day of the next year
+        let after = unlock_time(2019, 25) + Duration::from_secs(60);
+        assert_eq!(next_unlock(after), (2020, 1, unlock_time(2020, 1)));
+
+        // before the event, the first day
+        let (year, day, _) = next_unlock(UNIX_EPOCH + Duration::from_secs(1_561_939_200));
+        assert_eq!((year, day), (2019, 1));
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(
+            format_timestamp(unlock_time(2019, 1)),
+            "2019-12-01T05:00:00Z"
+        );
+        assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400 + 3599)),
+            "2000-02-29T00:59:59Z"
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! An arena of values of one type, allocated one after another into a single `Vec` and referred to
+//! by `Id`s, for puzzles which build graphs or trees of many small nodes (such as the steps of 2018
+//! day 7, or the branches of 2018 day 20's map). The nodes and the lists between them take a
+//! handful of allocations in all rather than a `Box` or `Vec` each, and are all freed together.
+//!
+//! A node's list of other nodes (its children, say) is allocated in one go as a `Span` of a second
+//! arena, once the whole list is known. Lists built up while their own items are still being built
+//! (as when parsing a tree) can gather on a stack which every list shares, then move into the arena
+//! with `alloc_extend(stack.drain(start..))`.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::ops::{Index, IndexMut};
+
+/// Values allocated one after another, which are never freed until the whole arena is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arena<T> {
+    items: Vec<T>,
+}
+
+/// The place of a value in an `Arena<T>`
+pub struct Id<T> {
+    index: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+/// The places of values allocated together in an `Arena<T>`, one after another
+pub struct Span<T> {
+    start: usize,
+    end: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self { items: Vec::new() }
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty arena with room for `capacity` values before it has to grow
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn alloc(&mut self, value: T) -> Id<T> {
+        self.items.push(value);
+        Id::new(self.items.len() - 1)
+    }
+
+    /// Allocates every value one after another, as a span which can be indexed as a slice
+    pub fn alloc_extend(&mut self, values: impl IntoIterator<Item = T>) -> Span<T> {
+        let start = self.items.len();
+        self.items.extend(values);
+        Span {
+            start,
+            end: self.items.len(),
+            marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Every value along with its id, in the order they were allocated
+    pub fn iter(&self) -> impl Iterator<Item = (Id<T>, &T)> {
+        self.items
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (Id::new(index), value))
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Id<T>, &mut T)> {
+        self.items
+            .iter_mut()
+            .enumerate()
+            .map(|(index, value)| (Id::new(index), value))
+    }
+
+    /// Every value, in the order they were allocated
+    pub fn values(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T> Id<T> {
+    fn new(index: usize) -> Self {
+        Self {
+            index,
+            marker: PhantomData,
+        }
+    }
+
+    /// Where the value is in the order values were allocated, counting from 0, such as to keep
+    /// something about each value in a `Vec` alongside the arena
+    pub fn index(self) -> usize {
+        self.index
+    }
+}
+
+impl<T> Span<T> {
+    /// A span of no values, for nodes whose lists are empty
+    pub fn empty() -> Self {
+        Self {
+            start: 0,
+            end: 0,
+            marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// The id of each value in the span, in order
+    pub fn ids(self) -> impl Iterator<Item = Id<T>> {
+        (self.start..self.end).map(Id::new)
+    }
+}
+
+impl<T> Index<Id<T>> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, id: Id<T>) -> &T {
+        &self.items[id.index]
+    }
+}
+
+impl<T> IndexMut<Id<T>> for Arena<T> {
+    fn index_mut(&mut self, id: Id<T>) -> &mut T {
+        &mut self.items[id.index]
+    }
+}
+
+impl<T> Index<Span<T>> for Arena<T> {
+    type Output = [T];
+
+    fn index(&self, span: Span<T>) -> &[T] {
+        &self.items[span.start..span.end]
+    }
+}
+
+impl<T> IndexMut<Span<T>> for Arena<T> {
+    fn index_mut(&mut self, span: Span<T>) -> &mut [T] {
+        &mut self.items[span.start..span.end]
+    }
+}
+
+// ids and spans are only numbers, whatever they refer to, so these aren't derived (which would
+// need `T` to have them too)
+
+impl<T> Clone for Id<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Id<T> {}
+
+impl<T> PartialEq for Id<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<T> Eq for Id<T> {}
+
+impl<T> PartialOrd for Id<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Id<T> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<T> Hash for Id<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Id<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Id({})", self.index)
+    }
+}
+
+impl<T> Clone for Span<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Span<T> {}
+
+impl<T> PartialEq for Span<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.start, self.end) == (other.start, other.end)
+    }
+}
+
+impl<T> Eq for Span<T> {}
+
+impl<T> fmt::Debug for Span<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Span({}..{})", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena() {
+        // a tree of names, each with a span of its children's ids in a second arena
+        let mut names = Arena::new();
+        let mut children: Arena<Id<&str>> = Arena::new();
+        let leaves = ["b", "c", "d"].map(|name| names.alloc(name));
+        let root = names.alloc("a");
+        let root_children = children.alloc_extend(leaves);
+
+        assert_eq!(names[root], "a");
+        assert_eq!(root.index(), 3);
+        assert_eq!(root_children.len(), 3);
+        assert_eq!(
+            children[root_children]
+                .iter()
+                .map(|child| names[*child])
+                .collect::<Vec<_>>(),
+            ["b", "c", "d"]
+        );
+
+        names[leaves[1]] = "e";
+        assert_eq!(names.values(), ["b", "e", "d", "a"]);
+        assert_eq!(names.iter().nth(1), Some((leaves[1], &"e")));
+        assert_eq!(
+            root_children.ids().collect::<Vec<_>>(),
+            children.iter().map(|(id, _)| id).collect::<Vec<_>>()
+        );
+
+        let none = children.alloc_extend(None);
+        assert!(none.is_empty());
+        assert_eq!(children[none], []);
+        assert!(children[Span::empty()].is_empty());
+        assert_eq!(children.len(), 3);
+    }
+}
@@ -0,0 +1,142 @@
+//! Intermediate data a solution hands over to be looked at afterwards, such as a parsed grid, a
+//! path it found or the state after each round, in place of printing it while debugging.
+//!
+//! `aoc run --debug-artifacts` sets `directory()` to `artifacts/{year}/{day}`, emptying it first,
+//! and each `emit` writes a text file into it. While a part is solved its files are named after it
+//! (`part1-grid.txt`), and a name emitted more than once is numbered after the first, such as
+//! `part1-round-2.txt`, so that a file is written for each round of a battle. Otherwise `emit`
+//! does nothing, and the artifact isn't even formatted, so emitting costs next to nothing unless
+//! it's asked for.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+/// The directory artifacts are written into, relative to the working directory, with a directory
+/// for each day within one for its year
+pub const ARTIFACTS_DIRECTORY: &str = "artifacts";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ARTIFACTS: Mutex<Artifacts> = Mutex::new(Artifacts {
+    directory: None,
+    part: None,
+    emitted: BTreeMap::new(),
+});
+
+struct Artifacts {
+    directory: Option<PathBuf>,
+    /// The part being solved, if any
+    part: Option<u8>,
+    /// How many times each file name has been written so far
+    emitted: BTreeMap<String, usize>,
+}
+
+fn artifacts() -> MutexGuard<'static, Artifacts> {
+    ARTIFACTS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Whether artifacts are being written, for a solution which has to do more than format its
+/// artifact to emit it
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// The directory artifacts are being written into, if any
+pub fn directory() -> Option<PathBuf> {
+    artifacts().directory.clone()
+}
+
+/// Has the solutions run after it write their artifacts into `directory` (which is emptied, or
+/// created when it doesn't exist), or stop writing them
+pub fn set_directory(directory: Option<PathBuf>) -> io::Result<()> {
+    if let Some(directory) = &directory {
+        match fs::remove_dir_all(directory) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+            _ => fs::create_dir_all(directory)?,
+        }
+    }
+
+    let mut artifacts = artifacts();
+    ENABLED.store(directory.is_some(), Ordering::Relaxed);
+    artifacts.directory = directory;
+    artifacts.emitted.clear();
+    Ok(())
+}
+
+/// Names the files emitted after it after `part`, or after nothing (as while an input is parsed)
+pub fn set_part(part: Option<u8>) {
+    artifacts().part = part;
+}
+
+/// Writes `artifact` into a file named after `name`, when artifacts are being written
+///
+/// Panics when the file can't be written, as that only happens while debugging.
+pub fn emit(name: &str, artifact: impl fmt::Display) {
+    if !enabled() {
+        return;
+    }
+
+    let mut artifacts = artifacts();
+    let Some(directory) = artifacts.directory.clone() else {
+        return;
+    };
+    let name = match artifacts.part {
+        Some(part) => format!("part{}-{}", part, name),
+        None => name.to_string(),
+    };
+    let count = artifacts.emitted.entry(name.clone()).or_insert(0);
+    *count += 1;
+
+    let path = match count {
+        1 => directory.join(format!("{}.txt", name)),
+        n => directory.join(format!("{}-{}.txt", name, n)),
+    };
+    if let Err(error) = fs::write(&path, artifact.to_string()) {
+        panic!("Failed to write {}: {}", path.display(), error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit() {
+        // not written anywhere
+        emit("grid", "#.#");
+
+        let directory = std::env::temp_dir().join(format!("artifacts-{}", std::process::id()));
+        set_directory(Some(directory.clone())).unwrap();
+        fs::write(directory.join("stale.txt"), "").unwrap();
+        set_directory(Some(directory.clone())).unwrap();
+        assert!(enabled());
+
+        emit("grid", "#.#");
+        set_part(Some(1));
+        emit("round", 1);
+        emit("round", 2);
+        set_part(None);
+
+        // other tests may emit artifacts of their own meanwhile
+        let files: Vec<_> = fs::read_dir(&directory)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        let round = fs::read_to_string(directory.join("part1-round-2.txt"));
+        set_directory(None).unwrap();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert!(!enabled());
+        for file in ["grid.txt", "part1-round.txt", "part1-round-2.txt"] {
+            assert!(files.iter().any(|f| f == file), "{} wasn't written", file);
+        }
+        assert!(!files.iter().any(|f| f == "stale.txt"));
+        assert_eq!(round.unwrap(), "2");
+    }
+}
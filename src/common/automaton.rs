@@ -0,0 +1,182 @@
+//! Cellular automata: grids where each generation, every cell becomes what a rule makes of it and
+//! of how many of each kind of cell are around it (such as an acre of open ground which becomes
+//! trees when three or more acres around it are trees).
+//!
+//! Dense automata step a `Grid2D`, where cells off its edges don't count. Sparse ones step a
+//! `SparseGrid`, which grows as far as its cells spread, with unset cells counted as nothing.
+
+use crate::common::cycles::nth_state;
+use crate::common::geometry::Point;
+use crate::common::grid::{Grid2D, SparseGrid};
+use crate::common::Counter;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// Which cells around a cell count towards its rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    /// The four cells above, below and to either side
+    VonNeumann,
+
+    /// The eight cells around, diagonals included
+    Moore,
+}
+
+impl Neighborhood {
+    fn around(self, point: Point) -> Vec<Point> {
+        match self {
+            Self::VonNeumann => point.neighbors4().to_vec(),
+            Self::Moore => point.neighbors8().to_vec(),
+        }
+    }
+}
+
+/// The next generation of `grid`, with `rule` given each cell and the counts of the cells around
+/// it
+pub fn step_dense<T: Hash + Eq>(
+    grid: &Grid2D<T>,
+    neighborhood: Neighborhood,
+    rule: impl Fn(&T, &Counter<&T>) -> T,
+) -> Grid2D<T> {
+    let cells = grid
+        .iter()
+        .map(|(position, cell)| {
+            let neighbors = match neighborhood {
+                Neighborhood::VonNeumann => grid.neighbors4(position).collect::<Vec<_>>(),
+                Neighborhood::Moore => grid.neighbors8(position).collect(),
+            };
+            let counts = neighbors
+                .into_iter()
+                .map(|neighbor| &grid[neighbor])
+                .collect();
+            rule(cell, &counts)
+        })
+        .collect();
+
+    Grid2D::from_cells(grid.width(), cells)
+}
+
+/// The next generation of `grid`, with `rule` given each cell (or `None` where it isn't set) and
+/// the counts of the cells set around it, and returning what the cell becomes (or `None` to leave
+/// it unset). Only the cells which are set and those around them are looked at, so a cell with
+/// nothing around it must stay unset.
+pub fn step_sparse<T: Hash + Eq>(
+    grid: &SparseGrid<T>,
+    neighborhood: Neighborhood,
+    rule: impl Fn(Option<&T>, &Counter<&T>) -> Option<T>,
+) -> SparseGrid<T> {
+    let candidates: HashSet<Point> = grid
+        .iter()
+        .flat_map(|(point, _)| neighborhood.around(point).into_iter().chain(Some(point)))
+        .collect();
+
+    candidates
+        .into_iter()
+        .filter_map(|point| {
+            let counts = neighborhood
+                .around(point)
+                .into_iter()
+                .filter_map(|neighbor| grid.get(neighbor))
+                .collect();
+            rule(grid.get(point), &counts).map(|cell| (point, cell))
+        })
+        .collect()
+}
+
+/// The state after `generations` steps, stepping through every one of them
+pub fn run<S>(initial: S, mut step: impl FnMut(&S) -> S, generations: u64) -> S {
+    let mut state = initial;
+    for _ in 0..generations {
+        state = step(&state);
+    }
+
+    state
+}
+
+/// The state after `generations` steps, stepping only until the states repeat, which is how
+/// puzzles asking for a billion generations can be answered. States are told apart by their
+/// hashes, so that finding the cycle needn't keep copies of them.
+pub fn run_detecting_cycles<S: Clone + Hash>(
+    initial: S,
+    step: impl FnMut(&S) -> S,
+    generations: u64,
+) -> S {
+    nth_state(initial, step, state_hash, generations)
+}
+
+/// A hash of a state, to tell states apart by
+pub fn state_hash<S: Hash>(state: &S) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    state.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The rule of Conway's game of life, where `#` is alive
+    fn life(cell: &char, neighbors: &Counter<&char>) -> char {
+        match (cell, neighbors.count(&&'#')) {
+            ('#', 2) | (_, 3) => '#',
+            _ => '.',
+        }
+    }
+
+    #[test]
+    fn test_automaton() {
+        let blinker = Grid2D::from_chars(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let flipped = step_dense(&blinker, Neighborhood::Moore, life);
+        assert_eq!(flipped.to_string(), ".....\n.....\n.###.\n.....\n.....");
+        assert_eq!(step_dense(&flipped, Neighborhood::Moore, life), blinker);
+
+        let step = |grid: &Grid2D<char>| step_dense(grid, Neighborhood::Moore, life);
+        assert_eq!(run(blinker.clone(), step, 1_001), flipped);
+        assert_eq!(
+            run_detecting_cycles(blinker.clone(), step, 1_000_000_001),
+            flipped
+        );
+
+        // a glider moves a cell down and right every four generations, however far it goes
+        let glider: SparseGrid<char> = Grid2D::from_chars(".#.\n..#\n###")
+            .unwrap()
+            .iter()
+            .filter(|(_, c)| **c == '#')
+            .map(|((x, y), c)| (Point::new(x as i64, y as i64), *c))
+            .collect();
+        let step = |grid: &SparseGrid<char>| {
+            step_sparse(grid, Neighborhood::Moore, |cell, neighbors| {
+                Some(life(cell.unwrap_or(&'.'), neighbors)).filter(|c| *c == '#')
+            })
+        };
+        let moved = run(glider.clone(), step, 40);
+        assert_eq!(moved.to_string(), glider.to_string());
+        assert_eq!(
+            moved.bounds(),
+            Some((Point::new(10, 10), Point::new(12, 12)))
+        );
+
+        // with only the cells beside it counted, a cell between two others is crowded out
+        let line: SparseGrid<char> = (0..3).map(|x| (Point::new(x, 0), '#')).collect();
+        let step = step_sparse(&line, Neighborhood::VonNeumann, |cell, neighbors| {
+            cell.filter(|_| neighbors.total() < 2).copied()
+        });
+        assert_eq!(step.to_string(), "# #");
+
+        let blinker: SparseGrid<char> = (0..3).map(|y| (Point::new(0, y), '#')).collect();
+        let flipped = run_detecting_cycles(
+            blinker,
+            |grid| {
+                step_sparse(grid, Neighborhood::Moore, |cell, neighbors| {
+                    Some(life(cell.unwrap_or(&'.'), neighbors)).filter(|c| *c == '#')
+                })
+            },
+            999_999_999,
+        );
+        assert_eq!(
+            flipped.bounds(),
+            Some((Point::new(-1, 1), Point::new(1, 1)))
+        );
+    }
+}
@@ -0,0 +1,237 @@
+//! Sets of small numbers kept a bit to each, for searches which visit millions of positions, where
+//! a `HashSet` of them spends more time hashing (and far more memory) than the search itself.
+
+use std::iter::FromIterator;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A set of `usize`s, taking a bit for every number up to the largest in it
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty set with room for the numbers below `bits` without growing
+    pub fn with_capacity(bits: usize) -> Self {
+        Self {
+            words: vec![0; bits.div_ceil(WORD_BITS)],
+            len: 0,
+        }
+    }
+
+    /// Adds `n`, returning whether it wasn't already in the set (as `HashSet::insert` does)
+    pub fn insert(&mut self, n: usize) -> bool {
+        let (word, bit) = (n / WORD_BITS, 1 << (n % WORD_BITS));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        self.len += usize::from(added);
+        added
+    }
+
+    /// Takes out `n`, returning whether it was in the set
+    pub fn remove(&mut self, n: usize) -> bool {
+        let (word, bit) = (n / WORD_BITS, 1 << (n % WORD_BITS));
+        let removed = self.words.get(word).is_some_and(|w| w & bit != 0);
+        if removed {
+            self.words[word] &= !bit;
+            self.len -= 1;
+        }
+        removed
+    }
+
+    pub fn contains(&self, n: usize) -> bool {
+        self.words
+            .get(n / WORD_BITS)
+            .is_some_and(|word| word & (1 << (n % WORD_BITS)) != 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Takes out every number, keeping the room for them
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|word| *word = 0);
+        self.len = 0;
+    }
+
+    /// Adds every number of `other`
+    pub fn union_with(&mut self, other: &BitSet) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (word, other) in self.words.iter_mut().zip(&other.words) {
+            *word |= other;
+        }
+        self.len = self
+            .words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum();
+    }
+
+    /// The numbers in the set, smallest first
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * WORD_BITS + bit)
+            })
+        })
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(numbers: I) -> Self {
+        let mut set = BitSet::new();
+        set.extend(numbers);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, numbers: I) {
+        for n in numbers {
+            self.insert(n);
+        }
+    }
+}
+
+/// A set of the positions of a grid, indexed by `(x, y)` as a `Grid2D` is, such as the cells a
+/// flood fill has reached
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    bits: BitSet,
+}
+
+impl BitGrid {
+    /// An empty set of the positions of a grid `width` by `height`
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            bits: BitSet::with_capacity(width * height),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Adds a position, returning whether it wasn't already in the set. Panics when it's off the
+    /// grid.
+    pub fn insert(&mut self, position: (usize, usize)) -> bool {
+        let index = self.index(position).unwrap_or_else(|| {
+            panic!(
+                "{:?} is off a {}x{} grid",
+                position, self.width, self.height
+            )
+        });
+        self.bits.insert(index)
+    }
+
+    /// Takes out a position, returning whether it was in the set
+    pub fn remove(&mut self, position: (usize, usize)) -> bool {
+        self.index(position)
+            .is_some_and(|index| self.bits.remove(index))
+    }
+
+    /// Whether a position is in the set, which those off the grid never are
+    pub fn contains(&self, position: (usize, usize)) -> bool {
+        self.index(position)
+            .is_some_and(|index| self.bits.contains(index))
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// The positions in the set, in reading order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.bits
+            .iter()
+            .map(move |index| (index % self.width, index / self.width))
+    }
+
+    fn index(&self, (x, y): (usize, usize)) -> Option<usize> {
+        if x < self.width && y < self.height {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset() {
+        let mut set: BitSet = vec![3, 64, 0, 200, 3].into_iter().collect();
+        assert_eq!(set.len(), 4);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [0, 3, 64, 200]);
+        assert!(set.contains(64) && !set.contains(63) && !set.contains(10_000));
+
+        assert!(set.insert(63));
+        assert!(!set.insert(63));
+        assert!(set.remove(0));
+        assert!(!set.remove(0) && !set.remove(10_000));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 63, 64, 200]);
+
+        let mut other: BitSet = vec![1, 3, 500].into_iter().collect();
+        other.union_with(&set);
+        assert_eq!(other.iter().collect::<Vec<_>>(), [1, 3, 63, 64, 200, 500]);
+        assert_eq!(other.len(), 6);
+        other.clear();
+        assert!(other.is_empty() && other.iter().next().is_none());
+
+        let mut visited = BitGrid::new(100, 50);
+        assert!(visited.insert((99, 0)));
+        assert!(visited.insert((0, 49)));
+        assert!(!visited.insert((99, 0)));
+        assert!(visited.contains((0, 49)) && !visited.contains((100, 0)));
+        assert_eq!(visited.iter().collect::<Vec<_>>(), [(99, 0), (0, 49)]);
+        assert!(visited.remove((99, 0)) && !visited.remove((99, 0)));
+        assert_eq!(visited.len(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "(3, 0) is off a 3x3 grid")]
+    fn test_bitgrid_off_grid() {
+        BitGrid::new(3, 3).insert((3, 0));
+    }
+}
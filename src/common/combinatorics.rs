@@ -0,0 +1,132 @@
+//! Every ordering or choice of some items, for puzzles which are solved by trying them all (such
+//! as the phase settings of the amplifiers of 2019 day 7)
+
+/// Every ordering of `items`, in lexicographic order of their positions (so starting with `items`
+/// as they are). Items which are equal are still told apart, so orderings may repeat.
+pub fn permutations<T: Clone>(items: &[T]) -> Permutations<'_, T> {
+    Permutations {
+        items,
+        indices: Some((0..items.len()).collect()),
+    }
+}
+
+/// Every choice of `k` of `items`, each in the order they appear in `items`, in lexicographic order
+/// of their positions. There are none when `k` is more than the number of items.
+pub fn combinations<T: Clone>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations {
+        items,
+        indices: if k <= items.len() {
+            Some((0..k).collect())
+        } else {
+            None
+        },
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    /// The positions of the next ordering, or `None` once they've all been given
+    indices: Option<Vec<usize>>,
+}
+
+impl<T: Clone> Iterator for Permutations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let items = self.items;
+        let indices = self.indices.as_mut()?;
+        let permutation = indices.iter().map(|&i| items[i].clone()).collect();
+
+        // the next ordering in lexicographic order: swap the last position which is followed by a
+        // larger one with the smallest larger one after it, and reverse everything after it
+        match (1..indices.len())
+            .rev()
+            .find(|&i| indices[i - 1] < indices[i])
+        {
+            Some(i) => {
+                let pivot = i - 1;
+                let successor = (i..indices.len())
+                    .rev()
+                    .find(|&j| indices[j] > indices[pivot])
+                    .expect("the position after the pivot is larger");
+                indices.swap(pivot, successor);
+                indices[i..].reverse();
+            }
+            None => self.indices = None,
+        }
+
+        Some(permutation)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    /// The positions of the next choice, or `None` once they've all been given
+    indices: Option<Vec<usize>>,
+}
+
+impl<T: Clone> Iterator for Combinations<'_, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let items = self.items;
+        let indices = self.indices.as_mut()?;
+        let combination = indices.iter().map(|&i| items[i].clone()).collect();
+
+        // move along the last position which can be, and put those after it right behind it
+        let (n, k) = (items.len(), indices.len());
+        match (0..k).rev().find(|&i| indices[i] < n - k + i) {
+            Some(i) => {
+                indices[i] += 1;
+                for j in i + 1..k {
+                    indices[j] = indices[j - 1] + 1;
+                }
+            }
+            None => self.indices = None,
+        }
+
+        Some(combination)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combinatorics() {
+        assert_eq!(
+            permutations(&[1, 2, 3]).collect::<Vec<_>>(),
+            [
+                [1, 2, 3],
+                [1, 3, 2],
+                [2, 1, 3],
+                [2, 3, 1],
+                [3, 1, 2],
+                [3, 2, 1]
+            ]
+        );
+        assert_eq!(permutations(&[0, 1, 2, 3, 4]).count(), 120);
+        assert_eq!(permutations(&['a', 'a']).count(), 2);
+        assert_eq!(
+            permutations::<u8>(&[]).collect::<Vec<_>>(),
+            [Vec::<u8>::new()]
+        );
+
+        assert_eq!(
+            combinations(&['a', 'b', 'c', 'd'], 2)
+                .map(|c| c.into_iter().collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ab", "ac", "ad", "bc", "bd", "cd"]
+        );
+        assert_eq!(combinations(&[1, 2, 3], 3).collect::<Vec<_>>(), [[1, 2, 3]]);
+        assert_eq!(
+            combinations(&[1, 2, 3], 0).collect::<Vec<_>>(),
+            [Vec::<i32>::new()]
+        );
+        assert_eq!(combinations(&[1, 2, 3], 4).count(), 0);
+        assert_eq!(combinations(&(0..10).collect::<Vec<_>>(), 3).count(), 120);
+    }
+}
@@ -0,0 +1,166 @@
+//! Counting how many times each item turns up, such as the letters of a box ID or the minutes a
+//! guard spends asleep, without rebuilding a map of counts by hand each time.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::ops::{Add, AddAssign, Sub};
+
+/// A multiset: each item along with the number of times it was added. Items which were never added
+/// (or were all taken away again) are counted as 0, and aren't kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter<T: Hash + Eq> {
+    counts: HashMap<T, usize>,
+}
+
+impl<T: Hash + Eq> Default for Counter<T> {
+    fn default() -> Self {
+        Self {
+            counts: HashMap::new(),
+        }
+    }
+}
+
+impl<T: Hash + Eq> Counter<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts `item` once more
+    pub fn add(&mut self, item: T) {
+        self.add_n(item, 1);
+    }
+
+    /// Counts `item` `n` more times
+    pub fn add_n(&mut self, item: T, n: usize) {
+        if n > 0 {
+            *self.counts.entry(item).or_insert(0) += n;
+        }
+    }
+
+    /// The number of times `item` was counted
+    pub fn count(&self, item: &T) -> usize {
+        self.counts.get(item).copied().unwrap_or(0)
+    }
+
+    /// The number of different items counted
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// The number of times every item was counted, all together
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Each different item with its count, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&T, usize)> {
+        self.counts.iter().map(|(item, &count)| (item, count))
+    }
+
+    /// Each different item with its count, the most common first, and the smallest item first
+    /// among those counted as often (so the order is the same every time)
+    pub fn most_common(&self) -> Vec<(&T, usize)>
+    where
+        T: Ord,
+    {
+        let mut counts: Vec<_> = self.iter().collect();
+        counts.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+        counts
+    }
+
+    /// The most common item with its count (the smallest of them when there's a tie), or `None`
+    /// when nothing was counted
+    pub fn most_common_one(&self) -> Option<(&T, usize)>
+    where
+        T: Ord,
+    {
+        self.iter()
+            .max_by(|(a, a_count), (b, b_count)| a_count.cmp(b_count).then(b.cmp(a)))
+    }
+}
+
+impl<T: Hash + Eq> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(items);
+        counter
+    }
+}
+
+impl<T: Hash + Eq> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, items: I) {
+        for item in items {
+            self.add(item);
+        }
+    }
+}
+
+/// The counts of both counters added together
+impl<T: Hash + Eq> Add for Counter<T> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+        self
+    }
+}
+
+impl<T: Hash + Eq> AddAssign for Counter<T> {
+    fn add_assign(&mut self, other: Self) {
+        for (item, count) in other.counts {
+            self.add_n(item, count);
+        }
+    }
+}
+
+/// The counts of the first counter less those of the second, where counts which would go below 0
+/// stop at 0 (and the item is dropped)
+impl<T: Hash + Eq> Sub for Counter<T> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        for (item, count) in other.counts {
+            if let Some(own) = self.counts.get_mut(&item) {
+                if *own > count {
+                    *own -= count;
+                } else {
+                    self.counts.remove(&item);
+                }
+            }
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter() {
+        let letters: Counter<char> = "bababc".chars().collect();
+        assert_eq!(letters.count(&'a'), 2);
+        assert_eq!(letters.count(&'b'), 3);
+        assert_eq!(letters.count(&'z'), 0);
+        assert_eq!((letters.len(), letters.total()), (3, 6));
+        assert_eq!(letters.most_common(), [(&'b', 3), (&'a', 2), (&'c', 1)]);
+
+        let tied: Counter<u8> = [4, 2, 4, 2, 9].iter().copied().collect();
+        assert_eq!(tied.most_common_one(), Some((&2, 2)));
+        assert_eq!(Counter::<u8>::new().most_common_one(), None);
+
+        let more: Counter<char> = "aad".chars().collect();
+        let sum = letters.clone() + more.clone();
+        assert_eq!((sum.count(&'a'), sum.count(&'d'), sum.total()), (4, 1, 9));
+
+        let difference = letters - more;
+        assert_eq!(difference.most_common(), [(&'b', 3), (&'c', 1)]);
+        assert!((Counter::from_iter("ab".chars()) - "abb".chars().collect()).is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+//! Finding where a simulation starts to repeat itself, for puzzles which ask for its state after
+//! far more steps than could be simulated (such as a billion minutes).
+//!
+//! States are compared by a key, which is the state itself for simulations which repeat exactly.
+//! Those which drift as they repeat (such as a pattern moving along as it grows) can key on the
+//! shape alone, and extrapolate the drift from the difference one cycle makes.
+
+/// The number of steps before the states start to repeat and the length of the cycle they repeat
+/// in, as `(start, length)`, using Brent's algorithm. Every state reached by `step` must have a
+/// key, and the keys must eventually repeat, or this never returns.
+pub fn find_cycle<S: Clone, K: Eq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    key: impl Fn(&S) -> K,
+) -> (usize, usize) {
+    // find the length, by moving the tortoise up to the hare at each power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = key(&initial);
+    let mut hare = step(&initial);
+    while tortoise != key(&hare) {
+        if power == length {
+            tortoise = key(&hare);
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // then the start, by moving two states a length apart until they meet
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    (start, length)
+}
+
+/// The step before the end of the first cycle which the states at step `n` are the same as, for a
+/// cycle found by `find_cycle`
+pub fn equivalent_step((start, length): (usize, usize), n: u64) -> u64 {
+    let (start, length) = (start as u64, length as u64);
+    if n < start {
+        n
+    } else {
+        start + (n - start) % length
+    }
+}
+
+/// The state after `n` steps, simulating only as many as it takes to find the cycle and to reach
+/// the equivalent step within it
+pub fn nth_state<S: Clone, K: Eq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    key: impl Fn(&S) -> K,
+    n: u64,
+) -> S {
+    let cycle = find_cycle(initial.clone(), &mut step, key);
+
+    let mut state = initial;
+    for _ in 0..equivalent_step(cycle, n) {
+        state = step(&state);
+    }
+
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cycles() {
+        // powers of 3 modulo 25 go through all 20 numbers which share no factor with 25
+        let step = |x: &u64| x * 3 % 25;
+        assert_eq!(find_cycle(3, step, |x| *x), (0, 20));
+        assert_eq!(find_cycle(5, step, |x| *x), (0, 4));
+
+        // the states of a list which goes 0, 1, 2, then round 3, 4, 5, 6
+        let next = [1, 2, 3, 4, 5, 6, 3];
+        let cycle = find_cycle(0, |x: &usize| next[*x], |x| *x);
+        assert_eq!(cycle, (3, 4));
+        assert_eq!(equivalent_step(cycle, 2), 2);
+        assert_eq!(equivalent_step(cycle, 1_000_000_000), 4);
+        assert_eq!(nth_state(0, |x: &usize| next[*x], |x| *x, 1_000_000_000), 4);
+
+        // keyed on the shape alone, a pattern which moves along each step repeats every step
+        let drifting = find_cycle(
+            (0, "#.#"),
+            |(offset, pattern)| (offset + 2, *pattern),
+            |s| s.1,
+        );
+        assert_eq!(drifting, (0, 1));
+    }
+}
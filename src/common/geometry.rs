@@ -0,0 +1,522 @@
+//! Points on a grid and the directions to move between them, as puzzles draw them: `x` grows to
+//! the right and `y` grows downwards, so that `Up` is towards `y` of -1
+//!
+//! Along with points in space and the boxes around them, for the puzzles in three dimensions
+
+use crate::common::math::gcd;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position on a grid, or the offset between two positions
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_distance(self, to: Point) -> i64 {
+        (self.x - to.x).abs() + (self.y - to.y).abs()
+    }
+
+    /// The point a step away in `direction`
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.unit()
+    }
+
+    /// The point `distance` steps away in `direction`, or `None` when it is further than an `i64`
+    /// goes
+    pub fn checked_steps(self, direction: Direction, distance: i64) -> Option<Self> {
+        let unit = direction.unit();
+        Some(Self {
+            x: self.x.checked_add(unit.x.checked_mul(distance)?)?,
+            y: self.y.checked_add(unit.y.checked_mul(distance)?)?,
+        })
+    }
+
+    /// The points a step away in each direction, in reading order (above, left, right, below)
+    pub fn neighbors4(self) -> [Point; 4] {
+        [
+            self.step(Direction::Up),
+            self.step(Direction::Left),
+            self.step(Direction::Right),
+            self.step(Direction::Down),
+        ]
+    }
+
+    /// The points around this one, diagonals included, in reading order
+    pub fn neighbors8(self) -> [Point; 8] {
+        [
+            self + Point::new(-1, -1),
+            self + Point::new(0, -1),
+            self + Point::new(1, -1),
+            self + Point::new(-1, 0),
+            self + Point::new(1, 0),
+            self + Point::new(-1, 1),
+            self + Point::new(0, 1),
+            self + Point::new(1, 1),
+        ]
+    }
+
+    /// The smallest step in the same direction as this offset, which every point along the line
+    /// of sight through it is a whole number of (such as 2,-1 for 6,-3). The origin stays as it is.
+    pub fn reduced(self) -> Self {
+        match gcd(self.x, self.y) {
+            0 => self,
+            divisor => Self::new(self.x / divisor, self.y / divisor),
+        }
+    }
+
+    /// Orders offsets by their direction clockwise from straight up (as a laser sweeping around
+    /// from `Up` would meet them), exactly rather than by their angles as floats. Offsets in the
+    /// same direction are equal, and the origin comes before every direction.
+    pub fn cmp_clockwise(self, other: Point) -> Ordering {
+        // whether an offset is in the half from up round to (but not including) down, or the half
+        // from down round to up
+        let half = |p: Point| match (p.x.signum(), p.y.signum()) {
+            (0, 0) => 0,
+            (1, _) | (0, -1) => 1,
+            _ => 2,
+        };
+
+        half(self).cmp(&half(other)).then_with(|| {
+            // within a half, the cross product is positive when other is clockwise of self
+            let cross = self.x as i128 * other.y as i128 - self.y as i128 * other.x as i128;
+            0.cmp(&cross)
+        })
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, scale: i64) -> Point {
+        Point::new(self.x * scale, self.y * scale)
+    }
+}
+
+/// As puzzles write them, `x,y`
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// One of the four directions along a grid, in clockwise order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from `Up`
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The direction a letter (`U`, `D`, `L` or `R`) or an arrow (`^`, `v`, `<` or `>`) points
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | '^' => Some(Self::Up),
+            'R' | '>' => Some(Self::Right),
+            'D' | 'v' => Some(Self::Down),
+            'L' | '<' => Some(Self::Left),
+            _ => None,
+        }
+    }
+
+    /// The arrow which points in the direction
+    pub fn arrow(self) -> char {
+        match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        }
+    }
+
+    /// The offset of a step in the direction
+    pub fn unit(self) -> Point {
+        match self {
+            Self::Up => Point::new(0, -1),
+            Self::Right => Point::new(1, 0),
+            Self::Down => Point::new(0, 1),
+            Self::Left => Point::new(-1, 0),
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+}
+
+/// A position in space, or the offset between two positions
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub const ORIGIN: Point3 = Point3 { x: 0, y: 0, z: 0 };
+
+    pub const fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan_distance(self, to: Point3) -> i64 {
+        (self.x - to.x).abs() + (self.y - to.y).abs() + (self.z - to.z).abs()
+    }
+
+    /// The sign of each coordinate, such as the pull of one moon towards another along each axis
+    pub fn signum(self) -> Self {
+        Self::new(self.x.signum(), self.y.signum(), self.z.signum())
+    }
+
+    /// The points a step away along each axis
+    pub fn neighbors6(self) -> [Point3; 6] {
+        [
+            self + Point3::new(-1, 0, 0),
+            self + Point3::new(1, 0, 0),
+            self + Point3::new(0, -1, 0),
+            self + Point3::new(0, 1, 0),
+            self + Point3::new(0, 0, -1),
+            self + Point3::new(0, 0, 1),
+        ]
+    }
+}
+
+impl From<(i64, i64, i64)> for Point3 {
+    fn from((x, y, z): (i64, i64, i64)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl Add for Point3 {
+    type Output = Point3;
+
+    fn add(self, other: Point3) -> Point3 {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl AddAssign for Point3 {
+    fn add_assign(&mut self, other: Point3) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point3 {
+    type Output = Point3;
+
+    fn sub(self, other: Point3) -> Point3 {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl SubAssign for Point3 {
+    fn sub_assign(&mut self, other: Point3) {
+        *self = *self - other;
+    }
+}
+
+impl Neg for Point3 {
+    type Output = Point3;
+
+    fn neg(self) -> Point3 {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl Mul<i64> for Point3 {
+    type Output = Point3;
+
+    fn mul(self, scale: i64) -> Point3 {
+        Point3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+/// As puzzles write them, `x,y,z`
+impl fmt::Display for Point3 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// A box in space with its sides along the axes, from `min` to `max` with both corners included
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Aabb3 {
+    pub min: Point3,
+    pub max: Point3,
+}
+
+impl Aabb3 {
+    /// The box between two opposite corners, whichever they are
+    pub fn new(a: Point3, b: Point3) -> Self {
+        Self {
+            min: Point3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            max: Point3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    }
+
+    /// The smallest box around every point, or `None` when there are none
+    pub fn around(points: impl IntoIterator<Item = Point3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |bounds, point| {
+            Self::new(
+                Point3::new(
+                    bounds.min.x.min(point.x),
+                    bounds.min.y.min(point.y),
+                    bounds.min.z.min(point.z),
+                ),
+                Point3::new(
+                    bounds.max.x.max(point.x),
+                    bounds.max.y.max(point.y),
+                    bounds.max.z.max(point.z),
+                ),
+            )
+        }))
+    }
+
+    pub fn contains(&self, point: Point3) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x)
+            && (self.min.y..=self.max.y).contains(&point.y)
+            && (self.min.z..=self.max.z).contains(&point.z)
+    }
+
+    /// Whether the box is a single point, after which it can't be split any further
+    pub fn is_point(&self) -> bool {
+        self.min == self.max
+    }
+
+    /// The Manhattan distance from `point` to the nearest point in the box, which is 0 for points
+    /// in it. A nanobot is in range of some point of the box when this is at most its radius.
+    pub fn distance_to(&self, point: Point3) -> i64 {
+        let axis = |p: i64, min: i64, max: i64| (min - p).max(0) + (p - max).max(0);
+        axis(point.x, self.min.x, self.max.x)
+            + axis(point.y, self.min.y, self.max.y)
+            + axis(point.z, self.min.z, self.max.z)
+    }
+
+    /// The box split in half along each axis, into boxes which together hold every point of it
+    /// exactly once. There are fewer than 8 when the box is a single point wide along some axis,
+    /// and only the box itself when it's a single point.
+    pub fn octants(&self) -> Vec<Aabb3> {
+        let halves = |min: i64, max: i64| {
+            let mid = min + (max - min) / 2;
+            if mid < max {
+                vec![(min, mid), (mid + 1, max)]
+            } else {
+                vec![(min, max)]
+            }
+        };
+
+        let mut octants = Vec::with_capacity(8);
+        for &(min_x, max_x) in &halves(self.min.x, self.max.x) {
+            for &(min_y, max_y) in &halves(self.min.y, self.max.y) {
+                for &(min_z, max_z) in &halves(self.min.z, self.max.z) {
+                    octants.push(Aabb3 {
+                        min: Point3::new(min_x, min_y, min_z),
+                        max: Point3::new(max_x, max_y, max_z),
+                    });
+                }
+            }
+        }
+
+        octants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_geometry() {
+        let point = Point::new(3, -2);
+        assert_eq!(point + Point::new(1, 1), Point::new(4, -1));
+        assert_eq!(point - Point::new(1, 1), Point::new(2, -3));
+        assert_eq!(-point * 2, Point::new(-6, 4));
+        assert_eq!(point.manhattan_distance(Point::ORIGIN), 5);
+        assert_eq!(Point::new(-5, -5).manhattan_distance(Point::new(5, 5)), 20);
+        assert_eq!(point.to_string(), "3,-2");
+
+        assert_eq!(point.step(Direction::Up), Point::new(3, -3));
+        assert_eq!(
+            point.checked_steps(Direction::Left, 10),
+            Some(Point::new(-7, -2))
+        );
+        assert_eq!(point.checked_steps(Direction::Right, i64::MAX), None);
+        assert_eq!(Point::ORIGIN.checked_steps(Direction::Left, i64::MIN), None);
+        assert_eq!(
+            Point::ORIGIN.neighbors4(),
+            [(0, -1), (-1, 0), (1, 0), (0, 1)].map(Point::from)
+        );
+        assert!(Point::ORIGIN
+            .neighbors8()
+            .iter()
+            .all(|neighbor| neighbor.manhattan_distance(Point::ORIGIN) <= 2));
+
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert_eq!(Direction::Right.reverse(), Direction::Left);
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().unit(), -direction.unit());
+            assert_eq!(Direction::from_char(direction.arrow()), Some(direction));
+        }
+        assert_eq!(Direction::from_char('D'), Some(Direction::Down));
+        assert_eq!(Direction::from_char('x'), None);
+
+        assert_eq!(Point::new(6, -3).reduced(), Point::new(2, -1));
+        assert_eq!(Point::new(0, 5).reduced(), Point::new(0, 1));
+        assert_eq!(Point::ORIGIN.reduced(), Point::ORIGIN);
+
+        let mut offsets = [
+            (-1, -1),
+            (1, 0),
+            (0, 3),
+            (-2, 0),
+            (0, -1),
+            (2, -1),
+            (1, 1),
+            (-1, 2),
+            (0, -4),
+        ]
+        .map(Point::from);
+        offsets.sort_by(|a, b| a.cmp_clockwise(*b));
+        assert_eq!(
+            offsets,
+            [
+                (0, -1),
+                (0, -4),
+                (2, -1),
+                (1, 0),
+                (1, 1),
+                (0, 3),
+                (-1, 2),
+                (-2, 0),
+                (-1, -1)
+            ]
+            .map(Point::from)
+        );
+        assert_eq!(
+            Point::new(2, 2).cmp_clockwise(Point::new(3, 3)),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn test_geometry_3d() {
+        let point = Point3::new(1, -2, 3);
+        assert_eq!(point + Point3::new(1, 1, 1), Point3::new(2, -1, 4));
+        assert_eq!(-point * 2, Point3::new(-2, 4, -6));
+        assert_eq!(point.manhattan_distance(Point3::ORIGIN), 6);
+        assert_eq!(
+            (point - Point3::new(4, -2, 0)).signum(),
+            Point3::new(-1, 0, 1)
+        );
+        assert_eq!(point.to_string(), "1,-2,3");
+        assert!(point
+            .neighbors6()
+            .iter()
+            .all(|neighbor| neighbor.manhattan_distance(point) == 1));
+
+        let bounds = Aabb3::around([(0, 0, 0), (3, -1, 2), (1, 4, 1)].map(Point3::from)).unwrap();
+        assert_eq!(
+            bounds,
+            Aabb3::new(Point3::new(3, 4, 2), Point3::new(0, -1, 0))
+        );
+        assert!(bounds.contains(Point3::new(2, 2, 2)));
+        assert!(!bounds.contains(Point3::new(2, 2, 3)));
+        assert_eq!(bounds.distance_to(Point3::new(1, 1, 1)), 0);
+        assert_eq!(bounds.distance_to(Point3::new(5, -3, 1)), 4);
+        assert_eq!(Aabb3::around(Vec::new()), None);
+
+        // the octants hold every point once between them
+        let octants = bounds.octants();
+        assert_eq!(octants.len(), 8);
+        let mut points = 0;
+        for x in 0..=3 {
+            for y in -1..=4 {
+                for z in 0..=2 {
+                    let point = Point3::new(x, y, z);
+                    assert_eq!(octants.iter().filter(|o| o.contains(point)).count(), 1);
+                    points += 1;
+                }
+            }
+        }
+        assert_eq!(points, 72);
+
+        let flat = Aabb3::new(Point3::new(0, 0, 5), Point3::new(1, 1, 5));
+        assert_eq!(flat.octants().len(), 4);
+        let single = Aabb3::new(point, point);
+        assert!(single.is_point());
+        assert_eq!(single.octants(), [single]);
+    }
+}
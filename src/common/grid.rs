@@ -0,0 +1,580 @@
+//! Grids of cells: `Grid2D`, a rectangle such as the maps many puzzles draw in characters, indexed
+//! by `(x, y)` with the top left cell at `(0, 0)`, and `SparseGrid`, which has no edges and grows
+//! wherever its cells are set (such as the panels a robot paints as it wanders).
+//!
+//! A `SummedAreaTable` sums up any rectangle of a `Grid2D` of numbers in constant time.
+
+use crate::common::geometry::Point;
+use crate::error::Error;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::{Index, IndexMut};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid2D<T> {
+    width: usize,
+    height: usize,
+    /// Row after row, from the top
+    cells: Vec<T>,
+}
+
+impl<T> Grid2D<T> {
+    /// A grid with every cell set to `cell`
+    pub fn new(width: usize, height: usize, cell: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![cell; width * height],
+        }
+    }
+
+    /// A grid of the cells given row after row, from the top. Panics when they don't fill a whole
+    /// number of rows.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't fill rows of {}",
+            cells.len(),
+            width
+        );
+
+        Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Parses a grid drawn in characters, a row to a line, with `parse_cell` given the position
+    /// and character of each cell. Every row must be as wide as the first.
+    pub fn parse(
+        input: &str,
+        mut parse_cell: impl FnMut((usize, usize), char) -> Result<T, String>,
+    ) -> Result<Self, Error> {
+        let mut width = None;
+        let mut cells = Vec::with_capacity(input.len());
+
+        for (y, line) in input.lines().enumerate() {
+            let mut row_width = 0;
+            for (x, c) in line.chars().enumerate() {
+                let cell = parse_cell((x, y), c).map_err(|reason| Error::line(y, line, reason))?;
+                cells.push(cell);
+                row_width += 1;
+            }
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(Error::line(
+                        y,
+                        line,
+                        format!("is {} wide, where the first row is {}", row_width, width),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self::from_cells(width, cells)),
+            _ => Err(Error::Input("has no cells".to_string())),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `(x, y)` is a cell of the grid, for positions which may have stepped off its edges
+    pub fn contains(&self, (x, y): (i64, i64)) -> bool {
+        (0..self.width as i64).contains(&x) && (0..self.height as i64).contains(&y)
+    }
+
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Each row from the top
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width)
+    }
+
+    /// The cells of a column, from the top
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {} is off a grid {} wide",
+            x,
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Each column from the left
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(move |x| self.column(x))
+    }
+
+    /// Every cell with its position, in reading order (row after row, left to right)
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// Every cell, mutably, with its position, in reading order
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// The positions of the cells above, left of, right of and below a cell (in reading order),
+    /// leaving out those off the edges of the grid
+    pub fn neighbors4(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(position, &[(0, -1), (-1, 0), (1, 0), (0, 1)])
+    }
+
+    /// The positions of the cells around a cell, diagonals included, in reading order, leaving out
+    /// those off the edges of the grid
+    pub fn neighbors8(&self, position: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbors(
+            position,
+            &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+        )
+    }
+
+    fn neighbors(
+        &self,
+        (x, y): (usize, usize),
+        offsets: &'static [(i64, i64)],
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let (x, y) = (x as i64 + dx, y as i64 + dy);
+            if (0..width).contains(&x) && (0..height).contains(&y) {
+                Some((x as usize, y as usize))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// A grid of the same size with each cell converted by `f`
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid2D<U> {
+        Grid2D {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid2D<char> {
+    /// A grid of the characters an input draws, a row to a line
+    pub fn from_chars(input: &str) -> Result<Self, Error> {
+        Self::parse(input, |_, c| Ok(c))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid2D<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get((x, y)).unwrap_or_else(|| {
+            panic!(
+                "({}, {}) is off a grid of {}x{}",
+                x, y, self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid2D<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut((x, y))
+            .unwrap_or_else(|| panic!("({}, {}) is off a grid of {}x{}", x, y, width, height))
+    }
+}
+
+/// Draws each cell as it displays, a row to a line
+impl<T: fmt::Display> fmt::Display for Grid2D<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The sums of a grid of numbers over every rectangle within it, where each sum takes constant time
+/// once the table is built (such as the total power of every square of fuel cells, of every size)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummedAreaTable {
+    width: usize,
+    height: usize,
+    /// The sum of the cells above and to the left of each corner between cells, row after row,
+    /// including the corners along the right and bottom edges
+    sums: Vec<i64>,
+}
+
+impl SummedAreaTable {
+    pub fn new(grid: &Grid2D<i64>) -> Self {
+        let (width, height) = (grid.width(), grid.height());
+        let mut sums = vec![0; (width + 1) * (height + 1)];
+        for ((x, y), cell) in grid.iter() {
+            sums[(y + 1) * (width + 1) + x + 1] =
+                cell + sums[y * (width + 1) + x + 1] + sums[(y + 1) * (width + 1) + x]
+                    - sums[y * (width + 1) + x];
+        }
+
+        Self {
+            width,
+            height,
+            sums,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The sum of the cells of the rectangle `width` by `height` cells with its top left cell at
+    /// `(x, y)`. Panics when the rectangle isn't within the grid.
+    pub fn sum(&self, (x, y): (usize, usize), (width, height): (usize, usize)) -> i64 {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "a {}x{} rectangle at ({}, {}) is off a grid of {}x{}",
+            width,
+            height,
+            x,
+            y,
+            self.width,
+            self.height
+        );
+
+        let corner = |x: usize, y: usize| self.sums[y * (self.width + 1) + x];
+        corner(x + width, y + height) - corner(x, y + height) - corner(x + width, y) + corner(x, y)
+    }
+}
+
+/// A grid without edges, of only the cells which have been set, along with the bounds around them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    /// The top left and bottom right corners of the cells, if there are any
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets a cell, returning what it was before
+    pub fn insert(&mut self, point: Point, cell: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point::new(min.x.min(point.x), min.y.min(point.y)),
+                Point::new(max.x.max(point.x), max.y.max(point.y)),
+            ),
+            None => (point, point),
+        });
+
+        self.cells.insert(point, cell)
+    }
+
+    /// Unsets a cell, returning what it was
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let cell = self.cells.remove(&point)?;
+
+        // the bounds only shrink when the cell was on their edge
+        if let Some((min, max)) = self.bounds {
+            if point.x == min.x || point.y == min.y || point.x == max.x || point.y == max.y {
+                self.bounds = self.cells.keys().fold(None, |bounds, point| {
+                    Some(match bounds {
+                        Some((min, max)) => (
+                            Point::new(point.x.min(min.x), point.y.min(min.y)),
+                            Point::new(point.x.max(max.x), point.y.max(max.y)),
+                        ),
+                        None => (*point, *point),
+                    })
+                });
+            }
+        }
+
+        Some(cell)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle around every cell which is
+    /// set, or `None` when none are
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// Every cell which is set, with its position, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(point, cell)| (*point, cell))
+    }
+
+    /// Draws the rectangle within the bounds, a row to a line, with `draw` given each cell (or
+    /// `None` for those which aren't set)
+    pub fn render(&self, mut draw: impl FnMut(Option<&T>) -> char) -> String {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+
+        (min.y..=max.y)
+            .map(|y| {
+                (min.x..=max.x)
+                    .map(|x| draw(self.get(Point::new(x, y))))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(cells: I) -> Self {
+        let mut grid = Self::new();
+        for (point, cell) in cells {
+            grid.insert(point, cell);
+        }
+
+        grid
+    }
+}
+
+/// Hashes the same whatever order the cells were set in, as grids with the same cells are equal
+impl<T: Hash> Hash for SparseGrid<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let cells = self.cells.iter().fold(0u64, |cells, cell| {
+            let mut hasher = DefaultHasher::new();
+            cell.hash(&mut hasher);
+            cells.wrapping_add(hasher.finish())
+        });
+
+        self.cells.len().hash(state);
+        cells.hash(state);
+    }
+}
+
+/// Draws the rectangle within the bounds a row to a line, with each cell as it displays and those
+/// which aren't set as spaces
+impl<T: fmt::Display> fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds else {
+            return Ok(());
+        };
+
+        for y in min.y..=max.y {
+            if y > min.y {
+                writeln!(f)?;
+            }
+            for x in min.x..=max.x {
+                match self.get(Point::new(x, y)) {
+                    Some(cell) => write!(f, "{}", cell)?,
+                    None => write!(f, " ")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid() {
+        let mut grid = Grid2D::from_chars("#..\n.#.\n..#\n#..\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid[(2, 2)], '#');
+        assert_eq!(grid.get((3, 0)), None);
+        assert!(grid.contains((0, 3)) && !grid.contains((-1, 0)) && !grid.contains((0, 4)));
+
+        assert_eq!(grid.row(1), &['.', '#', '.']);
+        assert_eq!(grid.column(0).collect::<String>(), "#..#");
+        assert_eq!(
+            grid.columns().map(|c| c.collect()).collect::<Vec<String>>(),
+            ["#..#", ".#..", "..#."]
+        );
+        assert_eq!(
+            grid.iter()
+                .filter(|(_, c)| **c == '#')
+                .map(|(position, _)| position)
+                .collect::<Vec<_>>(),
+            [(0, 0), (1, 1), (2, 2), (0, 3)]
+        );
+
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbors4((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1), (2, 1), (1, 2)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(
+            grid.neighbors8((2, 3)).collect::<Vec<_>>(),
+            [(1, 2), (2, 2), (1, 3)]
+        );
+
+        grid[(1, 0)] = '#';
+        assert_eq!(grid.to_string(), "##.\n.#.\n..#\n#..");
+        assert_eq!(
+            grid.map(|c| u8::from(*c == '#')).to_string(),
+            "110\n010\n001\n100"
+        );
+
+        assert_eq!(
+            Grid2D::from_chars("#..\n.#\n"),
+            Err(Error::line(1, ".#", "is 2 wide, where the first row is 3"))
+        );
+        assert_eq!(
+            Grid2D::parse("#.x", |_, c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(format!("has {:?}, which isn't a cell", c)),
+            }),
+            Err(Error::line(0, "#.x", "has 'x', which isn't a cell"))
+        );
+        assert!(Grid2D::from_chars("").is_err());
+    }
+
+    #[test]
+    fn test_summed_area_table() {
+        let grid = Grid2D::from_cells(3, vec![1, 2, 3, 4, 5, 6, -7, 8, 9]);
+        let table = SummedAreaTable::new(&grid);
+        assert_eq!((table.width(), table.height()), (3, 3));
+        assert_eq!(table.sum((0, 0), (3, 3)), 31);
+        assert_eq!(table.sum((1, 1), (2, 2)), 28);
+        assert_eq!(table.sum((0, 2), (2, 1)), 1);
+        assert_eq!(table.sum((2, 0), (1, 1)), 3);
+        assert_eq!(table.sum((1, 1), (0, 2)), 0);
+
+        for x in 0..3 {
+            for y in 0..3 {
+                assert_eq!(table.sum((x, y), (1, 1)), grid[(x, y)]);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_summed_area_table_off_the_grid() {
+        let table = SummedAreaTable::new(&Grid2D::new(3, 2, 1));
+        table.sum((1, 1), (2, 2));
+    }
+
+    #[test]
+    fn test_sparse_grid() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+
+        grid.insert(Point::new(0, 0), '#');
+        grid.insert(Point::new(-2, 1), '#');
+        assert_eq!(grid.insert(Point::new(1, -1), '.'), None);
+        assert_eq!(grid.insert(Point::new(1, -1), '#'), Some('.'));
+        assert_eq!(grid.len(), 3);
+        assert_eq!(grid.bounds(), Some((Point::new(-2, -1), Point::new(1, 1))));
+        assert_eq!(grid.to_string(), "   #\n  # \n#   ");
+        assert_eq!(
+            grid.render(|cell| if cell.is_some() { '#' } else { '.' }),
+            "...#\n..#.\n#..."
+        );
+
+        assert_eq!(grid.remove(Point::new(-2, 1)), Some('#'));
+        assert_eq!(grid.remove(Point::new(-2, 1)), None);
+        assert_eq!(grid.bounds(), Some((Point::new(0, -1), Point::new(1, 0))));
+        assert!(grid.contains(Point::ORIGIN) && !grid.contains(Point::new(-2, 1)));
+
+        let collected: SparseGrid<u8> = std::iter::once((Point::new(5, 5), 1)).collect();
+        assert_eq!(collected.get(Point::new(5, 5)), Some(&1));
+        assert_eq!(collected.iter().count(), 1);
+    }
+}
@@ -0,0 +1,183 @@
+//! A priority queue of numbered items whose priorities can be lowered in place, for searches over
+//! states which number densely (such as a position and the tool in hand), where a `BinaryHeap`
+//! fills up with stale entries each time a cheaper way to a state is found.
+
+/// A min-heap of `usize` keys, each in the heap at most once, with its priority
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<P> {
+    /// The keys and their priorities, as a binary heap with the lowest priority first
+    heap: Vec<(usize, P)>,
+    /// Where each key is in `heap`, or `ABSENT` if it isn't in the heap
+    positions: Vec<usize>,
+}
+
+const ABSENT: usize = usize::MAX;
+
+impl<P> Default for IndexedHeap<P> {
+    fn default() -> Self {
+        Self {
+            heap: Vec::new(),
+            positions: Vec::new(),
+        }
+    }
+}
+
+impl<P: Ord + Copy> IndexedHeap<P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An empty heap with room for the keys below `keys` without growing
+    pub fn with_capacity(keys: usize) -> Self {
+        Self {
+            heap: Vec::new(),
+            positions: vec![ABSENT; keys],
+        }
+    }
+
+    /// Adds `key` with `priority`, or lowers its priority to `priority` if it's already in the
+    /// heap with a higher one, returning whether the heap changed
+    pub fn push_or_decrease(&mut self, key: usize, priority: P) -> bool {
+        if key >= self.positions.len() {
+            self.positions.resize(key + 1, ABSENT);
+        }
+
+        let i = match self.positions[key] {
+            ABSENT => {
+                self.heap.push((key, priority));
+                self.heap.len() - 1
+            }
+            i if self.heap[i].1 <= priority => return false,
+            i => i,
+        };
+
+        self.sift_up(i, (key, priority));
+        true
+    }
+
+    /// Takes out the key with the lowest priority (the lowest key of those tied), along with its
+    /// priority
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+
+        let last = self.heap.pop()?;
+        if self.heap.is_empty() {
+            self.positions[last.0] = ABSENT;
+            return Some(last);
+        }
+
+        let first = self.heap[0];
+        self.positions[first.0] = ABSENT;
+        self.sift_down(0, last);
+
+        Some(first)
+    }
+
+    /// The priority of `key`, if it's in the heap
+    pub fn priority(&self, key: usize) -> Option<P> {
+        match self.positions.get(key) {
+            Some(&i) if i != ABSENT => Some(self.heap[i].1),
+            _ => None,
+        }
+    }
+
+    pub fn contains(&self, key: usize) -> bool {
+        self.priority(key).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Moves `entry`, which belongs at `i`, up until its parent comes out before it, shifting
+    /// the parents it passes down into the hole it leaves
+    fn sift_up(&mut self, mut i: usize, entry: (usize, P)) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if before(self.heap[parent], entry) {
+                break;
+            }
+            self.place(i, self.heap[parent]);
+            i = parent;
+        }
+        self.place(i, entry);
+    }
+
+    /// Moves `entry`, which belongs at `i`, down until it comes out before its children,
+    /// shifting the children it passes up into the hole it leaves
+    fn sift_down(&mut self, mut i: usize, entry: (usize, P)) {
+        loop {
+            let mut child = 2 * i + 1;
+            if child >= self.heap.len() {
+                break;
+            }
+            if child + 1 < self.heap.len() && before(self.heap[child + 1], self.heap[child]) {
+                child += 1;
+            }
+            if before(entry, self.heap[child]) {
+                break;
+            }
+            self.place(i, self.heap[child]);
+            i = child;
+        }
+        self.place(i, entry);
+    }
+
+    fn place(&mut self, i: usize, entry: (usize, P)) {
+        self.heap[i] = entry;
+        self.positions[entry.0] = i;
+    }
+}
+
+/// Whether entry `a` comes out of the heap before entry `b`
+fn before<P: Ord>((a, a_priority): (usize, P), (b, b_priority): (usize, P)) -> bool {
+    (a_priority, a) < (b_priority, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indexed_heap() {
+        let mut heap = IndexedHeap::with_capacity(4);
+        assert!(heap.push_or_decrease(3, 30));
+        assert!(heap.push_or_decrease(1, 10));
+        assert!(heap.push_or_decrease(7, 20));
+        assert!(heap.push_or_decrease(2, 10));
+        assert_eq!(heap.len(), 4);
+
+        // a higher priority leaves the key where it is, and a lower one moves it up
+        assert!(!heap.push_or_decrease(1, 15));
+        assert!(heap.push_or_decrease(3, 5));
+        assert_eq!(heap.priority(3), Some(5));
+        assert!(!heap.contains(4));
+
+        assert_eq!(heap.pop(), Some((3, 5)));
+        assert_eq!(heap.pop(), Some((1, 10)));
+        assert!(heap.push_or_decrease(3, 1));
+        assert_eq!(heap.pop(), Some((3, 1)));
+        assert_eq!(heap.pop(), Some((2, 10)));
+        assert_eq!(heap.pop(), Some((7, 20)));
+        assert_eq!(heap.pop(), None);
+        assert!(heap.is_empty());
+
+        // as many keys as priorities in a scrambled order come out sorted
+        let mut heap = IndexedHeap::new();
+        for key in 0..100 {
+            heap.push_or_decrease(key, (key * 37) % 100);
+        }
+        for key in 0..100 {
+            heap.push_or_decrease(key, (key * 37) % 100 / 2);
+        }
+        let priorities: Vec<usize> = std::iter::from_fn(|| heap.pop()).map(|(_, p)| p).collect();
+        assert_eq!(priorities.len(), 100);
+        assert!(priorities.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+}
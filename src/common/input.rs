@@ -0,0 +1,101 @@
+//! Parsing the usual shapes of puzzle input: a value to a line, values separated by commas, or
+//! groups of lines separated by blank lines.
+//!
+//! Each reads from a `BufRead`, as a solution's `parse_reader` is given (see `InputSource::open`),
+//! and a solution's `parse` can pass its input as `&mut input.as_bytes()`. `what` describes a
+//! single value (such as `"the mass of a module"`) to explain the values which don't parse.
+
+use crate::common::parse_lines;
+use crate::error::Error;
+use std::io::BufRead;
+use std::str::FromStr;
+
+/// A value parsed from each line, where lines are trimmed first
+pub fn lines<T: FromStr>(input: &mut dyn BufRead, what: &str) -> Result<Vec<T>, Error> {
+    parse_lines(input, |i, line| {
+        line.trim()
+            .parse()
+            .map_err(|_| Error::line(i, line, format!("isn't {}", what)))
+    })
+}
+
+/// The values separated by commas, each trimmed, such as an Intcode program
+pub fn comma_separated<T: FromStr>(input: &mut dyn BufRead, what: &str) -> Result<Vec<T>, Error> {
+    let mut text = String::new();
+    input.read_to_string(&mut text).map_err(Error::read)?;
+
+    text.trim()
+        .split(',')
+        .enumerate()
+        .map(|(i, value)| {
+            value.trim().parse().map_err(|_| {
+                Error::Input(format!(
+                    "has {:?} as value {}, which isn't {}",
+                    value.trim(),
+                    i + 1,
+                    what
+                ))
+            })
+        })
+        .collect()
+}
+
+/// The lines of each group, where groups are separated by one or more blank lines (or lines of
+/// only whitespace). Blank lines before the first group and after the last are left out.
+pub fn blank_line_separated_groups(input: &mut dyn BufRead) -> Result<Vec<Vec<String>>, Error> {
+    let mut groups = vec![Vec::new()];
+    for line in parse_lines(input, |_, line| Ok(line.to_string()))? {
+        if !line.trim().is_empty() {
+            groups
+                .last_mut()
+                .expect("there's always a group")
+                .push(line);
+        } else if !groups.last().expect("there's always a group").is_empty() {
+            groups.push(Vec::new());
+        }
+    }
+
+    groups.retain(|group| !group.is_empty());
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input() {
+        assert_eq!(
+            lines::<i64>(&mut "+7\n-2 \n3".as_bytes(), "a change"),
+            Ok(vec![7, -2, 3])
+        );
+        assert_eq!(
+            lines::<i64>(&mut "1\nfour\n".as_bytes(), "a number"),
+            Err(Error::line(1, "four", "isn't a number"))
+        );
+
+        assert_eq!(
+            comma_separated::<i64>(&mut "1,0, -3,99\n".as_bytes(), "an integer"),
+            Ok(vec![1, 0, -3, 99])
+        );
+        assert_eq!(
+            comma_separated::<u8>(&mut "1,,2".as_bytes(), "a byte"),
+            Err(Error::Input(
+                "has \"\" as value 2, which isn't a byte".to_string()
+            ))
+        );
+
+        assert_eq!(
+            blank_line_separated_groups(&mut "\n1000\n2000\n\n\n3000\n  \n4000\n\n".as_bytes()),
+            Ok(vec![
+                vec!["1000".to_string(), "2000".to_string()],
+                vec!["3000".to_string()],
+                vec!["4000".to_string()],
+            ])
+        );
+        assert_eq!(
+            blank_line_separated_groups(&mut "".as_bytes()),
+            Ok(Vec::<Vec<String>>::new())
+        );
+    }
+}
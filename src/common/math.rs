@@ -0,0 +1,153 @@
+//! Number theory for puzzles whose answers lie too far out to simulate, such as combining the
+//! periods of several cycles, or undoing a shuffle of a huge deck modulo its size.
+//!
+//! Products are taken in `i128`, so that moduli can be as large as an `i64` without overflowing.
+
+/// The greatest common divisor of `a` and `b`, which is never negative
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// The least common multiple of `a` and `b`, which is never negative (and 0 when either is)
+pub fn lcm(a: i64, b: i64) -> i64 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+
+    (a / gcd(a, b) * b).abs()
+}
+
+/// The least common multiple of every number, such as the periods of cycles which all start
+/// together, after which they're all back where they started. It's 1 for no numbers at all.
+pub fn lcm_all(numbers: impl IntoIterator<Item = i64>) -> i64 {
+    numbers.into_iter().fold(1, lcm)
+}
+
+/// The greatest common divisor `g` of `a` and `b`, along with `x` and `y` such that
+/// `a * x + b * y == g`, as `(g, x, y)`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// `a` modulo `m`, from 0 up to (but not including) `m`, for `a` of either sign
+pub fn modulo(a: i128, m: i64) -> i64 {
+    a.rem_euclid(m as i128) as i64
+}
+
+/// The `x` such that `a * x` is 1 modulo `m`, if there is one (which there is when `a` and `m`
+/// share no factor)
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(modulo(a as i128, m), m);
+    if g == 1 {
+        Some(modulo(x as i128, m))
+    } else {
+        None
+    }
+}
+
+/// `base` to the power of `exponent`, modulo `m`
+pub fn mod_pow(base: i64, mut exponent: u64, m: i64) -> i64 {
+    let m128 = m as i128;
+    let mut base = modulo(base as i128, m) as i128;
+    let mut result = 1 % m128;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % m128;
+        }
+        base = base * base % m128;
+        exponent >>= 1;
+    }
+
+    result as i64
+}
+
+/// The smallest non-negative `x` which is each residue modulo its modulus, given as
+/// `(residue, modulus)`, along with the modulus it repeats at (the least common multiple of the
+/// moduli) as `(x, modulus)`. Moduli which share factors are fine, but `None` is returned when
+/// they disagree about the residue of those factors, as then there is no such `x`.
+pub fn crt(congruences: &[(i64, i64)]) -> Option<(i64, i64)> {
+    let mut x: i64 = 0;
+    let mut modulus: i64 = 1;
+
+    for &(residue, next_modulus) in congruences {
+        let residue = modulo(residue as i128, next_modulus);
+        let (g, p, _) = extended_gcd(modulus, next_modulus);
+        if (residue - x) % g != 0 {
+            return None;
+        }
+
+        // x + modulus * k is the residue modulo next_modulus, for k = (residue - x) / g * p
+        let step = next_modulus / g;
+        let k = modulo((residue - x) as i128 / g as i128 * p as i128, step);
+        let combined = (modulus as i128) * (step as i128);
+        x = (x as i128 + modulus as i128 * k as i128).rem_euclid(combined) as i64;
+        modulus = combined as i64;
+    }
+
+    Some((x, modulus))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_math() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        // the periods of the moons of the example of 2019 day 12
+        assert_eq!(lcm_all([18, 28, 44]), 2772);
+        assert_eq!(lcm_all([]), 1);
+
+        for (a, b) in [(240, 46), (-35, 15), (17, 0), (0, 0)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+
+        assert_eq!(modulo(-7, 5), 3);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(4, 8), None);
+        assert_eq!(mod_pow(2, 10, 1000), 24);
+        assert_eq!(mod_pow(-2, 3, 5), 2);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        // the deck of 2019 day 22 part 2
+        let deck = 119_315_717_514_047;
+        assert_eq!(mod_pow(deck - 1, 2, deck), 1);
+        assert_eq!(
+            (mod_inverse(123_456_789, deck).unwrap() as i128 * 123_456_789) % deck as i128,
+            1
+        );
+
+        assert_eq!(crt(&[(0, 3), (3, 4), (4, 5)]), Some((39, 60)));
+        assert_eq!(crt(&[(2, 6), (8, 10)]), Some((8, 30)));
+        assert_eq!(crt(&[(1, 6), (2, 10)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+}
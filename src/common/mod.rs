@@ -0,0 +1,171 @@
+pub mod arena;
+pub mod artifacts;
+pub mod automaton;
+pub mod bitset;
+pub mod combinatorics;
+pub mod counter;
+pub mod cycles;
+pub mod geometry;
+pub mod grid;
+pub mod heap;
+pub mod input;
+pub mod math;
+pub mod ocr;
+pub mod parse;
+pub mod pathfinding;
+pub mod ranges;
+pub mod rational;
+pub mod ring;
+pub mod rng;
+pub mod search;
+pub mod strings;
+pub mod timestamp;
+#[cfg(any(feature = "visualize", feature = "image"))]
+pub mod vis;
+
+pub use counter::Counter;
+
+use crate::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Cursor, IsTerminal, Read};
+use std::path::PathBuf;
+
+/// Collects from stdin into a String until an empty line is encountered
+pub fn read_stdin_to_string() -> String {
+    let mut input = String::new();
+    let mut line = String::new();
+
+    println!("Enter puzzle input followed by an empty line:");
+    loop {
+        io::stdin().read_line(&mut line).unwrap();
+        if line.trim() == "" {
+            if input.trim() == "" {
+                continue;
+            }
+            break;
+        }
+        input.push_str(&line);
+        line.clear();
+    }
+
+    input
+}
+
+/// Where a puzzle input is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A file, such as a stored puzzle input
+    File(PathBuf),
+
+    /// The input itself
+    String(String),
+
+    /// Typed in until an empty line when stdin is a terminal, or everything piped in otherwise
+    Stdin,
+
+    /// The text on the clipboard, where the puzzle input usually is after copying it from the
+    /// puzzle page
+    #[cfg(feature = "clipboard")]
+    Clipboard,
+}
+
+impl InputSource {
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            Self::File(path) => fs::read_to_string(path),
+            Self::String(input) => Ok(input.clone()),
+            Self::Stdin if io::stdin().is_terminal() => Ok(read_stdin_to_string()),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            #[cfg(feature = "clipboard")]
+            Self::Clipboard => arboard::Clipboard::new()
+                .and_then(|mut clipboard| clipboard.get_text())
+                .map_err(io::Error::other),
+        }
+    }
+
+    /// Opens the input to be read a line at a time, so that a huge input needn't be held in
+    /// memory all at once when the day parsing it streams it (see `Solver::parse_reader`)
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Self::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Self::Stdin if !io::stdin().is_terminal() => Ok(Box::new(io::stdin().lock())),
+            _ => Ok(Box::new(Cursor::new(self.read()?))),
+        }
+    }
+}
+
+/// Parses an input one line at a time as it's read, with `parse_line` given the index of each
+/// line (counted from 0) along with the line. Lines end as they do for `str::lines`.
+pub fn parse_lines<T>(
+    input: &mut dyn BufRead,
+    mut parse_line: impl FnMut(usize, &str) -> Result<T, Error>,
+) -> Result<Vec<T>, Error> {
+    let mut parsed = Vec::new();
+    let mut line = String::new();
+
+    for i in 0.. {
+        line.clear();
+        if input.read_line(&mut line).map_err(Error::read)? == 0 {
+            break;
+        }
+        let line = line.strip_suffix('\n').unwrap_or(&line);
+        parsed.push(parse_line(i, line.strip_suffix('\r').unwrap_or(line))?);
+    }
+
+    Ok(parsed)
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::String(_) => write!(f, "the given string"),
+            Self::Stdin => write!(f, "stdin"),
+            #[cfg(feature = "clipboard")]
+            Self::Clipboard => write!(f, "the clipboard"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_source() {
+        let path = std::env::temp_dir().join(format!("input-source-{}.txt", std::process::id()));
+        fs::write(&path, "1\n2\n").unwrap();
+
+        let input = InputSource::File(path.clone()).read();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(input.unwrap(), "1\n2\n");
+
+        assert_eq!(
+            InputSource::String("dabAcCaCBAcCcaDA".to_string())
+                .read()
+                .unwrap(),
+            "dabAcCaCBAcCcaDA"
+        );
+        assert!(InputSource::File(PathBuf::from("/nonexistent/input.txt"))
+            .read()
+            .is_err());
+
+        let mut lines = InputSource::String("1\r\n2\n\nthree".to_string())
+            .open()
+            .unwrap();
+        let parsed = parse_lines(&mut lines, |i, line| {
+            line.parse::<i64>()
+                .map_err(|_| Error::line(i, line, "isn't a number"))
+        });
+        assert_eq!(parsed, Err(Error::line(2, "", "isn't a number")));
+        assert_eq!(
+            parse_lines(&mut "1\r\n2\n".as_bytes(), |_, line| Ok(line.to_string())),
+            Ok(vec!["1".to_string(), "2".to_string()])
+        );
+    }
+}
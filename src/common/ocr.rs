@@ -0,0 +1,193 @@
+//! Reads the letters which some puzzles draw in pixels as their answer, such as the image of 2019
+//! day 8, so that the answer can be shown (and checked) as the text entered on the website.
+//!
+//! Two fonts are known: the one 6 pixels tall of the 2019 puzzles, and the one 10 pixels tall
+//! which the stars of 2018 day 10 line up into. Only the letters which puzzles have been seen to
+//! draw are in either.
+
+/// The letters of the font 6 pixels tall, each a row to a line, with `#` for the pixels drawn
+const LETTERS: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// The letters of the font 10 pixels tall, as for `LETTERS`
+const LARGE_LETTERS: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// The font of the letters which are as many pixels tall as `height`, if there is one
+fn font(height: usize) -> Option<&'static [(char, &'static str)]> {
+    match height {
+        6 => Some(LETTERS),
+        10 => Some(LARGE_LETTERS),
+        _ => None,
+    }
+}
+
+/// The letters drawn in `rows`, where `#` and `█` are the pixels drawn and anything else is
+/// blank, or `None` when they aren't all letters of the font (or there are none)
+pub fn recognize<S: AsRef<str>>(rows: &[S]) -> Option<String> {
+    let letters_of_font = font(rows.len())?;
+
+    // each column, top to bottom, as whether its pixels are drawn
+    let rows: Vec<Vec<bool>> = rows
+        .iter()
+        .map(|row| row.as_ref().chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let column = |x: usize| -> Vec<bool> {
+        rows.iter()
+            .map(|row| row.get(x).copied().unwrap_or(false))
+            .collect()
+    };
+    let blank = |x: usize| column(x).iter().all(|drawn| !drawn);
+
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < width {
+        if blank(x) {
+            x += 1;
+            continue;
+        }
+
+        // letters may touch the next without a blank column between (as Y does), so each is
+        // matched by its own width
+        let (letter, letter_width) = letters_of_font.iter().find_map(|(letter, pixels)| {
+            let letter_width = pixels.lines().next()?.len();
+            let matches = pixels.lines().zip(&rows).all(|(letter_row, row)| {
+                letter_row
+                    .chars()
+                    .enumerate()
+                    .all(|(i, c)| (c == '#') == row.get(x + i).copied().unwrap_or(false))
+            });
+            matches.then_some((*letter, letter_width))
+        })?;
+        letters.push(letter);
+        x += letter_width;
+    }
+
+    if letters.is_empty() {
+        None
+    } else {
+        Some(letters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws letters of a font as puzzles do, each at the start of a cell `cell_width` pixels wide
+    fn draw(font: &[(char, &str)], cell_width: usize, word: &str, pixel: char) -> Vec<String> {
+        let mut rows = vec![String::new(); font[0].1.lines().count()];
+        for c in word.chars() {
+            let (_, pixels) = font.iter().find(|(letter, _)| *letter == c).unwrap();
+            for (row, letter_row) in rows.iter_mut().zip(pixels.lines()) {
+                let cell = format!("{:.<width$}", letter_row, width = cell_width);
+                row.extend(cell.chars().map(|c| if c == '#' { pixel } else { ' ' }));
+            }
+        }
+
+        rows
+    }
+
+    #[test]
+    fn test_recognize() {
+        let alphabet: String = LETTERS.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(recognize(&draw(LETTERS, 5, &alphabet, '#')), Some(alphabet));
+        assert_eq!(
+            recognize(&draw(LETTERS, 5, "YZIL", '█')),
+            Some("YZIL".to_string())
+        );
+
+        // the stars of 2018 day 10 leave two blank columns between letters
+        let large_alphabet: String = LARGE_LETTERS.iter().map(|(letter, _)| letter).collect();
+        assert_eq!(
+            recognize(&draw(LARGE_LETTERS, 8, &large_alphabet, '█')),
+            Some(large_alphabet)
+        );
+
+        let mut smudged = draw(LETTERS, 5, "HELLO", '#');
+        smudged[0].replace_range(..1, " ");
+        assert_eq!(recognize(&smudged), None);
+        assert_eq!(recognize(&[" █", "█ "]), None);
+        assert_eq!(recognize(&["     "; 6]), None);
+        assert_eq!(recognize(&["      "; 10]), None);
+    }
+}
@@ -0,0 +1,126 @@
+//! Helpers for the lines of puzzle inputs: the numbers in a line, whatever is around them, and
+//! the typed pieces of lines which follow a pattern (such as `#1 @ 1,3: 4x4`).
+
+use regex::Regex;
+use std::str::FromStr;
+
+/// Every whole number in `s`, in order, where a `-` right before one makes it negative. Numbers
+/// too big for an `i64` are left out.
+pub fn ints_in(s: &str) -> Vec<i64> {
+    numbers_in(s, true)
+}
+
+/// Every whole number in `s`, in order, where `-` is just another separator (as in ranges like
+/// `1-3`). Numbers too big for a `u64` are left out.
+pub fn unsigned_ints_in(s: &str) -> Vec<u64> {
+    numbers_in(s, false)
+}
+
+fn numbers_in<T: FromStr>(s: &str, signed: bool) -> Vec<T> {
+    let bytes = s.as_bytes();
+    let mut numbers = Vec::new();
+
+    let mut i = 0;
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = if signed && i > 0 && bytes[i - 1] == b'-' {
+            i - 1
+        } else {
+            i
+        };
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if let Ok(number) = s[start..i].parse() {
+            numbers.push(number);
+        }
+    }
+
+    numbers
+}
+
+/// A regex for the lines of an input, along with what those lines are (such as
+/// `a claim like #123 @ 3,2: 5x4`) to explain lines which don't match
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+    description: &'static str,
+}
+
+impl Pattern {
+    /// Panics when `regex` isn't a regex, as patterns are written in the code
+    pub fn new(regex: &str, description: &'static str) -> Self {
+        Self {
+            regex: Regex::new(regex).expect("the pattern is a valid regex"),
+            description,
+        }
+    }
+
+    /// The pieces of `line`, or the reason it isn't what the pattern describes (to go in an
+    /// `Error::line`)
+    pub fn captures<'a>(&self, line: &'a str) -> Result<Captures<'a>, String> {
+        self.regex
+            .captures(line)
+            .map(Captures)
+            .ok_or_else(|| format!("isn't {}", self.description))
+    }
+}
+
+/// The pieces of a line which matched a `Pattern`
+#[derive(Debug)]
+pub struct Captures<'a>(regex::Captures<'a>);
+
+impl<'a> Captures<'a> {
+    /// The text of the group `name`, if it took part in the match
+    pub fn str(&self, name: &str) -> Option<&'a str> {
+        self.0.name(name).map(|capture| capture.as_str())
+    }
+
+    /// The group `name` parsed as a `T`. Panics when the pattern has no such group.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<T, String> {
+        let capture = self
+            .str(name)
+            .unwrap_or_else(|| panic!("the pattern has no group named {}", name));
+
+        capture
+            .parse()
+            .map_err(|_| format!("has {:?}, which isn't a valid {}", capture, name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(ints_in("#1 @ 1,3: 4x4"), [1, 1, 3, 4, 4]);
+        assert_eq!(ints_in("pos=<-12,0,3>, r=-4"), [-12, 0, 3, -4]);
+        assert_eq!(unsigned_ints_in("1-3 a: abcde"), [1, 3]);
+        assert_eq!(ints_in("99999999999999999999 7"), [7]);
+        assert!(ints_in("-").is_empty());
+
+        let claim = Pattern::new(
+            r"^#(?P<id>\d+) @ (?P<x>\d+),(?P<y>\d+): (?P<width>\d+)x(?P<height>\d+)$",
+            "a claim like #123 @ 3,2: 5x4",
+        );
+        let captures = claim.captures("#1 @ 1,3: 4x4").unwrap();
+        assert_eq!(captures.get::<i64>("y"), Ok(3));
+        assert_eq!(captures.str("id"), Some("1"));
+        assert_eq!(
+            claim.captures("#1 @ 1,3 4x4").unwrap_err(),
+            "isn't a claim like #123 @ 3,2: 5x4"
+        );
+        assert_eq!(
+            claim
+                .captures("#1 @ 1,3: 4x300")
+                .unwrap()
+                .get::<u8>("height"),
+            Err("has \"300\", which isn't a valid height".to_string())
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Shortest paths through anything which can list the neighbors of a node, such as the open cells
+//! of a map, the rooms of a maze, or the states of a puzzle.
+//!
+//! `bfs` and `dijkstra` explore everything reachable from the start, for puzzles which ask about
+//! every node (such as the furthest room) or which pick among several goals. `astar` heads
+//! straight for a single goal, guided by an estimate of the cost left to it.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The cheapest cost to each node reached from a start, along with the way there
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    start: N,
+    /// The cost of the cheapest path to each node, and the node before it on that path
+    reached: HashMap<N, (C, Option<N>)>,
+}
+
+impl<N: Eq + Hash + Clone, C: Copy> Paths<N, C> {
+    pub fn start(&self) -> &N {
+        &self.start
+    }
+
+    /// The cost of the cheapest path to `node`, or `None` when it can't be reached
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.reached.get(node).map(|(cost, _)| *cost)
+    }
+
+    /// The nodes of the cheapest path to `node`, from the start to `node` itself
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut previous = self.reached.get(node)?.1.as_ref();
+        while let Some(node) = previous {
+            path.push(node.clone());
+            previous = self.reached[node].1.as_ref();
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Every node reached along with its cost, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.reached.iter().map(|(node, (cost, _))| (node, *cost))
+    }
+
+    pub fn len(&self) -> usize {
+        self.reached.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reached.is_empty()
+    }
+}
+
+/// The fewest steps to each node reachable from `start`, where every step costs the same. Where
+/// paths tie, the one through the neighbor listed first wins, so neighbors listed in reading order
+/// find the path which puzzles break ties in favor of.
+pub fn bfs<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Paths<N, usize>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = HashMap::new();
+    reached.insert(start.clone(), (0, None));
+    let mut queue = VecDeque::from([(start.clone(), 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        for neighbor in neighbors(&node) {
+            if !reached.contains_key(&neighbor) {
+                reached.insert(neighbor.clone(), (steps + 1, Some(node.clone())));
+                queue.push_back((neighbor, steps + 1));
+            }
+        }
+    }
+
+    Paths { start, reached }
+}
+
+/// The cheapest cost to each node reachable from `start`, where `neighbors` lists each neighbor of
+/// a node along with the cost of the step to it
+pub fn dijkstra<N, C, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Paths<N, C>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reached = HashMap::new();
+    reached.insert(start.clone(), (C::default(), None));
+    let mut queue = BinaryHeap::from([Reverse((C::default(), start.clone()))]);
+
+    while let Some(Reverse((cost, node))) = queue.pop() {
+        if reached[&node].0 < cost {
+            // already reached more cheaply since this was queued
+            continue;
+        }
+
+        for (neighbor, step) in neighbors(&node) {
+            let neighbor_cost = cost + step;
+            let cheaper = reached
+                .get(&neighbor)
+                .is_none_or(|(reached_cost, _)| neighbor_cost < *reached_cost);
+            if cheaper {
+                reached.insert(neighbor.clone(), (neighbor_cost, Some(node.clone())));
+                queue.push(Reverse((neighbor_cost, neighbor)));
+            }
+        }
+    }
+
+    Paths { start, reached }
+}
+
+/// The cheapest path from `start` to a node which `is_goal`, and its cost. `heuristic` estimates
+/// the cost from a node to the nearest goal, and must never overestimate it (such as the Manhattan
+/// distance on a grid) for the path to be the cheapest.
+pub fn astar<N, C, I>(
+    start: N,
+    mut is_goal: impl FnMut(&N) -> bool,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+) -> Option<(Vec<N>, C)>
+where
+    N: Eq + Hash + Clone + Ord,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths {
+        start: start.clone(),
+        reached: HashMap::new(),
+    };
+    paths.reached.insert(start.clone(), (C::default(), None));
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), C::default(), start))]);
+
+    while let Some(Reverse((_, cost, node))) = queue.pop() {
+        if paths.reached[&node].0 < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some((paths.path_to(&node)?, cost));
+        }
+
+        for (neighbor, step) in neighbors(&node) {
+            let neighbor_cost = cost + step;
+            let cheaper = paths
+                .reached
+                .get(&neighbor)
+                .is_none_or(|(reached_cost, _)| neighbor_cost < *reached_cost);
+            if cheaper {
+                let estimate = neighbor_cost + heuristic(&neighbor);
+                paths
+                    .reached
+                    .insert(neighbor.clone(), (neighbor_cost, Some(node.clone())));
+                queue.push(Reverse((estimate, neighbor_cost, neighbor)));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::grid::Grid2D;
+
+    const MAZE: &str = "\
+#########
+#S..#...#
+#.#.#.#.#
+#.#...#G#
+#########";
+
+    #[test]
+    fn test_pathfinding() {
+        let maze = Grid2D::from_chars(MAZE).unwrap();
+        let find = |c| maze.iter().find(|(_, cell)| **cell == c).unwrap().0;
+        let (start, goal) = (find('S'), find('G'));
+        let open = |position: &(usize, usize)| {
+            maze.neighbors4(*position)
+                .filter(|neighbor| maze[*neighbor] != '#')
+                .collect::<Vec<_>>()
+        };
+
+        let paths = bfs(start, open);
+        assert_eq!(paths.cost(&goal), Some(12));
+        assert_eq!(paths.cost(&(0, 0)), None);
+        assert_eq!(paths.len(), 15);
+        let path = paths.path_to(&goal).unwrap();
+        assert_eq!((path[0], path[12], path.len()), (start, goal, 13));
+        assert_eq!(paths.path_to(&start), Some(vec![start]));
+
+        // steps down cost 5, and the way to the goal takes 4 of them
+        let weighted = |position: &(usize, usize)| {
+            let position = *position;
+            open(&position)
+                .into_iter()
+                .map(move |neighbor| (neighbor, if neighbor.1 > position.1 { 5 } else { 1 }))
+        };
+        let paths = dijkstra(start, weighted);
+        assert_eq!(paths.cost(&goal), Some(28));
+        assert_eq!(paths.cost(&(1, 3)), Some(10));
+
+        let manhattan =
+            |position: &(usize, usize)| goal.0.abs_diff(position.0) + goal.1.abs_diff(position.1);
+        let (path, cost) = astar(start, |node| *node == goal, weighted, manhattan).unwrap();
+        assert_eq!(cost, 28);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(astar(start, |node| *node == (0, 0), weighted, |_| 0).is_none());
+    }
+}
@@ -0,0 +1,173 @@
+//! Sets of whole numbers kept as the spans they cover, such as the depths a vein of clay reaches
+//! or the part of a row within range of some sensor, so that spans which overlap or touch are
+//! merged in one place rather than by hand in each puzzle.
+
+use std::iter::FromIterator;
+use std::ops::RangeInclusive;
+
+/// A set of `i64`s as the spans it covers, both ends included (as puzzles usually write them, such
+/// as `y=1..5`). The spans are kept in order, and never overlap or touch.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct Ranges {
+    spans: Vec<(i64, i64)>,
+}
+
+impl Ranges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every number of `range`, which adds nothing when it's empty
+    pub fn insert(&mut self, range: RangeInclusive<i64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        // the spans which overlap or touch the range are merged into it
+        let first = self
+            .spans
+            .partition_point(|&(_, e)| e < start.saturating_sub(1));
+        let last = self
+            .spans
+            .partition_point(|&(s, _)| s <= end.saturating_add(1));
+        if first < last {
+            start = start.min(self.spans[first].0);
+            end = end.max(self.spans[last - 1].1);
+        }
+        self.spans
+            .splice(first..last, std::iter::once((start, end)));
+    }
+
+    /// Takes away every number of `range`
+    pub fn remove(&mut self, range: RangeInclusive<i64>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+
+        let mut spans = Vec::with_capacity(self.spans.len() + 1);
+        for &(s, e) in &self.spans {
+            if e < start || s > end {
+                spans.push((s, e));
+                continue;
+            }
+            if s < start {
+                spans.push((s, start - 1));
+            }
+            if e > end {
+                spans.push((end + 1, e));
+            }
+        }
+        self.spans = spans;
+    }
+
+    pub fn contains(&self, n: i64) -> bool {
+        let i = self.spans.partition_point(|&(_, e)| e < n);
+        self.spans.get(i).is_some_and(|&(s, _)| s <= n)
+    }
+
+    /// The numbers in either set
+    pub fn union(&self, other: &Ranges) -> Ranges {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+
+    /// The numbers in both sets
+    pub fn intersection(&self, other: &Ranges) -> Ranges {
+        let mut spans = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.spans.len() && j < other.spans.len() {
+            let (a, b) = (self.spans[i], other.spans[j]);
+            let (start, end) = (a.0.max(b.0), a.1.min(b.1));
+            if start <= end {
+                spans.push((start, end));
+            }
+            // whichever span ends first can't overlap anything further along
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Ranges { spans }
+    }
+
+    /// The numbers of `within` which aren't in the set (such as the gaps in a row)
+    pub fn complement(&self, within: RangeInclusive<i64>) -> Ranges {
+        let mut complement = Ranges::new();
+        complement.insert(within);
+        for range in self.iter() {
+            complement.remove(range);
+        }
+        complement
+    }
+
+    /// The number of numbers in the set, which only stops at `u64::MAX` for a set of every `i64`
+    pub fn len(&self) -> u64 {
+        self.spans
+            .iter()
+            .map(|&(s, e)| e.abs_diff(s).saturating_add(1))
+            .fold(0, u64::saturating_add)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// The spans of the set, in order
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<i64>> + '_ {
+        self.spans.iter().map(|&(s, e)| s..=e)
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for Ranges {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(ranges: I) -> Self {
+        let mut set = Ranges::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_ranges() {
+        let ranges: Ranges = vec![5..=8, 1..=2, 3..=3, 12..=20, 7..=10, 30..=29]
+            .into_iter()
+            .collect();
+        assert_eq!(ranges.iter().collect::<Vec<_>>(), [1..=3, 5..=10, 12..=20]);
+        assert_eq!(ranges.len(), 18);
+        assert!(ranges.contains(5) && ranges.contains(20));
+        assert!(!ranges.contains(4) && !ranges.contains(11) && !ranges.contains(0));
+
+        let other: Ranges = vec![0..=5, 9..=14].into_iter().collect();
+        assert_eq!(
+            ranges.intersection(&other).iter().collect::<Vec<_>>(),
+            [1..=3, 5..=5, 9..=10, 12..=14]
+        );
+        assert_eq!(ranges.union(&other).iter().collect::<Vec<_>>(), [0..=20]);
+        assert_eq!(
+            ranges.complement(0..=25).iter().collect::<Vec<_>>(),
+            [0..=0, 4..=4, 11..=11, 21..=25]
+        );
+
+        let mut removed = ranges.clone();
+        removed.remove(2..=13);
+        assert_eq!(removed.iter().collect::<Vec<_>>(), [1..=1, 14..=20]);
+        assert!(Ranges::new().complement(5..=1).is_empty());
+
+        let everything: Ranges = std::iter::once(i64::MIN..=i64::MAX).collect();
+        assert_eq!(everything.len(), u64::MAX);
+        assert!(everything.complement(-3..=3).is_empty());
+    }
+}
@@ -0,0 +1,136 @@
+//! Items in a circle with a cursor at one of them, such as the marbles of 2018 day 9, where items
+//! are put in and taken out next to the cursor far more often than the circle is looked through.
+
+use std::collections::VecDeque;
+use std::iter::FromIterator;
+
+/// A circle of items, with a current item at the cursor. Moving the cursor a few places, and
+/// inserting or removing at it, take constant time however large the circle is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ring<T> {
+    /// Clockwise from the item after the cursor, so that the current item is at the back
+    items: VecDeque<T>,
+}
+
+impl<T> Default for Ring<T> {
+    fn default() -> Self {
+        Self {
+            items: VecDeque::new(),
+        }
+    }
+}
+
+impl<T> Ring<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The item at the cursor, or `None` when the circle is empty
+    pub fn current(&self) -> Option<&T> {
+        self.items.back()
+    }
+
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        self.items.back_mut()
+    }
+
+    /// Moves the cursor `places` items clockwise, or counter-clockwise when it's negative
+    pub fn rotate(&mut self, places: i64) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let places = places.rem_euclid(self.items.len() as i64) as usize;
+        if places <= self.items.len() / 2 {
+            self.items.rotate_left(places);
+        } else {
+            self.items.rotate_right(self.items.len() - places);
+        }
+    }
+
+    /// Puts `item` clockwise of the current item, and moves the cursor to it
+    pub fn insert(&mut self, item: T) {
+        self.items.push_back(item);
+    }
+
+    /// Takes out the current item, and moves the cursor to the item which was clockwise of it
+    pub fn remove(&mut self) -> Option<T> {
+        let item = self.items.pop_back()?;
+        self.items.rotate_left(usize::from(!self.items.is_empty()));
+        Some(item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Every item once, clockwise from the current one
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.items
+            .back()
+            .into_iter()
+            .chain(self.items.iter().take(self.items.len().saturating_sub(1)))
+    }
+}
+
+/// A circle of the items in clockwise order, with the cursor at the first
+impl<T> FromIterator<T> for Ring<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(items: I) -> Self {
+        let mut items: VecDeque<T> = items.into_iter().collect();
+        items.rotate_left(usize::from(!items.is_empty()));
+        Self { items }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring() {
+        let mut ring: Ring<u32> = (0..5).collect();
+        assert_eq!(ring.current(), Some(&0));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), [0, 1, 2, 3, 4]);
+
+        ring.rotate(2);
+        assert_eq!(ring.current(), Some(&2));
+        ring.rotate(-3);
+        assert_eq!(ring.current(), Some(&4));
+        ring.rotate(11);
+        assert_eq!(ring.current(), Some(&0));
+
+        ring.insert(10);
+        assert_eq!(
+            ring.iter().copied().collect::<Vec<_>>(),
+            [10, 1, 2, 3, 4, 0]
+        );
+        assert_eq!(ring.remove(), Some(10));
+        assert_eq!(ring.current(), Some(&1));
+        assert_eq!(ring.len(), 5);
+
+        let mut single: Ring<char> = "a".chars().collect();
+        assert_eq!(single.remove(), Some('a'));
+        assert_eq!(single.remove(), None);
+        single.rotate(3);
+        assert!(single.is_empty() && single.current().is_none());
+
+        // the marble game of 2018 day 9, with 9 players and the last marble worth 25 points
+        let mut circle: Ring<u32> = std::iter::once(0).collect();
+        let mut scores = [0; 9];
+        for marble in 1..=25 {
+            if marble % 23 == 0 {
+                circle.rotate(-7);
+                scores[marble as usize % 9] += marble + circle.remove().unwrap();
+            } else {
+                circle.rotate(1);
+                circle.insert(marble);
+            }
+        }
+        assert_eq!(scores.iter().max(), Some(&32));
+        assert_eq!(circle.current(), Some(&25));
+    }
+}
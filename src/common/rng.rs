@@ -0,0 +1,80 @@
+//! A small pseudorandom number generator, for the tools which make up or disguise puzzle inputs.
+//! The same seed always gives the same numbers, so that an input made from a seed can be made
+//! again from it.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+/// A xorshift pseudorandom number generator, which is plenty for shuffling and making up inputs
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift never leaves 0
+        Self(seed.max(1))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number from 0 up to (but not including) `n`
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    /// A number from `min` up to and including `max`
+    pub fn between(&mut self, min: i64, max: i64) -> i64 {
+        min + (self.next_u64() % (max - min + 1) as u64) as i64
+    }
+
+    /// Whether something which happens with the chance of `probability` (from 0 to 1) happens
+    pub fn chance(&mut self, probability: f64) -> bool {
+        (self.next_u64() as f64 / u64::MAX as f64) <= probability
+    }
+
+    /// One of `items`, which mustn't be empty
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    /// Fisher-Yates
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A seed which is different each time
+pub fn random_seed() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng() {
+        let numbers = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.between(-3, 3)).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(7), numbers(7));
+        assert_ne!(numbers(7), numbers(8));
+        assert!(numbers(7).iter().all(|n| (-3..=3).contains(n)));
+        assert!((-3..=3).all(|n| numbers(7).contains(&n)));
+
+        let mut rng = Rng::new(0);
+        let mut items: Vec<usize> = (0..10).collect();
+        rng.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..10).collect::<Vec<_>>());
+        assert!(!rng.chance(0.0) && rng.chance(1.0));
+    }
+}
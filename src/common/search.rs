@@ -0,0 +1,89 @@
+//! Searching for an answer rather than working it out, for puzzles where checking a guess is easy
+//! (such as whether a boost is enough for the reindeer's immune system to win, or whether some
+//! amount of fuel can be made from a trillion ore).
+//!
+//! The predicate must be monotonic over the range searched: false up to some value and true from
+//! there on for `binary_search_min`, or true up to some value and false from there on for
+//! `binary_search_max`.
+
+/// The smallest value from `lo` to `hi` (both included) which `predicate` holds for, or `None`
+/// when it holds for none of them
+pub fn binary_search_min(lo: i64, hi: i64, mut predicate: impl FnMut(i64) -> bool) -> Option<i64> {
+    if lo > hi || !predicate(hi) {
+        return None;
+    }
+
+    // predicate(hi) holds, and holds for no value below lo
+    let (mut lo, mut hi) = (lo, hi);
+    while lo < hi {
+        let mid = midpoint(lo, hi);
+        if predicate(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+
+    Some(lo)
+}
+
+/// The largest value from `lo` to `hi` (both included) which `predicate` holds for, or `None`
+/// when it holds for none of them
+pub fn binary_search_max(lo: i64, hi: i64, mut predicate: impl FnMut(i64) -> bool) -> Option<i64> {
+    if lo > hi || !predicate(lo) {
+        return None;
+    }
+
+    // predicate(lo) holds, and holds for no value above hi
+    let (mut lo, mut hi) = (lo, hi);
+    while lo < hi {
+        // rounding up, so that lo always moves
+        let mid = midpoint(lo, hi) + 1;
+        if predicate(mid) {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+
+    Some(lo)
+}
+
+/// The value halfway between `lo` and `hi`, rounded down, without overflowing
+fn midpoint(lo: i64, hi: i64) -> i64 {
+    ((lo as i128 + hi as i128).div_euclid(2)) as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search() {
+        assert_eq!(binary_search_min(0, 100, |x| x * x >= 50), Some(8));
+        assert_eq!(binary_search_min(0, 100, |x| x >= 0), Some(0));
+        assert_eq!(binary_search_min(0, 100, |x| x >= 100), Some(100));
+        assert_eq!(binary_search_min(0, 100, |x| x > 100), None);
+        assert_eq!(binary_search_min(5, 4, |_| true), None);
+        assert_eq!(binary_search_min(-10, 10, |x| x >= -3), Some(-3));
+        assert_eq!(binary_search_min(i64::MIN, i64::MAX, |x| x >= 7), Some(7));
+
+        // the most fuel a trillion ore makes, at 13312 ore for each fuel
+        assert_eq!(
+            binary_search_max(1, 1_000_000_000_000, |fuel| fuel * 13312
+                <= 1_000_000_000_000),
+            Some(75_120_192)
+        );
+        assert_eq!(binary_search_max(0, 100, |x| x <= 0), Some(0));
+        assert_eq!(binary_search_max(0, 100, |x| x <= 100), Some(100));
+        assert_eq!(binary_search_max(0, 100, |x| x < 0), None);
+        assert_eq!(
+            binary_search_max(i64::MIN, i64::MAX, |x| x < i64::MAX),
+            Some(i64::MAX - 1)
+        );
+        assert_eq!(
+            binary_search_max(i64::MIN, i64::MAX, |_| true),
+            Some(i64::MAX)
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Comparing strings a character at a time, such as box IDs which differ by a single letter
+
+use crate::common::Counter;
+
+/// The number of positions at which `a` and `b` have different characters, or `None` when they
+/// aren't the same length (and so can't be compared position by position)
+pub fn hamming_distance(a: &str, b: &str) -> Option<usize> {
+    let (mut a, mut b) = (a.chars(), b.chars());
+    let mut distance = 0;
+
+    loop {
+        match (a.next(), b.next()) {
+            (Some(char_a), Some(char_b)) => distance += usize::from(char_a != char_b),
+            (None, None) => return Some(distance),
+            _ => return None,
+        }
+    }
+}
+
+/// The characters `a` and `b` have at the same positions, in order
+pub fn common_chars(a: &str, b: &str) -> String {
+    a.chars()
+        .zip(b.chars())
+        .filter(|(char_a, char_b)| char_a == char_b)
+        .map(|(c, _)| c)
+        .collect()
+}
+
+/// How many times each character appears in `s`
+pub fn char_counts(s: &str) -> Counter<char> {
+    s.chars().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strings() {
+        assert_eq!(hamming_distance("abcde", "axcye"), Some(2));
+        assert_eq!(hamming_distance("fghij", "fguij"), Some(1));
+        assert_eq!(hamming_distance("", ""), Some(0));
+        assert_eq!(hamming_distance("abc", "abcd"), None);
+        assert_eq!(hamming_distance("é", "e"), Some(1));
+
+        assert_eq!(common_chars("fghij", "fguij"), "fgij");
+        assert_eq!(common_chars("abc", "xbcd"), "bc");
+
+        let counts = char_counts("bababc");
+        assert_eq!(
+            (counts.count(&'a'), counts.count(&'b'), counts.count(&'c')),
+            (2, 3, 1)
+        );
+    }
+}
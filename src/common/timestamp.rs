@@ -0,0 +1,149 @@
+//! The timestamps of logs like the guard records of 2018 day 4 (`[1518-11-01 00:05] falls
+//! asleep`), which are given out of order and have to be sorted into the order they happened in.
+
+use crate::common::parse::Pattern;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+/// A date and time to the minute, which sorts in the order they happened
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+}
+
+static TIMESTAMP: LazyLock<Pattern> = LazyLock::new(|| {
+    Pattern::new(
+        r"^\[(?P<year>\d+)-(?P<month>\d+)-(?P<day>\d+) (?P<hour>\d+):(?P<minute>\d+)\] ?(?P<rest>.*)$",
+        "a record like [1518-11-01 00:05] falls asleep",
+    )
+});
+
+/// The timestamp in brackets at the start of `line`, and the rest of the line after it (and the
+/// space after it)
+pub fn split_timestamp(line: &str) -> Result<(Timestamp, &str), String> {
+    let captures = TIMESTAMP.captures(line)?;
+
+    let timestamp = Timestamp {
+        year: captures.get("year")?,
+        month: captures.get("month")?,
+        day: captures.get("day")?,
+        hour: captures.get("hour")?,
+        minute: captures.get("minute")?,
+    };
+    if !(1..=12).contains(&timestamp.month)
+        || !(1..=31).contains(&timestamp.day)
+        || timestamp.hour > 23
+        || timestamp.minute > 59
+    {
+        return Err(format!("has {}, which isn't a time", timestamp));
+    }
+
+    Ok((timestamp, captures.str("rest").unwrap_or("")))
+}
+
+/// Sorts records into the order they happened in, then splits them into groups which each start
+/// with a record `starts_group` holds for (such as a guard beginning their shift). Records from
+/// before the first of those are a group of their own, at the start.
+pub fn chronological_groups<T>(
+    mut records: Vec<(Timestamp, T)>,
+    starts_group: impl Fn(&T) -> bool,
+) -> Vec<Vec<(Timestamp, T)>> {
+    records.sort_by_key(|(timestamp, _)| *timestamp);
+
+    let mut groups: Vec<Vec<(Timestamp, T)>> = Vec::new();
+    for record in records {
+        match groups.last_mut() {
+            Some(group) if !starts_group(&record.1) => group.push(record),
+            _ => groups.push(vec![record]),
+        }
+    }
+
+    groups
+}
+
+/// Parses a timestamp alone, with or without its brackets
+impl FromStr for Timestamp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bracketed = if s.starts_with('[') {
+            s.to_string()
+        } else {
+            format!("[{}]", s)
+        };
+
+        match split_timestamp(&bracketed)? {
+            (timestamp, "") => Ok(timestamp),
+            _ => Err("has more than a timestamp".to_string()),
+        }
+    }
+}
+
+/// As logs write them, `1518-11-01 00:05`
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp() {
+        let (timestamp, rest) = split_timestamp("[1518-11-01 00:05] falls asleep").unwrap();
+        assert_eq!(
+            timestamp,
+            Timestamp {
+                year: 1518,
+                month: 11,
+                day: 1,
+                hour: 0,
+                minute: 5
+            }
+        );
+        assert_eq!(rest, "falls asleep");
+        assert_eq!(timestamp.to_string(), "1518-11-01 00:05");
+        assert_eq!("1518-11-01 00:05".parse(), Ok(timestamp));
+        assert_eq!("[1518-11-01 00:05]".parse(), Ok(timestamp));
+
+        assert!(split_timestamp("1518-11-01 00:05 falls asleep").is_err());
+        assert_eq!(
+            split_timestamp("[1518-11-01 24:05] wakes up"),
+            Err("has 1518-11-01 24:05, which isn't a time".to_string())
+        );
+
+        let later: Timestamp = "1518-11-01 23:58".parse().unwrap();
+        let next_day: Timestamp = "1518-11-02 00:40".parse().unwrap();
+        assert!(timestamp < later && later < next_day);
+
+        let groups = chronological_groups(
+            vec![
+                (next_day, "falls asleep"),
+                (later, "Guard #99 begins shift"),
+                (timestamp, "wakes up"),
+            ],
+            |event| event.starts_with("Guard"),
+        );
+        assert_eq!(
+            groups,
+            [
+                vec![(timestamp, "wakes up")],
+                vec![
+                    (later, "Guard #99 begins shift"),
+                    (next_day, "falls asleep")
+                ]
+            ]
+        );
+    }
+}
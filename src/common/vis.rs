@@ -0,0 +1,750 @@
+//! Animations of puzzles in the terminal, drawn a frame at a time as a solution steps through the
+//! puzzle (such as the arcade game of 2019 day 13), and stills of them saved as images.
+//!
+//! `aoc run --visualize` enables animations, which solutions check with `enabled()` before drawing
+//! anything, so that they solve as fast as ever otherwise. While one plays, space pauses it, `n`
+//! draws the next frame while paused, `+` and `-` double or halve the frame rate, and `q` stops
+//! drawing so that the solution finishes at full speed. Any other keys are kept for the solution
+//! to read with `Renderer::take_pressed`, such as to play a game with the arrow keys.
+//!
+//! `aoc run --record <file>` also records every frame drawn, as an animated GIF for a `.gif` file
+//! or a video made by `ffmpeg` for any other kind, with each character of a frame drawn as a small
+//! square of the color `Renderer::set_colors` gives it.
+//!
+//! With the `image` feature, `save_png` saves a grid as a PNG, and `PngFrames` saves a numbered
+//! PNG for each frame of an animation. `aoc run --frames <dir>` sets `frames_directory()`, which
+//! solutions that can save their frames check in the same way as `enabled()`.
+
+use crate::common::geometry::Point;
+use crate::common::grid::{Grid2D, SparseGrid};
+#[cfg(feature = "visualize")]
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+#[cfg(feature = "visualize")]
+use crossterm::{cursor, execute, queue, style, terminal};
+#[cfg(feature = "visualize")]
+use std::collections::HashMap;
+#[cfg(feature = "visualize")]
+use std::convert::TryFrom;
+#[cfg(feature = "visualize")]
+use std::fmt;
+use std::fs;
+use std::io;
+#[cfg(feature = "visualize")]
+use std::io::{BufWriter, Write};
+#[cfg(feature = "image")]
+use std::path::Path;
+use std::path::PathBuf;
+#[cfg(feature = "visualize")]
+use std::process::{self, Child, ChildStdin, Command, Stdio};
+#[cfg(feature = "visualize")]
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Mutex;
+#[cfg(feature = "visualize")]
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static FRAMES_PER_SECOND: AtomicU32 = AtomicU32::new(60);
+#[cfg(feature = "image")]
+static FRAMES_DIRECTORY: Mutex<Option<PathBuf>> = Mutex::new(None);
+#[cfg(feature = "visualize")]
+static RECORDING: Mutex<Option<PathBuf>> = Mutex::new(None);
+/// How many renderers have recorded into `RECORDING`, to number the recordings after the first
+#[cfg(feature = "visualize")]
+static RECORDINGS: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether solutions should draw themselves as they solve the puzzle
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Turns animations on or off for the solutions run after it
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// The frame rate animations start at, 60 unless it's been set
+pub fn frames_per_second() -> u32 {
+    FRAMES_PER_SECOND.load(Ordering::Relaxed)
+}
+
+pub fn set_frames_per_second(frames_per_second: u32) {
+    FRAMES_PER_SECOND.store(frames_per_second.max(1), Ordering::Relaxed);
+}
+
+/// The directory solutions should save the frames of their animations into as PNGs, if any
+#[cfg(feature = "image")]
+pub fn frames_directory() -> Option<PathBuf> {
+    FRAMES_DIRECTORY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Has the solutions run after it save their frames into `directory`, or stop saving them
+#[cfg(feature = "image")]
+pub fn set_frames_directory(directory: Option<PathBuf>) {
+    *FRAMES_DIRECTORY
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = directory;
+}
+
+/// The file the animations are recorded into, if any
+#[cfg(feature = "visualize")]
+pub fn recording() -> Option<PathBuf> {
+    RECORDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Has the animations played after it recorded into `path` (with a `.gif` extension for a GIF,
+/// and any other for a video made by `ffmpeg`), or stops recording them. When more than one
+/// animation plays, the second is recorded into `out-2.gif` for a `path` of `out.gif`, and so on.
+#[cfg(feature = "visualize")]
+pub fn set_recording(path: Option<PathBuf>) {
+    RECORDINGS.store(0, Ordering::Relaxed);
+    *RECORDING
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = path;
+}
+
+/// Draws frames into the terminal in place, taking it over (in raw mode, to read the keys) until
+/// it's dropped
+#[cfg(feature = "visualize")]
+pub struct Renderer {
+    frames_per_second: u32,
+    last_frame: Option<Instant>,
+    paused: bool,
+    /// Set once `q` is pressed, after which frames are no longer drawn
+    stopped: bool,
+    /// The keys pressed which the renderer doesn't act on itself, oldest first
+    pressed: Vec<KeyCode>,
+    recorder: Option<Recorder>,
+}
+
+#[cfg(feature = "visualize")]
+impl Renderer {
+    /// Takes over the terminal, drawing at the frame rate of `frames_per_second()`
+    pub fn new() -> io::Result<Self> {
+        Self::with_frames_per_second(frames_per_second())
+    }
+
+    /// Takes over the terminal, drawing at most `frames_per_second` frames each second
+    pub fn with_frames_per_second(frames_per_second: u32) -> io::Result<Self> {
+        let recorder = recording().map(|path| {
+            let n = RECORDINGS.fetch_add(1, Ordering::Relaxed) + 1;
+            Recorder::new(numbered(&path, n))
+        });
+
+        terminal::enable_raw_mode()?;
+        execute!(
+            io::stdout(),
+            terminal::EnterAlternateScreen,
+            cursor::Hide,
+            terminal::Clear(terminal::ClearType::All)
+        )?;
+
+        Ok(Self {
+            frames_per_second: frames_per_second.max(1),
+            last_frame: None,
+            paused: false,
+            stopped: false,
+            pressed: Vec::new(),
+            recorder,
+        })
+    }
+
+    /// Has recordings draw each character as the color `colors` gives it (as red, green and
+    /// blue), in place of `default_color`
+    pub fn set_colors(&mut self, colors: fn(char) -> [u8; 3]) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.colors = colors;
+        }
+    }
+
+    /// Pauses the animation after the frame drawn last, so that the next is only drawn once it's
+    /// asked for (such as to step through a puzzle from its start)
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Whether `q` has been pressed, so that frames are no longer drawn
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    /// The keys pressed since this was last called, oldest first, leaving out the ones which
+    /// control the animation
+    pub fn take_pressed(&mut self) -> Vec<KeyCode> {
+        std::mem::take(&mut self.pressed)
+    }
+
+    /// Redraws the whole screen as `frame` (a row to a line) below a `header` line, waiting
+    /// first if the previous frame was drawn too recently, or until the next frame is asked for
+    /// while paused
+    pub fn render(&mut self, header: &str, frame: impl fmt::Display) -> io::Result<()> {
+        self.wait()?;
+        if self.stopped {
+            return Ok(());
+        }
+
+        let status = if self.paused {
+            "paused: space resumes, n steps"
+        } else {
+            "space pauses, +/- speed, q skips to the end"
+        };
+
+        let frame = frame.to_string();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record(&frame, self.frames_per_second)?;
+        }
+
+        let mut stdout = io::stdout();
+        queue!(
+            stdout,
+            cursor::MoveTo(0, 0),
+            style::Print(format!(
+                "{}  ({} fps, {})",
+                header, self.frames_per_second, status
+            )),
+            terminal::Clear(terminal::ClearType::UntilNewLine),
+            cursor::MoveToNextLine(1)
+        )?;
+        for line in frame.lines() {
+            queue!(
+                stdout,
+                style::Print(line),
+                terminal::Clear(terminal::ClearType::UntilNewLine),
+                cursor::MoveToNextLine(1)
+            )?;
+        }
+        queue!(stdout, terminal::Clear(terminal::ClearType::FromCursorDown))?;
+
+        stdout.flush()
+    }
+
+    /// Handles the keys pressed since the last frame, then waits out the rest of the frame, or
+    /// while paused, until a key asks for the next one
+    fn wait(&mut self) -> io::Result<()> {
+        if self.stopped {
+            return Ok(());
+        }
+
+        loop {
+            let frame_duration = Duration::from_secs(1) / self.frames_per_second;
+            let remaining = self.last_frame.map_or(Duration::ZERO, |last_frame| {
+                frame_duration.saturating_sub(last_frame.elapsed())
+            });
+
+            if self.paused {
+                if let Event::Key(key) = event::read()? {
+                    if self.handle(key) {
+                        break;
+                    }
+                }
+            } else if event::poll(remaining)? {
+                if let Event::Key(key) = event::read()? {
+                    self.handle(key);
+                }
+            } else {
+                break;
+            }
+
+            if self.stopped {
+                return Ok(());
+            }
+        }
+
+        self.last_frame = Some(Instant::now());
+        Ok(())
+    }
+
+    /// Acts on a key, returning whether it asks for the next frame while paused
+    fn handle(&mut self, key: KeyEvent) -> bool {
+        if key.kind != KeyEventKind::Press {
+            return false;
+        }
+
+        match key.code {
+            // raw mode swallows the interrupt, so it's handled here
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                restore();
+                process::exit(130);
+            }
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('n') | KeyCode::Right if self.paused => return true,
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.frames_per_second = self.frames_per_second.saturating_mul(2);
+            }
+            KeyCode::Char('-') => self.frames_per_second = (self.frames_per_second / 2).max(1),
+            KeyCode::Char('q') | KeyCode::Esc => {
+                self.stopped = true;
+                self.paused = false;
+            }
+            code => self.pressed.push(code),
+        }
+
+        false
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl Drop for Renderer {
+    fn drop(&mut self) {
+        restore();
+        if let Some(recorder) = self.recorder.take() {
+            let path = recorder.path.clone();
+            if let Err(error) = recorder.finish() {
+                eprintln!("Failed to record {}: {}", path.display(), error);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "visualize")]
+fn restore() {
+    // nothing more can be done if the terminal can't be restored
+    let _ = terminal::disable_raw_mode();
+    let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+}
+
+/// The color a character of a frame is recorded as, unless the renderer is given other colors:
+/// the walls, open ground and water most puzzles are drawn with get colors of their own, and any
+/// other character gets one of a handful of bright colors
+#[cfg(feature = "visualize")]
+pub fn default_color(c: char) -> [u8; 3] {
+    const BRIGHT: [[u8; 3]; 8] = [
+        [230, 60, 50],
+        [80, 200, 90],
+        [250, 200, 40],
+        [70, 140, 250],
+        [220, 90, 220],
+        [60, 210, 210],
+        [250, 140, 40],
+        [170, 230, 60],
+    ];
+
+    match c {
+        ' ' => [0, 0, 0],
+        '.' => [60, 60, 60],
+        '#' | '█' => [230, 230, 230],
+        '~' => [20, 60, 200],
+        c => BRIGHT[c as usize % BRIGHT.len()],
+    }
+}
+
+/// How many pixels across and down a character of a frame is recorded as
+#[cfg(feature = "visualize")]
+const PIXELS_PER_CHARACTER: usize = 4;
+
+/// Records the frames a renderer draws, each character a square of pixels. Every frame is as
+/// big as the first, cutting off or filling in (with spaces) any which are bigger or smaller.
+#[cfg(feature = "visualize")]
+struct Recorder {
+    path: PathBuf,
+    colors: fn(char) -> [u8; 3],
+    /// The width and height of the frames, in characters, once the first has been recorded
+    size: Option<(usize, usize)>,
+    sink: Option<Sink>,
+}
+
+#[cfg(feature = "visualize")]
+enum Sink {
+    /// A GIF, holding back the last frame until it's known how long it's shown for, as frames
+    /// which don't change are merged into one
+    Gif {
+        gif: gif::Encoder<BufWriter<fs::File>>,
+        last: Option<(Vec<[u8; 3]>, u32)>,
+    },
+    /// Raw frames piped into `ffmpeg`
+    Video { ffmpeg: Child, stdin: ChildStdin },
+}
+
+#[cfg(feature = "visualize")]
+impl Recorder {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            colors: default_color,
+            size: None,
+            sink: None,
+        }
+    }
+
+    fn is_gif(&self) -> bool {
+        self.path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("gif"))
+    }
+
+    /// Records `frame`, shown for a frame at `frames_per_second`, starting the recording with the
+    /// first frame
+    fn record(&mut self, frame: &str, frames_per_second: u32) -> io::Result<()> {
+        let lines: Vec<String> = frame.lines().map(strip_escapes).collect();
+        let (width, height) = *self.size.get_or_insert_with(|| {
+            let width = lines.iter().map(|line| line.chars().count()).max();
+            (width.unwrap_or(0).max(1), lines.len().max(1))
+        });
+
+        let color = self.colors;
+        let colors: Vec<[u8; 3]> = (0..height)
+            .flat_map(|y| {
+                let mut line = lines.get(y).map_or("", String::as_str).chars();
+                (0..width).map(move |_| color(line.next().unwrap_or(' ')))
+            })
+            .collect();
+
+        if self.sink.is_none() {
+            self.sink = Some(self.start(width, height, frames_per_second)?);
+        }
+        match self.sink.as_mut() {
+            Some(Sink::Gif { gif, last }) => {
+                // browsers show frames of less than 2 hundredths of a second for much longer
+                let delay = (100 / frames_per_second).max(2);
+                match last {
+                    Some((colors_shown, shown_for)) if *colors_shown == colors => {
+                        *shown_for += delay;
+                    }
+                    _ => {
+                        if let Some((colors, delay)) = last.replace((colors, delay)) {
+                            write_gif_frame(gif, width, &colors, delay)?;
+                        }
+                    }
+                }
+                Ok(())
+            }
+            Some(Sink::Video { stdin, .. }) => {
+                let pixels: Vec<u8> = scale_up(&colors, width).flatten().collect();
+                stdin.write_all(&pixels)
+            }
+            None => unreachable!("the recording was started above"),
+        }
+    }
+
+    fn start(&self, width: usize, height: usize, frames_per_second: u32) -> io::Result<Sink> {
+        let (width, height) = (width * PIXELS_PER_CHARACTER, height * PIXELS_PER_CHARACTER);
+
+        if self.is_gif() {
+            let too_big = || io::Error::other("the frames are too big for a GIF");
+            let mut gif = gif::Encoder::new(
+                BufWriter::new(fs::File::create(&self.path)?),
+                u16::try_from(width).map_err(|_| too_big())?,
+                u16::try_from(height).map_err(|_| too_big())?,
+                // each frame has its own palette
+                &[],
+            )
+            .map_err(gif_error)?;
+            gif.set_repeat(gif::Repeat::Infinite).map_err(gif_error)?;
+            return Ok(Sink::Gif { gif, last: None });
+        }
+
+        let mut ffmpeg = Command::new("ffmpeg")
+            .args(["-y", "-loglevel", "error", "-f", "rawvideo"])
+            .args(["-pixel_format", "rgb24", "-video_size"])
+            .arg(format!("{}x{}", width, height))
+            .arg("-framerate")
+            .arg(frames_per_second.to_string())
+            .args(["-i", "-", "-pix_fmt", "yuv420p"])
+            .arg(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .map_err(|error| {
+                io::Error::new(
+                    error.kind(),
+                    format!("couldn't run ffmpeg to record a video ({})", error),
+                )
+            })?;
+        let stdin = ffmpeg.stdin.take().expect("ffmpeg's stdin is piped");
+
+        Ok(Sink::Video { ffmpeg, stdin })
+    }
+
+    /// Writes out the last of the recording
+    fn finish(self) -> io::Result<()> {
+        match self.sink {
+            Some(Sink::Gif { mut gif, last }) => {
+                if let Some((colors, delay)) = last {
+                    let width = self.size.map_or(1, |(width, _)| width);
+                    write_gif_frame(&mut gif, width, &colors, delay)?;
+                }
+                gif.into_inner().map(drop).map_err(gif_error)
+            }
+            Some(Sink::Video { mut ffmpeg, stdin }) => {
+                // closing stdin ends the video
+                drop(stdin);
+                let status = ffmpeg.wait()?;
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(io::Error::other(format!("ffmpeg failed ({})", status)))
+                }
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Adds a frame of characters `width` across, as `colors`, to a GIF, showing it for `delay`
+/// hundredths of a second (or more than one frame, for longer delays than a GIF can hold)
+#[cfg(feature = "visualize")]
+fn write_gif_frame(
+    gif: &mut gif::Encoder<impl Write>,
+    width: usize,
+    colors: &[[u8; 3]],
+    delay: u32,
+) -> io::Result<()> {
+    let mut palette: Vec<[u8; 3]> = Vec::new();
+    let mut indices: HashMap<[u8; 3], u8> = HashMap::new();
+    let pixels: Vec<u8> = scale_up(colors, width)
+        .map(|color| {
+            *indices.entry(color).or_insert_with(|| {
+                // past 256 colors, the rest share the last
+                if palette.len() < 256 {
+                    palette.push(color);
+                }
+                (palette.len() - 1) as u8
+            })
+        })
+        .collect();
+
+    // the frame fits, as the GIF was started with frames as big
+    let pixel_width = (width * PIXELS_PER_CHARACTER) as u16;
+    let pixel_height = (pixels.len() / pixel_width as usize) as u16;
+    let mut frame =
+        gif::Frame::from_palette_pixels(pixel_width, pixel_height, pixels, palette.concat(), None);
+
+    let mut delay = delay;
+    loop {
+        let shown_for = delay.min(u16::MAX as u32);
+        frame.delay = shown_for as u16;
+        gif.write_frame(&frame).map_err(gif_error)?;
+        delay -= shown_for;
+        if delay == 0 {
+            return Ok(());
+        }
+    }
+}
+
+/// `error` from writing a GIF, as an I/O error
+#[cfg(feature = "visualize")]
+fn gif_error(error: gif::EncodingError) -> io::Error {
+    match error {
+        gif::EncodingError::Io(error) => error,
+        error => io::Error::other(error),
+    }
+}
+
+/// The pixels of a frame of characters `width` across, as `colors`, a row at a time
+#[cfg(feature = "visualize")]
+fn scale_up(colors: &[[u8; 3]], width: usize) -> impl Iterator<Item = [u8; 3]> + '_ {
+    colors.chunks(width).flat_map(move |row| {
+        (0..PIXELS_PER_CHARACTER).flat_map(move |_| {
+            row.iter()
+                .flat_map(|color| std::iter::repeat_n(*color, PIXELS_PER_CHARACTER))
+        })
+    })
+}
+
+/// A line of a frame without the escape sequences which style it in the terminal
+#[cfg(feature = "visualize")]
+fn strip_escapes(line: &str) -> String {
+    let mut stripped = String::with_capacity(line.len());
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequences (the only kind styles use) end with a letter
+            if chars.next() == Some('[') {
+                chars.find(|c| c.is_ascii_alphabetic());
+            }
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+/// `path` for the first recording, and numbered after its stem for the others, such as
+/// `out-2.gif`
+#[cfg(feature = "visualize")]
+fn numbered(path: &std::path::Path, n: usize) -> PathBuf {
+    if n <= 1 {
+        return path.to_path_buf();
+    }
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, n, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, n),
+    };
+    path.with_file_name(name)
+}
+
+/// A grid which can be drawn a cell to a pixel (or a square of them), with `None` for the cells
+/// which aren't set
+pub trait Raster {
+    type Cell;
+
+    /// The width and height of the grid, in cells
+    fn dimensions(&self) -> (usize, usize);
+
+    /// The cell at `(x, y)`, counted from the top left of the grid
+    fn cell(&self, position: (usize, usize)) -> Option<&Self::Cell>;
+}
+
+impl<T> Raster for Grid2D<T> {
+    type Cell = T;
+
+    fn dimensions(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    fn cell(&self, position: (usize, usize)) -> Option<&T> {
+        self.get(position)
+    }
+}
+
+/// Drawn within its bounds
+impl<T> Raster for SparseGrid<T> {
+    type Cell = T;
+
+    fn dimensions(&self) -> (usize, usize) {
+        self.bounds().map_or((0, 0), |(min, max)| {
+            ((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize)
+        })
+    }
+
+    fn cell(&self, (x, y): (usize, usize)) -> Option<&T> {
+        let (min, _) = self.bounds()?;
+        self.get(min + Point::new(x as i64, y as i64))
+    }
+}
+
+/// Saves `grid` as a PNG, each cell a `scale` pixel square of the color `palette` gives it (as
+/// red, green and blue)
+#[cfg(feature = "image")]
+pub fn save_png<G: Raster>(
+    grid: &G,
+    path: impl AsRef<Path>,
+    scale: u32,
+    palette: impl Fn(Option<&G::Cell>) -> [u8; 3],
+) -> image::ImageResult<()> {
+    let scale = scale.max(1);
+    let (width, height) = grid.dimensions();
+
+    let image = image::RgbImage::from_fn(
+        width.max(1) as u32 * scale,
+        height.max(1) as u32 * scale,
+        |x, y| {
+            let position = ((x / scale) as usize, (y / scale) as usize);
+            image::Rgb(palette(grid.cell(position)))
+        },
+    );
+
+    image.save_with_format(path, image::ImageFormat::Png)
+}
+
+/// Saves each frame of an animation as a numbered PNG in a directory (`frame-00000.png`,
+/// `frame-00001.png`, and so on), to be put together into a video or GIF afterwards
+#[cfg(feature = "image")]
+pub struct PngFrames {
+    directory: PathBuf,
+    scale: u32,
+    next: usize,
+}
+
+#[cfg(feature = "image")]
+impl PngFrames {
+    /// Saves frames into `directory`, which is created if it doesn't exist
+    pub fn new(directory: impl Into<PathBuf>, scale: u32) -> io::Result<Self> {
+        let directory = directory.into();
+        fs::create_dir_all(&directory)?;
+
+        Ok(Self {
+            directory,
+            scale,
+            next: 0,
+        })
+    }
+
+    /// Saves the next frame, returning where it was saved
+    pub fn save<G: Raster>(
+        &mut self,
+        grid: &G,
+        palette: impl Fn(Option<&G::Cell>) -> [u8; 3],
+    ) -> image::ImageResult<PathBuf> {
+        let path = self.directory.join(format!("frame-{:05}.png", self.next));
+        save_png(grid, &path, self.scale, palette)?;
+        self.next += 1;
+
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "visualize")]
+    fn test_recorder() {
+        let path = std::env::temp_dir().join(format!("vis-{}.gif", std::process::id()));
+        let mut recorder = Recorder::new(path.clone());
+        recorder.record("#.\n.#\n", 50).unwrap();
+        recorder.record("#.\n.#", 50).unwrap();
+        // styled, and too big, so cut down to the first frame's size
+        recorder.record("\x1b[7m~\x1b[0m..\n..\n..", 50).unwrap();
+        recorder.finish().unwrap();
+
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(bytes.starts_with(b"GIF89a\x08\x00\x08\x00"));
+        // the first two frames are one, shown for both
+        let delays: Vec<&[u8]> = bytes
+            .windows(6)
+            .filter(|w| w.starts_with(&[0x21, 0xF9, 0x04]))
+            .map(|w| &w[4..])
+            .collect();
+        assert_eq!(delays, [[4, 0], [2, 0]]);
+
+        assert_eq!(strip_escapes("\x1b[1;31mE\x1b[0m(200)"), "E(200)");
+        let out = PathBuf::from("frames/out.gif");
+        assert_eq!(numbered(&out, 1), out);
+        assert_eq!(numbered(&out, 3), PathBuf::from("frames/out-3.gif"));
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_save_png() {
+        let palette = |cell: Option<&char>| match cell {
+            Some('#') => [255, 255, 255],
+            Some(_) => [0, 0, 255],
+            None => [0, 0, 0],
+        };
+
+        let directory = std::env::temp_dir().join(format!("vis-{}", std::process::id()));
+        let mut frames = PngFrames::new(&directory, 2).unwrap();
+
+        let grid = Grid2D::from_chars("#..\n.#.").unwrap();
+        let first = frames.save(&grid, palette).unwrap();
+        let sparse: SparseGrid<char> = vec![(Point::new(-1, 5), '#'), (Point::new(1, 6), '~')]
+            .into_iter()
+            .collect();
+        let second = frames.save(&sparse, palette).unwrap();
+
+        let first_image = image::open(&first).unwrap().to_rgb8();
+        let second_image = image::open(&second).unwrap().to_rgb8();
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(first.file_name().unwrap(), "frame-00000.png");
+        assert_eq!(second.file_name().unwrap(), "frame-00001.png");
+        assert_eq!(first_image.dimensions(), (6, 4));
+        assert_eq!(first_image.get_pixel(1, 1).0, [255, 255, 255]);
+        assert_eq!(first_image.get_pixel(5, 3).0, [0, 0, 255]);
+        assert_eq!(second_image.dimensions(), (6, 4));
+        assert_eq!(second_image.get_pixel(0, 0).0, [255, 255, 255]);
+        assert_eq!(second_image.get_pixel(2, 0).0, [0, 0, 0]);
+        assert_eq!(second_image.get_pixel(5, 3).0, [0, 0, 255]);
+    }
+}
@@ -0,0 +1,185 @@
+//! Downloads the description of a puzzle, as Markdown which reads well in a terminal.
+//!
+//! The page is requested with the session cookie, so that the description of part two is
+//! included once part one has been solved. Descriptions are cached next to the inputs, and can be
+//! refreshed once the second part unlocks.
+
+use crate::calendar;
+use crate::download::{self, DownloadError};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Where the description of a puzzle is cached, next to its input in the inputs directory `inputs`
+pub fn description_path(inputs: &Path, year: u16, day: u8) -> PathBuf {
+    inputs.join(year.to_string()).join(format!("day{}.md", day))
+}
+
+/// Returns the description of a puzzle, from the cache at `path` unless `refresh` is set or it
+/// hasn't been downloaded yet
+pub fn fetch_description(
+    year: u16,
+    day: u8,
+    session: &str,
+    path: &Path,
+    cache_directory: &Path,
+    refresh: bool,
+) -> Result<String, DownloadError> {
+    if !refresh {
+        if let Ok(description) = fs::read_to_string(path) {
+            return Ok(description);
+        }
+    }
+
+    if let Ok(remaining) = calendar::unlock_time(year, day).duration_since(SystemTime::now()) {
+        return Err(DownloadError::Locked {
+            year,
+            day,
+            remaining,
+        });
+    }
+
+    let url = format!("https://adventofcode.com/{}/day/{}", year, day);
+    let description = html_to_markdown(&download::get(&url, session, cache_directory)?);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, &description)?;
+
+    Ok(description)
+}
+
+/// Converts the `<article>`s of a puzzle page, which hold the description of each part, to
+/// Markdown. Only the handful of tags used in descriptions are converted; the rest are dropped.
+fn html_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        let article = &rest[start..];
+        let end = article.find("</article>").unwrap_or(article.len());
+        convert_article(&article[..end], &mut markdown);
+        rest = &article[end..];
+    }
+
+    // a line ends up blank in place of each tag which is dropped between blocks
+    let mut collapsed = String::new();
+    let mut blank_lines = 0;
+    for line in markdown.lines() {
+        let line = line.trim_end();
+        blank_lines = if line.is_empty() { blank_lines + 1 } else { 0 };
+        if blank_lines <= 1 {
+            collapsed.push_str(line);
+            collapsed.push('\n');
+        }
+    }
+
+    collapsed.trim().to_string() + "\n"
+}
+
+fn convert_article(article: &str, markdown: &mut String) {
+    let mut in_pre = false;
+
+    let mut rest = article;
+    while !rest.is_empty() {
+        let (text, tag) = match rest.find('<') {
+            Some(start) => {
+                let end = rest[start..]
+                    .find('>')
+                    .map_or(rest.len(), |end| start + end + 1);
+                (&rest[..start], &rest[start..end])
+            }
+            None => (rest, ""),
+        };
+        rest = &rest[text.len() + tag.len()..];
+        // the line breaks between tags are only formatting, outside of code blocks
+        if in_pre || !(text.contains('\n') && text.trim().is_empty()) {
+            markdown.push_str(&decode_entities(text));
+        }
+
+        let closing = tag.starts_with("</");
+        let name: String = tag
+            .trim_start_matches("</")
+            .trim_start_matches('<')
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect();
+
+        match (name.as_str(), closing) {
+            ("h2", false) => markdown.push_str("\n## "),
+            ("h2", true) => markdown.push_str("\n\n"),
+            ("p", true) => markdown.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("\n```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => markdown.push('`'),
+            ("em", _) if !in_pre => markdown.push('*'),
+            ("li", false) => markdown.push_str("- "),
+            ("li", true) | ("ul", true) => markdown.push('\n'),
+            _ => {}
+        }
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_to_markdown() {
+        let html = r#"<main>
+<article class="day-desc"><h2>--- Day 5: Alchemical Reduction ---</h2><p>The polymer is formed by smaller <em>units</em> which, when triggered, react with each other such that two adjacent units of the same type and opposite polarity are destroyed. For example:</p>
+<ul>
+<li>In <code>aA</code>, <code>a</code> and <code>A</code> react, leaving nothing behind.</li>
+<li>In <code>abBA</code>, <code>bB</code> destroys itself, leaving <code>aA</code>.</li>
+</ul>
+<pre><code>dabAcCaCBAcCcaDA  The first 'cC' is removed.
+dabA<em>cC</em>aCBAcCcaDA
+</code></pre>
+<p>How many units remain after fully reacting the polymer you <a href="5/input" target="_blank">scanned</a>? <span title="Remember, 1 &lt; 2">(&quot;units&quot;)</span></p>
+</article>
+<p>Your puzzle answer was <code>10</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>What is the length of the shortest polymer you can produce?</p>
+</article>
+</main>"#;
+
+        assert_eq!(
+            html_to_markdown(html),
+            r#"## --- Day 5: Alchemical Reduction ---
+
+The polymer is formed by smaller *units* which, when triggered, react with each other such that two adjacent units of the same type and opposite polarity are destroyed. For example:
+
+- In `aA`, `a` and `A` react, leaving nothing behind.
+- In `abBA`, `bB` destroys itself, leaving `aA`.
+
+```
+dabAcCaCBAcCcaDA  The first 'cC' is removed.
+dabAcCaCBAcCcaDA
+```
+
+How many units remain after fully reacting the polymer you scanned? ("units")
+
+## --- Part Two ---
+
+What is the length of the shortest polymer you can produce?
+"#
+        );
+    }
+}
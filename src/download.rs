@@ -0,0 +1,177 @@
+//! Downloads puzzle inputs from adventofcode.com, using the session cookie of a logged in account.
+//!
+//! Every input is personal to an account and never changes, so each is only ever downloaded once
+//! and then cached in the inputs directory. Requests are spaced out so that downloading a whole
+//! year in one go doesn't hammer the servers.
+
+use crate::calendar;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The environment variable holding the value of the `session` cookie from a logged in browser
+pub const SESSION_VARIABLE: &str = "AOC_SESSION";
+
+/// The least time to leave between two requests
+const REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Identifies this tool to the Advent of Code servers, as requested by its author
+const USER_AGENT: &str = "github.com/alecdwm/advent-of-code-2018 by alec@owls.io";
+
+/// Remembers when the last request was made, so the interval holds between separate runs
+const LAST_REQUEST_FILE: &str = ".last-request";
+
+/// Reads the session token from the environment
+pub fn session_from_env() -> Result<String, DownloadError> {
+    env::var(SESSION_VARIABLE)
+        .ok()
+        .filter(|session| !session.trim().is_empty())
+        .map(|session| session.trim().to_string())
+        .ok_or(DownloadError::MissingSession)
+}
+
+/// Returns the input of a puzzle, from the cache at `path` if it has already been downloaded.
+///
+/// The last request time is kept next to the cache, in `cache_directory`.
+pub fn fetch_input(
+    year: u16,
+    day: u8,
+    session: &str,
+    path: &Path,
+    cache_directory: &Path,
+) -> Result<String, DownloadError> {
+    if let Ok(input) = fs::read_to_string(path) {
+        return Ok(input);
+    }
+
+    if let Ok(remaining) = calendar::unlock_time(year, day).duration_since(SystemTime::now()) {
+        return Err(DownloadError::Locked {
+            year,
+            day,
+            remaining,
+        });
+    }
+
+    let url = format!("https://adventofcode.com/{}/day/{}/input", year, day);
+    let input = get(&url, session, cache_directory)?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, &input)?;
+
+    Ok(input)
+}
+
+/// Downloads a page from adventofcode.com, waiting out the interval since the last request first.
+///
+/// The last request time is kept in `cache_directory`.
+pub(crate) fn get(
+    url: &str,
+    session: &str,
+    cache_directory: &Path,
+) -> Result<String, DownloadError> {
+    fs::create_dir_all(cache_directory)?;
+    wait_for_request_interval(&cache_directory.join(LAST_REQUEST_FILE))?;
+
+    ureq::get(url)
+        .header("Cookie", &format!("session={}", session))
+        .header("User-Agent", USER_AGENT)
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_string())
+        .map_err(|error| DownloadError::Request(url.to_string(), error.to_string()))
+}
+
+fn wait_for_request_interval(last_request_file: &Path) -> io::Result<()> {
+    let last_request = fs::read_to_string(last_request_file)
+        .ok()
+        .and_then(|seconds| seconds.trim().parse().ok())
+        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds));
+
+    if let Some(elapsed) = last_request.and_then(|last| SystemTime::now().duration_since(last).ok())
+    {
+        if elapsed < REQUEST_INTERVAL {
+            thread::sleep(REQUEST_INTERVAL - elapsed);
+        }
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    fs::write(last_request_file, now.to_string())
+}
+
+#[derive(Debug)]
+pub enum DownloadError {
+    /// No session token was given
+    MissingSession,
+
+    /// The puzzle hasn't unlocked yet
+    Locked {
+        year: u16,
+        day: u8,
+        remaining: Duration,
+    },
+
+    /// The request failed, including when the server refused it (such as for an expired session)
+    Request(String, String),
+
+    Io(io::Error),
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingSession => write!(
+                f,
+                "Set {} to the session cookie of adventofcode.com to download inputs and descriptions",
+                SESSION_VARIABLE
+            ),
+            Self::Locked {
+                year,
+                day,
+                remaining,
+            } => write!(
+                f,
+                "{} day {} unlocks in {}s",
+                year,
+                day,
+                remaining.as_secs()
+            ),
+            Self::Request(url, error) => write!(f, "Failed to download {}: {}", url, error),
+            Self::Io(error) => write!(f, "Failed to cache input: {}", error),
+        }
+    }
+}
+
+impl Error for DownloadError {}
+
+impl From<io::Error> for DownloadError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fetch_cached_input() {
+        let directory = env::temp_dir().join(format!("download-{}", std::process::id()));
+        let path = directory.join("2018").join("day5.txt");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "dabAcCaCBAcCcaDA").unwrap();
+
+        let input = fetch_input(2018, 5, "session", &path, &directory);
+        fs::remove_dir_all(&directory).unwrap();
+
+        assert_eq!(input.unwrap(), "dabAcCaCBAcCcaDA");
+    }
+}
@@ -0,0 +1,62 @@
+//! Why a solution couldn't answer its puzzle, which is nearly always that the input isn't a puzzle
+//! input for its day
+
+use crate::solutions::SelectionError;
+use std::io;
+use thiserror::Error;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Error {
+    /// A line of the input isn't what the puzzle describes
+    #[error("line {number} of the input ({text:?}) {reason}")]
+    Line {
+        /// Counted from 1, as editors do
+        number: usize,
+        text: String,
+        reason: String,
+    },
+
+    /// The input as a whole isn't what the puzzle describes
+    #[error("the input {0}")]
+    Input(String),
+
+    /// The input has no answer, such as an Intcode program which never gives the output searched for
+    #[error("there is no answer, as {0}")]
+    NoAnswer(String),
+
+    /// The input couldn't be read while it was being parsed, as inputs which are streamed are
+    #[error("failed to read the input: {0}")]
+    Read(String),
+
+    #[error(transparent)]
+    Selection(#[from] SelectionError),
+}
+
+impl Error {
+    /// An error in the line at `index` (counted from 0, as `lines().enumerate()` does)
+    pub fn line(index: usize, text: &str, reason: impl Into<String>) -> Self {
+        Self::Line {
+            number: index + 1,
+            text: text.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    /// An error reading the input, or that it isn't UTF-8
+    pub fn read(error: io::Error) -> Self {
+        Self::Read(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error() {
+        assert_eq!(
+            Error::line(2, "#3 @ 5,5 2x2", "isn't a claim").to_string(),
+            "line 3 of the input (\"#3 @ 5,5 2x2\") isn't a claim"
+        );
+    }
+}
@@ -0,0 +1,501 @@
+//! The examples from each puzzle's description along with their answers, which `aoc run --example`
+//! checks the solutions against before they're let loose on the real puzzle input.
+//!
+//! Some puzzles change a setting for their example (such as the number of workers in 2018 day 7
+//! part 2), and those examples are solved with their own settings.
+
+use crate::params::Params;
+
+/// An example puzzle input of a single part, and its answer
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+
+    pub input: &'static str,
+    pub answer: &'static str,
+    /// The settings the example changes, by name
+    pub params: &'static [(&'static str, i64)],
+}
+
+impl Example {
+    /// The settings to solve the example with
+    pub fn params(&self) -> Params {
+        let mut params = Params::default();
+        for (name, value) in self.params {
+            params.set(name, *value);
+        }
+
+        params
+    }
+}
+
+/// Registers an example of a part with its answer, and the settings it changes if it does
+macro_rules! example {
+    ($year:literal, $day:literal, $part:literal, $input:expr, $answer:literal) => {
+        example!($year, $day, $part, $input, $answer, [])
+    };
+    (
+        $year:literal,
+        $day:literal,
+        $part:literal,
+        $input:expr,
+        $answer:literal,
+        [$(($name:literal, $value:expr)),*]
+    ) => {
+        Example {
+            year: $year,
+            day: $day,
+            part: $part,
+            input: $input,
+            answer: $answer,
+            params: &[$(($name, $value)),*],
+        }
+    };
+}
+
+const CLAIMS: &str = "#1 @ 1,3: 4x4\n#2 @ 3,1: 4x4\n#3 @ 5,5: 2x2";
+
+const GUARD_RECORDS: &str = "[1518-11-01 00:00] Guard #10 begins shift
+[1518-11-01 00:05] falls asleep
+[1518-11-01 00:25] wakes up
+[1518-11-01 00:30] falls asleep
+[1518-11-01 00:55] wakes up
+[1518-11-01 23:58] Guard #99 begins shift
+[1518-11-02 00:40] falls asleep
+[1518-11-02 00:50] wakes up
+[1518-11-03 00:05] Guard #10 begins shift
+[1518-11-03 00:24] falls asleep
+[1518-11-03 00:29] wakes up
+[1518-11-04 00:02] Guard #99 begins shift
+[1518-11-04 00:36] falls asleep
+[1518-11-04 00:46] wakes up
+[1518-11-05 00:03] Guard #99 begins shift
+[1518-11-05 00:45] falls asleep
+[1518-11-05 00:55] wakes up
+";
+
+const COORDINATES: &str = "1, 1\n1, 6\n8, 3\n3, 4\n5, 5\n8, 9";
+
+const STEP_INSTRUCTIONS: &str = "Step C must be finished before step A can begin.
+Step C must be finished before step F can begin.
+Step A must be finished before step B can begin.
+Step A must be finished before step D can begin.
+Step B must be finished before step E can begin.
+Step D must be finished before step E can begin.
+Step F must be finished before step E can begin.
+";
+
+const WIRES: [&str; 3] = [
+    "R8,U5,L5,D3\nU7,R6,D4,L4",
+    "R75,D30,R83,U83,L12,D49,R71,U7,L72\nU62,R66,U55,R34,D71,R55,D58,R83",
+    "R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51\nU98,R91,D20,R16,D67,R40,U7,R15,U6,R7",
+];
+
+const AMPLIFIER_CONTROLLERS: [&str; 5] = [
+    "3,15,3,16,1002,16,10,16,1,16,15,15,4,15,99,0,0",
+    "3,23,3,24,1002,24,10,24,1002,23,-1,23,101,5,23,23,1,24,23,23,4,23,99,0,0",
+    "3,31,3,32,1002,32,10,32,1001,31,-2,31,1007,31,0,33,1002,33,7,33,1,33,31,31,1,32,31,31,4,31,99,0,0,0",
+    "3,26,1001,26,-4,26,3,27,1002,27,2,27,1,27,26,27,4,27,1001,28,-1,28,1005,28,6,99,0,0,5",
+    "3,52,1001,52,-5,52,3,53,1,52,56,54,1007,54,5,55,1005,55,26,1001,54,-5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10",
+];
+
+const ASTEROID_MAPS: [&str; 5] = [
+    ".#..#\n.....\n#####\n....#\n...##",
+    "......#.#.\n#..#.#....\n..#######.\n.#.#.###..\n.#..#.....\n..#....#.#\n#..#....#.\n.##.#..###\n##...#..#.\n.#....####",
+    "#.#...#.#.\n.###....#.\n.#....#...\n##.#.#.#.#\n....#.#.#.\n.##..###.#\n..#...##..\n..##....##\n......#...\n.####.###.",
+    ".#..#..###\n####.###.#\n....###.#.\n..###.##.#\n##.##.#.#.\n....###..#\n..#.#..#.#\n#..#.#.###\n.##...##.#\n.....#.#..",
+    ".#..##.###...#######\n##.############..##.\n.#.######.########.#\n.###.#######.####.#.\n#####.##.#.##.###.##\n..#####..#.#########\n####################\n#.####....###.#.#.##\n##.#################\n#####.##.###..####..\n..######..##.#######\n####.##.####...##..#\n.#####..#.######.###\n##...#.##########...\n#.##########.#######\n.####.#.###.###.#.##\n....##.##.###..#####\n.#.#.###########.###\n#.#.#.#####.####.###\n###.##.####.##.#..##",
+];
+
+const TUNNEL: &str = "initial state: #..#.#..##......###...###
+
+...## => #
+..#.. => #
+.#... => #
+.#.#. => #
+.#.## => #
+.##.. => #
+.#### => #
+#.#.# => #
+#.### => #
+##.#. => #
+##.## => #
+###.. => #
+###.# => #
+####. => #
+";
+
+const COMBATS: [&str; 6] = [
+    "#######\n#.G...#\n#...EG#\n#.#.#G#\n#..G#E#\n#.....#\n#######",
+    "#######\n#G..#E#\n#E#E.E#\n#G.##.#\n#...#E#\n#...E.#\n#######",
+    "#######\n#E..EG#\n#.#G.E#\n#E.##E#\n#G..#.#\n#..E#.#\n#######",
+    "#######\n#E.G#.#\n#.#G..#\n#G.#.G#\n#G..#.#\n#...E.#\n#######",
+    "#######\n#.E...#\n#.#..G#\n#.###.#\n#E#G#G#\n#...#G#\n#######",
+    "#########\n#G......#\n#.E.#...#\n#..##..G#\n#...##..#\n#...#...#\n#.G...G.#\n#.....G.#\n#########",
+];
+
+const STARS: &str = "position=< 9,  1> velocity=< 0,  2>
+position=< 7,  0> velocity=<-1,  0>
+position=< 3, -2> velocity=<-1,  1>
+position=< 6, 10> velocity=<-2, -1>
+position=< 2, -4> velocity=< 2,  2>
+position=<-6, 10> velocity=< 2, -2>
+position=< 1,  8> velocity=< 1, -1>
+position=< 1,  7> velocity=< 1,  0>
+position=<-3, 11> velocity=< 1, -2>
+position=< 7,  6> velocity=<-1, -1>
+position=<-2,  3> velocity=< 1,  0>
+position=<-4,  3> velocity=< 2,  0>
+position=<10, -3> velocity=<-1,  1>
+position=< 5, 11> velocity=< 1, -2>
+position=< 4,  7> velocity=< 0, -1>
+position=< 8, -2> velocity=< 0,  1>
+position=<15,  0> velocity=<-2,  0>
+position=< 1,  6> velocity=< 1,  0>
+position=< 8,  9> velocity=< 0, -1>
+position=< 3,  3> velocity=<-1,  1>
+position=< 0,  5> velocity=< 0, -1>
+position=<-2,  2> velocity=< 2,  0>
+position=< 5, -2> velocity=< 1,  2>
+position=< 1,  4> velocity=< 2,  1>
+position=<-2,  7> velocity=< 2, -2>
+position=< 3,  6> velocity=<-1, -1>
+position=< 5,  0> velocity=< 1,  0>
+position=<-6,  0> velocity=< 2,  0>
+position=< 5,  9> velocity=< 1, -2>
+position=<14,  7> velocity=<-2,  0>
+position=<-3,  6> velocity=< 2, -1>";
+
+const MINE_TRACKS: &str = r"/->-\        
+|   |  /----\
+| /-+--+-\  |
+| | |  | v  |
+\-+-/  \-+--/
+  \------/   ";
+
+const MINE_TRACKS_CRASHING: &str = r"/>-<\  
+|   |  
+| /<+-\
+| | | v
+\>+</ |
+  |   ^
+  \<->/";
+
+const CLAY_VEINS: &str = "x=495, y=2..7
+y=7, x=495..501
+x=501, y=3..7
+x=498, y=2..4
+x=506, y=1..2
+x=498, y=10..13
+x=504, y=10..13
+y=13, x=498..504";
+
+const ARMIES: &str = "Immune System:
+17 units each with 5390 hit points (weak to radiation, bludgeoning) with an attack that does 4507 fire damage at initiative 2
+989 units each with 1274 hit points (immune to fire; weak to bludgeoning, slashing) with an attack that does 25 slashing damage at initiative 3
+
+Infection:
+801 units each with 4706 hit points (weak to radiation) with an attack that does 116 bludgeoning damage at initiative 1
+4485 units each with 2961 hit points (immune to radiation; weak to fire, cold) with an attack that does 12 slashing damage at initiative 4";
+
+const LUMBER_COLLECTION_AREA: &str = ".#.#...|#.
+.....#|##|
+.|..|...#.
+..|#.....#
+#.#|||#|#|
+...#.||...
+.|....|...
+||...#|.#|
+|.||||..|.
+...#.|..|.";
+
+const MOONS: [&str; 2] = [
+    "<x=-1, y=0, z=2>\n<x=2, y=-10, z=-7>\n<x=4, y=-8, z=8>\n<x=3, y=5, z=-1>",
+    "<x=-8, y=-10, z=0>\n<x=5, y=5, z=10>\n<x=2, y=-7, z=3>\n<x=9, y=-8, z=-3>",
+];
+
+const VAULTS: [&str; 9] = [
+    "#########\n#b.A.@.a#\n#########",
+    "########################\n#f.D.E.e.C.b.A.@.a.B.c.#\n######################.#\n#d.....................#\n########################",
+    "########################\n#...............b.C.D.f#\n#.######################\n#.....@.a.B.c.d.A.e.F.g#\n########################",
+    "#################\n#i.G..c...e..H.p#\n########.########\n#j.A..b...f..D.o#\n########@########\n#k.E..a...g..B.n#\n########.########\n#l.F..d...h..C.m#\n#################",
+    "########################\n#@..............ac.GI.b#\n###d#e#f################\n###A#B#C################\n###g#h#i################\n########################",
+    "#######\n#a.#Cd#\n##@#@##\n#######\n##@#@##\n#cB#Ab#\n#######",
+    "###############\n#d.ABC.#.....a#\n######@#@######\n###############\n######@#@######\n#b.....#.....c#\n###############",
+    "#############\n#DcBa.#.GhKl#\n#.###@#@#I###\n#e#d#####j#k#\n###C#@#@###J#\n#fEbA.#.FgHi#\n#############",
+    "#############\n#g#f.D#..h#l#\n#F###e#E###.#\n#dCba@#@BcIJ#\n#############\n#nK.L@#@G...#\n#M###N#H###.#\n#o#m..#i#jk.#\n#############",
+];
+
+/// Every example, in order of year, day and part
+pub const EXAMPLES: &[Example] = &[
+    example!(2018, 1, 1, "+1\n-2\n+3\n+1", "3"),
+    example!(2018, 1, 1, "+1\n+1\n+1", "3"),
+    example!(2018, 1, 1, "+1\n+1\n-2", "0"),
+    example!(2018, 1, 1, "-1\n-2\n-3", "-6"),
+    example!(2018, 1, 2, "+1\n-1", "0"),
+    example!(2018, 1, 2, "+3\n+3\n+4\n-2\n-4", "10"),
+    example!(2018, 1, 2, "-6\n+3\n+8\n+5\n-6", "5"),
+    example!(2018, 1, 2, "+7\n+7\n-2\n-7\n-4", "14"),
+    example!(
+        2018,
+        2,
+        1,
+        "abcdef\nbababc\nabbcde\nabcccd\naabcdd\nabcdee\nababab",
+        "12"
+    ),
+    example!(
+        2018,
+        2,
+        2,
+        "abcde\nfghij\nklmno\npqrst\nfguij\naxcye\nwvxyz",
+        "fgij"
+    ),
+    example!(2018, 3, 1, CLAIMS, "4"),
+    example!(2018, 3, 2, CLAIMS, "3"),
+    example!(2018, 4, 1, GUARD_RECORDS, "240"),
+    example!(2018, 4, 2, GUARD_RECORDS, "4455"),
+    example!(2018, 5, 1, "dabAcCaCBAcCcaDA", "10"),
+    example!(2018, 5, 2, "dabAcCaCBAcCcaDA", "4"),
+    example!(2018, 6, 1, COORDINATES, "17"),
+    example!(2018, 6, 2, COORDINATES, "16", [("distance", 32)]),
+    example!(2018, 7, 1, STEP_INSTRUCTIONS, "CABDFE"),
+    example!(
+        2018,
+        7,
+        2,
+        STEP_INSTRUCTIONS,
+        "15",
+        [("workers", 2), ("seconds", 0)]
+    ),
+    example!(2018, 8, 1, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2", "138"),
+    example!(2018, 8, 2, "2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2", "66"),
+    example!(
+        2018,
+        9,
+        1,
+        "9 players; last marble is worth 25 points",
+        "32"
+    ),
+    example!(
+        2018,
+        9,
+        1,
+        "10 players; last marble is worth 1618 points",
+        "8317"
+    ),
+    example!(
+        2018,
+        9,
+        1,
+        "13 players; last marble is worth 7999 points",
+        "146373"
+    ),
+    example!(
+        2018,
+        9,
+        1,
+        "17 players; last marble is worth 1104 points",
+        "2764"
+    ),
+    example!(
+        2018,
+        9,
+        1,
+        "21 players; last marble is worth 6111 points",
+        "54718"
+    ),
+    example!(
+        2018,
+        9,
+        1,
+        "30 players; last marble is worth 5807 points",
+        "37305"
+    ),
+    example!(2018, 10, 1, STARS, "█   █  ███\n█   █   █ \n█   █   █ \n█████   █ \n█   █   █ \n█   █   █ \n█   █   █ \n█   █  ███"),
+    example!(2018, 10, 2, STARS, "3"),
+    example!(2018, 11, 1, "18", "33,45"),
+    example!(2018, 11, 1, "42", "21,61"),
+    example!(2018, 11, 2, "18", "90,269,16"),
+    example!(2018, 11, 2, "42", "232,251,12"),
+    example!(2018, 12, 1, TUNNEL, "325"),
+    example!(2018, 13, 1, MINE_TRACKS, "7,3"),
+    example!(2018, 13, 2, MINE_TRACKS_CRASHING, "6,4"),
+    example!(2018, 14, 1, "9", "5158916779"),
+    example!(2018, 14, 1, "5", "0124515891"),
+    example!(2018, 14, 1, "18", "9251071085"),
+    example!(2018, 14, 1, "2018", "5941429882"),
+    example!(2018, 14, 2, "51589", "9"),
+    example!(2018, 14, 2, "01245", "5"),
+    example!(2018, 14, 2, "92510", "18"),
+    example!(2018, 14, 2, "59414", "2018"),
+    example!(2018, 15, 1, COMBATS[0], "27730"),
+    example!(2018, 15, 1, COMBATS[1], "36334"),
+    example!(2018, 15, 1, COMBATS[2], "39514"),
+    example!(2018, 15, 1, COMBATS[3], "27755"),
+    example!(2018, 15, 1, COMBATS[4], "28944"),
+    example!(2018, 15, 1, COMBATS[5], "18740"),
+    example!(2018, 15, 2, COMBATS[0], "4988"),
+    example!(2018, 15, 2, COMBATS[2], "31284"),
+    example!(2018, 15, 2, COMBATS[3], "3478"),
+    example!(2018, 15, 2, COMBATS[4], "6474"),
+    example!(2018, 15, 2, COMBATS[5], "1140"),
+    example!(2018, 17, 1, CLAY_VEINS, "57"),
+    example!(2018, 17, 2, CLAY_VEINS, "29"),
+    example!(2018, 18, 1, LUMBER_COLLECTION_AREA, "1147"),
+    example!(2018, 20, 1, "^WNE$", "3"),
+    example!(2018, 20, 1, "^ENWWW(NEEE|SSE(EE|N))$", "10"),
+    example!(2018, 20, 1, "^ENNWSWW(NEWS|)SSSEEN(WNSE|)EE(SWEN|)NNN$", "18"),
+    example!(2018, 20, 1, "^ESSWWN(E|NNENN(EESS(WNSE|)SSS|WWWSSSSE(SW|NNNE)))$", "23"),
+    example!(2018, 20, 1, "^WSSEESWWWNW(S|NENNEEEENN(ESSSSW(NWSW|SSEN)|WSWWN(E|WWS(E|SS))))$", "31"),
+    example!(2018, 22, 1, "depth: 510\ntarget: 10,10", "114"),
+    example!(2018, 22, 2, "depth: 510\ntarget: 10,10", "45"),
+    example!(2018, 23, 1, "pos=<0,0,0>, r=4\npos=<1,0,0>, r=1\npos=<4,0,0>, r=3\npos=<0,2,0>, r=1\npos=<0,5,0>, r=3\npos=<0,0,3>, r=1\npos=<1,1,1>, r=1\npos=<1,1,2>, r=1\npos=<1,3,1>, r=1", "7"),
+    example!(2018, 24, 1, ARMIES, "5216"),
+    example!(2018, 24, 2, ARMIES, "51"),
+    example!(2018, 23, 2, "pos=<10,12,12>, r=2\npos=<12,14,12>, r=2\npos=<16,12,12>, r=4\npos=<14,14,14>, r=6\npos=<50,50,50>, r=200\npos=<10,10,10>, r=5", "36"),
+    example!(2019, 1, 1, "12", "2"),
+    example!(2019, 1, 1, "14", "2"),
+    example!(2019, 1, 1, "1969", "654"),
+    example!(2019, 1, 1, "100756", "33583"),
+    example!(2019, 1, 2, "14", "2"),
+    example!(2019, 1, 2, "1969", "966"),
+    example!(2019, 1, 2, "100756", "50346"),
+    example!(2019, 3, 1, WIRES[0], "6"),
+    example!(2019, 3, 1, WIRES[1], "159"),
+    example!(2019, 3, 1, WIRES[2], "135"),
+    example!(2019, 3, 2, WIRES[0], "30"),
+    example!(2019, 3, 2, WIRES[1], "610"),
+    example!(2019, 3, 2, WIRES[2], "410"),
+    example!(
+        2019,
+        6,
+        1,
+        "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L",
+        "42"
+    ),
+    example!(
+        2019,
+        6,
+        2,
+        "COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\nK)YOU\nI)SAN",
+        "4"
+    ),
+    example!(2019, 7, 1, AMPLIFIER_CONTROLLERS[0], "43210"),
+    example!(2019, 7, 1, AMPLIFIER_CONTROLLERS[1], "54321"),
+    example!(2019, 7, 1, AMPLIFIER_CONTROLLERS[2], "65210"),
+    example!(2019, 7, 2, AMPLIFIER_CONTROLLERS[3], "139629729"),
+    example!(2019, 7, 2, AMPLIFIER_CONTROLLERS[4], "18216"),
+    example!(
+        2019,
+        8,
+        1,
+        "123456789012",
+        "1",
+        [("width", 3), ("height", 2)]
+    ),
+    example!(
+        2019,
+        8,
+        2,
+        "0222112222120000",
+        " █\n█ ",
+        [("width", 2), ("height", 2)]
+    ),
+    example!(2019, 10, 1, ASTEROID_MAPS[0], "8"),
+    example!(2019, 10, 1, ASTEROID_MAPS[1], "33"),
+    example!(2019, 10, 1, ASTEROID_MAPS[2], "35"),
+    example!(2019, 10, 1, ASTEROID_MAPS[3], "41"),
+    example!(2019, 10, 1, ASTEROID_MAPS[4], "210"),
+    example!(2019, 10, 2, ASTEROID_MAPS[4], "1112", [("vaporized", 1)]),
+    example!(2019, 10, 2, ASTEROID_MAPS[4], "1201", [("vaporized", 2)]),
+    example!(2019, 10, 2, ASTEROID_MAPS[4], "1208", [("vaporized", 10)]),
+    example!(2019, 10, 2, ASTEROID_MAPS[4], "1609", [("vaporized", 50)]),
+    example!(2019, 10, 2, ASTEROID_MAPS[4], "906", [("vaporized", 199)]),
+    example!(2019, 10, 2, ASTEROID_MAPS[4], "802"),
+    example!(2019, 10, 2, ASTEROID_MAPS[4], "1101", [("vaporized", 299)]),
+    example!(2019, 12, 1, MOONS[0], "179", [("steps", 10)]),
+    example!(2019, 12, 1, MOONS[1], "1940", [("steps", 100)]),
+    example!(2019, 12, 2, MOONS[0], "2772"),
+    example!(2019, 12, 2, MOONS[1], "4686774924"),
+    example!(2019, 18, 1, VAULTS[0], "8"),
+    example!(2019, 18, 1, VAULTS[1], "86"),
+    example!(2019, 18, 1, VAULTS[2], "132"),
+    example!(2019, 18, 1, VAULTS[3], "136"),
+    example!(2019, 18, 1, VAULTS[4], "81"),
+    example!(2019, 18, 2, VAULTS[5], "8"),
+    example!(2019, 18, 2, VAULTS[6], "24"),
+    example!(2019, 18, 2, VAULTS[7], "32"),
+    example!(2019, 18, 2, VAULTS[8], "72"),
+];
+
+/// Adds a test to a day's module which solves each of the day's examples with its `Solver`, as
+/// `examples!(2018, 5, Day5)`
+#[cfg(test)]
+#[allow(unused_macros)]
+macro_rules! examples {
+    ($year:literal, $day:literal, $solver:ty) => {
+        #[test]
+        fn test_examples() {
+            use $crate::solutions::Solver;
+
+            for part in 1..=2 {
+                for example in $crate::examples::select($year, $day, part) {
+                    let parsed = <$solver>::parse(example.input).unwrap();
+                    let params = example.params();
+                    let answer = match part {
+                        1 => <$solver>::part1(&parsed, &params),
+                        _ => <$solver>::part2(&parsed, &params),
+                    };
+                    assert_eq!(
+                        answer.unwrap().to_string(),
+                        example.answer,
+                        "part {} example {:?}",
+                        part,
+                        example.input
+                    );
+                }
+            }
+        }
+    };
+}
+
+// unused when the features leave out the years which use it
+#[cfg(test)]
+#[allow(unused_imports)]
+pub(crate) use examples;
+
+/// The examples of a part
+pub fn select(year: u16, day: u8, part: u8) -> Vec<&'static Example> {
+    EXAMPLES
+        .iter()
+        .filter(|example| (example.year, example.day, example.part) == (year, day, part))
+        .collect()
+}
+
+#[cfg(test)]
+#[cfg(all(feature = "year-2018", feature = "year-2019"))]
+mod tests {
+    use super::*;
+    use crate::solutions::{self, SOLUTIONS};
+
+    #[test]
+    fn test_examples() {
+        for example in EXAMPLES {
+            let solution =
+                solutions::select(SOLUTIONS, example.year, example.day, Some(example.part))
+                    .unwrap()[0];
+            assert_eq!(
+                solution
+                    .solve(example.input, &example.params())
+                    .unwrap()
+                    .to_string(),
+                example.answer,
+                "{} example {:?}",
+                solution,
+                example.input
+            );
+        }
+    }
+}
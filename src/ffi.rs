@@ -0,0 +1,319 @@
+//! C bindings for the solutions and the Intcode computer, for harnesses which would otherwise run
+//! `aoc` once for every input. The declarations are in `include/advent_of_code.h`.
+//!
+//! Build with the `ffi` feature as a cdylib (or staticlib), as in the README. Strings handed back
+//! are owned by the caller, who frees them with `aoc_string_free`, and an Intcode computer is
+//! freed with `aoc_intcode_free`. Nothing unwinds into the caller: a solution which panics fails
+//! with an error like any other.
+
+use crate::params::Params;
+use crate::solutions::{self, SOLUTIONS};
+use std::ffi::{c_char, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+
+/// Solves a part for a UTF-8 puzzle input of `input_len` bytes. Returns the answer as a string,
+/// or null with `*error` set to why there is none (when `error` isn't null).
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, and `error` must be null or point to a
+/// writable pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    let input = slice::from_raw_parts(input, input_len);
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| {
+        let input =
+            std::str::from_utf8(input).map_err(|_| "the puzzle input isn't UTF-8".to_string())?;
+        let solution = solutions::select(SOLUTIONS, year, day, Some(part))
+            .map_err(|error| error.to_string())?[0];
+
+        solution
+            .solve(input, &Params::default())
+            .map_err(|error| error.to_string())
+    }))
+    .unwrap_or_else(|_| Err("the solution panicked".to_string()));
+
+    match answer {
+        Ok(answer) => into_c_string(answer.to_string()),
+        Err(reason) => {
+            if !error.is_null() {
+                *error = into_c_string(reason);
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Frees a string returned by these bindings
+///
+/// # Safety
+///
+/// `string` must be null, or a string returned by these bindings which hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+fn into_c_string(string: String) -> *mut c_char {
+    // answers and errors never contain a nul, but one mustn't cut the string short unnoticed
+    CString::new(string.replace('\0', "\u{fffd}"))
+        .expect("the nuls were replaced")
+        .into_raw()
+}
+
+#[cfg(feature = "year-2019")]
+pub use self::intcode::*;
+
+#[cfg(feature = "year-2019")]
+mod intcode {
+    use super::into_c_string;
+    use crate::year_2019::intcode_computer::{IntcodeError, IntcodeState};
+    use crate::year_2019::{IntcodeComputer, IntcodeProgram, IntcodeWord};
+    use std::ffi::c_char;
+    use std::ptr;
+    use std::slice;
+    use std::sync::mpsc::Receiver;
+
+    /// What an Intcode computer can do next
+    #[repr(C)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum AocIntcodeStatus {
+        /// The computer can execute its next instruction
+        Running = 0,
+
+        /// The next instruction is an input instruction, but no input has been pushed
+        AwaitingInput = 1,
+
+        Halted = 2,
+
+        /// An instruction couldn't be executed, which `aoc_intcode_error` describes
+        Failed = 3,
+    }
+
+    /// An Intcode computer, which is stepped by the caller and never blocks
+    pub struct AocIntcode {
+        computer: IntcodeComputer,
+        output: Receiver<IntcodeWord>,
+        halted: bool,
+        error: Option<IntcodeError>,
+    }
+
+    impl AocIntcode {
+        fn status(&self) -> AocIntcodeStatus {
+            if self.error.is_some() {
+                AocIntcodeStatus::Failed
+            } else if self.halted {
+                AocIntcodeStatus::Halted
+            } else if self.computer.needs_input() {
+                AocIntcodeStatus::AwaitingInput
+            } else {
+                AocIntcodeStatus::Running
+            }
+        }
+
+        fn step(&mut self) -> AocIntcodeStatus {
+            if self.status() == AocIntcodeStatus::Running {
+                match self.computer.step() {
+                    Ok(IntcodeState::Running) => {}
+                    Ok(IntcodeState::Halted) => self.halted = true,
+                    Err(error) => self.error = Some(error),
+                }
+            }
+
+            self.status()
+        }
+    }
+
+    /// Loads a program from its comma separated text of `program_len` bytes, returning null when
+    /// it isn't a program
+    ///
+    /// # Safety
+    ///
+    /// `program` must point to `program_len` readable bytes.
+    #[no_mangle]
+    pub unsafe extern "C" fn aoc_intcode_new(
+        program: *const u8,
+        program_len: usize,
+    ) -> *mut AocIntcode {
+        let program = slice::from_raw_parts(program, program_len);
+        let program = match std::str::from_utf8(program).map(IntcodeProgram::parse) {
+            Ok(Ok(program)) => program,
+            _ => return ptr::null_mut(),
+        };
+
+        let mut computer = IntcodeComputer::from(&program);
+        let output = computer.create_output();
+
+        Box::into_raw(Box::new(AocIntcode {
+            computer,
+            output,
+            halted: false,
+            error: None,
+        }))
+    }
+
+    /// # Safety
+    ///
+    /// `intcode` must be null, or a computer from `aoc_intcode_new` which hasn't been freed yet.
+    #[no_mangle]
+    pub unsafe extern "C" fn aoc_intcode_free(intcode: *mut AocIntcode) {
+        if !intcode.is_null() {
+            drop(Box::from_raw(intcode));
+        }
+    }
+
+    /// Queues an input for the program to read
+    ///
+    /// # Safety
+    ///
+    /// `intcode` must be a computer from `aoc_intcode_new` which hasn't been freed yet.
+    #[no_mangle]
+    pub unsafe extern "C" fn aoc_intcode_push_input(intcode: *mut AocIntcode, input: IntcodeWord) {
+        (*intcode).computer.push_input(input);
+    }
+
+    /// Executes instructions until the computer halts, fails or awaits input, or `max_steps` have
+    /// been executed, returning what it can do next
+    ///
+    /// # Safety
+    ///
+    /// `intcode` must be a computer from `aoc_intcode_new` which hasn't been freed yet.
+    #[no_mangle]
+    pub unsafe extern "C" fn aoc_intcode_run(
+        intcode: *mut AocIntcode,
+        max_steps: u64,
+    ) -> AocIntcodeStatus {
+        let intcode = &mut *intcode;
+        for _ in 0..max_steps {
+            if intcode.step() != AocIntcodeStatus::Running {
+                break;
+            }
+        }
+
+        intcode.status()
+    }
+
+    /// Takes the oldest output which hasn't been taken yet into `*output`, returning whether
+    /// there was one
+    ///
+    /// # Safety
+    ///
+    /// `intcode` must be a computer from `aoc_intcode_new` which hasn't been freed yet, and
+    /// `output` must point to a writable word.
+    #[no_mangle]
+    pub unsafe extern "C" fn aoc_intcode_poll_output(
+        intcode: *mut AocIntcode,
+        output: *mut IntcodeWord,
+    ) -> bool {
+        match (*intcode).output.try_recv() {
+            Ok(word) => {
+                *output = word;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+    /// Reads a single address of memory
+    ///
+    /// # Safety
+    ///
+    /// `intcode` must be a computer from `aoc_intcode_new` which hasn't been freed yet.
+    #[no_mangle]
+    pub unsafe extern "C" fn aoc_intcode_peek(
+        intcode: *const AocIntcode,
+        address: usize,
+    ) -> IntcodeWord {
+        (*intcode).computer.memory.get(address)
+    }
+
+    /// Why the computer failed, to be freed with `aoc_string_free`, or null when it hasn't
+    ///
+    /// # Safety
+    ///
+    /// `intcode` must be a computer from `aoc_intcode_new` which hasn't been freed yet.
+    #[no_mangle]
+    pub unsafe extern "C" fn aoc_intcode_error(intcode: *const AocIntcode) -> *mut c_char {
+        match &(*intcode).error {
+            Some(error) => into_c_string(error.to_string()),
+            None => ptr::null_mut(),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::ffi::aoc_string_free;
+        use std::ffi::CStr;
+
+        #[test]
+        fn test_intcode() {
+            unsafe {
+                let program = "3,0,4,0,99";
+                let intcode = aoc_intcode_new(program.as_ptr(), program.len());
+                assert_eq!(
+                    aoc_intcode_run(intcode, 100),
+                    AocIntcodeStatus::AwaitingInput
+                );
+                aoc_intcode_push_input(intcode, 42);
+                assert_eq!(aoc_intcode_run(intcode, 100), AocIntcodeStatus::Halted);
+                let mut output = 0;
+                assert!(aoc_intcode_poll_output(intcode, &mut output));
+                assert_eq!(output, 42);
+                assert!(!aoc_intcode_poll_output(intcode, &mut output));
+                assert_eq!(aoc_intcode_peek(intcode, 0), 42);
+                assert!(aoc_intcode_error(intcode).is_null());
+                aoc_intcode_free(intcode);
+
+                let program = "104,1,0";
+                let intcode = aoc_intcode_new(program.as_ptr(), program.len());
+                assert_eq!(aoc_intcode_run(intcode, 100), AocIntcodeStatus::Failed);
+                assert_eq!(aoc_intcode_run(intcode, 100), AocIntcodeStatus::Failed);
+                let error = aoc_intcode_error(intcode);
+                assert_eq!(
+                    CStr::from_ptr(error).to_str(),
+                    Ok("unknown opcode 0 at address 2")
+                );
+                aoc_string_free(error);
+                aoc_intcode_free(intcode);
+
+                assert!(aoc_intcode_new("1,x".as_ptr(), 3).is_null());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    #[test]
+    fn test_solve() {
+        unsafe {
+            let input = "dabAcCaCBAcCcaDA";
+            let answer = aoc_solve(2018, 5, 2, input.as_ptr(), input.len(), ptr::null_mut());
+            assert_eq!(CStr::from_ptr(answer).to_str(), Ok("4"));
+            aoc_string_free(answer);
+
+            let mut error = ptr::null_mut();
+            let input = "+1\nnot a change";
+            assert!(aoc_solve(2018, 1, 1, input.as_ptr(), input.len(), &mut error).is_null());
+            assert!(CStr::from_ptr(error)
+                .to_str()
+                .unwrap()
+                .starts_with("line 2 of the input"));
+            aoc_string_free(error);
+        }
+    }
+}
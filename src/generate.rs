@@ -0,0 +1,198 @@
+//! Made-up puzzle inputs of any size, for seeing how a solution copes with inputs bigger (or
+//! smaller) than the real one, and for benchmarking it on them.
+//!
+//! A day which can make up inputs registers a `Generator` in the `GENERATORS` of its year. Its
+//! inputs have the structure the puzzle promises (such as there being exactly one pair of box IDs
+//! which differ by a single letter), so that both parts still have an answer (at least at the
+//! size of a real input), though not one anyone has checked.
+
+use crate::common::rng::Rng;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Makes up puzzle inputs for a day
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    pub year: u16,
+    pub day: u8,
+
+    /// Makes up an input of the given size, with every random choice taken from the `Rng`
+    pub generate: fn(&mut Rng, Scale) -> String,
+}
+
+impl Generator {
+    /// An input of `scale` times the size of a real one. The same seed always makes the same
+    /// input.
+    pub fn generate(&self, scale: Scale, seed: u64) -> String {
+        (self.generate)(&mut Rng::new(seed), scale)
+    }
+}
+
+impl fmt::Display for Generator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// Registers the function which makes up the inputs of a day
+#[allow(unused_macros)]
+macro_rules! generator {
+    ($year:literal, $day:literal, $function:path) => {
+        $crate::generate::Generator {
+            year: $year,
+            day: $day,
+            generate: $function,
+        }
+    };
+}
+
+// unused when the features leave out the years which use it
+#[allow(unused_imports)]
+pub(crate) use generator;
+
+/// The generators of each year, from the `GENERATORS` of its module
+const YEARS: &[&[Generator]] = &[
+    #[cfg(feature = "year-2018")]
+    crate::year_2018::GENERATORS,
+    #[cfg(feature = "year-2019")]
+    crate::year_2019::GENERATORS,
+];
+
+/// Every generator, in order of year and day
+pub fn generators() -> impl Iterator<Item = &'static Generator> {
+    YEARS.iter().flat_map(|generators| generators.iter())
+}
+
+/// The generator of a day, explaining which days have one when it has none
+pub fn select(year: u16, day: u8) -> Result<&'static Generator, GenerateError> {
+    generators()
+        .find(|generator| (generator.year, generator.day) == (year, day))
+        .ok_or_else(|| GenerateError {
+            year,
+            day,
+            available: generators()
+                .filter(|generator| generator.year == year)
+                .map(|generator| generator.day)
+                .collect(),
+        })
+}
+
+/// How big an input to make up, as a multiple of the size of a real puzzle input, written like
+/// `10x` (or `10`, or `0.5x`)
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Scale(f64);
+
+impl Scale {
+    /// The size of a real puzzle input
+    pub const REAL: Scale = Scale(1.0);
+
+    /// `factor` times the size of a real puzzle input, when that's a size at all
+    pub fn new(factor: f64) -> Option<Self> {
+        if factor.is_finite() && factor > 0.0 {
+            Some(Self(factor))
+        } else {
+            None
+        }
+    }
+
+    pub fn factor(self) -> f64 {
+        self.0
+    }
+
+    /// How many of something there are at this scale, when a real input has `n` of them (but
+    /// never none)
+    pub fn of(self, n: usize) -> usize {
+        ((n as f64 * self.0).round() as usize).max(1)
+    }
+
+    /// How long the sides of a square are at this scale, when a real input's are `n` long, so
+    /// that its area grows with the scale
+    pub fn side(self, n: usize) -> usize {
+        ((n as f64 * self.0.sqrt()).round() as usize).max(1)
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self::REAL
+    }
+}
+
+impl FromStr for Scale {
+    type Err = String;
+
+    fn from_str(scale: &str) -> Result<Self, String> {
+        scale
+            .strip_suffix('x')
+            .unwrap_or(scale)
+            .parse()
+            .ok()
+            .and_then(Scale::new)
+            .ok_or_else(|| format!("{:?} isn't a scale like 10x or 0.5x", scale))
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x", self.0)
+    }
+}
+
+/// There's no generator for a day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GenerateError {
+    pub year: u16,
+    pub day: u8,
+    /// The days of the same year which have one
+    pub available: BTreeSet<u8>,
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No generator for {} day {}", self.year, self.day)?;
+        if self.available.is_empty() {
+            write!(f, ", or for any day of {}", self.year)
+        } else {
+            let days: Vec<String> = self.available.iter().map(u8::to_string).collect();
+            write!(f, ". Days with one in {}: {}", self.year, days.join(", "))
+        }
+    }
+}
+
+impl Error for GenerateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Params;
+    use crate::solutions::{self, SOLUTIONS};
+
+    #[test]
+    fn test_generated_inputs_are_solved() {
+        for generator in generators() {
+            let input = generator.generate(Scale::REAL, 7);
+            assert_eq!(input, generator.generate(Scale::REAL, 7));
+
+            for solution in
+                solutions::select(SOLUTIONS, generator.year, generator.day, None).unwrap()
+            {
+                if let Err(error) = solution.solve(&input, &Params::default()) {
+                    panic!("{} fails on a generated input: {}", solution, error);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale() {
+        assert_eq!("10x".parse(), Ok(Scale(10.0)));
+        assert_eq!("0.5".parse(), Ok(Scale(0.5)));
+        assert!("0x".parse::<Scale>().is_err());
+        assert!("tenx".parse::<Scale>().is_err());
+        assert_eq!(Scale(4.0).of(50), 200);
+        assert_eq!(Scale(4.0).side(50), 100);
+        assert_eq!(Scale(0.001).of(50), 1);
+    }
+}
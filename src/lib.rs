@@ -3,41 +3,48 @@
 //! You don't need a computer science background to participate - just a little programming knowledge and some problem solving skills will get you pretty far. Nor do you need a fancy computer; every problem has a solution that completes in at most 15 seconds on ten-year-old hardware.
 
 /// Common code used between the various puzzles
-mod common;
-
-/// "We've detected some temporal anomalies," one of Santa's Elves at the Temporal Anomaly Research and Detection Instrument Station tells you. She sounded pretty worried when she called you down here. "At 500-year intervals into the past, someone has been changing Santa's history!"
-///
-/// "The good news is that the changes won't propagate to our time stream for another 25 days, and we have a device" - she attaches something to your wrist - "that will let you fix the changes with no such propagation delay. It's configured to send you 500 years further into the past every few days; that was the best we could do on such short notice."
-///
-/// "The bad news is that we are detecting roughly fifty anomalies throughout time; the device will indicate fixed anomalies with stars. The other bad news is that we only have one device and you're the best person for the job! Good lu--" She taps a button on the device and you suddenly feel like you're falling. To save Christmas, you need to get all fifty stars by December 25th.
-///
-/// Collect stars by solving puzzles. Two puzzles will be made available on each day in the Advent calendar; the second puzzle is unlocked when you complete the first. Each puzzle grants one star. Good luck!
-pub mod year_2018 {
-    pub mod day1;
-    pub mod day2;
-    pub mod day3;
-    pub mod day4;
-    pub mod day5;
-    pub mod day6;
-    pub mod day7;
-}
-
-/// Santa has become stranded at the edge of the Solar System while delivering presents to other planets! To accurately calculate his position in space, safely align his warp drive, and return to Earth in time to save Christmas, he needs you to bring him measurements from fifty stars.
-///
-/// Collect stars by solving puzzles. Two puzzles will be made available on each day in the Advent calendar; the second puzzle is unlocked when you complete the first. Each puzzle grants one star. Good luck!
-pub mod year_2019 {
-    pub mod intcode_computer;
-    pub use intcode_computer::{IntcodeComputer, IntcodeProgram};
-
-    pub mod day1;
-    pub mod day2;
-    pub mod day3;
-    pub mod day4;
-    pub mod day5;
-    pub mod day6;
-    pub mod day7;
-    pub mod day8;
-    pub mod day9;
-
-    pub mod day10;
-}
+pub mod common;
+
+pub mod solutions;
+pub use solutions::solve;
+
+pub mod answer;
+pub use answer::Answer;
+
+pub mod error;
+pub use error::Error;
+
+pub mod examples;
+
+pub mod generate;
+
+pub mod params;
+
+pub mod answers;
+
+pub mod progress;
+
+pub mod memory;
+
+pub mod calendar;
+
+#[cfg(feature = "download")]
+pub mod download;
+
+#[cfg(feature = "download")]
+pub mod description;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+// the solutions of each year, which `aoc new` adds new years to
+#[cfg(feature = "year-2018")]
+pub mod year_2018;
+#[cfg(feature = "year-2019")]
+pub mod year_2019;
@@ -0,0 +1,86 @@
+//! Measures how much memory solutions use, with an allocator which keeps count of the bytes
+//! allocated through it.
+//!
+//! Only a binary can choose the global allocator, so `aoc` installs `TrackingAllocator` itself
+//! when built with the `memory` feature. The counts are shared by every thread, so a measurement
+//! only belongs to one solution when nothing else runs at the same time.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting the bytes currently allocated and the most there have been
+pub struct TrackingAllocator;
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc_zeroed(layout);
+        if !pointer.is_null() {
+            allocated(layout.size());
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_pointer = System.realloc(pointer, layout, new_size);
+        if !new_pointer.is_null() {
+            if new_size > layout.size() {
+                allocated(new_size - layout.size());
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_pointer
+    }
+}
+
+fn allocated(size: usize) {
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+/// Runs `f`, returning the most memory it had allocated at once (on top of what was already
+/// allocated beforehand). This is always 0 unless `TrackingAllocator` is the global allocator.
+pub fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+
+    let result = f();
+
+    let peak = PEAK.load(Ordering::Relaxed);
+    (result, peak.saturating_sub(baseline))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_allocated() {
+        // the tests don't use the tracking allocator, so this counts the calls made here alone
+        let ((), peak) = measure_peak(|| {
+            allocated(1024);
+            allocated(512);
+            CURRENT.fetch_sub(1536, Ordering::Relaxed);
+            allocated(256);
+            CURRENT.fetch_sub(256, Ordering::Relaxed);
+        });
+
+        assert_eq!(peak, 1536);
+    }
+}
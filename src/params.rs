@@ -0,0 +1,99 @@
+//! Settings which puzzles give in their descriptions, such as the number of workers in 2018 day 7,
+//! and which their examples often change. Solutions declare them as `Param`s and read them from
+//! the `Params` they're solved with, so that an example can be solved with its own settings, and
+//! so that `aoc run --param workers=2` can change them.
+
+use std::convert::TryFrom;
+use std::fmt;
+
+/// A setting of a puzzle, and its value in the puzzle itself. Every setting so far is a number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub default: i64,
+}
+
+/// The settings a part is solved with, which are the defaults unless they've been set
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params {
+    values: Vec<(String, i64)>,
+}
+
+impl Params {
+    /// Sets a setting by its name, replacing it if it was already set
+    pub fn set(&mut self, name: &str, value: i64) {
+        self.values.retain(|(set, _)| set != name);
+        self.values.push((name.to_string(), value));
+    }
+
+    /// The value of a setting, as the type the solution needs it as
+    ///
+    /// Panics when the value doesn't fit in that type, which is how solutions fail on an input
+    /// they can't solve.
+    pub fn get<T: TryFrom<i64>>(&self, param: &Param) -> T {
+        let value = self
+            .values
+            .iter()
+            .find(|(name, _)| name == param.name)
+            .map_or(param.default, |(_, value)| *value);
+
+        T::try_from(value).unwrap_or_else(|_| panic!("{} can't be {}", param.name, value))
+    }
+
+    /// The settings which have been set, by name
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i64)> {
+        self.values
+            .iter()
+            .map(|(name, value)| (name.as_str(), *value))
+    }
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} ({}, {} by default)",
+            self.name, self.description, self.default
+        )
+    }
+}
+
+/// Parses a setting given as `name=value`
+pub fn parse_assignment(assignment: &str) -> Result<(String, i64), String> {
+    let (name, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("{} isn't of the form name=value", assignment))?;
+    let value = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} isn't a number", value))?;
+
+    Ok((name.trim().to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORKERS: Param = Param {
+        name: "workers",
+        description: "how many workers there are",
+        default: 5,
+    };
+
+    #[test]
+    fn test_params() {
+        let mut params = Params::default();
+        assert_eq!(params.get::<usize>(&WORKERS), 5);
+
+        let (name, value) = parse_assignment("workers=2").unwrap();
+        params.set(&name, value);
+        params.set("workers", 3);
+        assert_eq!(params.get::<usize>(&WORKERS), 3);
+        assert_eq!(params.iter().collect::<Vec<_>>(), [("workers", 3)]);
+
+        assert!(parse_assignment("workers").is_err());
+        assert!(parse_assignment("workers=two").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+//! Progress bars for solutions which take a while, so that they don't appear to have hung.
+//!
+//! Solutions create a `Progress` and tick it as they go. Nothing is drawn unless the runner has
+//! called `enable` (the `aoc` binary does when stderr is a terminal), so solutions can tick
+//! freely when called from tests, benchmarks or other code. The bars are drawn to stderr and
+//! cleared once finished, which keeps stdout clean.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Draws the progress of solutions from now on
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    cfg!(feature = "progress") && ENABLED.load(Ordering::Relaxed)
+}
+
+/// Every bar is part of one group, so that solutions running in parallel each get a line
+#[cfg(feature = "progress")]
+fn bars() -> &'static indicatif::MultiProgress {
+    static BARS: std::sync::OnceLock<indicatif::MultiProgress> = std::sync::OnceLock::new();
+    BARS.get_or_init(indicatif::MultiProgress::new)
+}
+
+/// The progress of a solution through `len` steps, which is cleared when dropped
+#[derive(Debug)]
+pub struct Progress {
+    #[cfg(feature = "progress")]
+    bar: Option<indicatif::ProgressBar>,
+}
+
+impl Progress {
+    /// Starts a progress bar of `len` steps, each of which is one of `unit` (such as "passwords")
+    #[cfg_attr(not(feature = "progress"), allow(unused_variables))]
+    pub fn new(len: u64, unit: &str) -> Self {
+        #[cfg(feature = "progress")]
+        {
+            let bar = if is_enabled() {
+                let style = indicatif::ProgressStyle::with_template(
+                    "{bar:40} {human_pos}/{human_len} {msg} ({eta} left)",
+                )
+                .expect("valid progress template");
+                let bar = indicatif::ProgressBar::new(len)
+                    .with_style(style)
+                    .with_message(unit.to_string());
+                Some(bars().add(bar))
+            } else {
+                None
+            };
+
+            Self { bar }
+        }
+
+        #[cfg(not(feature = "progress"))]
+        Self {}
+    }
+
+    /// Counts one more step as done
+    pub fn tick(&self) {
+        self.inc(1);
+    }
+
+    /// Counts `steps` more steps as done
+    #[cfg_attr(not(feature = "progress"), allow(unused_variables))]
+    pub fn inc(&self, steps: u64) {
+        #[cfg(feature = "progress")]
+        {
+            if let Some(bar) = &self.bar {
+                bar.inc(steps);
+            }
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        #[cfg(feature = "progress")]
+        {
+            if let Some(bar) = self.bar.take() {
+                bar.finish_and_clear();
+                bars().remove(&bar);
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Python bindings for the solutions and the Intcode computer, for exploring puzzles from a
+//! notebook.
+//!
+//! Build with the `python` feature as a cdylib named for the module, as in the README:
+//!
+//! ```python
+//! import advent_of_code
+//!
+//! part1, part2 = advent_of_code.solve(2019, 9, open("inputs/2019/day9.txt").read())
+//! ```
+//!
+//! An input without an answer raises ValueError with the reason, as does an Intcode program
+//! which doesn't parse.
+
+use crate::solutions::SOLUTIONS;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Solves both parts of a day, where part 2 is None for days without a solution to it
+#[pyfunction]
+fn solve(year: u16, day: u8, input: &str) -> PyResult<(String, Option<String>)> {
+    let (part1, part2) =
+        crate::solve(year, day, input).map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+    Ok((part1.to_string(), part2.map(|answer| answer.to_string())))
+}
+
+/// Every part with a solution, as (year, day, part)
+#[pyfunction]
+fn solutions() -> Vec<(u16, u8, u8)> {
+    SOLUTIONS
+        .iter()
+        .map(|solution| (solution.year, solution.day, solution.part))
+        .collect()
+}
+
+#[pymodule]
+fn advent_of_code(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(solve, module)?)?;
+    module.add_function(wrap_pyfunction!(solutions, module)?)?;
+
+    #[cfg(feature = "year-2019")]
+    {
+        use crate::year_2019::intcode_python::{IntcodeStatus, PyIntcodeComputer};
+
+        module.add_class::<PyIntcodeComputer>()?;
+        module.add_class::<IntcodeStatus>()?;
+    }
+
+    Ok(())
+}
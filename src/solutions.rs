@@ -0,0 +1,492 @@
+//! The registry of every puzzle solution, which the `aoc` binary selects from
+
+use crate::answer::Answer;
+use crate::common::InputSource;
+#[cfg(feature = "download")]
+use crate::download;
+use crate::error::Error;
+use crate::params::{Param, Params};
+use std::any::Any;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A day's puzzle, whose input is parsed into what both of its parts work from
+pub trait Solver {
+    /// The puzzle input, parsed
+    type Parsed: Send + Sync + 'static;
+
+    /// The settings of the puzzle which the parts read from the `Params` they're solved with
+    const PARAMS: &'static [Param] = &[];
+
+    fn parse(input: &str) -> Result<Self::Parsed, Error>;
+
+    /// Parses the puzzle input as it's read, which days whose inputs may be huge do a line at a
+    /// time (with `common::parse_lines`) rather than holding the whole input at once. Others
+    /// read it all and `parse` that.
+    fn parse_reader(input: &mut dyn BufRead) -> Result<Self::Parsed, Error> {
+        let mut string = String::new();
+        input.read_to_string(&mut string).map_err(Error::read)?;
+        Self::parse(&string)
+    }
+
+    fn part1(parsed: &Self::Parsed, params: &Params) -> Result<Answer, Error>;
+
+    fn part2(parsed: &Self::Parsed, params: &Params) -> Result<Answer, Error>;
+}
+
+/// A day's puzzle input parsed by one of its parts, which the other parts of the same day can be
+/// solved from too
+pub type Parsed = Arc<dyn Any + Send + Sync>;
+
+/// A single part of a single day's puzzle
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+
+    /// Parses a puzzle input into what the parts of the day are solved from
+    pub parse: fn(&str) -> Result<Parsed, Error>,
+
+    /// Parses a puzzle input as it's read, like `parse`
+    pub parse_reader: fn(&mut dyn BufRead) -> Result<Parsed, Error>,
+
+    /// Solves the part from its day's parsed input with the given settings, returning the answer,
+    /// or why the input has none
+    pub solve_parsed: fn(&Parsed, &Params) -> Result<Answer, Error>,
+
+    /// The settings of the puzzle which the part reads from the `Params` it's solved with
+    pub params: &'static [Param],
+}
+
+impl Solution {
+    /// Where the puzzle input for this solution's day is stored, in the inputs directory `inputs`
+    pub fn input_path(&self, inputs: &Path) -> PathBuf {
+        input_path(inputs, self.year, self.day)
+    }
+
+    /// Reads the stored puzzle input for this solution's day, if there is one
+    pub fn stored_input(&self, inputs: &Path) -> Option<String> {
+        fs::read_to_string(self.input_path(inputs)).ok()
+    }
+
+    /// The stored puzzle input for this solution's day, or stdin when there isn't one
+    pub fn default_input_source(&self, inputs: &Path) -> InputSource {
+        let path = self.input_path(inputs);
+        if path.is_file() {
+            InputSource::File(path)
+        } else {
+            InputSource::Stdin
+        }
+    }
+
+    /// Downloads the puzzle input for this solution's day into the inputs directory, unless it is
+    /// already stored there
+    #[cfg(feature = "download")]
+    pub fn download_input(
+        &self,
+        session: &str,
+        inputs: &Path,
+    ) -> Result<String, download::DownloadError> {
+        download::fetch_input(
+            self.year,
+            self.day,
+            session,
+            &self.input_path(inputs),
+            inputs,
+        )
+    }
+
+    /// Solves the part for a puzzle input with the given settings, returning the answer, or why
+    /// the input has none
+    pub fn solve(&self, input: &str, params: &Params) -> Result<Answer, Error> {
+        (self.solve_parsed)(&(self.parse)(input)?, params)
+    }
+
+    /// Solves the part, timing how long it takes along with parsing the input
+    pub fn solve_timed(&self, input: &str, params: &Params) -> (Result<Answer, Error>, Duration) {
+        let start = Instant::now();
+        let answer = self.solve(input, params);
+
+        (answer, start.elapsed())
+    }
+
+    /// Solves the part from its day's parsed input, timing how long it takes
+    pub fn solve_parsed_timed(
+        &self,
+        parsed: &Parsed,
+        params: &Params,
+    ) -> (Result<Answer, Error>, Duration) {
+        let start = Instant::now();
+        let answer = (self.solve_parsed)(parsed, params);
+
+        (answer, start.elapsed())
+    }
+}
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} day {} part {}", self.year, self.day, self.part)
+    }
+}
+
+/// Puzzle inputs are stored in this directory (relative to the working directory) unless another
+/// is configured, as `{year}/day{day}.txt`. Inputs are personal to each Advent of Code account,
+/// so they aren't checked in.
+pub const INPUTS_DIRECTORY: &str = "inputs";
+
+/// Where the puzzle input for a day is stored, in the inputs directory `inputs`
+pub fn input_path(inputs: &Path, year: u16, day: u8) -> PathBuf {
+    inputs
+        .join(year.to_string())
+        .join(format!("day{}.txt", day))
+}
+
+/// The title of each day's puzzle by year and day, collected from the first line of its source
+/// file by the build script
+const TITLES: &[(u16, u8, &str)] = include!(concat!(env!("OUT_DIR"), "/titles.rs"));
+
+/// The title of a day's puzzle, such as "Alchemical Reduction"
+pub fn title(year: u16, day: u8) -> Option<&'static str> {
+    TITLES
+        .iter()
+        .find(|(title_year, title_day, _)| (*title_year, *title_day) == (year, day))
+        .map(|(_, _, title)| *title)
+}
+
+/// The parsed input of a day, as the type its parts were parsed into
+///
+/// Panics when it was parsed by another day, which the parts of a day never are.
+// unused when the features leave out the years which use it
+#[cfg(any(feature = "year-2018", feature = "year-2019"))]
+pub(crate) fn downcast<T: Any>(parsed: &Parsed) -> &T {
+    parsed
+        .downcast_ref()
+        .expect("the input was parsed by a part of the same day")
+}
+
+/// Registers a part whose function takes the puzzle input as it is and returns something which
+/// converts into an `Answer`, for parts which have no other part of their day to share a parsed
+/// input with
+#[allow(unused_macros)]
+macro_rules! solution {
+    ($year:literal, $day:literal, $part:literal, $function:path) => {{
+        use std::sync::Arc;
+        use $crate::answer::Answer;
+        use $crate::error::Error;
+        use $crate::solutions::{downcast, Solution};
+
+        Solution {
+            year: $year,
+            day: $day,
+            part: $part,
+            parse: |input| Ok(Arc::new(input.to_string())),
+            parse_reader: |input| {
+                let mut string = String::new();
+                input.read_to_string(&mut string).map_err(Error::read)?;
+                Ok(Arc::new(string))
+            },
+            solve_parsed: |parsed, _| Ok(Answer::from($function(downcast::<String>(parsed)))),
+            params: &[],
+        }
+    }};
+}
+
+/// Registers a part of a day which implements `Solver`, parsing the puzzle input before solving it
+#[allow(unused_macros)]
+macro_rules! solver {
+    ($year:literal, $day:literal, 1, $solver:ty) => {
+        solver!(@part $year, $day, 1, $solver, part1)
+    };
+    ($year:literal, $day:literal, 2, $solver:ty) => {
+        solver!(@part $year, $day, 2, $solver, part2)
+    };
+    (@part $year:literal, $day:literal, $part:literal, $solver:ty, $function:ident) => {{
+        use $crate::solutions::{downcast, Solution, Solver};
+        use std::sync::Arc;
+
+        Solution {
+            year: $year,
+            day: $day,
+            part: $part,
+            parse: |input| Ok(Arc::new(<$solver>::parse(input)?)),
+            parse_reader: |input| Ok(Arc::new(<$solver>::parse_reader(input)?)),
+            solve_parsed: |parsed, params| <$solver>::$function(downcast(parsed), params),
+            params: <$solver>::PARAMS,
+        }
+    }};
+}
+
+// unused when the features leave out the years which use them
+#[allow(unused_imports)]
+pub(crate) use {solution, solver};
+
+/// The solutions of each year, from the `SOLUTIONS` of its module. A year is only included when
+/// its feature (such as `year-2018`) is enabled.
+const YEARS: &[&[Solution]] = &[
+    #[cfg(feature = "year-2018")]
+    crate::year_2018::SOLUTIONS,
+    #[cfg(feature = "year-2019")]
+    crate::year_2019::SOLUTIONS,
+];
+
+/// Every solution, in order of year, day and part
+pub const SOLUTIONS: &[Solution] = &flatten::<{ count(YEARS) }>(YEARS);
+
+/// Solutions which draw the puzzle as they solve it, run in place of the plain solution of the
+/// same part with `--visualize`
+#[cfg(feature = "visualize")]
+pub const VISUALIZATIONS: &[Solution] =
+    &flatten::<{ count(YEAR_VISUALIZATIONS) }>(YEAR_VISUALIZATIONS);
+
+#[cfg(feature = "visualize")]
+const YEAR_VISUALIZATIONS: &[&[Solution]] = &[
+    #[cfg(feature = "year-2018")]
+    crate::year_2018::VISUALIZATIONS,
+    #[cfg(feature = "year-2019")]
+    crate::year_2019::VISUALIZATIONS,
+];
+
+/// How many solutions the years have altogether
+const fn count(years: &[&[Solution]]) -> usize {
+    let (mut count, mut year) = (0, 0);
+    while year < years.len() {
+        count += years[year].len();
+        year += 1;
+    }
+
+    count
+}
+
+/// The solutions of every year in a single array, of the `N` which they have altogether
+const fn flatten<const N: usize>(years: &[&[Solution]]) -> [Solution; N] {
+    // every one of these is replaced, as there are `N` solutions
+    let mut solutions = [PLACEHOLDER; N];
+    let (mut i, mut year) = (0, 0);
+    while year < years.len() {
+        let mut j = 0;
+        while j < years[year].len() {
+            solutions[i] = years[year][j];
+            i += 1;
+            j += 1;
+        }
+        year += 1;
+    }
+
+    solutions
+}
+
+const PLACEHOLDER: Solution = Solution {
+    year: 0,
+    day: 0,
+    part: 0,
+    parse: |_| unreachable!(),
+    parse_reader: |_| unreachable!(),
+    solve_parsed: |_, _| unreachable!(),
+    params: &[],
+};
+
+/// Selects the solutions of a day, or of a single part of it, explaining what is available when
+/// nothing matches
+pub fn select(
+    solutions: &'static [Solution],
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+) -> Result<Vec<&'static Solution>, SelectionError> {
+    let in_year: Vec<_> = solutions.iter().filter(|s| s.year == year).collect();
+    if in_year.is_empty() {
+        return Err(SelectionError::Year {
+            year,
+            available: solutions.iter().map(|s| s.year).collect(),
+        });
+    }
+
+    let in_day: Vec<_> = in_year.iter().copied().filter(|s| s.day == day).collect();
+    if in_day.is_empty() {
+        return Err(SelectionError::Day {
+            year,
+            day,
+            available: in_year.iter().map(|s| s.day).collect(),
+        });
+    }
+
+    let selected: Vec<_> = in_day
+        .iter()
+        .copied()
+        .filter(|s| part.is_none_or(|part| s.part == part))
+        .collect();
+    if selected.is_empty() {
+        return Err(SelectionError::Part {
+            year,
+            day,
+            part: part.unwrap_or_default(),
+            available: in_day.iter().map(|s| s.part).collect(),
+        });
+    }
+
+    Ok(selected)
+}
+
+/// Solves both parts of a day's puzzle with the puzzle's own settings, for calling the solutions
+/// from other code. The input is parsed once for both parts. Part 2 is `None` for days whose
+/// second part has no solution yet.
+pub fn solve(year: u16, day: u8, input: &str) -> Result<(Answer, Option<Answer>), Error> {
+    let selected = select(SOLUTIONS, year, day, None)?;
+    let parsed = (selected[0].parse)(input)?;
+
+    let params = Params::default();
+    let mut answers = selected
+        .into_iter()
+        .map(|solution| (solution.solve_parsed)(&parsed, &params));
+
+    let part1 = answers.next().expect("a selected day has a solution")?;
+    Ok((part1, answers.next().transpose()?))
+}
+
+/// A year, day or part which has no solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectionError {
+    Year {
+        year: u16,
+        available: BTreeSet<u16>,
+    },
+    Day {
+        year: u16,
+        day: u8,
+        available: BTreeSet<u8>,
+    },
+    Part {
+        year: u16,
+        day: u8,
+        part: u8,
+        available: BTreeSet<u8>,
+    },
+}
+
+impl fmt::Display for SelectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Year { year, available } => write!(
+                f,
+                "No solutions for {}. Available years: {}",
+                year,
+                join(available)
+            ),
+            Self::Day {
+                year,
+                day,
+                available,
+            } => write!(
+                f,
+                "No solution for {} day {}. Available days in {}: {}",
+                year,
+                day,
+                year,
+                join(available)
+            ),
+            Self::Part {
+                year,
+                day,
+                part,
+                available,
+            } => write!(
+                f,
+                "No solution for {} day {} part {}. Available parts: {}",
+                year,
+                day,
+                part,
+                join(available)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SelectionError {}
+
+fn join<T: ToString>(values: &BTreeSet<T>) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(all(feature = "year-2018", feature = "year-2019"))]
+    fn test_select() {
+        let parts: Vec<_> = select(SOLUTIONS, 2018, 5, None)
+            .unwrap()
+            .iter()
+            .map(|s| s.part)
+            .collect();
+        assert_eq!(parts, vec![1, 2]);
+
+        let parts: Vec<_> = select(SOLUTIONS, 2018, 5, Some(2))
+            .unwrap()
+            .iter()
+            .map(|s| s.part)
+            .collect();
+        assert_eq!(parts, vec![2]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "year-2018", feature = "year-2019"))]
+    fn test_solve() {
+        let part1 = select(SOLUTIONS, 2018, 5, Some(1)).unwrap()[0];
+        let (answer, _) = part1.solve_timed("dabAcCaCBAcCcaDA", &Params::default());
+        assert_eq!(answer.unwrap(), Answer::Int(10));
+
+        assert_eq!(
+            solve(2018, 5, "dabAcCaCBAcCcaDA"),
+            Ok((Answer::Int(10), Some(Answer::Int(4))))
+        );
+        // with only nine asteroids, none is the 200th to be vaporized
+        assert!(solve(2019, 10, ".#..#\n.....\n#####\n....#\n...##").is_err());
+        assert!(solve(2018, 25, "").is_err());
+
+        assert_eq!(
+            part1.input_path(Path::new(INPUTS_DIRECTORY)),
+            Path::new("inputs").join("2018").join("day5.txt")
+        );
+    }
+
+    #[test]
+    fn test_title() {
+        // every day with a solution has a title
+        for solution in SOLUTIONS {
+            assert!(title(solution.year, solution.day).is_some(), "{}", solution);
+        }
+        assert_eq!(title(2018, 5), Some("Alchemical Reduction"));
+        assert_eq!(title(2019, 2), Some("1202 Program Alarm"));
+        assert_eq!(title(2018, 25), None);
+    }
+
+    #[test]
+    #[cfg(all(feature = "year-2018", feature = "year-2019"))]
+    fn test_select_missing() {
+        assert_eq!(
+            select(SOLUTIONS, 2017, 1, None).unwrap_err().to_string(),
+            "No solutions for 2017. Available years: 2018, 2019"
+        );
+        assert_eq!(
+            select(SOLUTIONS, 2018, 25, None).unwrap_err().to_string(),
+            "No solution for 2018 day 25. Available days in 2018: 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 17, 18, 20, 22, 23, 24"
+        );
+        assert_eq!(
+            select(SOLUTIONS, 2019, 10, Some(3))
+                .unwrap_err()
+                .to_string(),
+            "No solution for 2019 day 10 part 3. Available parts: 1, 2"
+        );
+    }
+}
@@ -0,0 +1,28 @@
+//! JavaScript bindings for the solutions, so that a page can solve puzzle inputs in the browser.
+//!
+//! Build with the `wasm` feature (and the features of the years to include) for the
+//! `wasm32-unknown-unknown` target, as in the README. The solutions run on the calling thread,
+//! as there are no threads to spread them across in a browser.
+
+use crate::solutions;
+use wasm_bindgen::prelude::*;
+
+/// The answers to both parts of a day, where part 2 is `undefined` for days without a solution
+/// to it
+#[wasm_bindgen(getter_with_clone)]
+pub struct Solved {
+    pub part1: String,
+    pub part2: Option<String>,
+}
+
+/// Solves both parts of a day, throwing the reason when there is no solution or no answer
+#[wasm_bindgen]
+pub fn solve(year: u16, day: u8, input: &str) -> Result<Solved, JsValue> {
+    let (part1, part2) = solutions::solve(year, day, input)
+        .map_err(|error| JsValue::from_str(&error.to_string()))?;
+
+    Ok(Solved {
+        part1: part1.to_string(),
+        part2: part2.map(|answer| answer.to_string()),
+    })
+}
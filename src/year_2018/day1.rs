@@ -1,113 +1,163 @@
 //! --- Day 1: Chronal Calibration ---
 
-use std::collections::BTreeSet;
+use crate::answer::Answer;
+use crate::common;
+use crate::common::rng::Rng;
+use crate::error::Error;
+use crate::generate::Scale;
+use crate::params::Params;
+use crate::solutions::Solver;
+use std::collections::HashSet;
+use std::io::BufRead;
 
-#[derive(Debug)]
-struct FrequencyChange {
-    operation: FrequencyOperation,
-    magnitude: i64,
-}
+pub struct Day1;
 
-#[derive(Debug)]
-enum FrequencyOperation {
-    Add,
-    Subtract,
-}
+impl Solver for Day1 {
+    type Parsed = Vec<i64>;
 
-/// After feeling like you've been falling for a few minutes, you look at the device's tiny screen. "Error: Device must be calibrated before first use. Frequency drift detected. Cannot maintain destination lock." Below the message, the device shows a sequence of changes in frequency (your puzzle input). A value like +6 means the current frequency increases by 6; a value like -3 means the current frequency decreases by 3.
-///
-/// For example, if the device displays frequency changes of +1, -2, +3, +1, then starting from a frequency of zero, the following changes would occur:
-///
-///     Current frequency  0, change of +1; resulting frequency  1.
-///     Current frequency  1, change of -2; resulting frequency -1.
-///     Current frequency -1, change of +3; resulting frequency  2.
-///     Current frequency  2, change of +1; resulting frequency  3.
-///
-/// In this example, the resulting frequency is 3.
-///
-/// Here are other example situations:
-///
-///     +1, +1, +1 results in  3
-///     +1, +1, -2 results in  0
-///     -1, -2, -3 results in -6
-///
-/// Starting with a frequency of zero, what is the resulting frequency after all of the changes in frequency have been applied?
-pub fn part1() {
-    let input = crate::common::read_stdin_to_string();
-    let changes = build_changes(&input);
-
-    let mut frequency: i64 = 0;
-    for change in changes.iter() {
-        frequency = match change.operation {
-            FrequencyOperation::Add => frequency + change.magnitude,
-            FrequencyOperation::Subtract => frequency - change.magnitude,
-        }
+    fn parse(input: &str) -> Result<Vec<i64>, Error> {
+        Self::parse_reader(&mut input.as_bytes())
     }
 
-    println!("the resulting frequency: {}", frequency);
-}
+    fn parse_reader(input: &mut dyn BufRead) -> Result<Vec<i64>, Error> {
+        common::parse_lines(input, parse_change)
+    }
+
+    /// After feeling like you've been falling for a few minutes, you look at the device's tiny screen. "Error: Device must be calibrated before first use. Frequency drift detected. Cannot maintain destination lock." Below the message, the device shows a sequence of changes in frequency (your puzzle input). A value like +6 means the current frequency increases by 6; a value like -3 means the current frequency decreases by 3.
+    ///
+    /// For example, if the device displays frequency changes of +1, -2, +3, +1, then starting from a frequency of zero, the following changes would occur:
+    ///
+    ///     Current frequency  0, change of +1; resulting frequency  1.
+    ///     Current frequency  1, change of -2; resulting frequency -1.
+    ///     Current frequency -1, change of +3; resulting frequency  2.
+    ///     Current frequency  2, change of +1; resulting frequency  3.
+    ///
+    /// In this example, the resulting frequency is 3.
+    ///
+    /// Here are other example situations:
+    ///
+    ///     +1, +1, +1 results in  3
+    ///     +1, +1, -2 results in  0
+    ///     -1, -2, -3 results in -6
+    ///
+    /// Starting with a frequency of zero, what is the resulting frequency after all of the changes in frequency have been applied?
+    fn part1(changes: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(changes.iter().sum::<i64>()))
+    }
 
-/// You notice that the device repeats the same frequency change list over and over. To calibrate the device, you need to find the first frequency it reaches twice.
-///
-/// For example, using the same list of changes above, the device would loop as follows:
-///
-///     Current frequency  0, change of +1; resulting frequency  1.
-///     Current frequency  1, change of -2; resulting frequency -1.
-///     Current frequency -1, change of +3; resulting frequency  2.
-///     Current frequency  2, change of +1; resulting frequency  3.
-///     (At this point, the device continues from the start of the list.)
-///     Current frequency  3, change of +1; resulting frequency  4.
-///     Current frequency  4, change of -2; resulting frequency  2, which has already been seen.
-///
-/// In this example, the first frequency reached twice is 2. Note that your device might need to repeat its list of frequency changes many times before a duplicate frequency is found, and that duplicates might be found while in the middle of processing the list.
-///
-/// Here are other examples:
-///
-///     +1, -1 first reaches 0 twice.
-///     +3, +3, +4, -2, -4 first reaches 10 twice.
-///     -6, +3, +8, +5, -6 first reaches 5 twice.
-///     +7, +7, -2, -7, -4 first reaches 14 twice.
-///
-/// What is the first frequency your device reaches twice?
-pub fn part2() {
-    let input = crate::common::read_stdin_to_string();
-    let changes = build_changes(&input);
-
-    let mut frequency: i64 = 0;
-    let mut frequency_seen: BTreeSet<i64> = BTreeSet::new();
-    'find_duplicate: loop {
-        for change in changes.iter() {
-            frequency = match change.operation {
-                FrequencyOperation::Add => frequency + change.magnitude,
-                FrequencyOperation::Subtract => frequency - change.magnitude,
-            };
-            if frequency_seen.contains(&frequency) {
-                break 'find_duplicate;
+    /// You notice that the device repeats the same frequency change list over and over. To calibrate the device, you need to find the first frequency it reaches twice.
+    ///
+    /// For example, using the same list of changes above, the device would loop as follows:
+    ///
+    ///     Current frequency  0, change of +1; resulting frequency  1.
+    ///     Current frequency  1, change of -2; resulting frequency -1.
+    ///     Current frequency -1, change of +3; resulting frequency  2.
+    ///     Current frequency  2, change of +1; resulting frequency  3.
+    ///     (At this point, the device continues from the start of the list.)
+    ///     Current frequency  3, change of +1; resulting frequency  4.
+    ///     Current frequency  4, change of -2; resulting frequency  2, which has already been seen.
+    ///
+    /// In this example, the first frequency reached twice is 2. Note that your device might need to repeat its list of frequency changes many times before a duplicate frequency is found, and that duplicates might be found while in the middle of processing the list.
+    ///
+    /// Here are other examples:
+    ///
+    ///     +1, -1 first reaches 0 twice.
+    ///     +3, +3, +4, -2, -4 first reaches 10 twice.
+    ///     -6, +3, +8, +5, -6 first reaches 5 twice.
+    ///     +7, +7, -2, -7, -4 first reaches 14 twice.
+    ///
+    /// What is the first frequency your device reaches twice?
+    fn part2(changes: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        if changes.is_empty() {
+            return Err(Error::NoAnswer("there are no changes".to_string()));
+        }
+
+        // every frequency of the first pass comes round again, the drift higher, on each pass
+        // after it, so one is only ever reached twice if two are a whole number of drifts apart
+        let drift: i64 = changes.iter().sum();
+        if drift != 0 {
+            let mut residues = HashSet::with_capacity(changes.len());
+            let mut frequency: i64 = 0;
+            let repeats = changes.iter().any(|change| {
+                let repeat = !residues.insert(frequency.rem_euclid(drift));
+                frequency += change;
+                repeat
+            });
+            if !repeats {
+                return Err(Error::NoAnswer(
+                    "the frequencies drift away without reaching any twice".to_string(),
+                ));
+            }
+        }
+
+        let mut frequency: i64 = 0;
+        // the starting frequency counts as reached
+        let mut frequency_seen = HashSet::with_capacity(changes.len());
+        frequency_seen.insert(frequency);
+        for change in changes.iter().cycle() {
+            frequency += change;
+            if !frequency_seen.insert(frequency) {
+                break;
             }
-            frequency_seen.insert(frequency);
         }
-    }
 
-    println!("the first duplicate resulting frequency: {}", frequency);
+        Ok(Answer::from(frequency))
+    }
 }
 
-fn build_changes(input: &str) -> Vec<FrequencyChange> {
-    let mut changes = Vec::new();
-
-    for line in input.lines() {
-        changes.push(FrequencyChange {
-            operation: match line.chars().next() {
-                Some('+') => FrequencyOperation::Add,
-                Some('-') => FrequencyOperation::Subtract,
-                Some(not_found) => panic!("Unhandled operation: '{}'", not_found),
-                None => panic!(),
-            },
-            magnitude: line
-                .trim_start_matches(|c| c == '+' || c == '-')
-                .parse()
-                .unwrap_or_else(|_| panic!("parsing frequency change magnitude '{}'", line)),
-        })
+/// A change like `+7` or `-2`, as the number it adds to the frequency
+fn parse_change(i: usize, line: &str) -> Result<i64, Error> {
+    let not_a_change = || Error::line(i, line, "isn't a frequency change like +7 or -2");
+
+    let (sign, digits) = match line.as_bytes() {
+        [b'+', digits @ ..] => (1, digits),
+        [b'-', digits @ ..] => (-1, digits),
+        _ => return Err(not_a_change()),
+    };
+    // any 18 digits fit in an i64
+    if digits.is_empty() || digits.len() > 18 || !digits.iter().all(u8::is_ascii_digit) {
+        return Err(not_a_change());
     }
 
+    let magnitude = digits.iter().fold(0, |magnitude, digit| {
+        magnitude * 10 + i64::from(digit - b'0')
+    });
+    Ok(sign * magnitude)
+}
+
+/// Frequency changes like the thousand or so of a real input, which drift by exactly 1 each time
+/// through them, so that some frequency is always reached twice (by the time the drift has gone
+/// from the lowest frequency of the first pass to the highest, if not before)
+pub fn generate(rng: &mut Rng, scale: Scale) -> String {
+    let mut changes: Vec<i64> = (0..scale.of(1000))
+        .map(|_| rng.between(1, 20) * if rng.chance(0.5) { 1 } else { -1 })
+        .collect();
+    let drift = if rng.chance(0.5) { 1 } else { -1 };
+    let last = changes.len() - 1;
+    changes[last] = drift - changes[..last].iter().sum::<i64>();
+
     changes
+        .iter()
+        .map(|change| format!("{:+}\n", change))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    #[test]
+    fn test_no_repeat() {
+        let params = Params::default();
+        for input in ["+1", "+1\n+1", "+3\n-1\n+3"] {
+            let changes = Day1::parse(input).unwrap();
+            assert!(Day1::part2(&changes, &params).is_err(), "{}", input);
+        }
+
+        let changes = Day1::parse("+3\n-2\n+1").unwrap();
+        assert_eq!(Day1::part2(&changes, &params).unwrap(), Answer::from(3));
+    }
+
+    examples!(2018, 1, Day1);
 }
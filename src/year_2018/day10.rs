@@ -0,0 +1,214 @@
+//! --- Day 10: The Stars Align ---
+//!
+//! It's no use; your navigation system simply isn't capable of providing walking directions in the arctic circle, and certainly not in 1018.
+
+use crate::answer::Answer;
+use crate::common::geometry::Point;
+use crate::common::parse::ints_in;
+use crate::common::search::binary_search_min;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day10;
+
+impl Solver for Day10 {
+    type Parsed = Vec<Star>;
+
+    fn parse(input: &str) -> Result<Vec<Star>, Error> {
+        let stars: Vec<Star> = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| match ints_in(line)[..] {
+                [x, y, dx, dy] => Ok(Star {
+                    position: Point::new(x, y),
+                    velocity: Point::new(dx, dy),
+                }),
+                _ => Err(Error::line(
+                    i,
+                    line,
+                    "isn't a point like position=< 9,  1> velocity=< 0,  2>",
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        if stars.is_empty() {
+            return Err(Error::Input("has no points of light".to_string()));
+        }
+        Ok(stars)
+    }
+
+    /// The Elves suggest an alternative. In times like these, North Pole rescue operations will arrange points of light in the sky to guide missing Elves back to base. Unfortunately, the message is easy to miss: the points move slowly enough that it takes hours to align them, but have so much momentum that they only stay aligned for a second. If you blink at the wrong time, it might be hours before another message appears.
+    ///
+    /// You can see these points of light floating in the distance, and record their position in the sky and their velocity, the relative change in position per second (your puzzle input). The coordinates are all given from your perspective; given enough time, those positions and velocities will move the points into a cohesive message!
+    ///
+    /// Rather than wait, you decide to fast-forward the process and calculate what the points will eventually spell.
+    ///
+    /// Each line represents one point. Positions are given as <X, Y> pairs: X represents how far left (negative) or right (positive) the point appears, while Y represents how far up (negative) or down (positive) the point appears.
+    ///
+    /// At 0 seconds, each point has the position given. Each second, each point has its velocity added to its position. So, a point with velocity <1, -2> is moving to the right, but is moving upward twice as quickly. If this point's initial position were <3, 9>, after 3 seconds, its position would become <6, 3>.
+    ///
+    /// What message will eventually appear in the sky?
+    fn part1(stars: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let time = message_time(stars)?;
+
+        Ok(Answer::from_pixels(message(stars, time)))
+    }
+
+    /// Good thing you didn't have to wait, because that would have taken a long time - much longer than the 3 seconds in the example above.
+    ///
+    /// Impressed by your sub-hour communication capabilities, the Elves are curious: exactly how many seconds would they have needed to wait for that message to appear?
+    fn part2(stars: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(message_time(stars)?))
+    }
+}
+
+/// Draws the points coming together in the terminal, slowing down as they get close, and holds
+/// the message for a couple of seconds once it appears
+#[cfg(feature = "visualize")]
+pub fn part1_visualize(input: &str) -> Answer {
+    use crate::common::vis::{self, Renderer};
+
+    /// How many cells of the terminal the sky is drawn in, across and down
+    const SKY: (usize, usize) = (100, 40);
+
+    let stars = Day10::parse(input).expect("Failed to parse the points of light");
+    let time = message_time(&stars).expect("Failed to find the message");
+
+    {
+        let mut renderer = Renderer::new().expect("Failed to set up the terminal");
+
+        let hold = std::iter::repeat_n(time, vis::frames_per_second() as usize * 2);
+        for second in approach(time).into_iter().chain(hold) {
+            renderer
+                .render(&format!("Second {}", second), sky(&stars, second, SKY))
+                .expect("Failed to render frame");
+        }
+    }
+
+    Answer::from_pixels(message(&stars, time))
+}
+
+/// A point of light, and how far it moves each second
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Star {
+    position: Point,
+    velocity: Point,
+}
+
+impl Star {
+    fn at(self, time: i64) -> Point {
+        self.position + self.velocity * time
+    }
+}
+
+/// The second the points spell out the message, which is when the box around them is smallest
+///
+/// The box shrinks as the points come together and grows once they've passed one another, so the
+/// message is at the first second after which it stops shrinking. By the time the farthest apart
+/// points could have passed one another (even moving as slowly as they can), all of them have.
+fn message_time(stars: &[Star]) -> Result<i64, Error> {
+    let farthest = stars
+        .iter()
+        .map(|star| star.position.x.abs().max(star.position.y.abs()))
+        .max()
+        .unwrap_or(0);
+
+    binary_search_min(0, farthest.saturating_mul(2), |time| {
+        area(stars, time + 1) >= area(stars, time)
+    })
+    .ok_or_else(|| Error::NoAnswer("the points never come together".to_string()))
+}
+
+/// The top left and bottom right corners of the box around the points at `time`
+fn bounds(stars: &[Star], time: i64) -> (Point, Point) {
+    let mut positions = stars.iter().map(|star| star.at(time));
+    let first = positions.next().unwrap_or_default();
+
+    positions.fold((first, first), |(min, max), position| {
+        (
+            Point::new(min.x.min(position.x), min.y.min(position.y)),
+            Point::new(max.x.max(position.x), max.y.max(position.y)),
+        )
+    })
+}
+
+fn area(stars: &[Star], time: i64) -> i128 {
+    let (min, max) = bounds(stars, time);
+    (max.x - min.x + 1) as i128 * (max.y - min.y + 1) as i128
+}
+
+/// The points at `time` drawn within the box around them, a row to a string
+fn message(stars: &[Star], time: i64) -> Vec<String> {
+    sky(stars, time, (usize::MAX, usize::MAX))
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+/// The points at `time` drawn within the box around them, shrunk to fit into `cells` (across and
+/// down) when the box is bigger than that, with a cell drawn wherever there's any point in it
+fn sky(stars: &[Star], time: i64, cells: (usize, usize)) -> String {
+    let (min, max) = bounds(stars, time);
+    let scale = |span: i64, cells: usize| span / (cells.min(i64::MAX as usize) as i64).max(1) + 1;
+    let (scale_x, scale_y) = (scale(max.x - min.x, cells.0), scale(max.y - min.y, cells.1));
+
+    let width = ((max.x - min.x) / scale_x + 1) as usize;
+    let height = ((max.y - min.y) / scale_y + 1) as usize;
+    let mut rows = vec![vec![' '; width]; height];
+    for star in stars {
+        let offset = star.at(time) - min;
+        rows[(offset.y / scale_y) as usize][(offset.x / scale_x) as usize] = '█';
+    }
+
+    rows.iter()
+        .map(|row| row.iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The seconds to draw on the way to the message at `time`, each a tenth closer to it than the
+/// last (or a second, when that's closer), so that the points slow down as they come together
+#[cfg(feature = "visualize")]
+fn approach(time: i64) -> Vec<i64> {
+    let mut seconds = Vec::new();
+    let mut remaining = time;
+    while remaining > 0 {
+        seconds.push(time - remaining);
+        remaining = (remaining * 9 / 10).min(remaining - 1);
+    }
+    seconds.push(time);
+
+    seconds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{examples, select};
+
+    examples!(2018, 10, Day10);
+
+    #[test]
+    fn test_message() {
+        let stars = Day10::parse(select(2018, 10, 1)[0].input).unwrap();
+
+        assert_eq!(message_time(&stars), Ok(3));
+        assert_eq!(area(&stars, 3), 10 * 8);
+        assert!(area(&stars, 2) > area(&stars, 3));
+        assert!(area(&stars, 4) > area(&stars, 3));
+
+        // the points spread over 22 columns at the start, drawn two to a cell in 11 cells
+        assert_eq!(
+            sky(&stars, 0, (11, 20))
+                .lines()
+                .next()
+                .unwrap()
+                .chars()
+                .count(),
+            11
+        );
+        assert_eq!(sky(&stars, 3, (11, 20)), message(&stars, 3).join("\n"));
+    }
+}
@@ -0,0 +1,155 @@
+//! --- Day 11: Chronal Charge ---
+//!
+//! You watch the Elves and their sleigh fade into the distance as they head toward the North Pole.
+//!
+//! Actually, you're the one fading. The falling sensation returns.
+
+use crate::answer::Answer;
+use crate::common::grid::{Grid2D, SummedAreaTable};
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+use std::ops::RangeInclusive;
+
+/// The width and height of the grid of fuel cells
+const GRID_SIZE: usize = 300;
+
+pub struct Day11;
+
+impl Solver for Day11 {
+    type Parsed = SummedAreaTable;
+
+    fn parse(input: &str) -> Result<SummedAreaTable, Error> {
+        let serial_number = input
+            .trim()
+            .parse()
+            .map_err(|_| Error::Input(format!("{:?} isn't a grid serial number", input.trim())))?;
+
+        Ok(SummedAreaTable::new(&power_levels(serial_number)))
+    }
+
+    /// The low fuel warning light is illuminated on your wrist-mounted device. Tapping it once causes it to project a hologram of the situation: a 300x300 grid of fuel cells and their current power levels, some negative. You're not sure what negative power means in the context of time travel, but it can't be good.
+    ///
+    /// Each fuel cell has a coordinate ranging from 1 to 300 in both the X (horizontal) and Y (vertical) direction. In X,Y notation, the top-left cell is 1,1, and the top-right cell is 300,1.
+    ///
+    /// The interface lets you select any 3x3 square of fuel cells. To increase your chances of getting to your destination, you decide to choose the 3x3 square with the largest total power.
+    ///
+    /// The power level in a given fuel cell can be found through the following process:
+    ///
+    /// - Find the fuel cell's rack ID, which is its X coordinate plus 10.
+    /// - Begin with a power level of the rack ID times the Y coordinate.
+    /// - Increase the power level by the value of the grid serial number (your puzzle input).
+    /// - Set the power level to itself multiplied by the rack ID.
+    /// - Keep only the hundreds digit of the power level (so 12345 becomes 3; numbers with no hundreds digit become 0).
+    /// - Subtract 5 from the power level.
+    ///
+    /// Your goal is to find the 3x3 square which has the largest total power. The square must be entirely within the 300x300 grid. Identify this square using the X,Y coordinate of its top-left fuel cell.
+    ///
+    /// What is the X,Y coordinate of the top-left fuel cell of the 3x3 square with the largest total power?
+    fn part1(table: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let square = best_square(table, 3..=3).expect("the grid is larger than 3x3");
+        Ok(Answer::from((square.x as i64, square.y as i64)))
+    }
+
+    /// You discover a dial on the side of the device; it seems to let you select a square of any size, not just 3x3. Sizes from 1x1 to 300x300 are supported.
+    ///
+    /// Realizing this, you now must find the square of any size with the largest total power. Identify this square by including its size as a third parameter after the top-left coordinate: a 9x9 square with a top-left corner of 3,5 is identified as 3,5,9.
+    ///
+    /// What is the X,Y,size identifier of the square with the largest total power?
+    fn part2(table: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let square = best_square(table, 1..=GRID_SIZE).expect("the grid isn't empty");
+        Ok(Answer::from(format!(
+            "{},{},{}",
+            square.x, square.y, square.size
+        )))
+    }
+}
+
+/// A square of fuel cells, by the X,Y coordinate of its top-left fuel cell (counted from 1,1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Square {
+    pub x: usize,
+    pub y: usize,
+    pub size: usize,
+    pub total_power: i64,
+}
+
+/// The power level of the fuel cell at X,Y
+fn power_level(x: usize, y: usize, serial_number: i64) -> i64 {
+    let rack_id = x as i64 + 10;
+    let power = (rack_id * y as i64 + serial_number) * rack_id;
+    (power / 100).rem_euclid(10) - 5
+}
+
+/// The power level of every fuel cell, where the cell at `(0, 0)` is the fuel cell at 1,1
+fn power_levels(serial_number: i64) -> Grid2D<i64> {
+    let mut grid = Grid2D::new(GRID_SIZE, GRID_SIZE, 0);
+    for ((x, y), power) in grid.iter_mut() {
+        *power = power_level(x + 1, y + 1, serial_number);
+    }
+    grid
+}
+
+/// The square with the largest total power of those of the sizes in `sizes`, or `None` when none
+/// of them fit on the grid. When squares tie, the smallest is chosen, and then the first in
+/// reading order.
+pub fn best_square(table: &SummedAreaTable, sizes: RangeInclusive<usize>) -> Option<Square> {
+    let mut best: Option<Square> = None;
+    for size in sizes {
+        for y in 0..(table.height() + 1).saturating_sub(size) {
+            for x in 0..(table.width() + 1).saturating_sub(size) {
+                let total_power = table.sum((x, y), (size, size));
+                if best.is_none_or(|best| total_power > best.total_power) {
+                    best = Some(Square {
+                        x: x + 1,
+                        y: y + 1,
+                        size,
+                        total_power,
+                    });
+                }
+            }
+        }
+    }
+
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    examples!(2018, 11, Day11);
+
+    #[test]
+    fn test_power_level() {
+        assert_eq!(power_level(3, 5, 8), 4);
+        assert_eq!(power_level(122, 79, 57), -5);
+        assert_eq!(power_level(217, 196, 39), 0);
+        assert_eq!(power_level(101, 153, 71), 4);
+    }
+
+    #[test]
+    fn test_best_square() {
+        let table = SummedAreaTable::new(&power_levels(18));
+        assert_eq!(
+            best_square(&table, 3..=3),
+            Some(Square {
+                x: 33,
+                y: 45,
+                size: 3,
+                total_power: 29
+            })
+        );
+        assert_eq!(
+            best_square(&table, 1..=GRID_SIZE),
+            Some(Square {
+                x: 90,
+                y: 269,
+                size: 16,
+                total_power: 113
+            })
+        );
+        assert_eq!(best_square(&table, 301..=400), None);
+    }
+}
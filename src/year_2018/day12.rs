@@ -0,0 +1,201 @@
+//! --- Day 12: Subterranean Sustainability ---
+//!
+//! The year 518 is significantly more underground than your history books implied. Either that, or you've arrived in a vast cavern network under the North Pole.
+
+use crate::answer::Answer;
+use crate::common::automaton::run;
+use crate::common::cycles::{equivalent_step, find_cycle};
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Parsed = Tunnel;
+
+    fn parse(input: &str) -> Result<Tunnel, Error> {
+        let mut lines = input.lines().enumerate();
+        let plants = lines
+            .next()
+            .and_then(|(_, line)| line.strip_prefix("initial state: "))
+            .ok_or_else(|| Error::Input("doesn't start with the initial state".to_string()))?
+            .chars()
+            .map(|c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                _ => Err(Error::Input(format!(
+                    "has {:?} in the initial state, which isn't a pot",
+                    c
+                ))),
+            })
+            .collect::<Result<Vec<bool>, Error>>()?;
+
+        let mut rules = [false; 32];
+        for (i, line) in lines.filter(|(_, line)| !line.trim().is_empty()) {
+            let not_a_rule = || Error::line(i, line, "isn't a rule like ...## => #");
+            let (pattern, plant) = line.split_once(" => ").ok_or_else(not_a_rule)?;
+            if pattern.len() != 5 || !matches!(plant, "#" | ".") {
+                return Err(not_a_rule());
+            }
+
+            let mut index = 0;
+            for c in pattern.chars() {
+                index = index << 1
+                    | match c {
+                        '#' => 1,
+                        '.' => 0,
+                        _ => return Err(not_a_rule()),
+                    };
+            }
+            rules[index] = plant == "#";
+        }
+
+        if rules[0] {
+            return Err(Error::Input(
+                "grows plants in pots with none around them, which would fill endlessly many pots"
+                    .to_string(),
+            ));
+        }
+
+        Ok(Tunnel {
+            initial: Pots::new(0, plants),
+            rules,
+        })
+    }
+
+    /// The pots are numbered, with 0 in front of you. To the left, the pots are numbered -1, -2, -3, and so on; to the right, 1, 2, 3.... Your puzzle input contains a list of pots from 0 to the right and whether they do (#) or do not (.) currently contain a plant, the initial state. (No other pots currently contain plants.) For example, an initial state of #..##.... indicates that pots 0, 3, and 4 currently contain plants.
+    ///
+    /// Your puzzle input also contains some notes you find on a nearby table: someone has been trying to figure out how these plants spread to nearby pots. Based on the notes, for each generation of plants, a given pot has or does not have a plant based on whether that pot (and the two pots on either side of it) had a plant in the last generation. These are written as LLCRR => N, where L are pots to the left, C is the current pot being considered, R are the pots to the right, and N is whether the current pot will have a plant in the next generation.
+    ///
+    /// It will be necessary to use these rules to predict the future state of the plants in the pots. After one generation, only the pots which currently contain a plant and which follow a rule which results in a plant will still contain one. All other pots will be empty.
+    ///
+    /// After 20 generations, what is the sum of the numbers of all pots which contain a plant?
+    fn part1(tunnel: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let pots = run(tunnel.initial.clone(), |pots| pots.step(&tunnel.rules), 20);
+        Ok(Answer::from(pots.sum()))
+    }
+
+    /// You realize that 20 generations aren't enough. After all, these plants will need to last another 1500 years to even reach your timeline, not to mention your future.
+    ///
+    /// After fifty billion (50000000000) generations, what is the sum of the numbers of all pots which contain a plant?
+    fn part2(tunnel: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(
+            after_generations(tunnel, 50_000_000_000).sum(),
+        ))
+    }
+}
+
+/// The pots in their initial state, and the rules of how plants spread
+pub struct Tunnel {
+    initial: Pots,
+    /// Whether the middle pot of each pattern of five has a plant in the next generation, with the
+    /// pattern read as a binary number from left to right (so `##..#` is 25)
+    rules: [bool; 32],
+}
+
+/// The pots from the first with a plant to the last
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pots {
+    /// The number of the first pot with a plant
+    first: i64,
+    plants: Vec<bool>,
+}
+
+impl Pots {
+    /// The pots `plants` starting from the pot numbered `first`, with the empty pots at either end
+    /// left off
+    fn new(first: i64, mut plants: Vec<bool>) -> Self {
+        let end = plants.iter().rposition(|plant| *plant).map_or(0, |i| i + 1);
+        plants.truncate(end);
+        let start = plants.iter().position(|plant| *plant).unwrap_or(0);
+        plants.drain(..start);
+
+        Self {
+            first: first + start as i64,
+            plants,
+        }
+    }
+
+    /// The next generation, which reaches at most two pots further out either way
+    fn step(&self, rules: &[bool; 32]) -> Self {
+        let plant = |i: usize| self.plants.get(i).copied().unwrap_or(false);
+
+        let mut pattern = 0;
+        let plants = (0..self.plants.len() + 4)
+            .map(|i| {
+                // slide the pattern along to the five pots up to `i`, centred on the pot `i - 2`
+                pattern = (pattern << 1 | plant(i) as usize) & 31;
+                rules[pattern]
+            })
+            .collect();
+
+        Self::new(self.first - 2, plants)
+    }
+
+    /// The sum of the numbers of the pots with plants
+    fn sum(&self) -> i64 {
+        (self.first..)
+            .zip(&self.plants)
+            .filter(|(_, plant)| **plant)
+            .map(|(number, _)| number)
+            .sum()
+    }
+}
+
+/// The pots after `generations` generations
+///
+/// Once the same pattern of plants comes round again it repeats, though it may have moved along,
+/// so only as many generations are stepped through as it takes to find that cycle. The pattern at
+/// the same point of the cycle has moved as far again each time round it since.
+fn after_generations(tunnel: &Tunnel, generations: u64) -> Pots {
+    let step = |pots: &Pots| pots.step(&tunnel.rules);
+    let (start, length) = find_cycle(tunnel.initial.clone(), step, |pots| pots.plants.clone());
+
+    let equivalent = equivalent_step((start, length), generations);
+    let mut pots = run(tunnel.initial.clone(), step, equivalent);
+    if equivalent == generations {
+        return pots;
+    }
+
+    let round_again = run(pots.clone(), step, length as u64);
+    let cycles = ((generations - equivalent) / length as u64) as i64;
+    pots.first += (round_again.first - pots.first) * cycles;
+    pots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{examples, select};
+
+    examples!(2018, 12, Day12);
+
+    #[test]
+    fn test_after_generations() {
+        let tunnel = Day12::parse(select(2018, 12, 1)[0].input).unwrap();
+        let step = |pots: &Pots| pots.step(&tunnel.rules);
+
+        let pots = run(tunnel.initial.clone(), step, 20);
+        assert_eq!(pots.first, -2);
+        assert_eq!(
+            pots.plants
+                .iter()
+                .map(|plant| if *plant { '#' } else { '.' })
+                .collect::<String>(),
+            "#....##....#####...#######....#.#..##"
+        );
+
+        // the example's plants settle after 86 generations into a pattern moving right a pot each
+        // generation, which is skipped ahead through the same as if every generation were stepped
+        // through
+        for generations in [0, 20, 85, 86, 87, 300] {
+            assert_eq!(
+                after_generations(&tunnel, generations),
+                run(tunnel.initial.clone(), step, generations),
+                "after {} generations",
+                generations
+            );
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! --- Day 13: Mine Cart Madness ---
+//!
+//! A crop of this size requires significant logistics to transport produce, soil, fertilizer, and so on. The Elves are very busy pushing things around in carts on some kind of rudimentary system of tracks they've come up with.
+
+use crate::answer::Answer;
+use crate::common::geometry::{Direction, Point};
+use crate::common::grid::Grid2D;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Parsed = Mine;
+
+    fn parse(input: &str) -> Result<Mine, Error> {
+        let width = input.lines().map(|line| line.chars().count()).max();
+        let width = width
+            .filter(|width| *width > 0)
+            .ok_or_else(|| Error::Input("has no tracks".to_string()))?;
+
+        let mut carts = Vec::new();
+        let mut cells = Vec::with_capacity(width * input.lines().count());
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if let Some(direction) = Direction::from_char(c) {
+                    carts.push(Cart {
+                        position: (x, y),
+                        direction,
+                        intersections: 0,
+                    });
+                }
+
+                cells.push(match c {
+                    ' ' => Track::Empty,
+                    '|' | '^' | 'v' => Track::Vertical,
+                    '-' | '<' | '>' => Track::Horizontal,
+                    '/' => Track::Slash,
+                    '\\' => Track::Backslash,
+                    '+' => Track::Intersection,
+                    _ => {
+                        return Err(Error::line(
+                            y,
+                            line,
+                            format!("has {:?}, which isn't a track or a cart", c),
+                        ))
+                    }
+                });
+            }
+            // rows which end early have nothing more in them
+            cells.resize((y + 1) * width, Track::Empty);
+        }
+
+        if carts.len() < 2 {
+            return Err(Error::Input(
+                "has fewer than two carts, which can never crash".to_string(),
+            ));
+        }
+        Ok(Mine {
+            tracks: Grid2D::from_cells(width, cells),
+            carts,
+        })
+    }
+
+    /// Tracks consist of straight paths (| and -), curves (/ and \), and intersections (+). Curves connect exactly two perpendicular pieces of track; intersections occur when two perpendicular paths cross.
+    ///
+    /// Several carts are also on the tracks. Carts always face either up (^), down (v), left (<), or right (>). (On your initial map, the track under each cart is a straight path going the same direction as the cart.)
+    ///
+    /// Each time a cart has the option to turn (by arriving at any intersection), it turns left the first time, goes straight the second time, turns right the third time, and then repeats those directions starting again with left the fourth time, straight the fifth time, and so on. This process is independent of the particular intersection at which the cart has arrived - that is, the cart has no per-intersection memory.
+    ///
+    /// Carts all move at the same speed; they take turns moving a single step at a time. They do this based on their current location: carts on the top row move first (acting from left to right), then carts on the second row move (again from left to right), then carts on the third row, and so on. Once each cart has moved one step, the process repeats; each of these loops is called a tick.
+    ///
+    /// After following their respective paths for a while, the carts eventually crash. To help prevent crashes, you'd like to know the location of the first crash. Locations are given in X,Y coordinates, where the furthest left column is X=0 and the furthest top row is Y=0.
+    ///
+    /// This location is the first crash, and it is the answer.
+    fn part1(mine: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let carts = mine.run_until(|carts| !carts.crashes.is_empty())?;
+
+        Ok(Answer::from(to_coordinates(carts.crashes[0])))
+    }
+
+    /// There isn't much you can do to prevent crashes in this ridiculous system. However, by predicting the crashes, the Elves know where to be in advance and instantly remove the two crashing carts the moment any crash occurs.
+    ///
+    /// They can proceed like this for a while, but eventually, they're going to run out of carts. It could be useful to figure out where the last cart that hasn't crashed will end up.
+    ///
+    /// What is the location of the last cart at the end of the first tick where it is the only cart left?
+    fn part2(mine: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let carts = mine.run_until(|carts| carts.carts.len() <= 1)?;
+
+        match carts.carts[..] {
+            [last] => Ok(Answer::from(to_coordinates(last.position))),
+            _ => Err(Error::NoAnswer(
+                "the carts crash in pairs until there are none left".to_string(),
+            )),
+        }
+    }
+}
+
+/// The tracks, and the carts on them before any of them move
+pub struct Mine {
+    tracks: Grid2D<Track>,
+    carts: Vec<Cart>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Track {
+    Empty,
+    Vertical,
+    Horizontal,
+    /// A curve drawn as `/`
+    Slash,
+    /// A curve drawn as `\`
+    Backslash,
+    Intersection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Cart {
+    position: (usize, usize),
+    direction: Direction,
+    /// How many intersections it's come to, which picks how it turns at the next
+    intersections: usize,
+}
+
+/// The carts as they move around the tracks, tick after tick
+struct Carts<'a> {
+    tracks: &'a Grid2D<Track>,
+    /// The carts which haven't crashed
+    carts: Vec<Cart>,
+    /// Where each crash so far was, in the order they happened
+    crashes: Vec<(usize, usize)>,
+    ticks: usize,
+}
+
+impl Mine {
+    /// Ticks the carts until `done` says so, drawing each tick in the terminal with `--visualize`
+    fn run_until(&self, mut done: impl FnMut(&Carts) -> bool) -> Result<Carts<'_>, Error> {
+        let mut carts = Carts {
+            tracks: &self.tracks,
+            carts: self.carts.clone(),
+            crashes: Vec::new(),
+            ticks: 0,
+        };
+
+        #[cfg(feature = "visualize")]
+        let mut renderer = crate::common::vis::enabled()
+            .then(|| crate::common::vis::Renderer::new().expect("Failed to set up the terminal"));
+
+        while !done(&carts) {
+            carts.tick()?;
+
+            #[cfg(feature = "visualize")]
+            if let Some(renderer) = &mut renderer {
+                renderer
+                    .render(&carts.header(), carts.draw())
+                    .expect("Failed to render frame");
+            }
+        }
+
+        Ok(carts)
+    }
+}
+
+impl Carts<'_> {
+    /// Moves every cart a step, in reading order, taking away any which crash
+    fn tick(&mut self) -> Result<(), Error> {
+        self.carts
+            .sort_unstable_by_key(|cart| (cart.position.1, cart.position.0));
+
+        let mut crashed = vec![false; self.carts.len()];
+        for i in 0..self.carts.len() {
+            if crashed[i] {
+                continue;
+            }
+
+            let cart = &mut self.carts[i];
+            let (x, y) = cart.position;
+            let next = Point::new(x as i64, y as i64).step(cart.direction);
+            let off_the_tracks = || {
+                Error::Input(format!(
+                    "has a cart at {},{} which runs off the tracks",
+                    x, y
+                ))
+            };
+            if !self.tracks.contains((next.x, next.y)) {
+                return Err(off_the_tracks());
+            }
+            cart.position = (next.x as usize, next.y as usize);
+            cart.direction = match (self.tracks[cart.position], cart.direction) {
+                (Track::Empty, _) => return Err(off_the_tracks()),
+                (Track::Slash, Direction::Up | Direction::Down) => cart.direction.turn_right(),
+                (Track::Slash, _) => cart.direction.turn_left(),
+                (Track::Backslash, Direction::Up | Direction::Down) => cart.direction.turn_left(),
+                (Track::Backslash, _) => cart.direction.turn_right(),
+                (Track::Intersection, direction) => {
+                    cart.intersections += 1;
+                    match cart.intersections % 3 {
+                        1 => direction.turn_left(),
+                        2 => direction,
+                        _ => direction.turn_right(),
+                    }
+                }
+                (Track::Vertical | Track::Horizontal, direction) => direction,
+            };
+
+            let position = cart.position;
+            let hit = (0..self.carts.len())
+                .find(|j| *j != i && !crashed[*j] && self.carts[*j].position == position);
+            if let Some(j) = hit {
+                crashed[i] = true;
+                crashed[j] = true;
+                self.crashes.push(position);
+            }
+        }
+
+        let mut crashed = crashed.into_iter();
+        self.carts.retain(|_| !crashed.next().unwrap_or(false));
+        self.ticks += 1;
+
+        Ok(())
+    }
+
+    /// What has happened so far, with where each crash was in order
+    #[cfg(feature = "visualize")]
+    fn header(&self) -> String {
+        let crashes: Vec<String> = self
+            .crashes
+            .iter()
+            .map(|(x, y)| format!("{},{}", x, y))
+            .collect();
+
+        format!(
+            "Tick {}: {} carts left, crashes at [{}]",
+            self.ticks,
+            self.carts.len(),
+            crashes.join(" ")
+        )
+    }
+
+    /// The tracks with each cart drawn as the arrow it faces, and an `X` where each crash was
+    #[cfg(feature = "visualize")]
+    fn draw(&self) -> Grid2D<char> {
+        let mut drawing = self.tracks.map(|track| match track {
+            Track::Empty => ' ',
+            Track::Vertical => '|',
+            Track::Horizontal => '-',
+            Track::Slash => '/',
+            Track::Backslash => '\\',
+            Track::Intersection => '+',
+        });
+        for crash in &self.crashes {
+            drawing[*crash] = 'X';
+        }
+        for cart in &self.carts {
+            drawing[cart.position] = cart.direction.arrow();
+        }
+
+        drawing
+    }
+}
+
+fn to_coordinates((x, y): (usize, usize)) -> (i64, i64) {
+    (x as i64, y as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    examples!(2018, 13, Day13);
+}
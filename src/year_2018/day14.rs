@@ -0,0 +1,165 @@
+//! --- Day 14: Chocolate Charts ---
+//!
+//! You finally have a chance to look at all of the produce moving around. Chocolate, cinnamon, mint, chili peppers, nutmeg, vanilla... the Elves must be growing these plants to make hot chocolate! As you realize this, you hear a conversation in the distance. When you go to investigate, you discover two Elves in what appears to be a makeshift underground kitchen/laboratory.
+
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+/// The number of recipe scores room is made for up front when searching for a sequence of them,
+/// which is more than puzzle inputs take
+const EXPECTED_RECIPES: usize = 1 << 25;
+
+/// The most digits an input can have, as a number of recipes that many digits long (puzzle
+/// inputs are 6) would need a scoreboard too large to make room for
+const MAX_DIGITS: usize = 8;
+
+pub struct Day14;
+
+impl Solver for Day14 {
+    type Parsed = Vec<u8>;
+
+    fn parse(input: &str) -> Result<Vec<u8>, Error> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(Error::Input("is empty".to_string()));
+        }
+        if input.len() > MAX_DIGITS {
+            return Err(Error::Input(format!(
+                "is longer than {} digits",
+                MAX_DIGITS
+            )));
+        }
+
+        input
+            .chars()
+            .map(|c| {
+                c.to_digit(10)
+                    .map(|digit| digit as u8)
+                    .ok_or_else(|| Error::Input(format!("has {:?}, which isn't a digit", c)))
+            })
+            .collect()
+    }
+
+    /// The Elves are trying to come up with the ultimate hot chocolate recipe; they're even maintaining a scoreboard which tracks the quality score (0-9) of each recipe.
+    ///
+    /// Only two recipes are on the board: the first recipe got a score of 3, the second, 7. Each of the two Elves has a current recipe: the first Elf starts with the first recipe, and the second Elf starts with the second recipe.
+    ///
+    /// To create new recipes, the two Elves combine their current recipes. This creates new recipes from the digits of the sum of the current recipes' scores. With the current recipes' scores of 3 and 7, their sum is 10, and so two new recipes would be created: the first with score 1 and the second with score 0. If the current recipes' scores were 2 and 3, the sum, 5, would only create one recipe (with a score of 5) with its single digit.
+    ///
+    /// The new recipes are added to the end of the scoreboard in the order they are created. So, after the first round, the scoreboard is 3, 7, 1, 0.
+    ///
+    /// After all new recipes are added to the scoreboard, each Elf picks a new current recipe. To do this, the Elf steps forward through the scoreboard a number of recipes equal to 1 plus the score of their current recipe. So, after the first round, the first Elf moves forward 1 + 3 = 4 times, while the second Elf moves forward 1 + 7 = 8 times. If they run out of recipes, they loop back around to the beginning.
+    ///
+    /// The Elves think their skill will improve after making a few recipes (your puzzle input). However, that could take ages; you can speed this up considerably by identifying the scores of the ten recipes after that. For example:
+    ///
+    /// - If the Elves think their skill will improve after making 9 recipes, the scores of the ten recipes after the first nine on the scoreboard would be 5158916779.
+    /// - After 5 recipes, the scores of the next ten would be 0124515891.
+    /// - After 18 recipes, the scores of the next ten would be 9251071085.
+    /// - After 2018 recipes, the scores of the next ten would be 5941429882.
+    ///
+    /// What are the scores of the ten recipes immediately after the number of recipes in your puzzle input?
+    fn part1(digits: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let recipes = digits
+            .iter()
+            .try_fold(0, |recipes: usize, digit| {
+                recipes.checked_mul(10)?.checked_add(*digit as usize)
+            })
+            .ok_or_else(|| Error::Input("is too many recipes to score".to_string()))?;
+
+        let mut scoreboard = Scoreboard::with_capacity(recipes + 11);
+        while scoreboard.scores.len() < recipes + 10 {
+            scoreboard.make_recipes(|_| false);
+        }
+
+        Ok(Answer::from(
+            scoreboard.scores[recipes..recipes + 10]
+                .iter()
+                .map(|score| char::from(b'0' + score))
+                .collect::<String>(),
+        ))
+    }
+
+    /// As it turns out, you got the Elves' plan backwards. They actually want to know how many recipes appear on the scoreboard to the left of the first recipes whose scores are the digits from your puzzle input.
+    ///
+    /// - 51589 first appears after 9 recipes.
+    /// - 01245 first appears after 5 recipes.
+    /// - 92510 first appears after 18 recipes.
+    /// - 59414 first appears after 2018 recipes.
+    ///
+    /// How many recipes appear on the scoreboard to the left of the score sequence in your puzzle input?
+    fn part2(digits: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let mut scoreboard = Scoreboard::with_capacity(EXPECTED_RECIPES);
+
+        // the sequence could be on the board before any recipes are made (such as 37)
+        for end in 1..=scoreboard.scores.len() {
+            if scoreboard.scores[..end].ends_with(digits) {
+                return Ok(Answer::from(end - digits.len()));
+            }
+        }
+
+        loop {
+            if let Some(end) = scoreboard.make_recipes(|scores| scores.ends_with(digits)) {
+                return Ok(Answer::from(end - digits.len()));
+            }
+        }
+    }
+}
+
+struct Scoreboard {
+    scores: Vec<u8>,
+    /// The current recipe of each Elf
+    elves: [usize; 2],
+}
+
+impl Scoreboard {
+    fn with_capacity(capacity: usize) -> Self {
+        let mut scores = Vec::with_capacity(capacity);
+        scores.extend_from_slice(&[3, 7]);
+
+        Self {
+            scores,
+            elves: [0, 1],
+        }
+    }
+
+    /// Adds the recipes the Elves make from their current recipes, then moves them on to their
+    /// next. `found` is checked after each recipe is added (as one round can add two), and the
+    /// number of recipes on the board when it's first true is returned.
+    fn make_recipes(&mut self, found: impl Fn(&[u8]) -> bool) -> Option<usize> {
+        let sum = self.elves.iter().map(|elf| self.scores[*elf]).sum::<u8>();
+
+        let mut found_at = None;
+        let digits: &[u8] = if sum >= 10 { &[1, sum - 10] } else { &[sum] };
+        for digit in digits {
+            self.scores.push(*digit);
+            if found_at.is_none() && found(&self.scores) {
+                found_at = Some(self.scores.len());
+            }
+        }
+
+        let len = self.scores.len();
+        for elf in self.elves.iter_mut() {
+            *elf = (*elf + 1 + self.scores[*elf] as usize) % len;
+        }
+
+        found_at
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(Day14::parse("").is_err());
+        assert!(Day14::parse("2O18").is_err());
+        assert!(Day14::parse("99999999").is_ok());
+        assert!(Day14::parse("18446744073709551616").is_err());
+    }
+
+    examples!(2018, 14, Day14);
+}
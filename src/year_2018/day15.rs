@@ -0,0 +1,481 @@
+//! --- Day 15: Beverage Bandits ---
+//!
+//! Having perfected their hot chocolate, the Elves have a new problem: the Goblins that live in these caves will do anything to steal it. Looks like they're here for a fight.
+
+use crate::answer::Answer;
+use crate::common::grid::Grid2D;
+use crate::common::pathfinding::bfs;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+/// The attack power and hit points every unit starts with
+const ATTACK_POWER: i64 = 3;
+const HIT_POINTS: i64 = 200;
+
+pub struct Day15;
+
+impl Solver for Day15 {
+    type Parsed = Cave;
+
+    fn parse(input: &str) -> Result<Cave, Error> {
+        let mut units = Vec::new();
+        let open = Grid2D::parse(input, |position, c| match c {
+            '#' => Ok(false),
+            '.' => Ok(true),
+            'E' | 'G' => {
+                units.push(Unit {
+                    race: if c == 'E' { Race::Elf } else { Race::Goblin },
+                    position,
+                    hit_points: HIT_POINTS,
+                });
+                Ok(true)
+            }
+            _ => Err(format!("has {:?}, which isn't a wall, cavern or unit", c)),
+        })?;
+
+        Ok(Cave { open, units })
+    }
+
+    /// You scan the area, generating a map of the walls (#), open cavern (.), and starting position of every Goblin (G) and Elf (E) (your puzzle input).
+    ///
+    /// Combat proceeds in rounds; in each round, each unit that is still alive takes a turn, resolving all of its actions before the next unit's turn begins. On each unit's turn, it tries to move into range of an enemy (if it isn't already) and then attack (if it is in range).
+    ///
+    /// All units are very disciplined and always follow very strict combat rules. Units never move or attack diagonally, as doing so would be dishonorable. When multiple choices are equally valid, ties are broken in reading order: top-to-bottom, then left-to-right.
+    ///
+    /// Each unit begins its turn by identifying all possible targets (enemy units). If no targets remain, combat ends.
+    ///
+    /// Then, the unit identifies all of the open squares (.) that are in range of each target; these are the squares which are adjacent (immediately up, down, left, or right) to any target and which aren't already occupied by a wall or another unit. Alternatively, the unit might already be in range of a target. If the unit is not already in range of a target, and there are no open squares which are in range of a target, the unit ends its turn.
+    ///
+    /// If the unit is already in range of a target, it does not move, but continues its turn with an attack. Otherwise, since it is not in range of a target, it moves.
+    ///
+    /// To move, the unit first considers the squares that are in range and determines which of those squares it could reach in the fewest steps. If multiple squares are in range and tied for being reachable in the fewest steps, the square which is first in reading order is chosen. The unit then takes a single step toward the chosen square along the shortest path to that square. If multiple steps would put the unit equally closer to its destination, the unit chooses the step which is first in reading order.
+    ///
+    /// After moving (or if the unit began its turn in range of a target), the unit attacks. To attack, the unit first determines all of the targets that are in range of it by being immediately adjacent to it. If there are no such targets, the unit ends its turn. Otherwise, the adjacent target with the fewest hit points is selected; in a tie, the adjacent target with the fewest hit points which is first in reading order is selected.
+    ///
+    /// The unit deals damage equal to its attack power to the selected target, reducing its hit points by that amount. If this reduces its hit points to 0 or fewer, the selected target dies: its square becomes . and it takes no further turns.
+    ///
+    /// Each unit, either Goblin or Elf, has 3 attack power and starts with 200 hit points.
+    ///
+    /// The outcome of combat is the number of full rounds that were completed (not counting the round in which combat ends) multiplied by the sum of the hit points of all remaining units at the moment combat ends.
+    ///
+    /// What is the outcome of the combat described in your puzzle input?
+    fn part1(cave: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        cave.fight(ATTACK_POWER, false)
+            .map(|outcome| Answer::from(outcome.score()))
+            .ok_or_else(|| Error::NoAnswer("the combat never ends".to_string()))
+    }
+
+    /// The Elves look almost evenly matched against the Goblins. Worse, this is exactly the kind of situation where the Goblins thrive.
+    ///
+    /// Fortunately, the Elves have the advantage of being able to increase their attack power. They'd like to know the smallest attack power increase that would allow them to win the combat without any losses.
+    ///
+    /// After increasing the Elves' attack power until it is just barely enough for them to win without any Elves dying, what is the outcome of the combat described in your puzzle input?
+    fn part2(cave: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        // a stronger attack can still lose an elf that a weaker one didn't (by killing goblins in a
+        // different order), so the attack powers are tried in turn rather than searched for, each
+        // combat given up on as soon as an elf dies
+        (ATTACK_POWER + 1..=HIT_POINTS)
+            .find_map(|elf_attack_power| cave.fight(elf_attack_power, true))
+            .map(|outcome| Answer::from(outcome.score()))
+            .ok_or_else(|| {
+                Error::NoAnswer("an elf dies however strong the elves' attacks are".to_string())
+            })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Race {
+    Elf,
+    Goblin,
+}
+
+#[derive(Debug, Clone)]
+struct Unit {
+    race: Race,
+    position: (usize, usize),
+    hit_points: i64,
+}
+
+impl Unit {
+    fn is_alive(&self) -> bool {
+        self.hit_points > 0
+    }
+
+    /// The letter the unit is drawn as on the map
+    #[cfg(feature = "visualize")]
+    fn letter(&self) -> char {
+        match self.race {
+            Race::Elf => 'E',
+            Race::Goblin => 'G',
+        }
+    }
+}
+
+/// The map of the cave, and the units in it before combat begins
+pub struct Cave {
+    /// Whether each square is open cavern, rather than wall
+    open: Grid2D<bool>,
+    units: Vec<Unit>,
+}
+
+/// How a combat ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Outcome {
+    full_rounds: i64,
+    hit_points_left: i64,
+}
+
+impl Outcome {
+    fn score(self) -> i64 {
+        self.full_rounds * self.hit_points_left
+    }
+}
+
+/// The units still alive which are enemies of the `race`
+fn enemies_of(units: &[Unit], race: Race) -> Vec<usize> {
+    (0..units.len())
+        .filter(|j| units[*j].race != race && units[*j].is_alive())
+        .collect()
+}
+
+/// The key which sorts positions into reading order
+fn reading_order(&(x, y): &(usize, usize)) -> (usize, usize) {
+    (y, x)
+}
+
+impl Cave {
+    /// Fights until one side is wiped out, with the elves attacking with `elf_attack_power`. When
+    /// `stop_if_an_elf_dies`, it's given up on (returning `None`) as soon as any elf dies. It's
+    /// also `None` when a whole round passes with no unit moving or attacking, as then none ever
+    /// will again.
+    fn fight(&self, elf_attack_power: i64, stop_if_an_elf_dies: bool) -> Option<Outcome> {
+        let mut units = self.units.clone();
+        let mut occupied = self.open.map(|_| false);
+        for unit in &units {
+            occupied[unit.position] = true;
+        }
+
+        let mut full_rounds = 0;
+        #[cfg(feature = "visualize")]
+        let mut debugger = crate::common::vis::enabled()
+            .then(|| Debugger::new(&self.open, &units, elf_attack_power));
+
+        loop {
+            let mut acted = false;
+            let mut order: Vec<usize> = (0..units.len()).filter(|i| units[*i].is_alive()).collect();
+            order.sort_by_key(|i| reading_order(&units[*i].position));
+
+            for i in order {
+                if !units[i].is_alive() {
+                    continue;
+                }
+
+                let enemies = enemies_of(&units, units[i].race);
+                if enemies.is_empty() {
+                    let hit_points_left = units
+                        .iter()
+                        .filter(|unit| unit.is_alive())
+                        .map(|unit| unit.hit_points)
+                        .sum();
+                    let outcome = Outcome {
+                        full_rounds,
+                        hit_points_left,
+                    };
+
+                    #[cfg(feature = "visualize")]
+                    if let Some(debugger) = &mut debugger {
+                        debugger.end(&self.open, &units, outcome);
+                    }
+                    return Some(outcome);
+                }
+
+                let path = if self.adjacent_target(&units, &enemies, i).is_none() {
+                    let in_range: Vec<(usize, usize)> = enemies
+                        .iter()
+                        .flat_map(|j| self.open.neighbors4(units[*j].position))
+                        .filter(|square| self.open[*square] && !occupied[*square])
+                        .collect();
+
+                    self.path_towards(&occupied, units[i].position, &in_range)
+                } else {
+                    None
+                };
+
+                if let Some(&step) = path.as_ref().and_then(|path| path.get(1)) {
+                    occupied[units[i].position] = false;
+                    occupied[step] = true;
+                    units[i].position = step;
+                    acted = true;
+                }
+
+                let target = self.adjacent_target(&units, &enemies, i);
+                if let Some(j) = target {
+                    units[j].hit_points -= match units[i].race {
+                        Race::Elf => elf_attack_power,
+                        Race::Goblin => ATTACK_POWER,
+                    };
+                    acted = true;
+                }
+
+                #[cfg(feature = "visualize")]
+                if let Some(debugger) = &mut debugger {
+                    let turn = Turn {
+                        unit: i,
+                        path: path.as_deref(),
+                        target,
+                    };
+                    debugger.turn(&self.open, &units, full_rounds, &turn);
+                }
+
+                if let Some(j) = target.filter(|j| !units[*j].is_alive()) {
+                    occupied[units[j].position] = false;
+                    if stop_if_an_elf_dies && units[j].race == Race::Elf {
+                        return None;
+                    }
+                }
+            }
+
+            if !acted {
+                return None;
+            }
+            full_rounds += 1;
+
+            #[cfg(feature = "visualize")]
+            if let Some(debugger) = &mut debugger {
+                debugger.round(&self.open, &units, full_rounds);
+            }
+        }
+    }
+
+    /// The enemy next to unit `i` which it attacks (the one with the fewest hit points, and the
+    /// first in reading order of those), if there is one
+    fn adjacent_target(&self, units: &[Unit], enemies: &[usize], i: usize) -> Option<usize> {
+        enemies
+            .iter()
+            .copied()
+            .filter(|j| {
+                self.open
+                    .neighbors4(units[i].position)
+                    .any(|neighbor| neighbor == units[*j].position)
+            })
+            .min_by_key(|j| (units[*j].hit_points, reading_order(&units[*j].position)))
+    }
+
+    /// The shortest path from `from` (which it starts with) to the nearest of the squares
+    /// `in_range` (the first of them in reading order when they tie), or `None` when none of them
+    /// can be reached
+    fn path_towards(
+        &self,
+        occupied: &Grid2D<bool>,
+        from: (usize, usize),
+        in_range: &[(usize, usize)],
+    ) -> Option<Vec<(usize, usize)>> {
+        // with the squares around each listed in reading order, the path found to each square
+        // starts with the step first in reading order of those on its shortest paths
+        let paths = bfs(from, |square| {
+            self.open
+                .neighbors4(*square)
+                .filter(|neighbor| self.open[*neighbor] && !occupied[*neighbor])
+                .collect::<Vec<_>>()
+        });
+
+        let chosen = in_range
+            .iter()
+            .filter_map(|square| Some((paths.cost(square)?, reading_order(square), *square)))
+            .min()?
+            .2;
+
+        paths.path_to(&chosen)
+    }
+}
+
+/// The turn a unit just took: the path to the square it chose to move towards (if it moved),
+/// and the enemy it attacked (if it attacked one)
+#[cfg(feature = "visualize")]
+struct Turn<'a> {
+    unit: usize,
+    path: Option<&'a [(usize, usize)]>,
+    target: Option<usize>,
+}
+
+/// Draws a combat with `--visualize` to be stepped through, a frame to each unit's turn (or to
+/// each round, switching between them with `t`). It starts paused, so that `n` steps to the next
+/// frame and space plays the rest.
+#[cfg(feature = "visualize")]
+struct Debugger {
+    renderer: crate::common::vis::Renderer,
+    elf_attack_power: i64,
+    by_turn: bool,
+}
+
+#[cfg(feature = "visualize")]
+impl Debugger {
+    fn new(open: &Grid2D<bool>, units: &[Unit], elf_attack_power: i64) -> Self {
+        let renderer = crate::common::vis::Renderer::new().expect("Failed to set up the terminal");
+        let mut debugger = Self {
+            renderer,
+            elf_attack_power,
+            by_turn: true,
+        };
+
+        debugger.draw("Before combat", battlefield(open, units, None));
+        debugger.renderer.pause();
+        debugger
+    }
+
+    fn turn(&mut self, open: &Grid2D<bool>, units: &[Unit], full_rounds: i64, turn: &Turn) {
+        if !self.by_turn {
+            return;
+        }
+
+        let unit = &units[turn.unit];
+        let mut header = format!(
+            "Round {}: {} ({})",
+            full_rounds + 1,
+            unit.letter(),
+            unit.hit_points
+        );
+        if let Some((from, to)) = turn.path.and_then(|path| Some((path[0], *path.last()?))) {
+            header += &format!(
+                " steps from {},{} towards {},{}",
+                from.0, from.1, to.0, to.1
+            );
+        }
+        match turn.target.map(|j| &units[j]) {
+            Some(target) if target.is_alive() => {
+                header += &format!(
+                    " and attacks {} at {},{} (down to {})",
+                    target.letter(),
+                    target.position.0,
+                    target.position.1,
+                    target.hit_points
+                )
+            }
+            Some(target) => {
+                header += &format!(
+                    " and kills {} at {},{}",
+                    target.letter(),
+                    target.position.0,
+                    target.position.1
+                )
+            }
+            None if turn.path.is_none() => header += " can't reach an enemy",
+            None => {}
+        }
+
+        self.draw(&header, battlefield(open, units, Some(turn)));
+    }
+
+    fn round(&mut self, open: &Grid2D<bool>, units: &[Unit], full_rounds: i64) {
+        if !self.by_turn {
+            let header = format!("After {} rounds", full_rounds);
+            self.draw(&header, battlefield(open, units, None));
+        }
+    }
+
+    /// Draws how the combat ended, and holds it until the next frame is asked for
+    fn end(&mut self, open: &Grid2D<bool>, units: &[Unit], outcome: Outcome) {
+        let header = format!(
+            "Combat ends after {} full rounds: {} * {} = {}",
+            outcome.full_rounds,
+            outcome.full_rounds,
+            outcome.hit_points_left,
+            outcome.score()
+        );
+        let frame = battlefield(open, units, None);
+
+        self.draw(&header, &frame);
+        self.renderer.pause();
+        self.draw(&header, &frame);
+    }
+
+    fn draw(&mut self, header: &str, frame: impl std::fmt::Display) {
+        let header = format!(
+            "{}  [elves attack with {}, t steps by {}]",
+            header,
+            self.elf_attack_power,
+            if self.by_turn { "round" } else { "turn" }
+        );
+        self.renderer
+            .render(&header, frame)
+            .expect("Failed to render frame");
+
+        for key in self.renderer.take_pressed() {
+            if key == crossterm::event::KeyCode::Char('t') {
+                self.by_turn = !self.by_turn;
+            }
+        }
+    }
+}
+
+/// The cave drawn as the puzzle draws it, with the hit points of the units in each row after it.
+/// The unit whose turn it was stands out, along with the path it's taking (with `+` on the square
+/// at the end of it) and the enemy it attacked, in red (or as a red `x` where it died).
+#[cfg(feature = "visualize")]
+fn battlefield(open: &Grid2D<bool>, units: &[Unit], turn: Option<&Turn>) -> String {
+    use crossterm::style::Stylize;
+
+    let mut rows: Vec<Vec<String>> = open
+        .rows()
+        .map(|row| {
+            row.iter()
+                .map(|open| if *open { "." } else { "#" }.to_string())
+                .collect()
+        })
+        .collect();
+
+    let mut alive: Vec<&Unit> = units.iter().filter(|unit| unit.is_alive()).collect();
+    alive.sort_by_key(|unit| reading_order(&unit.position));
+    for unit in &alive {
+        rows[unit.position.1][unit.position.0] = unit.letter().to_string();
+    }
+
+    if let Some(turn) = turn {
+        if let Some(path) = turn.path {
+            for (x, y) in path.iter().skip(2) {
+                rows[*y][*x] = '*'.yellow().to_string();
+            }
+            if let Some(&(x, y)) = path.last().filter(|_| path.len() > 2) {
+                rows[y][x] = '+'.yellow().bold().to_string();
+            }
+        }
+
+        let unit = &units[turn.unit];
+        rows[unit.position.1][unit.position.0] = unit.letter().reverse().to_string();
+
+        if let Some(target) = turn.target.map(|j| &units[j]) {
+            let (x, y) = target.position;
+            rows[y][x] = if target.is_alive() {
+                target.letter().red().bold().to_string()
+            } else {
+                'x'.red().bold().to_string()
+            };
+        }
+    }
+
+    rows.iter()
+        .enumerate()
+        .map(|(y, row)| {
+            let hit_points: Vec<String> = alive
+                .iter()
+                .filter(|unit| unit.position.1 == y)
+                .map(|unit| format!("{}({})", unit.letter(), unit.hit_points))
+                .collect();
+            if hit_points.is_empty() {
+                row.concat()
+            } else {
+                format!("{}   {}", row.concat(), hit_points.join(", "))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    examples!(2018, 15, Day15);
+}
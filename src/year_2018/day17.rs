@@ -0,0 +1,410 @@
+//! --- Day 17: Reservoir Research ---
+//!
+//! You arrive in the year 18. If it weren't for the coat you got in 1018, you would be very cold: the North Pole base hasn't even been constructed.
+
+use crate::answer::Answer;
+use crate::common::artifacts;
+use crate::common::grid::Grid2D;
+use crate::common::parse::unsigned_ints_in;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+use std::fmt;
+
+/// Where the spring is, at the top of the scan
+const SPRING: (usize, usize) = (500, 0);
+
+/// How many cells of water are worked through between the frames drawn in the terminal
+#[cfg(feature = "visualize")]
+const CELLS_PER_FRAME: usize = 20;
+
+/// How many cells of water are worked through between the frames saved as PNGs, which are of the
+/// whole scan and so are saved less often
+#[cfg(feature = "image")]
+const CELLS_PER_PNG: usize = 250;
+
+pub struct Day17;
+
+impl Solver for Day17 {
+    type Parsed = Scan;
+
+    fn parse(input: &str) -> Result<Scan, Error> {
+        let mut veins = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let not_a_vein = || Error::line(i, line, "isn't a vein of clay like x=495, y=2..7");
+            let numbers: Vec<usize> = unsigned_ints_in(line)
+                .into_iter()
+                .map(|n| n as usize)
+                .collect();
+            let (at, from, to) = match numbers[..] {
+                [at, from, to] if from <= to => (at, from, to),
+                _ => return Err(not_a_vein()),
+            };
+            veins.push(match line.trim_start().chars().next() {
+                Some('x') => (at..=at, from..=to),
+                Some('y') => (from..=to, at..=at),
+                _ => return Err(not_a_vein()),
+            });
+        }
+
+        let (xs, ys) = (
+            veins.iter().flat_map(|(xs, _)| [*xs.start(), *xs.end()]),
+            veins.iter().flat_map(|(_, ys)| [*ys.start(), *ys.end()]),
+        );
+        let (top, bottom) = match (ys.clone().min(), ys.max()) {
+            (Some(top), Some(bottom)) => (top, bottom),
+            _ => return Err(Error::Input("has no clay".to_string())),
+        };
+        // a column to either side of the clay, for water running off its edges
+        let left = xs.clone().chain([SPRING.0]).min().unwrap_or(SPRING.0) - 1;
+        let right = xs.chain([SPRING.0]).max().unwrap_or(SPRING.0) + 1;
+
+        let mut ground = Grid2D::new(right - left + 1, bottom + 1, Ground::Sand);
+        for (xs, ys) in veins {
+            for x in xs {
+                for y in ys.clone() {
+                    ground[(x - left, y)] = Ground::Clay;
+                }
+            }
+        }
+
+        Ok(Scan { ground, left, top })
+    }
+
+    /// You scan a two-dimensional vertical slice of the ground nearby and discover that it is mostly sand with veins of clay. The scan only provides data with a granularity of square meters, but it should be good enough to determine how much water is trapped there. In the scan, x represents the distance to the right, and y represents the distance down. There is also a spring of water near the surface at x=500, y=0. The scan identifies which square meters are clay (your puzzle input).
+    ///
+    /// The spring of water will produce water forever. Water can move through sand, but is blocked by clay. Water always moves down when possible, and spreads to the left and right otherwise, filling space that has clay on both sides and falling out otherwise.
+    ///
+    /// How many tiles can be reached by the water? To prevent counting forever, ignore tiles with a y coordinate smaller than the smallest y coordinate in your scan data or larger than the largest one. Any x coordinate is valid. In this example, the lowest y coordinate given is 1, and the highest is 13, causing the water spring (in row 0) and the water falling off the bottom of the render (in rows 14 through infinity) to be ignored.
+    ///
+    /// How many tiles can the water reach within the range of y values in your scan?
+    fn part1(scan: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let ground = scan.fill();
+
+        Ok(Answer::from(scan.count(&ground, |ground| {
+            matches!(ground, Ground::Flowing | Ground::Settled)
+        })))
+    }
+
+    /// After a very long time, the water spring will run dry. How much water will be retained?
+    ///
+    /// How many water tiles are left after the water spring stops producing water and all remaining water not at rest has drained?
+    fn part2(scan: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let ground = scan.fill();
+
+        Ok(Answer::from(
+            scan.count(&ground, |ground| *ground == Ground::Settled),
+        ))
+    }
+}
+
+/// The ground the scan covers, from the spring's row down to the lowest clay
+pub struct Scan {
+    ground: Grid2D<Ground>,
+    /// The x of the leftmost column of `ground`, which is a column left of any clay
+    left: usize,
+    /// The y of the highest clay, above which water isn't counted
+    top: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ground {
+    Sand,
+    Clay,
+    /// Water which has passed through, and would drain away if the spring stopped
+    Flowing,
+    /// Water at rest, held in by clay
+    Settled,
+}
+
+/// The water as it spreads from the spring, a cell at a time
+///
+/// Cells of flowing water wait in a worklist to be spread from, so that however far water drops
+/// nothing recurses. When a row settles, the flowing water above it is spread from again, now that
+/// it has something to rest on.
+struct Flow {
+    ground: Grid2D<Ground>,
+    worklist: Vec<(usize, usize)>,
+}
+
+impl Flow {
+    fn new(scan: &Scan) -> Flow {
+        let spring = (SPRING.0 - scan.left, SPRING.1);
+        let mut ground = scan.ground.clone();
+        ground[spring] = Ground::Flowing;
+
+        Flow {
+            ground,
+            worklist: vec![spring],
+        }
+    }
+
+    /// Spreads from the next cell of flowing water in the worklist, returning where it was, or
+    /// `None` once the water has nowhere left to go
+    fn step(&mut self) -> Option<(usize, usize)> {
+        let (x, y) = self.worklist.pop()?;
+        if y + 1 == self.ground.height() {
+            // falls off the bottom of the scan
+            return Some((x, y));
+        }
+
+        match self.ground[(x, y + 1)] {
+            Ground::Sand => {
+                self.ground[(x, y + 1)] = Ground::Flowing;
+                self.worklist.push((x, y + 1));
+            }
+            // spread from again if what it's falling into settles
+            Ground::Flowing => {}
+            Ground::Clay | Ground::Settled => self.spread((x, y)),
+        }
+
+        Some((x, y))
+    }
+
+    /// Spreads water left and right along a row from `(x, y)`, which has something to rest on,
+    /// until it meets clay or runs off an edge. It settles when it meets clay on both sides.
+    fn spread(&mut self, (x, y): (usize, usize)) {
+        let (left, left_wall) = self.spread_to((x, y), |x| x - 1);
+        let (right, right_wall) = self.spread_to((x, y), |x| x + 1);
+
+        if left_wall && right_wall {
+            for x in left..=right {
+                self.ground[(x, y)] = Ground::Settled;
+                if y > 0 && self.ground[(x, y - 1)] == Ground::Flowing {
+                    self.worklist.push((x, y - 1));
+                }
+            }
+            return;
+        }
+
+        for x in left..=right {
+            self.ground[(x, y)] = Ground::Flowing;
+        }
+        for (edge, wall) in [(left, left_wall), (right, right_wall)] {
+            if !wall {
+                self.worklist.push((edge, y));
+            }
+        }
+    }
+
+    /// How far water spreads along a row from `(x, y)` in the direction `next` steps in, and
+    /// whether it stopped at clay rather than running off an edge
+    fn spread_to(&self, (x, y): (usize, usize), next: impl Fn(usize) -> usize) -> (usize, bool) {
+        let mut x = x;
+        loop {
+            if !matches!(self.ground[(x, y + 1)], Ground::Clay | Ground::Settled) {
+                return (x, false);
+            }
+            // there's always a column of sand beyond the clay, which water falls down before it
+            // reaches the edge of the scan
+            if self.ground[(next(x), y)] == Ground::Clay {
+                return (x, true);
+            }
+            x = next(x);
+        }
+    }
+}
+
+impl Scan {
+    /// The ground once the water has spread as far as it can, drawn in the terminal as it spreads
+    /// with `--visualize`, saved as PNG frames with `--frames`, and emitted as an artifact with
+    /// `--debug-artifacts`
+    fn fill(&self) -> Grid2D<Ground> {
+        let mut flow = Flow::new(self);
+        let mut frames = Frames::start();
+        while let Some(position) = flow.step() {
+            frames.step(&flow.ground, position);
+        }
+        frames.finish(&flow.ground);
+        artifacts::emit("ground", &flow.ground);
+
+        flow.ground
+    }
+
+    /// How many cells from the highest clay down are `counted`
+    fn count(&self, ground: &Grid2D<Ground>, counted: impl Fn(&Ground) -> bool) -> usize {
+        ground
+            .iter()
+            .filter(|((_, y), ground)| *y >= self.top && counted(ground))
+            .count()
+    }
+}
+
+impl fmt::Display for Ground {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Self::Sand => '.',
+            Self::Clay => '#',
+            Self::Flowing => '|',
+            Self::Settled => '~',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// Where the water is drawn as it spreads: in the terminal with `--visualize` (recorded in the
+/// colors of the PNG frames with `--record`), and as PNG frames with `--frames`
+struct Frames {
+    /// How many cells of water have been spread from so far
+    steps: usize,
+    #[cfg(feature = "visualize")]
+    renderer: Option<crate::common::vis::Renderer>,
+    #[cfg(feature = "image")]
+    pngs: Option<crate::common::vis::PngFrames>,
+}
+
+impl Frames {
+    fn start() -> Frames {
+        Frames {
+            steps: 0,
+            #[cfg(feature = "visualize")]
+            renderer: crate::common::vis::enabled().then(|| {
+                let mut renderer =
+                    crate::common::vis::Renderer::new().expect("Failed to set up the terminal");
+                renderer.set_colors(|c| match c {
+                    '#' => palette(Some(&Ground::Clay)),
+                    '|' => palette(Some(&Ground::Flowing)),
+                    '~' => palette(Some(&Ground::Settled)),
+                    _ => palette(Some(&Ground::Sand)),
+                });
+                renderer
+            }),
+            #[cfg(feature = "image")]
+            pngs: crate::common::vis::frames_directory().map(|directory| {
+                crate::common::vis::PngFrames::new(directory, 2)
+                    .expect("Failed to create the frames directory")
+            }),
+        }
+    }
+
+    /// Counts another cell of water spread from, at `position`, drawing the ground when a frame
+    /// is due
+    #[cfg_attr(not(feature = "visualize"), allow(unused_variables))]
+    fn step(&mut self, ground: &Grid2D<Ground>, position: (usize, usize)) {
+        self.steps += 1;
+
+        #[cfg(feature = "visualize")]
+        if let Some(renderer) = &mut self.renderer {
+            if self.steps.is_multiple_of(CELLS_PER_FRAME) {
+                renderer
+                    .render(
+                        &format!("Step {}", self.steps),
+                        Window::around(ground, position),
+                    )
+                    .expect("Failed to render frame");
+            }
+        }
+        #[cfg(feature = "image")]
+        if self.steps.is_multiple_of(CELLS_PER_PNG) {
+            self.save(ground);
+        }
+    }
+
+    /// Saves the ground once the water has stopped spreading as the last PNG frame
+    #[cfg_attr(not(feature = "image"), allow(unused_variables))]
+    fn finish(&mut self, ground: &Grid2D<Ground>) {
+        #[cfg(feature = "image")]
+        self.save(ground);
+    }
+
+    #[cfg(feature = "image")]
+    fn save(&mut self, ground: &Grid2D<Ground>) {
+        if let Some(pngs) = &mut self.pngs {
+            pngs.save(ground, palette).expect("Failed to save frame");
+        }
+    }
+}
+
+/// The part of the ground around where the water is spreading, as much of it as fits in the
+/// terminal
+#[cfg(feature = "visualize")]
+struct Window<'a> {
+    ground: &'a Grid2D<Ground>,
+    xs: std::ops::Range<usize>,
+    ys: std::ops::Range<usize>,
+}
+
+#[cfg(feature = "visualize")]
+impl Window<'_> {
+    /// How many cells across and down the window is
+    const SIZE: (usize, usize) = (120, 40);
+
+    fn around(ground: &Grid2D<Ground>, (x, y): (usize, usize)) -> Window<'_> {
+        let range = |at: usize, size: usize, length: usize| {
+            let start = at.saturating_sub(size / 2).min(length.saturating_sub(size));
+            start..(start + size).min(length)
+        };
+
+        Window {
+            ground,
+            xs: range(x, Self::SIZE.0, ground.width()),
+            ys: range(y, Self::SIZE.1, ground.height()),
+        }
+    }
+}
+
+#[cfg(feature = "visualize")]
+impl fmt::Display for Window<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in self.ys.clone() {
+            for x in self.xs.clone() {
+                write!(f, "{}", self.ground[(x, y)])?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The colors of the ground in PNG frames and recordings
+#[cfg(any(feature = "visualize", feature = "image"))]
+fn palette(ground: Option<&Ground>) -> [u8; 3] {
+    match ground {
+        Some(Ground::Sand) | None => [237, 201, 175],
+        Some(Ground::Clay) => [120, 72, 40],
+        Some(Ground::Flowing) => [120, 190, 255],
+        Some(Ground::Settled) => [20, 60, 200],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{examples, select};
+
+    examples!(2018, 17, Day17);
+
+    #[test]
+    fn test_fill() {
+        let scan = Day17::parse(select(2018, 17, 1)[0].input).unwrap();
+        assert_eq!(
+            scan.fill().to_string(),
+            "\
+......|.......
+......|.....#.
+.#..#||||...#.
+.#..#~~#|.....
+.#..#~~#|.....
+.#~~~~~#|.....
+.#~~~~~#|.....
+.#######|.....
+........|.....
+...|||||||||..
+...|#~~~~~#|..
+...|#~~~~~#|..
+...|#~~~~~#|..
+...|#######|.."
+        );
+
+        // a drop far deeper than the stack would allow if each cell of it were a call
+        let scan = Day17::parse("x=500, y=100000..100000").unwrap();
+        let ground = scan.fill();
+        assert_eq!(ground[(1, 99999)], Ground::Flowing);
+        assert_eq!(scan.count(&ground, |ground| *ground == Ground::Flowing), 2);
+    }
+}
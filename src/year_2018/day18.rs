@@ -0,0 +1,130 @@
+//! --- Day 18: Settlers of The North Pole ---
+//!
+//! On the outskirts of the North Pole base construction project, many Elves are collecting lumber.
+
+use crate::answer::Answer;
+use crate::common::automaton::{run, run_detecting_cycles, step_dense, Neighborhood};
+use crate::common::grid::Grid2D;
+use crate::common::Counter;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Parsed = Grid2D<Acre>;
+
+    fn parse(input: &str) -> Result<Grid2D<Acre>, Error> {
+        Grid2D::parse(input, |_, c| match c {
+            '.' => Ok(Acre::Open),
+            '|' => Ok(Acre::Trees),
+            '#' => Ok(Acre::Lumberyard),
+            _ => Err(format!("has {:?}, which isn't an acre", c)),
+        })
+    }
+
+    /// The lumber collection area is 50 acres by 50 acres; each acre can be either open ground (.), trees (|), or a lumberyard (#). You take a scan of the area (your puzzle input).
+    ///
+    /// Strange magic is influencing the growth of trees within the area; each minute, each acre changes according to the contents of the eight acres adjacent to it:
+    ///
+    /// - An open acre will become filled with trees if three or more adjacent acres contained trees. Otherwise, nothing happens.
+    /// - An acre filled with trees will become a lumberyard if three or more adjacent acres were lumberyards. Otherwise, nothing happens.
+    /// - An acre containing a lumberyard will remain a lumberyard if it was adjacent to at least one other lumberyard and at least one acre containing trees. Otherwise, it becomes open.
+    ///
+    /// These changes happen across all acres simultaneously, each of them using the state of all acres at the beginning of the minute and changing to their new form by the end of that same minute. Changes that happen during the minute don't affect each other.
+    ///
+    /// Multiplying the number of wooded acres by the number of lumberyards gives the total resource value.
+    ///
+    /// What will the total resource value of the lumber collection area be after 10 minutes?
+    fn part1(area: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let area = run(
+            area.clone(),
+            |area| step_dense(area, Neighborhood::Moore, grow),
+            10,
+        );
+        Ok(Answer::from(resource_value(&area)))
+    }
+
+    /// This important natural resource will need to last for at least thousands of years. Are the Elves collecting this lumber sustainably?
+    ///
+    /// What will the total resource value of the lumber collection area be after 1000000000 minutes?
+    fn part2(area: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(resource_value(&after_minutes(
+            area,
+            1_000_000_000,
+        ))))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Acre {
+    Open,
+    Trees,
+    Lumberyard,
+}
+
+/// How an acre changes over a minute, given the acres adjacent to it
+fn grow(acre: &Acre, adjacent: &Counter<&Acre>) -> Acre {
+    let trees = adjacent.count(&&Acre::Trees);
+    let lumberyards = adjacent.count(&&Acre::Lumberyard);
+
+    match acre {
+        Acre::Open if trees >= 3 => Acre::Trees,
+        Acre::Trees if lumberyards >= 3 => Acre::Lumberyard,
+        Acre::Lumberyard if lumberyards == 0 || trees == 0 => Acre::Open,
+        _ => *acre,
+    }
+}
+
+/// The area after `minutes` minutes, which skips ahead once the area starts repeating itself
+fn after_minutes(area: &Grid2D<Acre>, minutes: u64) -> Grid2D<Acre> {
+    run_detecting_cycles(
+        area.clone(),
+        |area| step_dense(area, Neighborhood::Moore, grow),
+        minutes,
+    )
+}
+
+/// The number of wooded acres multiplied by the number of lumberyards
+fn resource_value(area: &Grid2D<Acre>) -> usize {
+    let acres: Counter<&Acre> = area.iter().map(|(_, acre)| acre).collect();
+    acres.count(&&Acre::Trees) * acres.count(&&Acre::Lumberyard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::rng::Rng;
+    use crate::examples::examples;
+
+    examples!(2018, 18, Day18);
+
+    #[test]
+    fn test_skipping_ahead() {
+        // a made-up area which goes 72 minutes before settling into a cycle 36 minutes long,
+        // unlike the example's, which soon stops changing at all
+        let mut rng = Rng::new(77);
+        let cells = (0..25 * 25)
+            .map(|_| match rng.below(6) {
+                0..=2 => Acre::Open,
+                3 | 4 => Acre::Trees,
+                _ => Acre::Lumberyard,
+            })
+            .collect();
+        let area = Grid2D::from_cells(25, cells);
+
+        let mut simulated = area.clone();
+        for minute in 1..=300 {
+            simulated = step_dense(&simulated, Neighborhood::Moore, grow);
+            if minute == 150 || minute == 300 {
+                assert_eq!(
+                    after_minutes(&area, minute),
+                    simulated,
+                    "after {} minutes",
+                    minute
+                );
+            }
+        }
+    }
+}
@@ -6,120 +6,135 @@
 //!
 //! "Wouldn't they have had enough fabric to fill several boxes in the warehouse? They'd be stored together, so the box IDs should be similar. Too bad it would take forever to search the warehouse for two similar box IDs..." They walk too far away to hear any more.
 
-use std::collections::BTreeMap;
-
-/// Late at night, you sneak to the warehouse - who knows what kinds of paradoxes you could cause if you were discovered - and use your fancy wrist device to quickly scan every box and produce a list of the likely candidates (your puzzle input).
-///
-/// To make sure you didn't miss any, you scan the likely candidate boxes again, counting the number that have an ID containing exactly two of any letter and then separately counting those with exactly three of any letter. You can multiply those two counts together to get a rudimentary checksum and compare it to what your device predicts.
-///
-/// For example, if you see the following box IDs:
-///
-///     abcdef contains no letters that appear exactly two or three times.
-///     bababc contains two a and three b, so it counts for both.
-///     abbcde contains two b, but no letter appears exactly three times.
-///     abcccd contains three c, but no letter appears exactly two times.
-///     aabcdd contains two a and two d, but it only counts once.
-///     abcdee contains two e.
-///     ababab contains three a and three b, but it only counts once.
-///
-/// Of these box IDs, four of them contain a letter which appears exactly twice, and three of them contain a letter which appears exactly three times. Multiplying these together produces a checksum of 4 * 3 = 12.
-///
-/// What is the checksum for your list of box IDs?
-pub fn part1() {
-    let input = crate::common::read_stdin_to_string();
-
-    let mut two_letter_checksum_component: i64 = 0;
-    let mut three_letter_checksum_component: i64 = 0;
-
-    let mut seen_letter_counts: BTreeMap<char, i64> = BTreeMap::new();
-
-    for box_id in input.lines() {
-        for letter in box_id.chars() {
-            let count = (match seen_letter_counts.get(&letter) {
-                Some(count) => count,
-                None => &0,
-            }) + 1;
-            seen_letter_counts.insert(letter, count);
-        }
+use crate::answer::Answer;
+use crate::common::rng::Rng;
+use crate::common::strings;
+use crate::error::Error;
+use crate::generate::Scale;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day2;
+
+impl Solver for Day2 {
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Vec<String>, Error> {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    /// Late at night, you sneak to the warehouse - who knows what kinds of paradoxes you could cause if you were discovered - and use your fancy wrist device to quickly scan every box and produce a list of the likely candidates (your puzzle input).
+    ///
+    /// To make sure you didn't miss any, you scan the likely candidate boxes again, counting the number that have an ID containing exactly two of any letter and then separately counting those with exactly three of any letter. You can multiply those two counts together to get a rudimentary checksum and compare it to what your device predicts.
+    ///
+    /// For example, if you see the following box IDs:
+    ///
+    ///     abcdef contains no letters that appear exactly two or three times.
+    ///     bababc contains two a and three b, so it counts for both.
+    ///     abbcde contains two b, but no letter appears exactly three times.
+    ///     abcccd contains three c, but no letter appears exactly two times.
+    ///     aabcdd contains two a and two d, but it only counts once.
+    ///     abcdee contains two e.
+    ///     ababab contains three a and three b, but it only counts once.
+    ///
+    /// Of these box IDs, four of them contain a letter which appears exactly twice, and three of them contain a letter which appears exactly three times. Multiplying these together produces a checksum of 4 * 3 = 12.
+    ///
+    /// What is the checksum for your list of box IDs?
+    fn part1(box_ids: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let mut two_letter_checksum_component: i64 = 0;
+        let mut three_letter_checksum_component: i64 = 0;
 
-        let mut seen_two = false;
-        let mut seen_three = false;
-        for count in seen_letter_counts.values() {
-            if !seen_two && *count == 2 {
-                seen_two = true;
+        for box_id in box_ids {
+            let letter_counts = byte_counts(box_id.as_bytes());
+
+            if letter_counts.contains(&2) {
                 two_letter_checksum_component += 1;
             }
-            if !seen_three && *count == 3 {
-                seen_three = true;
+            if letter_counts.contains(&3) {
                 three_letter_checksum_component += 1;
             }
-            if seen_two && seen_three {
-                break;
-            }
         }
 
-        seen_letter_counts.clear();
+        Ok(Answer::from(
+            two_letter_checksum_component * three_letter_checksum_component,
+        ))
     }
 
-    let checksum = two_letter_checksum_component * three_letter_checksum_component;
+    /// Confident that your list of box IDs is complete, you're ready to find the boxes full of prototype fabric.
+    ///
+    /// The boxes will have IDs which differ by exactly one character at the same position in both strings. For example, given the following box IDs:
+    ///
+    /// abcde
+    /// fghij
+    /// klmno
+    /// pqrst
+    /// fguij
+    /// axcye
+    /// wvxyz
+    ///
+    /// The IDs abcde and axcye are close, but they differ by two characters (the second and fourth). However, the IDs fghij and fguij differ by exactly one character, the third (h and u). Those must be the correct boxes.
+    ///
+    /// What letters are common between the two correct box IDs? (In the example above, this is found by removing the differing character from either ID, producing fgij.)
+    fn part2(box_ids: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let matches = find_part2_matches(box_ids).ok_or_else(|| {
+            Error::NoAnswer("no two box IDs differ by exactly one letter".to_string())
+        })?;
 
-    println!("the checksum for your list of box IDs: {}", checksum);
+        Ok(Answer::from(strings::common_chars(matches.0, matches.1)))
+    }
 }
 
-/// Confident that your list of box IDs is complete, you're ready to find the boxes full of prototype fabric.
-///
-/// The boxes will have IDs which differ by exactly one character at the same position in both strings. For example, given the following box IDs:
-///
-/// abcde
-/// fghij
-/// klmno
-/// pqrst
-/// fguij
-/// axcye
-/// wvxyz
-///
-/// The IDs abcde and axcye are close, but they differ by two characters (the second and fourth). However, the IDs fghij and fguij differ by exactly one character, the third (h and u). Those must be the correct boxes.
-///
-/// What letters are common between the two correct box IDs? (In the example above, this is found by removing the differing character from either ID, producing fgij.)
-pub fn part2() {
-    let input = crate::common::read_stdin_to_string();
-
-    let matches = find_part2_matches(&input).expect("No matches found");
-
-    let common_letters: String = matches
-        .0
-        .chars()
-        .zip(matches.1.chars())
-        .filter(|(letter_1, letter_2)| letter_1 == letter_2)
-        .map(|letters| letters.0)
-        .collect();
-
-    println!(
-        "the common letters between the two correct box IDs: {}",
-        common_letters
-    );
+/// How many times each byte is in `bytes`, indexed by the byte. Counts wrap round past 255, which
+/// box IDs are far too short to reach.
+fn byte_counts(bytes: &[u8]) -> [u8; 256] {
+    let mut counts = [0u8; 256];
+    for byte in bytes {
+        counts[*byte as usize] = counts[*byte as usize].wrapping_add(1);
+    }
+
+    counts
 }
 
-fn find_part2_matches(input: &str) -> Option<(String, String)> {
-    for box_id_1 in input.lines() {
-        'test_inner: for box_id_2 in input.lines() {
-            if box_id_1 == box_id_2 {
-                continue;
-            }
+/// The number of places at which two byte strings of the same length differ, counted without
+/// branching so that it's compared many bytes at a time
+fn byte_differences(a: &[u8], b: &[u8]) -> usize {
+    a.iter().zip(b).map(|(a, b)| usize::from(a != b)).sum()
+}
 
-            let mut differences = 0;
-            for (letter_1, letter_2) in box_id_1.chars().zip(box_id_2.chars()) {
-                if letter_1 != letter_2 {
-                    differences += 1;
-                    if differences > 1 {
-                        continue 'test_inner;
-                    }
-                }
-            }
-            if differences == 1 {
-                return Some((box_id_1.to_string(), box_id_2.to_string()));
-            }
-        }
-    }
-    None
+fn find_part2_matches(box_ids: &[String]) -> Option<(&str, &str)> {
+    box_ids.iter().enumerate().find_map(|(i, box_id_1)| {
+        box_ids[i + 1..]
+            .iter()
+            .find(|box_id_2| {
+                box_id_1.len() == box_id_2.len()
+                    && byte_differences(box_id_1.as_bytes(), box_id_2.as_bytes()) == 1
+            })
+            .map(|box_id_2| (box_id_1.as_str(), box_id_2.as_str()))
+    })
+}
+
+/// Box IDs of 26 random letters, as many as the 250 of a real input, along with a copy of one of
+/// them with a single letter changed for the two boxes of prototype fabric. Any other two IDs
+/// differing by only one letter is as good as impossible.
+pub fn generate(rng: &mut Rng, scale: Scale) -> String {
+    let mut random_id = || -> Vec<u8> { (0..26).map(|_| b'a' + rng.below(26) as u8).collect() };
+    let mut box_ids: Vec<Vec<u8>> = (0..scale.of(250)).map(|_| random_id()).collect();
+
+    let mut fabric = box_ids[rng.below(box_ids.len())].clone();
+    let letter = rng.below(fabric.len());
+    fabric[letter] = b'a' + (fabric[letter] - b'a' + 1 + rng.below(25) as u8) % 26;
+    box_ids.insert(rng.below(box_ids.len() + 1), fabric);
+
+    box_ids
+        .iter()
+        .map(|id| format!("{}\n", String::from_utf8_lossy(id)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    examples!(2018, 2, Day2);
 }
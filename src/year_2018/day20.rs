@@ -0,0 +1,311 @@
+//! --- Day 20: A Regular Map ---
+//!
+//! While you were learning about instruction pointers, the Elves made considerable progress. When you look up, you discover that the North Pole base construction project has completely surrounded you.
+
+use crate::answer::Answer;
+use crate::common::arena::{Arena, Span};
+use crate::common::geometry::{Direction, Point};
+use crate::common::pathfinding::{bfs, Paths};
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+use std::collections::{HashMap, HashSet};
+
+/// How many doors away the rooms part 2 counts are at least
+const FAR_DOORS: usize = 1000;
+
+pub struct Day20;
+
+impl Solver for Day20 {
+    type Parsed = Facility;
+
+    fn parse(input: &str) -> Result<Facility, Error> {
+        Ok(Facility::from_directions(&Directions::parse(input.trim())?))
+    }
+
+    /// The area you are in is made up entirely of rooms and doors. The rooms are arranged in a grid, and rooms only connect to adjacent rooms when a door is present between them.
+    ///
+    /// Because of this, you can only get a rough idea of the layout; you'll have to build the map yourself. The Elves give you directions (your puzzle input), a regular expression which describes the routes through the facility: each character is N, S, E, or W, moving you one room in that direction, and a branch (a|b|c) lets you take any of its options. An empty option, as in (NEWS|), means the route can skip the detour entirely. Every route the regular expression matches is a real route through the facility, starting from your current position.
+    ///
+    /// What is the largest number of doors you would be required to pass through to reach a room? That is, find the room for which the shortest path from your starting location to that room would require passing through the most doors; what is the fewest doors you can pass through to reach it?
+    fn part1(facility: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let distances = facility.distances();
+        Ok(Answer::from(
+            distances.iter().map(|(_, doors)| doors).max().unwrap_or(0),
+        ))
+    }
+
+    /// Okay, so the facility is big.
+    ///
+    /// How many rooms have a shortest path from your current location that pass through at least 1000 doors?
+    fn part2(facility: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let distances = facility.distances();
+        Ok(Answer::from(
+            distances
+                .iter()
+                .filter(|(_, doors)| *doors >= FAR_DOORS)
+                .count(),
+        ))
+    }
+}
+
+/// The map of the facility the directions describe, drawn as the puzzle draws it, along with the
+/// fewest doors it takes to reach each room from the start (at the origin)
+pub fn map(input: &str) -> Result<(String, Paths<Point, usize>), Error> {
+    let facility = Day20::parse(input)?;
+    Ok((facility.render(), facility.distances()))
+}
+
+/// The rooms of the facility and the doors between them, with the room the routes start in at the
+/// origin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Facility {
+    /// The directions of the doors out of each room
+    rooms: HashMap<Point, Vec<Direction>>,
+}
+
+impl Facility {
+    /// The facility every route the directions match goes through
+    fn from_directions(directions: &Directions) -> Facility {
+        let mut rooms: HashMap<Point, Vec<Direction>> = HashMap::new();
+        rooms.insert(Point::ORIGIN, Vec::new());
+        for (room, direction) in directions.doors() {
+            let doors = rooms.entry(room).or_default();
+            if !doors.contains(&direction) {
+                doors.push(direction);
+            }
+        }
+
+        Facility { rooms }
+    }
+
+    /// The rooms through a door from `room`
+    pub fn neighbors(&self, room: Point) -> impl Iterator<Item = Point> + '_ {
+        self.rooms
+            .get(&room)
+            .into_iter()
+            .flatten()
+            .map(move |direction| room.step(*direction))
+    }
+
+    /// The fewest doors it takes to reach each room from the start, and the way there
+    pub fn distances(&self) -> Paths<Point, usize> {
+        bfs(Point::ORIGIN, |room| {
+            self.neighbors(*room).collect::<Vec<_>>()
+        })
+    }
+
+    /// The map drawn as the puzzle draws it: each room a `.` (or an `X` for the start) with a
+    /// wall or door on every side, where `#` is a wall, and `|` and `-` are doors
+    pub fn render(&self) -> String {
+        let min_x = self.rooms.keys().map(|room| room.x).min().unwrap_or(0);
+        let min_y = self.rooms.keys().map(|room| room.y).min().unwrap_or(0);
+        let max_x = self.rooms.keys().map(|room| room.x).max().unwrap_or(0);
+        let max_y = self.rooms.keys().map(|room| room.y).max().unwrap_or(0);
+
+        let width = 2 * (max_x - min_x + 1) as usize + 1;
+        let height = 2 * (max_y - min_y + 1) as usize + 1;
+        let mut cells = vec![vec!['#'; width]; height];
+        for (room, doors) in &self.rooms {
+            let cell = Point::new(2 * (room.x - min_x) + 1, 2 * (room.y - min_y) + 1);
+            cells[cell.y as usize][cell.x as usize] =
+                if *room == Point::ORIGIN { 'X' } else { '.' };
+            for direction in doors {
+                let door = cell.step(*direction);
+                cells[door.y as usize][door.x as usize] = match direction {
+                    Direction::Left | Direction::Right => '|',
+                    Direction::Up | Direction::Down => '-',
+                };
+            }
+        }
+
+        cells
+            .iter()
+            .map(|row| row.iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// The routes the directions' regular expression matches, as arenas of the steps and branches of
+/// every route in it
+struct Directions {
+    parts: Arena<Part>,
+    routes: Arena<Route>,
+    whole: Route,
+}
+
+/// A run of steps and branches, taken one after another
+#[derive(Debug, Clone, Copy)]
+struct Route {
+    parts: Span<Part>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Part {
+    Step(Direction),
+    /// A choice of the routes to take
+    Branch(Span<Route>),
+}
+
+/// A branch being read, and the route it's in
+struct OpenBranch {
+    /// Where the parts of the route the branch is in start on the stack of parts
+    parts_start: usize,
+    /// Where the branch's routes start on the stack of routes
+    routes_start: usize,
+}
+
+impl Directions {
+    /// Reads the directions without recursing, gathering the parts of every route being read on a
+    /// stack they share, and the routes of every branch being read on another
+    fn parse(input: &str) -> Result<Directions, Error> {
+        let inner = input
+            .strip_prefix('^')
+            .and_then(|input| input.strip_suffix('$'))
+            .ok_or_else(|| Error::Input("isn't between ^ and $".to_string()))?;
+
+        let mut parts = Arena::with_capacity(inner.len());
+        let mut routes = Arena::new();
+        let mut part_stack = Vec::new();
+        let mut route_stack = Vec::new();
+        let mut open_branches: Vec<OpenBranch> = Vec::new();
+        let mut parts_start = 0;
+
+        let not_open = |c| Error::Input(format!("has a {} outside of any branch", c));
+        for c in inner.chars() {
+            match c {
+                'N' | 'E' | 'S' | 'W' => part_stack.push(Part::Step(match c {
+                    'N' => Direction::Up,
+                    'E' => Direction::Right,
+                    'S' => Direction::Down,
+                    _ => Direction::Left,
+                })),
+                '(' => {
+                    open_branches.push(OpenBranch {
+                        parts_start,
+                        routes_start: route_stack.len(),
+                    });
+                    parts_start = part_stack.len();
+                }
+                '|' => {
+                    if open_branches.is_empty() {
+                        return Err(not_open(c));
+                    }
+                    route_stack.push(Route {
+                        parts: parts.alloc_extend(part_stack.drain(parts_start..)),
+                    });
+                }
+                ')' => {
+                    let branch = open_branches.pop().ok_or_else(|| not_open(c))?;
+                    route_stack.push(Route {
+                        parts: parts.alloc_extend(part_stack.drain(parts_start..)),
+                    });
+                    part_stack.push(Part::Branch(
+                        routes.alloc_extend(route_stack.drain(branch.routes_start..)),
+                    ));
+                    parts_start = branch.parts_start;
+                }
+                _ => {
+                    return Err(Error::Input(format!(
+                        "has {:?}, which isn't a direction or part of a branch",
+                        c
+                    )))
+                }
+            }
+        }
+
+        if !open_branches.is_empty() {
+            return Err(Error::Input("has a branch which isn't closed".to_string()));
+        }
+
+        let whole = Route {
+            parts: parts.alloc_extend(part_stack.drain(..)),
+        };
+        Ok(Directions {
+            parts,
+            routes,
+            whole,
+        })
+    }
+
+    /// The doors the routes go through, as the room on either side of each and the direction the
+    /// door is in from it
+    fn doors(&self) -> HashSet<(Point, Direction)> {
+        let mut doors = HashSet::new();
+        self.walk(self.whole, HashSet::from([Point::ORIGIN]), &mut doors);
+        doors
+    }
+
+    /// The rooms the `route` can end up in when it's started from any of the rooms `from`, adding
+    /// the doors it goes through
+    fn walk(
+        &self,
+        route: Route,
+        from: HashSet<Point>,
+        doors: &mut HashSet<(Point, Direction)>,
+    ) -> HashSet<Point> {
+        let mut rooms = from;
+        for part in &self.parts[route.parts] {
+            rooms = match part {
+                Part::Step(direction) => rooms
+                    .into_iter()
+                    .map(|room| {
+                        let next = room.step(*direction);
+                        doors.insert((room, *direction));
+                        doors.insert((next, direction.reverse()));
+                        next
+                    })
+                    .collect(),
+                Part::Branch(options) => self.routes[*options]
+                    .iter()
+                    .flat_map(|option| self.walk(*option, rooms.clone(), doors))
+                    .collect(),
+            };
+        }
+
+        rooms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    examples!(2018, 20, Day20);
+
+    #[test]
+    fn test_map() {
+        let (rendered, distances) = map("^ENWWW(NEEE|SSE(EE|N))$").unwrap();
+        assert_eq!(
+            rendered,
+            "\
+#########
+#.|.|.|.#
+#-#######
+#.|.|.|.#
+#-#####-#
+#.#.#X|.#
+#-#-#####
+#.|.|.|.#
+#########"
+        );
+
+        assert_eq!(distances.len(), 16);
+        assert_eq!(distances.cost(&Point::new(1, 0)), Some(1));
+        assert_eq!(distances.cost(&Point::new(1, -2)), Some(9));
+        assert_eq!(distances.cost(&Point::new(1, 1)), Some(10));
+        assert_eq!(distances.cost(&Point::new(2, 0)), None);
+        assert_eq!(
+            distances.path_to(&Point::new(-1, -1)),
+            Some(vec![
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(1, -1),
+                Point::new(0, -1),
+                Point::new(-1, -1),
+            ])
+        );
+    }
+}
@@ -0,0 +1,356 @@
+//! --- Day 22: Mode Maze ---
+//!
+//! This is it, your final stop: the year -483. It's snowing and dark outside; the only light you can see is coming from a small cottage in the distance. You make your way there and knock on the door.
+
+use crate::answer::Answer;
+use crate::common::grid::Grid2D;
+use crate::common::heap::IndexedHeap;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+/// The minutes it takes to move to an adjacent region, and to switch tools
+const MOVE_MINUTES: u32 = 1;
+const SWITCH_MINUTES: u32 = 7;
+
+pub struct Day22;
+
+impl Solver for Day22 {
+    type Parsed = Scan;
+
+    fn parse(input: &str) -> Result<Scan, Error> {
+        let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+        let depth = lines
+            .next()
+            .and_then(|line| line.strip_prefix("depth:"))
+            .and_then(|depth| depth.trim().parse().ok());
+        let target = lines
+            .next()
+            .and_then(|line| line.strip_prefix("target:"))
+            .and_then(|target| target.split_once(','))
+            .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)));
+
+        match (depth, target, lines.next()) {
+            (Some(depth), Some(target), None) => Ok(Scan { depth, target }),
+            _ => Err(Error::Input(
+                "isn't a depth and target like \"depth: 510\" and \"target: 10,10\"".to_string(),
+            )),
+        }
+    }
+
+    /// A portly man with a large, white beard and a red coat appears at the door. He has a friend who is trapped in a cave, and he needs your help to find him. The cave is divided into square regions which are either dominantly rocky, narrow, or wet (called its type). Each region occupies exactly one coordinate in X,Y format where X and Y are integers and zero or greater. (Adjacent regions can be the same type.)
+    ///
+    /// The scan (your puzzle input) is not very detailed: it only reveals the depth of the cave system and the coordinates of the target. However, it does not reveal the type of each region. The mouth of the cave is at 0,0.
+    ///
+    /// The type of each region can be determined by its erosion level, which depends on its geologic index:
+    ///
+    /// - The region at 0,0 (the mouth of the cave) has a geologic index of 0.
+    /// - The region at the coordinates of the target has a geologic index of 0.
+    /// - If the region's Y coordinate is 0, the geologic index is its X coordinate times 16807.
+    /// - If the region's X coordinate is 0, the geologic index is its Y coordinate times 48271.
+    /// - Otherwise, the region's geologic index is the result of multiplying the erosion levels of the regions at X-1,Y and X,Y-1.
+    ///
+    /// A region's erosion level is its geologic index plus the cave system's depth, all modulo 20183. Then:
+    ///
+    /// - If the erosion level modulo 3 is 0, the region's type is rocky.
+    /// - If the erosion level modulo 3 is 1, the region's type is wet.
+    /// - If the erosion level modulo 3 is 2, the region's type is narrow.
+    ///
+    /// Before you go in, you should determine the risk level of the area. For the rectangle that has a top-left corner of region 0,0 and a bottom-right corner of the region containing the target, add up the risk level of each individual region: 0 for rocky regions, 1 for wet regions, and 2 for narrow regions.
+    ///
+    /// What is the total risk level for the smallest rectangle that includes 0,0 and the target's coordinates?
+    fn part1(scan: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let mut cave = Cave::new(scan);
+        let (target_x, target_y) = scan.target;
+
+        let mut risk_level = 0;
+        for y in 0..=target_y {
+            for x in 0..=target_x {
+                risk_level += cave.region((x, y)) as u64;
+            }
+        }
+
+        Ok(Answer::from(risk_level))
+    }
+
+    /// Okay, it's time to go rescue the man's friend.
+    ///
+    /// As you leave, he hands you some tools: a torch and some climbing gear. You can't equip both tools at once, but you can choose to use neither.
+    ///
+    /// Tools can only be used in certain regions:
+    ///
+    /// - In rocky regions, you can use the climbing gear or the torch. You cannot use neither (you'll likely slip and fall).
+    /// - In wet regions, you can use the climbing gear or neither tool. You cannot use the torch (if it gets wet, you won't have a light source).
+    /// - In narrow regions, you can use the torch or neither tool. You cannot use the climbing gear (it's too bulky to fit).
+    ///
+    /// You start at 0,0 (the mouth of the cave) with the torch equipped and must reach the target coordinates as quickly as possible. The regions with negative X or Y are solid rock and cannot be traversed. The fastest route might involve entering regions beyond the X or Y coordinate of the target.
+    ///
+    /// You can move to an adjacent region (up, down, left, or right; never diagonally) if your currently equipped tool allows you to enter that region. Moving to an adjacent region takes one minute. (For example, if you have the torch equipped, you can move between rocky and narrow regions, but cannot enter wet regions.)
+    ///
+    /// You can change your currently equipped tool or put both away if your new equipment would be valid for your current region. Switching to using the climbing gear, torch, or neither always takes seven minutes, regardless of which tools you start with. (For example, if you are in a rocky region, you can switch from the torch to the climbing gear, but you cannot switch to neither.)
+    ///
+    /// Finally, once you reach the target, you need the torch equipped before you can find him in the dark. The target is always in a rocky region, so if you arrive there with climbing gear equipped, you will need to spend seven minutes switching to your torch.
+    ///
+    /// What is the fewest number of minutes you can take to reach the target?
+    fn part2(scan: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(rescue_minutes(&mut Cave::new(scan))?))
+    }
+}
+
+/// The depth of the cave system and the coordinates of the target
+pub struct Scan {
+    depth: u64,
+    target: (usize, usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Region {
+    Rocky = 0,
+    Wet = 1,
+    Narrow = 2,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tool {
+    Neither = 0,
+    Torch = 1,
+    ClimbingGear = 2,
+}
+
+const TOOLS: [Tool; 3] = [Tool::Neither, Tool::Torch, Tool::ClimbingGear];
+
+impl Region {
+    fn allows(self, tool: Tool) -> bool {
+        // each type of region rules out the one tool numbered the same
+        self as u8 != tool as u8
+    }
+}
+
+/// The erosion levels of the regions of the cave, worked out as far as they've been asked for
+struct Cave {
+    depth: u64,
+    target: (usize, usize),
+    erosion_levels: Grid2D<u64>,
+}
+
+impl Cave {
+    fn new(scan: &Scan) -> Self {
+        let mut cave = Self {
+            depth: scan.depth,
+            target: scan.target,
+            erosion_levels: Grid2D::new(0, 0, 0),
+        };
+        cave.grow(scan.target.0 + 1, scan.target.1 + 1);
+        cave
+    }
+
+    /// The type of the region at `position`, working out the erosion levels out to it first if
+    /// they haven't been yet
+    fn region(&mut self, (x, y): (usize, usize)) -> Region {
+        let (width, height) = (self.erosion_levels.width(), self.erosion_levels.height());
+        if x >= width || y >= height {
+            // grown by half again, so that a search edging outwards doesn't regrow every step
+            let grown = |size: usize, needed: usize| {
+                if needed < size {
+                    size
+                } else {
+                    (size + size / 2).max(needed + 1)
+                }
+            };
+            self.grow(grown(width, x), grown(height, y));
+        }
+
+        match self.erosion_levels[(x, y)] % 3 {
+            0 => Region::Rocky,
+            1 => Region::Wet,
+            _ => Region::Narrow,
+        }
+    }
+
+    /// Works out the erosion levels of the regions up to `width` by `height`, keeping those
+    /// already worked out
+    fn grow(&mut self, width: usize, height: usize) {
+        let mut erosion_levels = Grid2D::new(width, height, 0);
+        for y in 0..height {
+            for x in 0..width {
+                erosion_levels[(x, y)] = match self.erosion_levels.get((x, y)) {
+                    Some(erosion_level) => *erosion_level,
+                    None => {
+                        let geologic_index = match (x, y) {
+                            (0, 0) => 0,
+                            position if position == self.target => 0,
+                            (x, 0) => x as u64 * 16807,
+                            (0, y) => y as u64 * 48271,
+                            (x, y) => erosion_levels[(x - 1, y)] * erosion_levels[(x, y - 1)],
+                        };
+                        (geologic_index + self.depth) % 20183
+                    }
+                };
+            }
+        }
+
+        self.erosion_levels = erosion_levels;
+    }
+}
+
+/// How far right and down the quickest way to the target could possibly go, given a way to it
+/// which takes `upper_bound` minutes
+///
+/// Reaching `x, y` takes at least `x + y` minutes, and getting from there to the target takes at
+/// least the distance between them, so a region is only worth going to if those add up to no more
+/// than `upper_bound`. Going past the target to the right means going back again, and going down
+/// to any row on the way there means getting from there to the target's row.
+fn search_bounds((target_x, target_y): (usize, usize), upper_bound: u32) -> (usize, usize) {
+    let upper_bound = upper_bound as usize;
+    (
+        ((upper_bound + target_x).saturating_sub(target_y) / 2).max(target_x),
+        ((upper_bound + target_y).saturating_sub(target_x) / 2).max(target_y),
+    )
+}
+
+/// The fewest minutes it takes to get from the mouth of the cave to the target, holding the torch,
+/// or an error when the torch can't be held there
+fn rescue_minutes(cave: &mut Cave) -> Result<u32, Error> {
+    // any two types of region have a tool in common, so the target can always be reached without
+    // leaving the rectangle up to it (unless the depth makes it a region where the torch can't be
+    // held), and that way there bounds how far out a quicker way goes
+    let upper_bound = quickest_within(cave, cave.target, None).ok_or_else(|| {
+        Error::NoAnswer("the target isn't rocky, so the torch can't be held there".to_string())
+    })?;
+    let bounds = search_bounds(cave.target, upper_bound);
+
+    Ok(quickest_within(cave, bounds, Some(upper_bound)).unwrap_or(upper_bound))
+}
+
+/// The fewest minutes it takes to get to the target holding the torch without going further right
+/// or down than `(max_x, max_y)`, searching only the states from which the target could be reached
+/// within `upper_bound` minutes if it's given
+///
+/// The states come out of the queue in order of their minutes plus their distance from the target,
+/// which never overestimates the minutes left, so the target still comes out first by the quickest
+/// way there, but having searched less of the cave than it would in order of minutes alone. Of
+/// those tied, the closest to the target come out first.
+fn quickest_within(
+    cave: &mut Cave,
+    (max_x, max_y): (usize, usize),
+    upper_bound: Option<u32>,
+) -> Option<u32> {
+    // each state of the search is a region with the tool held there, numbered densely
+    let width = max_x + 1;
+    let states = width * (max_y + 1) * 3;
+    let key = |(x, y): (usize, usize), tool: Tool| (y * width + x) * 3 + tool as usize;
+    let target = key(cave.target, Tool::Torch);
+    let (target_x, target_y) = cave.target;
+    let distance = |(x, y): (usize, usize)| (x.abs_diff(target_x) + y.abs_diff(target_y)) as u32;
+
+    let mut queue = IndexedHeap::with_capacity(states);
+    let mut quickest = vec![u32::MAX; states];
+    let mut reach = |queue: &mut IndexedHeap<(u32, u32)>, position, tool, minutes| {
+        let state = key(position, tool);
+        let distance = distance(position);
+        let estimate = minutes + distance;
+        if minutes < quickest[state] && upper_bound.is_none_or(|bound| estimate <= bound) {
+            quickest[state] = minutes;
+            queue.push_or_decrease(state, (estimate, distance));
+        }
+    };
+    reach(&mut queue, (0, 0), Tool::Torch, 0);
+
+    while let Some((state, (estimate, distance))) = queue.pop() {
+        let (position, tool) = ((state / 3 % width, state / 3 / width), TOOLS[state % 3]);
+        let minutes = estimate - distance;
+        if state == target {
+            return Some(minutes);
+        }
+
+        let region = cave.region(position);
+        for other in TOOLS {
+            if other != tool && region.allows(other) {
+                reach(&mut queue, position, other, minutes + SWITCH_MINUTES);
+            }
+        }
+
+        let (x, y) = position;
+        let neighbors = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for neighbor in neighbors {
+            if neighbor.0 <= max_x && neighbor.1 <= max_y && cave.region(neighbor).allows(tool) {
+                reach(&mut queue, neighbor, tool, minutes + MOVE_MINUTES);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    examples!(2018, 22, Day22);
+
+    #[test]
+    fn test_cave() {
+        let scan = Scan {
+            depth: 510,
+            target: (10, 10),
+        };
+        let mut cave = Cave::new(&scan);
+        let row = |cave: &mut Cave, y| {
+            (0..16)
+                .map(|x| match cave.region((x, y)) {
+                    Region::Rocky => '.',
+                    Region::Wet => '=',
+                    Region::Narrow => '|',
+                })
+                .collect::<String>()
+        };
+
+        // the regions past the target are worked out as they're asked for, and match those worked
+        // out from the start
+        assert_eq!(row(&mut cave, 0), ".=.|=.|.|=.|=|=.");
+        assert_eq!(row(&mut cave, 1), ".|=|=|||..|.=...");
+        assert_eq!(row(&mut cave, 2), ".==|....||=..|==");
+        assert_eq!(cave.region((10, 10)), Region::Rocky);
+        assert!(cave.erosion_levels.width() >= 16);
+
+        // the quickest way there goes past the target, so that staying within the rectangle up to
+        // it takes longer, and the bounds that gives are far enough out to find the quickest way
+        let upper_bound = quickest_within(&mut cave, (10, 10), None).unwrap();
+        assert!(upper_bound > 45);
+        let bounds = search_bounds((10, 10), upper_bound);
+        assert!(bounds.0 > 10 && bounds.1 > 10);
+        assert_eq!(
+            quickest_within(&mut cave, bounds, Some(upper_bound)),
+            Some(45)
+        );
+        assert_eq!(quickest_within(&mut cave, bounds, Some(44)), None);
+        assert_eq!(rescue_minutes(&mut cave).unwrap(), 45);
+
+        assert_eq!(search_bounds((10, 10), 45), (22, 22));
+        assert_eq!(search_bounds((10, 785), 1000), (112, 887));
+        assert_eq!(search_bounds((10, 10), 0), (10, 10));
+    }
+
+    #[test]
+    fn test_invalid_scans() {
+        let scan = Day22::parse("depth: 1\ntarget: 1,0").unwrap();
+        assert!(matches!(
+            rescue_minutes(&mut Cave::new(&scan)),
+            Err(Error::NoAnswer(_))
+        ));
+
+        for input in [
+            "510 10 10",
+            "depth: 510\ntarget: 10",
+            "target: 10,10\ndepth: 510",
+        ] {
+            assert!(Day22::parse(input).is_err(), "{:?}", input);
+        }
+    }
+}
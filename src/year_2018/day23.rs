@@ -0,0 +1,206 @@
+//! --- Day 23: Experimental Emergency Teleportation ---
+//!
+//! Using your torch to search the darkness of the rocky cavern, you finally locate the man's friend: a small reindeer.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::answer::Answer;
+use crate::common::geometry::{Aabb3, Point3};
+use crate::common::parse::ints_in;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day23;
+
+impl Solver for Day23 {
+    type Parsed = Vec<Nanobot>;
+
+    fn parse(input: &str) -> Result<Vec<Nanobot>, Error> {
+        let nanobots: Vec<Nanobot> = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| match ints_in(line)[..] {
+                [x, y, z, radius] if radius >= 0 => Ok(Nanobot {
+                    position: Point3::new(x, y, z),
+                    radius,
+                }),
+                _ => Err(Error::line(
+                    i,
+                    line,
+                    "isn't a nanobot like pos=<0,0,0>, r=4",
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        if nanobots.is_empty() {
+            return Err(Error::Input("has no nanobots".to_string()));
+        }
+        Ok(nanobots)
+    }
+
+    /// The reindeer's symptoms are getting worse, and neither you nor the white-bearded man have a solution. At least the reindeer has a warm place to rest: a small bed near where you're sitting.
+    ///
+    /// As you reach down, the reindeer looks up at you, accidentally bumping a button on your wrist-mounted device with its nose in the process - a button labeled "help".
+    ///
+    /// "Hello, and welcome to the Time Travel Support Hotline! If you are lost in time and space, press 1. If you are trapped in a time paradox, press 2. If you need help caring for a sick reindeer, press 3. If you--"
+    ///
+    /// Beep.
+    ///
+    /// A few seconds later, you hear a new voice. "Hello; please state the nature of your reindeer." You try to describe the situation.
+    ///
+    /// "Just a moment, I think I can remotely run a diagnostic scan." A beam of light projects from the device and sweeps over the reindeer a few times.
+    ///
+    /// "Okay, it looks like your reindeer is very low on magical energy; it should fully recover if we can fix that. Let me check your timeline for a source.... Got one. There's actually a powerful source of magical energy about 1000 years forward from you, and at roughly your position, too! It looks like... hot chocolate? Anyway, you should be able to travel there to pick some up; just don't forget a mug! Is there anything else I can help you with today?"
+    ///
+    /// You explain that your device isn't capable of going forward in time. "I... see. That's tricky. Well, according to this information, your device should have the necessary hardware to open a small portal and send some hot chocolate back to you. You'll need a list of fixed points in spacetime; I'm transmitting it to you now."
+    ///
+    /// "You just need to lock on to a point, and then you can teleport it anywhere you want. However, the fixed points are unstable; you can't teleport anything that's too far from the nearest fixed point."
+    ///
+    /// Each nanobot can transmit signals to any integer coordinate which is a distance away from it less than or equal to its signal radius (as measured by Manhattan distance). Coordinates a distance away of less than or equal to a nanobot's signal radius are said to be in range of that nanobot.
+    ///
+    /// Before you start the teleportation process, you should determine which nanobot is the strongest (that is, which has the largest signal radius) and then, for that nanobot, the total number of nanobots that are in range of it, including itself.
+    ///
+    /// Find the nanobot with the largest signal radius. How many nanobots are in range of its signals?
+    fn part1(nanobots: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let strongest = nanobots
+            .iter()
+            .max_by_key(|nanobot| nanobot.radius)
+            .ok_or_else(|| Error::NoAnswer("there are no nanobots".to_string()))?;
+
+        Ok(Answer::from(
+            nanobots
+                .iter()
+                .filter(|nanobot| strongest.in_range(nanobot.position))
+                .count(),
+        ))
+    }
+
+    /// Now, you just need to figure out where to position yourself so that you're actually teleported when the nanobots activate.
+    ///
+    /// To increase the probability of success, you need to find the coordinate which puts you in range of the largest number of nanobots. If there are multiple, choose one closest to your position (0,0,0, measured by manhattan distance).
+    ///
+    /// Find the coordinates that are in range of the largest number of nanobots. What is the shortest manhattan distance between any of those points and 0,0,0?
+    fn part2(nanobots: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        best_position(nanobots)
+            .map(|position| Answer::from(position.manhattan_distance(Point3::ORIGIN)))
+            .ok_or_else(|| Error::NoAnswer("there are no nanobots".to_string()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nanobot {
+    position: Point3,
+    radius: i64,
+}
+
+impl Nanobot {
+    fn in_range(&self, point: Point3) -> bool {
+        self.position.manhattan_distance(point) <= self.radius
+    }
+}
+
+/// The point in range of the most nanobots, and the closest to the origin of those
+///
+/// Boxes of space come out of the queue in order of how many nanobots are in range of any of their
+/// points, which is at least as many as are in range of each point in them, so the first box to
+/// come out as a single point is in range of as many as any point can be. Of those tied, the boxes
+/// nearest the origin come out first (so that point is also the closest of the best to it), and
+/// then the smallest, which are split soonest into points.
+///
+/// Every point is as close to each nanobot and to the origin once it's moved inside the box around
+/// them all, so the search starts from that box.
+fn best_position(nanobots: &[Nanobot]) -> Option<Point3> {
+    let bounds = Aabb3::around(
+        nanobots
+            .iter()
+            .map(|nanobot| nanobot.position)
+            .chain([Point3::ORIGIN]),
+    )?;
+
+    let entry = |bounds: Aabb3| {
+        let in_range = nanobots
+            .iter()
+            .filter(|nanobot| bounds.distance_to(nanobot.position) <= nanobot.radius)
+            .count();
+        let size = bounds.max - bounds.min;
+        (
+            in_range,
+            Reverse(bounds.distance_to(Point3::ORIGIN)),
+            Reverse(size.x + size.y + size.z),
+            // the corners, so that the boxes left tied come out in a set order
+            Reverse((
+                (bounds.min.x, bounds.min.y, bounds.min.z),
+                (bounds.max.x, bounds.max.y, bounds.max.z),
+            )),
+        )
+    };
+
+    let mut queue = BinaryHeap::from([entry(bounds)]);
+    while let Some((_, _, _, Reverse((min, max)))) = queue.pop() {
+        let bounds = Aabb3::new(Point3::from(min), Point3::from(max));
+        if bounds.is_point() {
+            return Some(bounds.min);
+        }
+        queue.extend(bounds.octants().into_iter().map(entry));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::rng::Rng;
+    use crate::examples::examples;
+
+    examples!(2018, 23, Day23);
+
+    #[test]
+    fn test_best_position() {
+        // small made-up swarms, checked against every point of the box around them and the origin
+        let mut rng = Rng::new(23);
+        let mut random = |below: i64| rng.between(0, below - 1);
+
+        for _ in 0..20 {
+            let nanobots: Vec<Nanobot> = (0..1 + random(12))
+                .map(|_| Nanobot {
+                    position: Point3::new(random(17) - 4, random(17) - 4, random(17) - 4),
+                    radius: random(9),
+                })
+                .collect();
+
+            let bounds = Aabb3::around(
+                nanobots
+                    .iter()
+                    .map(|nanobot| nanobot.position)
+                    .chain([Point3::ORIGIN]),
+            )
+            .unwrap();
+            let mut brute_force = (0, 0);
+            for x in bounds.min.x..=bounds.max.x {
+                for y in bounds.min.y..=bounds.max.y {
+                    for z in bounds.min.z..=bounds.max.z {
+                        let point = Point3::new(x, y, z);
+                        let in_range = nanobots.iter().filter(|n| n.in_range(point)).count();
+                        let distance = point.manhattan_distance(Point3::ORIGIN);
+                        if (in_range, Reverse(distance)) > (brute_force.0, Reverse(brute_force.1)) {
+                            brute_force = (in_range, distance);
+                        }
+                    }
+                }
+            }
+
+            let best = best_position(&nanobots).unwrap();
+            let in_range = nanobots.iter().filter(|n| n.in_range(best)).count();
+            assert_eq!(
+                (in_range, best.manhattan_distance(Point3::ORIGIN)),
+                brute_force,
+                "{:?}",
+                nanobots
+            );
+        }
+    }
+}
@@ -0,0 +1,366 @@
+//! --- Day 24: Immune System Simulator 20XX ---
+//!
+//! After a weird buzzing noise, you appear back at the man's cottage. He seems relieved to see his friend, but quickly notices that the little reindeer caught some kind of cold while out exploring.
+
+use crate::answer::Answer;
+use crate::common::parse::Pattern;
+use crate::common::search::binary_search_min;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+use std::cmp::Reverse;
+use std::sync::LazyLock;
+
+pub struct Day24;
+
+impl Solver for Day24 {
+    type Parsed = Battle;
+
+    fn parse(input: &str) -> Result<Battle, Error> {
+        let mut groups = Vec::new();
+        let mut army = None;
+        for (i, line) in input.lines().enumerate() {
+            match line.trim() {
+                "" => continue,
+                "Immune System:" => army = Some(Army::ImmuneSystem),
+                "Infection:" => army = Some(Army::Infection),
+                line => {
+                    let army = army.ok_or_else(|| {
+                        Error::line(i, line, "comes before the name of either army")
+                    })?;
+                    let number = groups
+                        .iter()
+                        .filter(|group: &&Group| group.army == army)
+                        .count()
+                        + 1;
+                    groups.push(
+                        Group::parse(army, number, line)
+                            .map_err(|reason| Error::line(i, line, reason))?,
+                    );
+                }
+            }
+        }
+
+        Ok(Battle { groups })
+    }
+
+    /// The immune system and the infection each have an army made up of several groups; each group consists of one or more identical units. The armies repeatedly fight until only one army has units remaining.
+    ///
+    /// Units within a group all have the same hit points (amount of damage a unit can take before it is destroyed), attack damage (the amount of damage each unit deals), an attack type, an initiative (higher initiative units attack first and win ties), and sometimes weaknesses or immunities.
+    ///
+    /// Each group also has an effective power: the number of units in that group multiplied by their attack damage.
+    ///
+    /// Each fight consists of two phases: target selection and attacking.
+    ///
+    /// During the target selection phase, each group attempts to choose one target. In decreasing order of effective power, groups choose their targets; in a tie, the group with the higher initiative chooses first. The attacking group chooses to target the group in the enemy army to which it would deal the most damage (after accounting for weaknesses and immunities, but not accounting for whether the defending group has enough units to actually receive all of that damage).
+    ///
+    /// If an attacking group is considering two defending groups to which it would deal equal damage, it chooses to target the defending group with the largest effective power; if there is still a tie, it chooses the defending group with the highest initiative. If it cannot deal any defending groups damage, it does not choose a target. Defending groups can only be chosen as a target by one attacking group.
+    ///
+    /// During the attacking phase, each group deals damage to the target it selected, if any. Groups attack in decreasing order of initiative, regardless of whether they are part of the infection or the immune system. (If a group contains no units, it cannot attack.)
+    ///
+    /// The damage an attacking group deals to a defending group depends on the attacking group's attack type and the defending group's immunities and weaknesses. By default, an attacking group would deal damage equal to its effective power to the defending group. However, if the defending group is immune to the attacking group's attack type, the defending group instead takes no damage; if the defending group is weak to the attacking group's attack type, the defending group instead takes double damage.
+    ///
+    /// The defending group only loses whole units from damage; damage is always dealt in such a way that it kills the most units possible, and any remaining damage to a unit that does not immediately kill it is ignored.
+    ///
+    /// After the fight is over, if both armies still contain units, a new fight begins; combat only ends once one army has lost all of its units.
+    ///
+    /// In the example above, the winning army ends up with 4434 + 782 = 5216 units.
+    ///
+    /// You scan the reindeer's condition (your puzzle input); the white-bearded man looks nervous. As it stands now, how many units would the winning army have?
+    fn part1(battle: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let mut battle = battle.clone();
+
+        match battle.fight() {
+            Some(_) => Ok(Answer::from(battle.units())),
+            None => Err(Error::NoAnswer(
+                "neither army can kill the last of the other".to_string(),
+            )),
+        }
+    }
+
+    /// Things aren't looking good for the reindeer. The man asks whether more milk and cookies would help you think.
+    ///
+    /// If only you could give the reindeer's immune system a boost, you might be able to change the outcome of the combat.
+    ///
+    /// A boost is an integer increase in immune system units' attack damage.
+    ///
+    /// How many units does the immune system have left after getting the smallest boost it needs to win?
+    fn part2(battle: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let boosted = |boost| {
+            let mut battle = battle.boosted(boost);
+            let winner = battle.fight();
+            (winner, battle)
+        };
+
+        // enough for any unit of the immune system to kill every unit of the infection at once,
+        // unless they're immune to it
+        let most = battle
+            .groups
+            .iter()
+            .filter(|group| group.army == Army::Infection)
+            .map(|group| group.units * group.hit_points)
+            .sum();
+
+        // stalemates count as the immune system losing, and a bigger boost is taken never to turn
+        // a win back into a loss, as it doesn't for the puzzle's inputs
+        let boost = binary_search_min(0, most, |boost| {
+            boosted(boost).0 == Some(Army::ImmuneSystem)
+        })
+        .ok_or_else(|| {
+            Error::NoAnswer("the immune system can't win however big its boost".to_string())
+        })?;
+
+        Ok(Answer::from(boosted(boost).1.units()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Army {
+    ImmuneSystem,
+    Infection,
+}
+
+/// A group of identical units
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    pub army: Army,
+    /// Which group of its army it is, counting from 1 as the puzzle does
+    pub number: usize,
+    pub units: i64,
+    hit_points: i64,
+    attack_damage: i64,
+    attack_type: String,
+    initiative: i64,
+    weaknesses: Vec<String>,
+    immunities: Vec<String>,
+}
+
+static GROUP: LazyLock<Pattern> = LazyLock::new(|| {
+    Pattern::new(
+        r"^(?P<units>\d+) units each with (?P<hit_points>\d+) hit points (?:\((?P<modifiers>[^)]*)\) )?with an attack that does (?P<damage>\d+) (?P<type>\w+) damage at initiative (?P<initiative>\d+)$",
+        "a group like 17 units each with 5390 hit points (weak to radiation) with an attack that does 4507 fire damage at initiative 2",
+    )
+});
+
+impl Group {
+    fn parse(army: Army, number: usize, line: &str) -> Result<Group, String> {
+        let group = GROUP.captures(line)?;
+
+        let mut weaknesses = Vec::new();
+        let mut immunities = Vec::new();
+        for modifier in group
+            .str("modifiers")
+            .into_iter()
+            .flat_map(|m| m.split("; "))
+        {
+            let (list, types) = if let Some(types) = modifier.strip_prefix("weak to ") {
+                (&mut weaknesses, types)
+            } else if let Some(types) = modifier.strip_prefix("immune to ") {
+                (&mut immunities, types)
+            } else {
+                return Err(format!(
+                    "has {:?}, which isn't a weakness or an immunity",
+                    modifier
+                ));
+            };
+            list.extend(types.split(", ").map(str::to_string));
+        }
+
+        Ok(Group {
+            army,
+            number,
+            units: group.get("units")?,
+            hit_points: group.get("hit_points")?,
+            attack_damage: group.get("damage")?,
+            attack_type: group.get("type")?,
+            initiative: group.get("initiative")?,
+            weaknesses,
+            immunities,
+        })
+    }
+
+    pub fn effective_power(&self) -> i64 {
+        self.units * self.attack_damage
+    }
+
+    /// The damage an attack by the group would deal to `defender`
+    fn damage_to(&self, defender: &Group) -> i64 {
+        if defender.immunities.contains(&self.attack_type) {
+            0
+        } else if defender.weaknesses.contains(&self.attack_type) {
+            2 * self.effective_power()
+        } else {
+            self.effective_power()
+        }
+    }
+}
+
+/// The armies of the reindeer's immune system and of the infection, as they fight
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Battle {
+    /// The groups which have units left, in the order they were listed
+    groups: Vec<Group>,
+}
+
+/// What happened in a round of the battle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Round {
+    /// Groups attacked, and killed this many units between them
+    Fought { units_killed: i64 },
+    /// No group killed any units, so none ever will
+    Stalemate,
+    /// The army is the only one with groups left, so there was nothing to fight
+    Won(Army),
+}
+
+impl Battle {
+    /// The battle with the attack damage of every unit of the immune system raised by `boost`
+    pub fn boosted(&self, boost: i64) -> Battle {
+        let mut battle = self.clone();
+        for group in &mut battle.groups {
+            if group.army == Army::ImmuneSystem {
+                group.attack_damage += boost;
+            }
+        }
+
+        battle
+    }
+
+    /// The groups which have units left
+    pub fn groups(&self) -> &[Group] {
+        &self.groups
+    }
+
+    /// How many units are left, in both armies
+    pub fn units(&self) -> i64 {
+        self.groups.iter().map(|group| group.units).sum()
+    }
+
+    /// The army left standing, if only one is
+    pub fn winner(&self) -> Option<Army> {
+        let army = self.groups.first()?.army;
+        self.groups
+            .iter()
+            .all(|group| group.army == army)
+            .then_some(army)
+    }
+
+    /// Fights rounds until one army wins, returning the winner, or `None` if it's a stalemate
+    pub fn fight(&mut self) -> Option<Army> {
+        loop {
+            match self.round() {
+                Round::Fought { .. } => {}
+                Round::Stalemate => return None,
+                Round::Won(army) => return Some(army),
+            }
+        }
+    }
+
+    /// Fights a round, with each group choosing a target and then attacking it
+    pub fn round(&mut self) -> Round {
+        if let Some(army) = self.winner() {
+            return Round::Won(army);
+        }
+
+        // the groups choose their targets by the most effective power first
+        let mut choosing: Vec<usize> = (0..self.groups.len()).collect();
+        choosing.sort_by_key(|i| {
+            let group = &self.groups[*i];
+            Reverse((group.effective_power(), group.initiative))
+        });
+
+        let mut targets: Vec<Option<usize>> = vec![None; self.groups.len()];
+        for attacker in choosing {
+            let group = &self.groups[attacker];
+            targets[attacker] = (0..self.groups.len())
+                .filter(|defender| {
+                    self.groups[*defender].army != group.army
+                        && !targets.contains(&Some(*defender))
+                        && group.damage_to(&self.groups[*defender]) > 0
+                })
+                .max_by_key(|defender| {
+                    let defender = &self.groups[*defender];
+                    (
+                        group.damage_to(defender),
+                        defender.effective_power(),
+                        defender.initiative,
+                    )
+                });
+        }
+
+        // then attack by the highest initiative first
+        let mut attacking: Vec<usize> = (0..self.groups.len()).collect();
+        attacking.sort_by_key(|i| Reverse(self.groups[*i].initiative));
+
+        let mut units_killed = 0;
+        for attacker in attacking {
+            let defender = match targets[attacker] {
+                Some(defender) if self.groups[attacker].units > 0 => defender,
+                _ => continue,
+            };
+
+            let damage = self.groups[attacker].damage_to(&self.groups[defender]);
+            let defender = &mut self.groups[defender];
+            let killed = (damage / defender.hit_points).min(defender.units);
+            defender.units -= killed;
+            units_killed += killed;
+        }
+
+        self.groups.retain(|group| group.units > 0);
+        if units_killed == 0 {
+            Round::Stalemate
+        } else {
+            Round::Fought { units_killed }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{examples, select};
+
+    examples!(2018, 24, Day24);
+
+    /// The number and units of each group left, by army
+    fn units(battle: &Battle) -> Vec<(Army, usize, i64)> {
+        battle
+            .groups()
+            .iter()
+            .map(|group| (group.army, group.number, group.units))
+            .collect()
+    }
+
+    #[test]
+    fn test_battle() {
+        let battle = Day24::parse(select(2018, 24, 1)[0].input).unwrap();
+        assert_eq!(battle.groups()[3].effective_power(), 4485 * 12);
+
+        let mut unboosted = battle.clone();
+        assert_eq!(
+            unboosted.round(),
+            Round::Fought {
+                units_killed: 84 + 4 + 17 + 51
+            }
+        );
+        assert_eq!(
+            units(&unboosted),
+            [
+                (Army::ImmuneSystem, 2, 905),
+                (Army::Infection, 1, 797),
+                (Army::Infection, 2, 4434),
+            ]
+        );
+
+        assert_eq!(unboosted.fight(), Some(Army::Infection));
+        assert_eq!(
+            units(&unboosted),
+            [(Army::Infection, 1, 782), (Army::Infection, 2, 4434)]
+        );
+        assert_eq!(unboosted.round(), Round::Won(Army::Infection));
+
+        let mut boosted = battle.boosted(1570);
+        assert_eq!(boosted.fight(), Some(Army::ImmuneSystem));
+        assert_eq!(units(&boosted), [(Army::ImmuneSystem, 2, 51)]);
+    }
+}
@@ -2,234 +2,218 @@
 //!
 //! You've sneaked into another supply closet - this time, it's across from the prototype suit manufacturing lab. You need to sneak inside and fix the issues with the suit, but there's a guard stationed outside the lab, so this is as close as you can safely get.
 
+use crate::answer::Answer;
+use crate::common::timestamp::{chronological_groups, split_timestamp, Timestamp};
+use crate::common::Counter;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+use std::cmp::Reverse;
 use std::collections::BTreeMap;
+use std::ops::Range;
+use std::str::FromStr;
+
+pub struct Day4;
+
+impl Solver for Day4 {
+    type Parsed = Vec<Shift>;
+
+    /// Sorts the records into the order they happened in, which their timestamps sort in, and
+    /// splits them into the shift of each guard
+    fn parse(input: &str) -> Result<Vec<Shift>, Error> {
+        let records = input
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let (timestamp, event) = split_timestamp(line)
+                    .and_then(|(timestamp, event)| Ok((timestamp, event.parse()?)))
+                    .map_err(|reason: String| Error::line(i, line, reason))?;
+                Ok((timestamp, event))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        chronological_groups(records, |event| matches!(event, Event::BeginsShift(_)))
+            .into_iter()
+            .map(Shift::from_records)
+            .collect()
+    }
 
-/// As you search the closet for anything that might help, you discover that you're not the first person to want to sneak in. Covering the walls, someone has spent an hour starting every midnight for the past few months secretly observing this guard post! They've been writing down the ID of the one guard on duty that night - the Elves seem to have decided that one guard was enough for the overnight shift - as well as when they fall asleep or wake up while at their post (your puzzle input).
-///
-/// For example, consider the following records, which have already been organized into chronological order:
-///
-/// [1518-11-01 00:00] Guard #10 begins shift
-/// [1518-11-01 00:05] falls asleep
-/// [1518-11-01 00:25] wakes up
-/// [1518-11-01 00:30] falls asleep
-/// [1518-11-01 00:55] wakes up
-/// [1518-11-01 23:58] Guard #99 begins shift
-/// [1518-11-02 00:40] falls asleep
-/// [1518-11-02 00:50] wakes up
-/// [1518-11-03 00:05] Guard #10 begins shift
-/// [1518-11-03 00:24] falls asleep
-/// [1518-11-03 00:29] wakes up
-/// [1518-11-04 00:02] Guard #99 begins shift
-/// [1518-11-04 00:36] falls asleep
-/// [1518-11-04 00:46] wakes up
-/// [1518-11-05 00:03] Guard #99 begins shift
-/// [1518-11-05 00:45] falls asleep
-/// [1518-11-05 00:55] wakes up
-///
-/// Timestamps are written using year-month-day hour:minute format. The guard falling asleep or waking up is always the one whose shift most recently started. Because all asleep/awake times are during the midnight hour (00:00 - 00:59), only the minute portion (00 - 59) is relevant for those events.
-///
-/// Visually, these records show that the guards are asleep at these times:
-///
-/// Date   ID   Minute
-///             000000000011111111112222222222333333333344444444445555555555
-///             012345678901234567890123456789012345678901234567890123456789
-/// 11-01  #10  .....####################.....#########################.....
-/// 11-02  #99  ........................................##########..........
-/// 11-03  #10  ........................#####...............................
-/// 11-04  #99  ....................................##########..............
-/// 11-05  #99  .............................................##########.....
-///
-/// The columns are Date, which shows the month-day portion of the relevant day; ID, which shows the guard on duty that day; and Minute, which shows the minutes during which the guard was asleep within the midnight hour. (The Minute column's header shows the minute's ten's digit in the first row and the one's digit in the second row.) Awake is shown as ., and asleep is shown as #.
-///
-/// Note that guards count as asleep on the minute they fall asleep, and they count as awake on the minute they wake up. For example, because Guard #10 wakes up at 00:25 on 1518-11-01, minute 25 is marked as awake.
-///
-/// If you can figure out the guard most likely to be asleep at a specific time, you might be able to trick that guard into working tonight so you can have the best chance of sneaking in. You have two strategies for choosing the best guard/minute combination.
-///
-/// Strategy 1: Find the guard that has the most minutes asleep. What minute does that guard spend asleep the most?
-///
-/// In the example above, Guard #10 spent the most minutes asleep, a total of 50 minutes (20+25+5), while Guard #99 only slept for a total of 30 minutes (10+10+10). Guard #10 was asleep most during minute 24 (on two days, whereas any other minute the guard was asleep was only seen on one day).
-///
-/// While this example listed the entries in chronological order, your entries are in the order you found them. You'll need to organize them before they can be analyzed.
-///
-/// What is the ID of the guard you chose multiplied by the minute you chose? (In the above example, the answer would be 10 * 24 = 240.)
-pub fn part1() {
-    let input = crate::common::read_stdin_to_string();
-
-    let mut sorted_input: Vec<_> = input.lines().collect();
-    sorted_input.sort();
-
-    let sleep_schedule = build_part_1_sleep_schedule(sorted_input);
-
-    let most_slept_guard = &sleep_schedule
-        .iter()
-        .fold((0, 0), |most_slept, (k, v)| {
-            if most_slept.0 < v.0 {
-                (v.0, *k)
-            } else {
-                most_slept
-            }
-        })
-        .1;
+    /// As you search the closet for anything that might help, you discover that you're not the first person to want to sneak in. Covering the walls, someone has spent an hour starting every midnight for the past few months secretly observing this guard post! They've been writing down the ID of the one guard on duty that night - the Elves seem to have decided that one guard was enough for the overnight shift - as well as when they fall asleep or wake up while at their post (your puzzle input).
+    ///
+    /// For example, consider the following records, which have already been organized into chronological order:
+    ///
+    /// [1518-11-01 00:00] Guard #10 begins shift
+    /// [1518-11-01 00:05] falls asleep
+    /// [1518-11-01 00:25] wakes up
+    /// [1518-11-01 00:30] falls asleep
+    /// [1518-11-01 00:55] wakes up
+    /// [1518-11-01 23:58] Guard #99 begins shift
+    /// [1518-11-02 00:40] falls asleep
+    /// [1518-11-02 00:50] wakes up
+    /// [1518-11-03 00:05] Guard #10 begins shift
+    /// [1518-11-03 00:24] falls asleep
+    /// [1518-11-03 00:29] wakes up
+    /// [1518-11-04 00:02] Guard #99 begins shift
+    /// [1518-11-04 00:36] falls asleep
+    /// [1518-11-04 00:46] wakes up
+    /// [1518-11-05 00:03] Guard #99 begins shift
+    /// [1518-11-05 00:45] falls asleep
+    /// [1518-11-05 00:55] wakes up
+    ///
+    /// Timestamps are written using year-month-day hour:minute format. The guard falling asleep or waking up is always the one whose shift most recently started. Because all asleep/awake times are during the midnight hour (00:00 - 00:59), only the minute portion (00 - 59) is relevant for those events.
+    ///
+    /// Visually, these records show that the guards are asleep at these times:
+    ///
+    /// Date   ID   Minute
+    ///             000000000011111111112222222222333333333344444444445555555555
+    ///             012345678901234567890123456789012345678901234567890123456789
+    /// 11-01  #10  .....####################.....#########################.....
+    /// 11-02  #99  ........................................##########..........
+    /// 11-03  #10  ........................#####...............................
+    /// 11-04  #99  ....................................##########..............
+    /// 11-05  #99  .............................................##########.....
+    ///
+    /// The columns are Date, which shows the month-day portion of the relevant day; ID, which shows the guard on duty that day; and Minute, which shows the minutes during which the guard was asleep within the midnight hour. (The Minute column's header shows the minute's ten's digit in the first row and the one's digit in the second row.) Awake is shown as ., and asleep is shown as #.
+    ///
+    /// Note that guards count as asleep on the minute they fall asleep, and they count as awake on the minute they wake up. For example, because Guard #10 wakes up at 00:25 on 1518-11-01, minute 25 is marked as awake.
+    ///
+    /// If you can figure out the guard most likely to be asleep at a specific time, you might be able to trick that guard into working tonight so you can have the best chance of sneaking in. You have two strategies for choosing the best guard/minute combination.
+    ///
+    /// Strategy 1: Find the guard that has the most minutes asleep. What minute does that guard spend asleep the most?
+    ///
+    /// In the example above, Guard #10 spent the most minutes asleep, a total of 50 minutes (20+25+5), while Guard #99 only slept for a total of 30 minutes (10+10+10). Guard #10 was asleep most during minute 24 (on two days, whereas any other minute the guard was asleep was only seen on one day).
+    ///
+    /// While this example listed the entries in chronological order, your entries are in the order you found them. You'll need to organize them before they can be analyzed.
+    ///
+    /// What is the ID of the guard you chose multiplied by the minute you chose? (In the above example, the answer would be 10 * 24 = 240.)
+    fn part1(shifts: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let minutes_asleep = minutes_asleep(shifts);
+
+        // the smallest guard of those who slept the most
+        let (most_slept_guard, slept_minutes) = minutes_asleep
+            .iter()
+            .max_by_key(|(guard, minutes)| (minutes.total(), Reverse(**guard)))
+            .ok_or_else(|| Error::NoAnswer("no guard ever falls asleep".to_string()))?;
+        let most_slept_minute = slept_minutes
+            .most_common_one()
+            .map_or(0, |(minute, _)| *minute);
 
-    let mut slept_minutes: BTreeMap<i64, i64> = BTreeMap::new();
-    for minute in &sleep_schedule[&most_slept_guard].1 {
-        *slept_minutes.entry(*minute).or_insert(0) += 1;
+        Ok(Answer::from(most_slept_guard * most_slept_minute))
     }
 
-    let most_slept_minute = slept_minutes
-        .iter()
-        .fold((0, 0), |most_slept, (k, v)| {
-            if most_slept.1 < *v {
-                (*k, *v)
-            } else {
-                most_slept
-            }
-        })
-        .0;
-
-    println!(
-        "the id of the guard ({}) multiplied by the minute ({}): {}",
-        most_slept_guard,
-        most_slept_minute,
-        most_slept_guard * most_slept_minute
-    );
+    /// Strategy 2: Of all guards, which guard is most frequently asleep on the same minute?
+    ///
+    /// In the example above, Guard #99 spent minute 45 asleep more than any other guard or minute - three times in total. (In all other cases, any guard spent any minute asleep at most twice.)
+    ///
+    /// What is the ID of the guard you chose multiplied by the minute you chose? (In the above example, the answer would be 99 * 45 = 4455.)
+    fn part2(shifts: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        // the smallest guard of those who slept the most on the same minute
+        let (_, Reverse(most_slept_guard), most_slept_minute) = minutes_asleep(shifts)
+            .iter()
+            .filter_map(|(guard, minutes)| {
+                let (minute, slept_count) = minutes.most_common_one()?;
+                Some((slept_count, Reverse(*guard), *minute))
+            })
+            .max()
+            .ok_or_else(|| Error::NoAnswer("no guard ever falls asleep".to_string()))?;
+
+        Ok(Answer::from(most_slept_guard * most_slept_minute))
+    }
 }
 
-/// Strategy 2: Of all guards, which guard is most frequently asleep on the same minute?
-///
-/// In the example above, Guard #99 spent minute 45 asleep more than any other guard or minute - three times in total. (In all other cases, any guard spent any minute asleep at most twice.)
-///
-/// What is the ID of the guard you chose multiplied by the minute you chose? (In the above example, the answer would be 99 * 45 = 4455.)
-pub fn part2() {
-    let input = crate::common::read_stdin_to_string();
-
-    let mut sorted_input: Vec<_> = input.lines().collect();
-    sorted_input.sort();
-
-    let sleep_schedule = build_part_2_sleep_schedule(sorted_input);
+/// A line of the guards' records, of which only the minute matters since guards only sleep
+/// during the midnight hour
+/// The minutes a guard was asleep for during one of their shifts
+#[derive(Debug)]
+pub struct Shift {
+    guard: i64,
+    naps: Vec<Range<i64>>,
+}
 
-    let most_slept_count_and_minute_and_guard =
-        &sleep_schedule
-            .iter()
-            .fold((0, 0, 0), |most_slept, (guard, minutes)| {
-                minutes
-                    .iter()
-                    .fold(most_slept, |most_slept, (minute, slept_count)| {
-                        if most_slept.0 < *slept_count {
-                            (*slept_count, *minute, *guard)
-                        } else {
-                            most_slept
-                        }
-                    })
-            });
-    let most_slept_minute = most_slept_count_and_minute_and_guard.1;
-    let most_slept_guard = most_slept_count_and_minute_and_guard.2;
-
-    println!(
-        "the id of the guard ({}) multiplied by the minute ({}): {}",
-        most_slept_guard,
-        most_slept_minute,
-        most_slept_guard * most_slept_minute
-    );
+#[derive(Debug)]
+enum Event {
+    BeginsShift(i64),
+    FallsAsleep,
+    WakesUp,
 }
 
-fn build_part_1_sleep_schedule<'a, T: IntoIterator<Item = &'a str>>(
-    sorted_input: T,
-) -> BTreeMap<i64, (i64, Vec<i64>)> {
-    let mut sleep_schedule = BTreeMap::new();
-    let mut guard = 0;
-    let mut last_minute = 0;
-
-    for line in sorted_input {
-        let minute = line
-            .chars()
-            .skip(15)
-            .take(2)
-            .collect::<String>()
-            .parse::<i64>()
-            .unwrap();
-
-        match line.split(' ').nth(2).unwrap() {
-            "Guard" => {
-                guard = line
-                    .split(' ')
-                    .nth(3)
-                    .unwrap()
-                    .chars()
-                    .skip(1)
-                    .collect::<String>()
-                    .parse::<i64>()
-                    .unwrap();
+impl Shift {
+    /// The shift of the records from a guard beginning it up to the next guard beginning theirs
+    fn from_records(records: Vec<(Timestamp, Event)>) -> Result<Self, Error> {
+        let mut records = records.into_iter();
+        let guard = match records.next() {
+            Some((_, Event::BeginsShift(guard))) => guard,
+            _ => {
+                return Err(Error::Input(
+                    "has records from before any guard begins their shift".to_string(),
+                ))
             }
-            "falls" => {
-                last_minute = minute;
-            }
-            "wakes" => {
-                let entry = sleep_schedule.entry(guard).or_insert((0, Vec::new()));
-                let mut minutes_slept = minute - last_minute;
-                while minutes_slept < 0 {
-                    minutes_slept = 60 - minutes_slept;
+        };
+
+        let mut naps = Vec::new();
+        let mut fell_asleep = None;
+        for (timestamp, event) in records {
+            let minute = i64::from(timestamp.minute);
+            match (event, fell_asleep) {
+                (Event::FallsAsleep, None) => fell_asleep = Some(minute),
+                (Event::WakesUp, Some(asleep)) => {
+                    naps.push(asleep..minute);
+                    fell_asleep = None;
                 }
-                entry.0 += minutes_slept;
-                for min in last_minute..(last_minute + minutes_slept) {
-                    entry.1.push(min % 60);
+                _ => {
+                    return Err(Error::Input(format!(
+                        "has guard #{} falling asleep or waking up twice in a row at {}",
+                        guard, timestamp
+                    )))
                 }
-                last_minute = minute;
             }
-            _ => panic!("unhandled input: {}", line),
         }
+        if fell_asleep.is_some() {
+            return Err(Error::Input(format!(
+                "has guard #{} still asleep at the end of their shift",
+                guard
+            )));
+        }
+
+        Ok(Self { guard, naps })
     }
+}
 
-    sleep_schedule
+impl FromStr for Event {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "falls asleep" => Ok(Event::FallsAsleep),
+            "wakes up" => Ok(Event::WakesUp),
+            _ => s
+                .strip_prefix("Guard #")
+                .and_then(|s| s.strip_suffix(" begins shift"))
+                .and_then(|guard| guard.parse().ok())
+                .map(Event::BeginsShift)
+                .ok_or_else(|| "isn't a record like [1518-11-01 00:05] falls asleep".to_string()),
+        }
+    }
 }
 
-fn build_part_2_sleep_schedule<'a, T: IntoIterator<Item = &'a str>>(
-    sorted_input: T,
-) -> BTreeMap<i64, BTreeMap<i64, i64>> {
-    let mut sleep_schedule = BTreeMap::new();
-    let mut guard = 0;
-    let mut last_minute = 0;
-
-    for line in sorted_input {
-        let minute = line
-            .chars()
-            .skip(15)
-            .take(2)
-            .collect::<String>()
-            .parse::<i64>()
-            .unwrap();
-
-        match line.split(' ').nth(2).unwrap() {
-            "Guard" => {
-                guard = line
-                    .split(' ')
-                    .nth(3)
-                    .unwrap()
-                    .chars()
-                    .skip(1)
-                    .collect::<String>()
-                    .parse::<i64>()
-                    .unwrap();
-            }
-            "falls" => {
-                last_minute = minute;
-            }
-            "wakes" => {
-                let guard_entry = sleep_schedule.entry(guard).or_insert_with(BTreeMap::new);
-                let mut minutes_slept = minute - last_minute;
-                while minutes_slept < 0 {
-                    minutes_slept = 60 - minutes_slept;
-                }
+/// How many times each guard was asleep on each minute, over all their shifts, for the guards who
+/// ever fall asleep
+fn minutes_asleep(shifts: &[Shift]) -> BTreeMap<i64, Counter<i64>> {
+    let mut minutes_asleep = BTreeMap::new();
+    for shift in shifts.iter().filter(|shift| !shift.naps.is_empty()) {
+        minutes_asleep
+            .entry(shift.guard)
+            .or_insert_with(Counter::new)
+            .extend(shift.naps.iter().cloned().flatten());
+    }
 
-                for min in last_minute..(last_minute + minutes_slept) {
-                    *guard_entry.entry(min % 60).or_insert(0) += 1
-                }
+    minutes_asleep
+}
 
-                last_minute = minute;
-            }
-            _ => panic!("unhandled input: {}", line),
-        }
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
 
-    sleep_schedule
+    examples!(2018, 4, Day4);
 }
@@ -2,143 +2,215 @@
 //!
 //! You've managed to sneak in to the prototype suit manufacturing lab. The Elves are making decent progress, but are still struggling with the suit's size reduction capabilities.
 
-/// While the very latest in 1518 alchemical technology might have solved their problem eventually, you can do better. You scan the chemical composition of the suit's material and discover that it is formed by extremely long polymers (one of which is available as your puzzle input).
-///
-/// The polymer is formed by smaller units which, when triggered, react with each other such that two adjacent units of the same type and opposite polarity are destroyed. Units' types are represented by letters; units' polarity is represented by capitalization. For instance, r and R are units with the same type but opposite polarity, whereas r and s are entirely different types and do not react.
-///
-/// For example:
-///
-///     In aA, a and A react, leaving nothing behind.
-///     In abBA, bB destroys itself, leaving aA. As above, this then destroys itself, leaving nothing.
-///     In abAB, no two adjacent units are of the same type, and so nothing happens.
-///     In aabAAB, even though aa and AA are of the same type, their polarities match, and so nothing happens.
-///
-/// Now, consider a larger example, dabAcCaCBAcCcaDA:
-///
-/// dabAcCaCBAcCcaDA  The first 'cC' is removed.
-/// dabAaCBAcCcaDA    This creates 'Aa', which is removed.
-/// dabCBAcCcaDA      Either 'cC' or 'Cc' are removed (the result is the same).
-/// dabCBAcaDA        No further actions can be taken.
-///
-/// After all possible reactions, the resulting polymer contains 10 units.
-///
-/// How many units remain after fully reacting the polymer you scanned?
-pub fn part1() {
-    let input = crate::common::read_stdin_to_string();
-
-    let mut polymer: Vec<_> = input.trim().chars().collect();
-
-    react_polymer(&mut polymer, None);
-
-    let number_of_units = polymer.len();
-
-    println!(
-        "the number of units remaining after fully reacting the polymer you scanned: {}",
-        number_of_units
-    );
-}
-
-/// Time to improve the polymer.
-///
-/// One of the unit types is causing problems; it's preventing the polymer from collapsing as much as it should. Your goal is to figure out which unit type is causing the most problems, remove all instances of it (regardless of polarity), fully react the remaining polymer, and measure its length.
-///
-/// For example, again using the polymer dabAcCaCBAcCcaDA from above:
-///
-///     Removing all A/a units produces dbcCCBcCcD. Fully reacting this polymer produces dbCBcD, which has length 6.
-///     Removing all B/b units produces daAcCaCAcCcaDA. Fully reacting this polymer produces daCAcaDA, which has length 8.
-///     Removing all C/c units produces dabAaBAaDA. Fully reacting this polymer produces daDA, which has length 4.
-///     Removing all D/d units produces abAcCaCBAcCcaA. Fully reacting this polymer produces abCBAc, which has length 6.
-///
-/// In this example, removing all C/c units was best, producing the answer 4.
-///
-/// What is the length of the shortest polymer you can produce by removing all units of exactly one type and fully reacting the result?
-pub fn part2() {
-    let input = crate::common::read_stdin_to_string();
-
-    let polymer: Vec<_> = input.trim().chars().collect();
-    let mut shortest_polymer = polymer.len();
-
-    let drop_units = [
-        ('a', 'A'),
-        ('b', 'B'),
-        ('c', 'C'),
-        ('d', 'D'),
-        ('e', 'E'),
-        ('f', 'F'),
-        ('g', 'G'),
-        ('h', 'H'),
-        ('i', 'I'),
-        ('j', 'J'),
-        ('k', 'K'),
-        ('l', 'L'),
-        ('m', 'M'),
-        ('n', 'N'),
-        ('o', 'O'),
-        ('p', 'P'),
-        ('q', 'Q'),
-        ('r', 'R'),
-        ('s', 'S'),
-        ('t', 'T'),
-        ('u', 'U'),
-        ('v', 'V'),
-        ('w', 'W'),
-        ('x', 'X'),
-        ('y', 'Y'),
-        ('z', 'Z'),
-    ];
-
-    for drop_unit in drop_units.iter() {
-        let mut polymer = polymer.to_vec();
-        react_polymer(&mut polymer, *drop_unit);
-        if polymer.len() < shortest_polymer {
-            shortest_polymer = polymer.len();
+use crate::answer::Answer;
+use crate::common::rng::Rng;
+use crate::error::Error;
+use crate::generate::Scale;
+use crate::params::Params;
+use crate::solutions::Solver;
+use rayon::prelude::*;
+
+pub struct Day5;
+
+impl Solver for Day5 {
+    type Parsed = Vec<char>;
+
+    fn parse(input: &str) -> Result<Vec<char>, Error> {
+        let polymer: Vec<_> = input.trim().chars().collect();
+        if polymer.is_empty() {
+            return Err(Error::Input("is empty".to_string()));
+        }
+        if let Some(unit) = polymer.iter().find(|unit| !unit.is_ascii_alphabetic()) {
+            return Err(Error::Input(format!("has {:?}, which isn't a unit", unit)));
         }
+
+        Ok(polymer)
+    }
+
+    /// While the very latest in 1518 alchemical technology might have solved their problem eventually, you can do better. You scan the chemical composition of the suit's material and discover that it is formed by extremely long polymers (one of which is available as your puzzle input).
+    ///
+    /// The polymer is formed by smaller units which, when triggered, react with each other such that two adjacent units of the same type and opposite polarity are destroyed. Units' types are represented by letters; units' polarity is represented by capitalization. For instance, r and R are units with the same type but opposite polarity, whereas r and s are entirely different types and do not react.
+    ///
+    /// For example:
+    ///
+    ///     In aA, a and A react, leaving nothing behind.
+    ///     In abBA, bB destroys itself, leaving aA. As above, this then destroys itself, leaving nothing.
+    ///     In abAB, no two adjacent units are of the same type, and so nothing happens.
+    ///     In aabAAB, even though aa and AA are of the same type, their polarities match, and so nothing happens.
+    ///
+    /// Now, consider a larger example, dabAcCaCBAcCcaDA:
+    ///
+    /// dabAcCaCBAcCcaDA  The first 'cC' is removed.
+    /// dabAaCBAcCcaDA    This creates 'Aa', which is removed.
+    /// dabCBAcCcaDA      Either 'cC' or 'Cc' are removed (the result is the same).
+    /// dabCBAcaDA        No further actions can be taken.
+    ///
+    /// After all possible reactions, the resulting polymer contains 10 units.
+    ///
+    /// How many units remain after fully reacting the polymer you scanned?
+    fn part1(polymer: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let mut polymer = polymer.clone();
+
+        react_polymer(&mut polymer, None);
+
+        Ok(Answer::from(polymer.len()))
     }
 
-    println!("the length of the shortest polymer: {}", shortest_polymer);
+    /// Time to improve the polymer.
+    ///
+    /// One of the unit types is causing problems; it's preventing the polymer from collapsing as much as it should. Your goal is to figure out which unit type is causing the most problems, remove all instances of it (regardless of polarity), fully react the remaining polymer, and measure its length.
+    ///
+    /// For example, again using the polymer dabAcCaCBAcCcaDA from above:
+    ///
+    ///     Removing all A/a units produces dbcCCBcCcD. Fully reacting this polymer produces dbCBcD, which has length 6.
+    ///     Removing all B/b units produces daAcCaCAcCcaDA. Fully reacting this polymer produces daCAcaDA, which has length 8.
+    ///     Removing all C/c units produces dabAaBAaDA. Fully reacting this polymer produces daDA, which has length 4.
+    ///     Removing all D/d units produces abAcCaCBAcCcaA. Fully reacting this polymer produces abCBAc, which has length 6.
+    ///
+    /// In this example, removing all C/c units was best, producing the answer 4.
+    ///
+    /// What is the length of the shortest polymer you can produce by removing all units of exactly one type and fully reacting the result?
+    fn part2(polymer: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        // the units which react without dropping any also react once one is dropped, so reacting
+        // them first leaves far less to react for each unit dropped
+        let mut reacted = polymer.clone();
+        react_polymer(&mut reacted, None);
+
+        let shortest_polymer = ('a'..='z')
+            .into_par_iter()
+            .map(|unit| {
+                let mut polymer = reacted.clone();
+                react_polymer(&mut polymer, (unit, unit.to_ascii_uppercase()));
+                polymer.len()
+            })
+            .min()
+            .unwrap_or(reacted.len());
+
+        Ok(Answer::from(shortest_polymer))
+    }
 }
 
+/// Fully reacts a polymer in a single pass, keeping the units which have survived so far as a
+/// stack at the start of the polymer: each unit either reacts with the unit on top, taking it off,
+/// or goes on top itself
 fn react_polymer<T: Into<Option<(char, char)>>>(polymer: &mut Vec<char>, drop_unit: T) {
-    let mut i = 0;
-    let drop_unit = drop_unit.into();
+    if let Some(drop_unit) = drop_unit.into() {
+        polymer.retain(|&unit| unit != drop_unit.0 && unit != drop_unit.1);
+    }
 
-    while i < polymer.len() - 1 {
+    let mut top = 0;
+    for i in 0..polymer.len() {
         let unit = polymer[i];
-        let next_unit = polymer[i + 1];
+        if top > 0 && test_unit_reaction(polymer[top - 1], unit) {
+            top -= 1;
+        } else {
+            polymer[top] = unit;
+            top += 1;
+        }
+    }
 
-        if let Some(drop_unit) = drop_unit {
-            if unit == drop_unit.0 || unit == drop_unit.1 {
-                polymer.remove(i);
+    polymer.truncate(top);
+}
 
-                if i != 0 {
-                    i -= 1;
-                }
-                continue;
-            }
-            if next_unit == drop_unit.0 || next_unit == drop_unit.1 {
-                polymer.remove(i + 1);
-                continue;
+fn test_unit_reaction(a: char, b: char) -> bool {
+    match (a.is_lowercase(), b.is_lowercase()) {
+        (true, true) | (false, false) => false,
+        (true, false) => a == b.to_lowercase().next().unwrap(),
+        (false, true) => a.to_lowercase().next().unwrap() == b,
+    }
+}
+
+/// A polymer of random units, as long as the 50,000 of a real input
+pub fn generate(rng: &mut Rng, scale: Scale) -> String {
+    let mut polymer: String = (0..scale.of(50_000))
+        .map(|_| {
+            let unit = char::from(b'a' + rng.below(26) as u8);
+            if rng.chance(0.5) {
+                unit.to_ascii_uppercase()
+            } else {
+                unit
             }
-        }
+        })
+        .collect();
+    polymer.push('\n');
 
-        if test_unit_reaction(unit, next_unit) {
-            polymer.remove(i);
-            polymer.remove(i);
+    polymer
+}
 
-            if i != 0 {
-                i -= 1;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+    use proptest::prelude::*;
+
+    examples!(2018, 5, Day5);
+
+    /// Reacts a polymer the slow way, removing each pair of units as it's found and stepping
+    /// back a unit, which reacts the units in a different order to `react_polymer`
+    fn react_by_removing(polymer: &[char]) -> Vec<char> {
+        let mut reacted = polymer.to_vec();
+        let mut i = 0;
+        while i + 1 < reacted.len() {
+            if test_unit_reaction(reacted[i], reacted[i + 1]) {
+                reacted.drain(i..i + 2);
+                i = i.saturating_sub(1);
+            } else {
+                i += 1;
             }
-            continue;
         }
 
-        i += 1;
+        reacted
     }
-}
 
-fn test_unit_reaction(a: char, b: char) -> bool {
-    match (a.is_lowercase(), b.is_lowercase()) {
-        (true, true) | (false, false) => false,
-        (true, false) => a == b.to_lowercase().next().unwrap(),
-        (false, true) => a.to_lowercase().next().unwrap() == b,
+    fn react(polymer: &str, drop_unit: Option<(char, char)>) -> String {
+        let mut polymer: Vec<char> = polymer.chars().collect();
+        react_polymer(&mut polymer, drop_unit);
+        polymer.into_iter().collect()
+    }
+
+    #[test]
+    fn test_react_polymer_examples() {
+        assert_eq!(react("aA", None), "");
+        assert_eq!(react("abBA", None), "");
+        assert_eq!(react("abAB", None), "abAB");
+        assert_eq!(react("aabAAB", None), "aabAAB");
+        assert_eq!(react("dabAcCaCBAcCcaDA", None), "dabCBAcaDA");
+
+        assert_eq!(react("dabAcCaCBAcCcaDA", Some(('a', 'A'))), "dbCBcD");
+        assert_eq!(react("dabAcCaCBAcCcaDA", Some(('b', 'B'))), "daCAcaDA");
+        assert_eq!(react("dabAcCaCBAcCcaDA", Some(('c', 'C'))), "daDA");
+        assert_eq!(react("dabAcCaCBAcCcaDA", Some(('d', 'D'))), "abCBAc");
+    }
+
+    fn polymer() -> impl Strategy<Value = Vec<char>> {
+        prop::collection::vec(
+            prop::sample::select(vec!['a', 'A', 'b', 'B', 'c', 'C']),
+            0..200,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_react_polymer(polymer in polymer()) {
+            let mut reacted = polymer.clone();
+            react_polymer(&mut reacted, None);
+
+            prop_assert_eq!(&reacted, &react_by_removing(&polymer));
+            prop_assert!(reacted.windows(2).all(|pair| !test_unit_reaction(pair[0], pair[1])));
+
+            let mut reacted_again = reacted.clone();
+            react_polymer(&mut reacted_again, None);
+            prop_assert_eq!(reacted_again, reacted);
+        }
+
+        #[test]
+        fn test_react_polymer_dropping_a_unit(polymer in polymer()) {
+            let mut reacted = polymer.clone();
+            react_polymer(&mut reacted, ('b', 'B'));
+
+            let mut dropped: Vec<char> = polymer.into_iter().filter(|&unit| unit != 'b' && unit != 'B').collect();
+            react_polymer(&mut dropped, None);
+            prop_assert_eq!(reacted, dropped);
+        }
     }
 }
@@ -2,177 +2,231 @@
 //!
 //! The device on your wrist beeps several times, and once again you feel like you're falling.
 
+use crate::answer::Answer;
+use crate::common::grid::Grid2D;
+use crate::common::parse::ints_in;
+use crate::common::rng::Rng;
+use crate::error::Error;
+use crate::generate::Scale;
+use crate::params::{Param, Params};
+use crate::solutions::Solver;
 use std::cmp;
-use std::collections::BTreeMap;
-use std::i64;
+use std::collections::HashSet;
 
-/// "Situation critical," the device announces. "Destination indeterminate. Chronal interference detected. Please specify new target coordinates."
-///
-/// The device then produces a list of coordinates (your puzzle input). Are they places it thinks are safe or dangerous? It recommends you check manual page 729. The Elves did not give you a manual.
-///
-/// If they're dangerous, maybe you can minimize the danger by finding the coordinate that gives the largest distance from the other points.
-///
-/// Using only the Manhattan distance, determine the area around each coordinate by counting the number of integer X,Y locations that are closest to that coordinate (and aren't tied in distance to any other coordinate).
-///
-/// Your goal is to find the size of the largest area that isn't infinite. For example, consider the following list of coordinates:
-///
-/// 1, 1
-/// 1, 6
-/// 8, 3
-/// 3, 4
-/// 5, 5
-/// 8, 9
-///
-/// If we name these coordinates A through F, we can draw them on a grid, putting 0,0 at the top left:
-///
-/// ..........
-/// .A........
-/// ..........
-/// ........C.
-/// ...D......
-/// .....E....
-/// .B........
-/// ..........
-/// ..........
-/// ........F.
-///
-/// This view is partial - the actual grid extends infinitely in all directions. Using the Manhattan distance, each location's closest coordinate can be determined, shown here in lowercase:
-///
-/// aaaaa.cccc
-/// aAaaa.cccc
-/// aaaddecccc
-/// aadddeccCc
-/// ..dDdeeccc
-/// bb.deEeecc
-/// bBb.eeee..
-/// bbb.eeefff
-/// bbb.eeffff
-/// bbb.ffffFf
-///
-/// Locations shown as . are equally far from two or more coordinates, and so they don't count as being closest to any.
-///
-/// In this example, the areas of coordinates A, B, C, and F are infinite - while not shown here, their areas extend forever outside the visible grid. However, the areas of coordinates D and E are finite: D is closest to 9 locations, and E is closest to 17 (both including the coordinate's location itself). Therefore, in this example, the size of the largest area is 17.
-///
-/// What is the size of the largest area that isn't infinite?
-pub fn part1() {
-    let input = crate::common::read_stdin_to_string();
+pub struct Day6;
 
-    let coords = input_to_coords(&input);
-    let bounds = get_bounds(&coords);
-    let areas = calculate_areas(&coords, bounds);
+impl Solver for Day6 {
+    type Parsed = Vec<(i64, i64)>;
 
-    let largest_area = areas.values().fold(0, |acc, v| cmp::max(acc, *v));
+    const PARAMS: &'static [Param] = &[REGION_DISTANCE];
 
-    println!(
-        "the size of the largest area that isn't infinite: {}",
-        largest_area
-    );
-}
+    fn parse(input: &str) -> Result<Vec<(i64, i64)>, Error> {
+        input_to_coords(input)
+    }
 
-/// On the other hand, if the coordinates are safe, maybe the best you can do is try to find a region near as many coordinates as possible.
-///
-/// For example, suppose you want the sum of the Manhattan distance to all of the coordinates to be less than 32. For each location, add up the distances to all of the given coordinates; if the total of those distances is less than 32, that location is within the desired region. Using the same coordinates as above, the resulting region looks like this:
-///
-/// ..........
-/// .A........
-/// ..........
-/// ...###..C.
-/// ..#D###...
-/// ..###E#...
-/// .B.###....
-/// ..........
-/// ..........
-/// ........F.
-///
-/// In particular, consider the highlighted location 4,3 located at the top middle of the region. Its calculation is as follows, where abs() is the absolute value function:
-///
-///     Distance to coordinate A: abs(4-1) + abs(3-1) =  5
-///     Distance to coordinate B: abs(4-1) + abs(3-6) =  6
-///     Distance to coordinate C: abs(4-8) + abs(3-3) =  4
-///     Distance to coordinate D: abs(4-3) + abs(3-4) =  2
-///     Distance to coordinate E: abs(4-5) + abs(3-5) =  3
-///     Distance to coordinate F: abs(4-8) + abs(3-9) = 10
-///     Total distance: 5 + 6 + 4 + 2 + 3 + 10 = 30
-///
-/// Because the total distance to all coordinates (30) is less than 32, the location is within the region.
-///
-/// This region, which also includes coordinates D and E, has a total size of 16.
-///
-/// Your actual region will need to be much larger than this example, though, instead including all locations with a total distance of less than 10000.
-///
-/// What is the size of the region containing all locations which have a total distance to all given coordinates of less than 10000?
-pub fn part2() {
-    let input = crate::common::read_stdin_to_string();
-
-    let coords = input_to_coords(&input);
-    let bounds = get_bounds(&coords);
-    let region_size = calculate_region_size(&coords, bounds);
-
-    println!(
-        "the size of the region containing all locations which have a total distance to all given coordinates of less than 10000: {}",
-        region_size
-    );
+    /// "Situation critical," the device announces. "Destination indeterminate. Chronal interference detected. Please specify new target coordinates."
+    ///
+    /// The device then produces a list of coordinates (your puzzle input). Are they places it thinks are safe or dangerous? It recommends you check manual page 729. The Elves did not give you a manual.
+    ///
+    /// If they're dangerous, maybe you can minimize the danger by finding the coordinate that gives the largest distance from the other points.
+    ///
+    /// Using only the Manhattan distance, determine the area around each coordinate by counting the number of integer X,Y locations that are closest to that coordinate (and aren't tied in distance to any other coordinate).
+    ///
+    /// Your goal is to find the size of the largest area that isn't infinite. For example, consider the following list of coordinates:
+    ///
+    /// 1, 1
+    /// 1, 6
+    /// 8, 3
+    /// 3, 4
+    /// 5, 5
+    /// 8, 9
+    ///
+    /// If we name these coordinates A through F, we can draw them on a grid, putting 0,0 at the top left:
+    ///
+    /// ..........
+    /// .A........
+    /// ..........
+    /// ........C.
+    /// ...D......
+    /// .....E....
+    /// .B........
+    /// ..........
+    /// ..........
+    /// ........F.
+    ///
+    /// This view is partial - the actual grid extends infinitely in all directions. Using the Manhattan distance, each location's closest coordinate can be determined, shown here in lowercase:
+    ///
+    /// aaaaa.cccc
+    /// aAaaa.cccc
+    /// aaaddecccc
+    /// aadddeccCc
+    /// ..dDdeeccc
+    /// bb.deEeecc
+    /// bBb.eeee..
+    /// bbb.eeefff
+    /// bbb.eeffff
+    /// bbb.ffffFf
+    ///
+    /// Locations shown as . are equally far from two or more coordinates, and so they don't count as being closest to any.
+    ///
+    /// In this example, the areas of coordinates A, B, C, and F are infinite - while not shown here, their areas extend forever outside the visible grid. However, the areas of coordinates D and E are finite: D is closest to 9 locations, and E is closest to 17 (both including the coordinate's location itself). Therefore, in this example, the size of the largest area is 17.
+    ///
+    /// What is the size of the largest area that isn't infinite?
+    fn part1(coords: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let bounds = get_bounds(coords)
+            .ok_or_else(|| Error::NoAnswer("there are no coordinates".to_string()))?;
+
+        calculate_areas(coords, bounds)
+            .into_iter()
+            .flatten()
+            .max()
+            .map(Answer::from)
+            .ok_or_else(|| Error::NoAnswer("every coordinate's area is infinite".to_string()))
+    }
+
+    /// On the other hand, if the coordinates are safe, maybe the best you can do is try to find a region near as many coordinates as possible.
+    ///
+    /// For example, suppose you want the sum of the Manhattan distance to all of the coordinates to be less than 32. For each location, add up the distances to all of the given coordinates; if the total of those distances is less than 32, that location is within the desired region. Using the same coordinates as above, the resulting region looks like this:
+    ///
+    /// ..........
+    /// .A........
+    /// ..........
+    /// ...###..C.
+    /// ..#D###...
+    /// ..###E#...
+    /// .B.###....
+    /// ..........
+    /// ..........
+    /// ........F.
+    ///
+    /// In particular, consider the highlighted location 4,3 located at the top middle of the region. Its calculation is as follows, where abs() is the absolute value function:
+    ///
+    ///     Distance to coordinate A: abs(4-1) + abs(3-1) =  5
+    ///     Distance to coordinate B: abs(4-1) + abs(3-6) =  6
+    ///     Distance to coordinate C: abs(4-8) + abs(3-3) =  4
+    ///     Distance to coordinate D: abs(4-3) + abs(3-4) =  2
+    ///     Distance to coordinate E: abs(4-5) + abs(3-5) =  3
+    ///     Distance to coordinate F: abs(4-8) + abs(3-9) = 10
+    ///     Total distance: 5 + 6 + 4 + 2 + 3 + 10 = 30
+    ///
+    /// Because the total distance to all coordinates (30) is less than 32, the location is within the region.
+    ///
+    /// This region, which also includes coordinates D and E, has a total size of 16.
+    ///
+    /// Your actual region will need to be much larger than this example, though, instead including all locations with a total distance of less than 10000.
+    ///
+    /// What is the size of the region containing all locations which have a total distance to all given coordinates of less than 10000?
+    fn part2(coords: &Self::Parsed, params: &Params) -> Result<Answer, Error> {
+        let bounds = get_bounds(coords)
+            .ok_or_else(|| Error::NoAnswer("there are no coordinates".to_string()))?;
+
+        Ok(Answer::from(calculate_region_size(
+            coords,
+            bounds,
+            params.get(&REGION_DISTANCE),
+        )))
+    }
 }
 
-fn input_to_coords(input: &str) -> Vec<(i64, i64)> {
+/// The total distance to every coordinate which the locations of the region are within
+pub const REGION_DISTANCE: Param = Param {
+    name: "distance",
+    description: "the total distance the region's locations are within",
+    default: 10000,
+};
+
+fn input_to_coords(input: &str) -> Result<Vec<(i64, i64)>, Error> {
     input
         .lines()
-        .map(|line| {
-            let split: Vec<_> = line.split(',').collect();
-            (
-                split[0].trim().parse().unwrap(),
-                split[1].trim().parse().unwrap(),
-            )
+        .enumerate()
+        .map(|(i, line)| match ints_in(line)[..] {
+            [x, y] => Ok((x, y)),
+            _ => Err(Error::line(i, line, "isn't a coordinate like 1, 6")),
         })
         .collect()
 }
 
-fn get_bounds(coords: &[(i64, i64)]) -> ((i64, i64), (i64, i64)) {
-    coords.iter().fold(
-        ((i64::MAX, i64::MAX), (0, 0)),
-        |(min_coord, max_coord), (x, y)| {
-            (
-                (cmp::min(min_coord.0, *x), cmp::min(min_coord.1, *y)),
-                (cmp::max(max_coord.0, *x), cmp::max(max_coord.1, *y)),
-            )
-        },
+/// The top left and bottom right corners of the box around every coordinate
+fn get_bounds(coords: &[(i64, i64)]) -> Option<((i64, i64), (i64, i64))> {
+    let first = *coords.first()?;
+    Some(
+        coords
+            .iter()
+            .fold((first, first), |(min_coord, max_coord), (x, y)| {
+                (
+                    (cmp::min(min_coord.0, *x), cmp::min(min_coord.1, *y)),
+                    (cmp::max(max_coord.0, *x), cmp::max(max_coord.1, *y)),
+                )
+            }),
     )
 }
 
+/// Which coordinate a location is closest to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Closest {
+    Unreached,
+    Coord(usize),
+    /// Two or more coordinates are as close
+    Tied,
+}
+
+/// The number of locations closest to each coordinate, or `None` where the area is infinite
+///
+/// Every location within the bounds is found in a single breadth-first flood from all of the
+/// coordinates at once, rather than measuring its distance to each of them. A location first
+/// reached from locations closest to different coordinates (or tied) is tied itself.
 fn calculate_areas(
     coords: &[(i64, i64)],
     (min_coord, max_coord): ((i64, i64), (i64, i64)),
-) -> BTreeMap<(i64, i64), i64> {
-    let mut areas = BTreeMap::new();
-
-    for x in min_coord.0..=max_coord.0 {
-        for y in min_coord.1..=max_coord.1 {
-            let (_, coord) =
-                coords
-                    .iter()
-                    .fold((i64::MAX, None), |(closest, closest_coord), coord| {
-                        let distance = taxicab_distance((x, y), *coord);
-                        match distance {
-                            d if d < closest => (d, Some(*coord)),
-                            d if d == closest => (d, None),
-                            _ => (closest, closest_coord),
-                        }
-                    });
-
-            if let Some(coord) = coord {
-                if x == min_coord.0 || x == max_coord.0 {
-                    *areas.entry(coord).or_insert(0) = -1;
-                    continue;
-                }
-                if y == min_coord.1 || y == max_coord.1 {
-                    *areas.entry(coord).or_insert(0) = -1;
-                    continue;
-                }
-                let entry = areas.entry(coord).or_insert(0);
-                if *entry == -1 {
-                    continue;
+) -> Vec<Option<usize>> {
+    let width = (max_coord.0 - min_coord.0 + 1) as usize;
+    let height = (max_coord.1 - min_coord.1 + 1) as usize;
+    let mut closest = Grid2D::new(width, height, Closest::Unreached);
+    let mut distances = Grid2D::new(width, height, None);
+
+    let mut frontier = Vec::new();
+    for (i, (x, y)) in coords.iter().enumerate() {
+        let position = ((x - min_coord.0) as usize, (y - min_coord.1) as usize);
+        closest[position] = match closest[position] {
+            Closest::Unreached => Closest::Coord(i),
+            _ => Closest::Tied,
+        };
+        distances[position] = Some(0);
+        frontier.push(position);
+    }
+
+    let mut distance = 0;
+    while !frontier.is_empty() {
+        distance += 1;
+
+        let mut next = Vec::new();
+        for position in frontier {
+            for neighbor in closest.neighbors4(position).collect::<Vec<_>>() {
+                match distances[neighbor] {
+                    None => {
+                        distances[neighbor] = Some(distance);
+                        closest[neighbor] = closest[position];
+                        next.push(neighbor);
+                    }
+                    Some(d) if d == distance && closest[neighbor] != closest[position] => {
+                        closest[neighbor] = Closest::Tied;
+                    }
+                    _ => {}
                 }
-                *entry += 1
+            }
+        }
+
+        frontier = next;
+    }
+
+    // an area reaching the edge of the bounds carries on past it forever
+    let mut areas = vec![Some(0); coords.len()];
+    for ((x, y), location) in closest.iter() {
+        if let Closest::Coord(i) = *location {
+            if x == 0 || y == 0 || x == width - 1 || y == height - 1 {
+                areas[i] = None;
+            } else if let Some(area) = &mut areas[i] {
+                *area += 1;
             }
         }
     }
@@ -180,27 +234,75 @@ fn calculate_areas(
     areas
 }
 
+/// The number of locations whose total distance to every coordinate is less than
+/// `region_distance`
+///
+/// The total distance to a location is the total distance along the x axis plus that along the y
+/// axis, so each axis is summed up once, and the two are paired up afterwards.
 fn calculate_region_size(
     coords: &[(i64, i64)],
     (min_coord, max_coord): ((i64, i64), (i64, i64)),
-) -> i64 {
-    let mut region_size = 0;
+    region_distance: i64,
+) -> usize {
+    // past the bounds, every step away adds a step to every coordinate's distance
+    let margin = region_distance / coords.len() as i64 + 1;
 
-    for x in min_coord.0..=max_coord.0 {
-        for y in min_coord.1..=max_coord.1 {
-            let distance_sum = coords
-                .iter()
-                .fold(0, |accum, coord| accum + taxicab_distance((x, y), *coord));
+    let xs: Vec<i64> = coords.iter().map(|(x, _)| *x).collect();
+    let ys: Vec<i64> = coords.iter().map(|(_, y)| *y).collect();
+    let x_sums = distance_sums(&xs, min_coord.0 - margin, max_coord.0 + margin);
+    let mut y_sums = distance_sums(&ys, min_coord.1 - margin, max_coord.1 + margin);
+    y_sums.sort_unstable();
 
-            if distance_sum < 10000 {
-                region_size += 1;
-            }
+    x_sums
+        .into_iter()
+        .map(|x_sum| y_sums.partition_point(|y_sum| x_sum + y_sum < region_distance))
+        .sum()
+}
+
+/// The total distance from each of `start..=end` to every one of `values`
+fn distance_sums(values: &[i64], start: i64, end: i64) -> Vec<i64> {
+    let mut values = values.to_vec();
+    values.sort_unstable();
+
+    let mut sum: i64 = values.iter().map(|value| (value - start).abs()).sum();
+    let mut at_or_before = values.partition_point(|value| *value <= start);
+
+    let mut sums = vec![sum];
+    for position in start..end {
+        // one step on, the values behind are a step further away, and those ahead a step closer
+        sum += at_or_before as i64 - (values.len() - at_or_before) as i64;
+        sums.push(sum);
+
+        while at_or_before < values.len() && values[at_or_before] <= position + 1 {
+            at_or_before += 1;
+        }
+    }
+
+    sums
+}
+
+/// Coordinates like the 50 of a real input, none of them the same, spread over a square whose area
+/// grows with their number
+pub fn generate(rng: &mut Rng, scale: Scale) -> String {
+    let side = scale.side(300) as i64;
+    let count = scale.of(50).min((side * side) as usize);
+
+    let mut coordinates = HashSet::new();
+    let mut input = String::new();
+    while coordinates.len() < count {
+        let coordinate = (40 + rng.between(0, side - 1), 40 + rng.between(0, side - 1));
+        if coordinates.insert(coordinate) {
+            input += &format!("{}, {}\n", coordinate.0, coordinate.1);
         }
     }
 
-    region_size
+    input
 }
 
-fn taxicab_distance(a: (i64, i64), b: (i64, i64)) -> i64 {
-    (cmp::max(a.0, b.0) - cmp::min(a.0, b.0)) + (cmp::max(a.1, b.1) - cmp::min(a.1, b.1))
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    examples!(2018, 6, Day6);
 }
@@ -0,0 +1,181 @@
+//! --- Day 8: Memory Maneuver ---
+//!
+//! The sleigh is much easier to pull than you'd expect for something its weight. Unfortunately, neither you nor the Elves know which way the North Pole is from here.
+
+use crate::answer::Answer;
+use crate::common::arena::{Arena, Id, Span};
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day8;
+
+impl Solver for Day8 {
+    type Parsed = LicenseTree;
+
+    fn parse(input: &str) -> Result<LicenseTree, Error> {
+        let numbers = input
+            .split_whitespace()
+            .map(|word| {
+                word.parse()
+                    .map_err(|_| Error::Input(format!("has {:?}, which isn't a number", word)))
+            })
+            .collect::<Result<Vec<usize>, Error>>()?;
+
+        LicenseTree::parse(&numbers)
+    }
+
+    /// You check the screen on your handheld device; it's showing the navigation system's license file (your puzzle input).
+    ///
+    /// The tree is made up of nodes; a single, outermost node forms the tree's root, and it contains all other nodes in the tree (or contains nodes that contain nodes, and so on).
+    ///
+    /// Specifically, a node consists of:
+    ///
+    /// - A header, which is always exactly two numbers:
+    ///   - The quantity of child nodes.
+    ///   - The quantity of metadata entries.
+    /// - Zero or more child nodes (as specified in the header).
+    /// - One or more metadata entries (as specified in the header).
+    ///
+    /// Each child node is itself a node that has its own header, child nodes, and metadata.
+    ///
+    /// The first check done on the license file is to simply add up all of the metadata entries.
+    ///
+    /// What is the sum of all metadata entries?
+    fn part1(tree: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(tree.metadata.values().iter().sum::<usize>()))
+    }
+
+    /// The second check is slightly more complicated: you need to find the value of the root node.
+    ///
+    /// The value of a node depends on whether it has child nodes.
+    ///
+    /// If a node has no child nodes, its value is the sum of its metadata entries.
+    ///
+    /// However, if a node does have child nodes, the metadata entries become indexes which refer to those child nodes. A metadata entry of 1 refers to the first child node, 2 to the second, 3 to the third, and so on. The value of this node is the sum of the values of the child nodes referenced by the metadata entries. If a referenced child node does not exist, that reference is skipped. A child node can be referenced multiple time and counts each time it is referenced. A metadata entry of 0 does not refer to any child node.
+    ///
+    /// What is the value of the root node?
+    fn part2(tree: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(tree.values()[tree.root.index()]))
+    }
+}
+
+/// The nodes of the license file, each with its children and metadata entries in spans of the
+/// arenas they share
+pub struct LicenseTree {
+    nodes: Arena<Node>,
+    children: Arena<Id<Node>>,
+    metadata: Arena<usize>,
+    root: Id<Node>,
+}
+
+struct Node {
+    children: Span<Id<Node>>,
+    metadata: Span<usize>,
+}
+
+/// A node whose children are being read
+struct Partial {
+    children_left: usize,
+    metadata_entries: usize,
+    /// Where its children's ids start on the stack of them
+    children_start: usize,
+}
+
+impl Partial {
+    /// A node from its header, or `None` if the numbers run out first
+    fn read(numbers: &mut impl Iterator<Item = usize>, children_start: usize) -> Option<Partial> {
+        Some(Partial {
+            children_left: numbers.next()?,
+            metadata_entries: numbers.next()?,
+            children_start,
+        })
+    }
+}
+
+impl LicenseTree {
+    /// The tree the numbers of a license file describe, read without recursing so that however
+    /// deep it goes doesn't matter
+    fn parse(numbers: &[usize]) -> Result<LicenseTree, Error> {
+        let ends_early = || Error::Input("ends partway through a node".to_string());
+
+        let mut nodes = Arena::new();
+        let mut children = Arena::new();
+        let mut metadata = Arena::new();
+
+        // the ids of the children read so far of every node being read, in a stack they share
+        let mut child_ids = Vec::new();
+        let mut partials: Vec<Partial> = Vec::new();
+        let mut numbers = numbers.iter().copied();
+        partials.push(Partial::read(&mut numbers, 0).ok_or_else(ends_early)?);
+
+        let root = loop {
+            let partial = partials.last_mut().ok_or_else(ends_early)?;
+            if partial.children_left > 0 {
+                partial.children_left -= 1;
+                let child = Partial::read(&mut numbers, child_ids.len()).ok_or_else(ends_early)?;
+                partials.push(child);
+                continue;
+            }
+
+            let partial = partials.pop().ok_or_else(ends_early)?;
+            let entries = metadata.alloc_extend(numbers.by_ref().take(partial.metadata_entries));
+            if entries.len() < partial.metadata_entries {
+                return Err(ends_early());
+            }
+
+            let id = nodes.alloc(Node {
+                children: children.alloc_extend(child_ids.drain(partial.children_start..)),
+                metadata: entries,
+            });
+            if partials.is_empty() {
+                break id;
+            }
+            child_ids.push(id);
+        };
+
+        if numbers.next().is_some() {
+            return Err(Error::Input(
+                "has numbers left over after the root node".to_string(),
+            ));
+        }
+
+        Ok(LicenseTree {
+            nodes,
+            children,
+            metadata,
+            root,
+        })
+    }
+
+    /// The value of every node, by its index
+    ///
+    /// Nodes are allocated after their children, so going through them in order comes to each
+    /// one's children before the node itself.
+    fn values(&self) -> Vec<usize> {
+        let mut values = Vec::with_capacity(self.nodes.len());
+        for (_, node) in self.nodes.iter() {
+            let children = &self.children[node.children];
+            let entries = self.metadata[node.metadata].iter();
+
+            values.push(if children.is_empty() {
+                entries.sum()
+            } else {
+                entries
+                    .filter_map(|entry| children.get(entry.checked_sub(1)?))
+                    .map(|child| values[child.index()])
+                    .sum()
+            });
+        }
+
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    examples!(2018, 8, Day8);
+}
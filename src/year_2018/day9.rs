@@ -0,0 +1,108 @@
+//! --- Day 9: Marble Mania ---
+//!
+//! You talk to the Elves while you wait for your navigation system to initialize. To pass the time, they introduce you to their favorite marble game.
+
+use crate::answer::Answer;
+use crate::common::parse::ints_in;
+use crate::common::ring::Ring;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+/// The most players a game can have (puzzle inputs have a few hundred), as each needs a score
+const MAX_PLAYERS: i64 = 10_000;
+
+/// The highest last marble a game can have (puzzle inputs are around 70,000), as part 2 plays
+/// 100 times as many marbles around the circle
+const MAX_LAST_MARBLE: i64 = 1_000_000;
+
+pub struct Day9;
+
+impl Solver for Day9 {
+    type Parsed = Game;
+
+    fn parse(input: &str) -> Result<Game, Error> {
+        match ints_in(input)[..] {
+            [players, _] if players > MAX_PLAYERS => Err(Error::Input(format!(
+                "has more than {} players",
+                MAX_PLAYERS
+            ))),
+            [_, last_marble] if last_marble > MAX_LAST_MARBLE => Err(Error::Input(format!(
+                "has a last marble worth more than {} points",
+                MAX_LAST_MARBLE
+            ))),
+            [players, last_marble] if players > 0 && last_marble >= 0 => Ok(Game {
+                players: players as usize,
+                last_marble: last_marble as u64,
+            }),
+            _ => Err(Error::Input(
+                "isn't like \"10 players; last marble is worth 1618 points\"".to_string(),
+            )),
+        }
+    }
+
+    /// The Elves play this game by taking turns arranging the marbles in a circle according to very particular rules. The marbles are numbered starting with 0 and increasing by 1 until every marble has a number.
+    ///
+    /// First, the marble numbered 0 is placed in the circle. At this point, while it contains only a single marble, it is still a circle: the marble is both clockwise from itself and counter-clockwise from itself. This marble is designated the current marble.
+    ///
+    /// Then, each Elf takes a turn placing the lowest-numbered remaining marble into the circle between the marbles that are 1 and 2 marbles clockwise of the current marble. (When the circle is large enough, this means that there is one marble between the marble that was just placed and the current marble.) The marble that was just placed then becomes the current marble.
+    ///
+    /// However, if the marble that is about to be placed has a number which is a multiple of 23, something entirely different happens. First, the current player keeps the marble they would have placed, adding it to their score. In addition, the marble 7 marbles counter-clockwise from the current marble is removed from the circle and also added to the current player's score. The marble located immediately clockwise of the marble that was removed becomes the new current marble.
+    ///
+    /// The goal is to be the player with the highest score after the last marble is used up.
+    ///
+    /// What is the winning Elf's score?
+    fn part1(game: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(game.high_score(1)))
+    }
+
+    /// Amused by the speed of your answer, the Elves are curious:
+    ///
+    /// What would the new winning Elf's score be if the number of the last marble were 100 times larger?
+    fn part2(game: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(game.high_score(100)))
+    }
+}
+
+pub struct Game {
+    players: usize,
+    last_marble: u64,
+}
+
+impl Game {
+    /// The winning score once every marble up to `last_marble * factor` has been played
+    fn high_score(&self, factor: u64) -> u64 {
+        let mut circle = Ring::new();
+        circle.insert(0);
+
+        let mut scores = vec![0; self.players];
+        for marble in 1..=self.last_marble * factor {
+            if marble % 23 == 0 {
+                circle.rotate(-7);
+                let removed = circle.remove().expect("the circle is never empty");
+                scores[(marble % self.players as u64) as usize] += marble + removed;
+            } else {
+                circle.rotate(1);
+                circle.insert(marble);
+            }
+        }
+
+        scores.into_iter().max().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+
+    #[test]
+    fn test_invalid_games() {
+        assert!(Day9::parse("0 players; last marble is worth 1618 points").is_err());
+        assert!(Day9::parse("10001 players; last marble is worth 1618 points").is_err());
+        assert!(Day9::parse("10 players; last marble is worth 1000001 points").is_err());
+        assert!(Day9::parse("10000 players; last marble is worth 1000000 points").is_ok());
+    }
+
+    examples!(2018, 9, Day9);
+}
@@ -0,0 +1,93 @@
+//! "We've detected some temporal anomalies," one of Santa's Elves at the Temporal Anomaly Research and Detection Instrument Station tells you. She sounded pretty worried when she called you down here. "At 500-year intervals into the past, someone has been changing Santa's history!"
+//!
+//! "The good news is that the changes won't propagate to our time stream for another 25 days, and we have a device" - she attaches something to your wrist - "that will let you fix the changes with no such propagation delay. It's configured to send you 500 years further into the past every few days; that was the best we could do on such short notice."
+//!
+//! "The bad news is that we are detecting roughly fifty anomalies throughout time; the device will indicate fixed anomalies with stars. The other bad news is that we only have one device and you're the best person for the job! Good lu--" She taps a button on the device and you suddenly feel like you're falling. To save Christmas, you need to get all fifty stars by December 25th.
+//!
+//! Collect stars by solving puzzles. Two puzzles will be made available on each day in the Advent calendar; the second puzzle is unlocked when you complete the first. Each puzzle grants one star. Good luck!
+
+use crate::generate::{generator, Generator};
+#[cfg(feature = "visualize")]
+use crate::solutions::solution;
+use crate::solutions::{solver, Solution};
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day17;
+pub mod day18;
+pub mod day2;
+pub mod day20;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+/// Every solution of the year, in order of day and part
+pub const SOLUTIONS: &[Solution] = &[
+    solver!(2018, 1, 1, day1::Day1),
+    solver!(2018, 1, 2, day1::Day1),
+    solver!(2018, 2, 1, day2::Day2),
+    solver!(2018, 2, 2, day2::Day2),
+    solver!(2018, 3, 1, day3::Day3),
+    solver!(2018, 3, 2, day3::Day3),
+    solver!(2018, 4, 1, day4::Day4),
+    solver!(2018, 4, 2, day4::Day4),
+    solver!(2018, 5, 1, day5::Day5),
+    solver!(2018, 5, 2, day5::Day5),
+    solver!(2018, 6, 1, day6::Day6),
+    solver!(2018, 6, 2, day6::Day6),
+    solver!(2018, 7, 1, day7::Day7),
+    solver!(2018, 7, 2, day7::Day7),
+    solver!(2018, 8, 1, day8::Day8),
+    solver!(2018, 8, 2, day8::Day8),
+    solver!(2018, 9, 1, day9::Day9),
+    solver!(2018, 9, 2, day9::Day9),
+    solver!(2018, 10, 1, day10::Day10),
+    solver!(2018, 10, 2, day10::Day10),
+    solver!(2018, 11, 1, day11::Day11),
+    solver!(2018, 11, 2, day11::Day11),
+    solver!(2018, 12, 1, day12::Day12),
+    solver!(2018, 12, 2, day12::Day12),
+    solver!(2018, 13, 1, day13::Day13),
+    solver!(2018, 13, 2, day13::Day13),
+    solver!(2018, 14, 1, day14::Day14),
+    solver!(2018, 14, 2, day14::Day14),
+    solver!(2018, 15, 1, day15::Day15),
+    solver!(2018, 15, 2, day15::Day15),
+    solver!(2018, 17, 1, day17::Day17),
+    solver!(2018, 17, 2, day17::Day17),
+    solver!(2018, 18, 1, day18::Day18),
+    solver!(2018, 18, 2, day18::Day18),
+    solver!(2018, 20, 1, day20::Day20),
+    solver!(2018, 20, 2, day20::Day20),
+    solver!(2018, 22, 1, day22::Day22),
+    solver!(2018, 22, 2, day22::Day22),
+    solver!(2018, 23, 1, day23::Day23),
+    solver!(2018, 23, 2, day23::Day23),
+    solver!(2018, 24, 1, day24::Day24),
+    solver!(2018, 24, 2, day24::Day24),
+];
+
+/// Days which can make up inputs of any size, with `aoc gen`
+pub const GENERATORS: &[Generator] = &[
+    generator!(2018, 1, day1::generate),
+    generator!(2018, 2, day2::generate),
+    generator!(2018, 5, day5::generate),
+    generator!(2018, 6, day6::generate),
+];
+
+/// Solutions which draw the puzzle as they solve it, run in place of the plain solution of the
+/// same part with `--visualize`
+#[cfg(feature = "visualize")]
+pub const VISUALIZATIONS: &[Solution] = &[solution!(2018, 10, 1, day10::part1_visualize)];
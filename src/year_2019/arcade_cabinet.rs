@@ -0,0 +1,248 @@
+use super::intcode_computer::IntcodeState;
+use super::{IntcodeComputer, IntcodeProgram, IntcodeWord};
+use crate::error::Error;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+
+/// The screen of the arcade cabinet, built up from the (x, y, tile id) triples output by the
+/// game software
+#[derive(Debug, Default, Clone)]
+pub struct ArcadeScreen {
+    tiles: BTreeMap<(IntcodeWord, IntcodeWord), Tile>,
+    score: IntcodeWord,
+    ball: Option<(IntcodeWord, IntcodeWord)>,
+    paddle: Option<(IntcodeWord, IntcodeWord)>,
+}
+
+impl ArcadeScreen {
+    /// Applies a single output triple to the screen, returning the tile drawn (or `None` for the
+    /// score), or an error when the value isn't a tile id
+    pub fn draw(
+        &mut self,
+        x: IntcodeWord,
+        y: IntcodeWord,
+        value: IntcodeWord,
+    ) -> Result<Option<Tile>, Error> {
+        if (x, y) == SCORE_POSITION {
+            self.score = value;
+            return Ok(None);
+        }
+
+        let tile = Tile::try_from(value)?;
+        match tile {
+            Tile::Ball => self.ball = Some((x, y)),
+            Tile::HorizontalPaddle => self.paddle = Some((x, y)),
+            _ => {}
+        }
+
+        self.tiles.insert((x, y), tile);
+        Ok(Some(tile))
+    }
+
+    pub fn score(&self) -> IntcodeWord {
+        self.score
+    }
+
+    /// The last position the ball was drawn at
+    pub fn ball(&self) -> Option<(IntcodeWord, IntcodeWord)> {
+        self.ball
+    }
+
+    /// The last position the paddle was drawn at
+    pub fn paddle(&self) -> Option<(IntcodeWord, IntcodeWord)> {
+        self.paddle
+    }
+
+    /// Counts the tiles of a type currently on the screen
+    pub fn count(&self, tile: Tile) -> usize {
+        self.tiles.values().filter(|other| **other == tile).count()
+    }
+}
+
+impl fmt::Display for ArcadeScreen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max_x = self.tiles.keys().map(|(x, _)| *x).max().unwrap_or(0);
+        let max_y = self.tiles.keys().map(|(_, y)| *y).max().unwrap_or(0);
+
+        for y in 0..=max_y {
+            let row = (0..=max_x)
+                .map(|x| match self.tiles.get(&(x, y)) {
+                    None | Some(Tile::Empty) => ' ',
+                    Some(Tile::Wall) => '█',
+                    Some(Tile::Block) => '▒',
+                    Some(Tile::HorizontalPaddle) => '▔',
+                    Some(Tile::Ball) => '●',
+                })
+                .collect::<String>();
+
+            writeln!(f, "{}", row)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// When three output instructions specify X=-1, Y=0, the third output instruction is not a tile;
+/// the value instead specifies the new score to show in the segment display.
+const SCORE_POSITION: (IntcodeWord, IntcodeWord) = (-1, 0);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    /// No game object appears in this tile.
+    Empty,
+
+    /// Walls are indestructible barriers.
+    Wall,
+
+    /// Blocks can be broken by the ball.
+    Block,
+
+    /// The paddle is indestructible.
+    HorizontalPaddle,
+
+    /// The ball moves diagonally and bounces off objects.
+    Ball,
+}
+
+impl TryFrom<IntcodeWord> for Tile {
+    type Error = Error;
+
+    fn try_from(tile_id: IntcodeWord) -> Result<Self, Error> {
+        match tile_id {
+            0 => Ok(Self::Empty),
+            1 => Ok(Self::Wall),
+            2 => Ok(Self::Block),
+            3 => Ok(Self::HorizontalPaddle),
+            4 => Ok(Self::Ball),
+            other => Err(Error::Input(format!(
+                "draws a tile with id {}, which isn't a tile of the arcade game",
+                other
+            ))),
+        }
+    }
+}
+
+/// The position of the arcade cabinet's joystick
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Joystick {
+    Left,
+    Neutral,
+    Right,
+}
+
+impl Joystick {
+    /// Tilts the joystick towards the ball, so that the paddle is always underneath it
+    pub fn track_ball(screen: &ArcadeScreen) -> Self {
+        match (screen.ball(), screen.paddle()) {
+            (Some((ball_x, _)), Some((paddle_x, _))) if ball_x < paddle_x => Self::Left,
+            (Some((ball_x, _)), Some((paddle_x, _))) if ball_x > paddle_x => Self::Right,
+            _ => Self::Neutral,
+        }
+    }
+}
+
+impl From<Joystick> for IntcodeWord {
+    fn from(joystick: Joystick) -> Self {
+        match joystick {
+            Joystick::Left => -1,
+            Joystick::Neutral => 0,
+            Joystick::Right => 1,
+        }
+    }
+}
+
+/// Runs the game software until it halts, and returns the final state of the screen, or an error
+/// when the program draws something which isn't a tile.
+///
+/// `player` is asked for the joystick's position each time the game reads it, which is once per
+/// tick, after the tick's ball has been drawn. The game is stepped on the calling thread, so that
+/// it also runs where there are no threads, such as in a browser.
+pub fn play<F>(program: &IntcodeProgram, mut player: F) -> Result<ArcadeScreen, Error>
+where
+    F: FnMut(&ArcadeScreen) -> Joystick,
+{
+    let mut game = IntcodeComputer::from(program);
+    let output = game.create_output();
+    let mut screen = ArcadeScreen::default();
+    let mut triple = Vec::with_capacity(3);
+
+    loop {
+        if game.needs_input() {
+            game.push_input(player(&screen).into());
+        }
+        let state = game.step()?;
+
+        triple.extend(output.try_iter());
+        if triple.len() == 3 {
+            screen.draw(triple[0], triple[1], triple[2])?;
+            triple.clear();
+        }
+
+        if state == IntcodeState::Halted {
+            return Ok(screen);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screen_draw() {
+        let mut screen = ArcadeScreen::default();
+
+        for triple in [1, 2, 3, 6, 5, 4, -1, 0, 12345].chunks(3) {
+            screen.draw(triple[0], triple[1], triple[2]).unwrap();
+        }
+
+        assert_eq!(screen.paddle(), Some((1, 2)));
+        assert_eq!(screen.ball(), Some((6, 5)));
+        assert_eq!(screen.score(), 12345);
+        assert_eq!(screen.count(Tile::Ball), 1);
+        assert_eq!(screen.count(Tile::Block), 0);
+    }
+
+    #[test]
+    fn test_track_ball() {
+        let mut screen = ArcadeScreen::default();
+        assert_eq!(Joystick::track_ball(&screen), Joystick::Neutral);
+
+        screen.draw(5, 10, 3).unwrap();
+        screen.draw(2, 4, 4).unwrap();
+        assert_eq!(Joystick::track_ball(&screen), Joystick::Left);
+
+        screen.draw(8, 5, 4).unwrap();
+        assert_eq!(Joystick::track_ball(&screen), Joystick::Right);
+
+        screen.draw(5, 6, 4).unwrap();
+        assert_eq!(Joystick::track_ball(&screen), Joystick::Neutral);
+    }
+
+    #[test]
+    fn test_play() {
+        // draws a wall, a block, the paddle and then the ball (reading the joystick), then
+        // outputs the joystick position as the score before halting
+        let program = IntcodeProgram::from(
+            "104,0,104,0,104,1,104,1,104,0,104,2,104,4,104,1,104,3,104,3,104,0,104,4,3,100,104,-1,104,0,4,100,99",
+        );
+
+        let screen = play(&program, Joystick::track_ball).unwrap();
+
+        assert_eq!(screen.count(Tile::Wall), 1);
+        assert_eq!(screen.count(Tile::Block), 1);
+        assert_eq!(screen.score(), -1);
+
+        // reads the joystick before drawing a ball
+        let program = IntcodeProgram::from("3,100,104,-1,104,0,4,100,99");
+        assert_eq!(play(&program, Joystick::track_ball).unwrap().score(), 0);
+
+        // a program which isn't an arcade game
+        assert!(play(
+            &IntcodeProgram::from("104,1,104,2,104,7,99"),
+            Joystick::track_ball
+        )
+        .is_err());
+    }
+}
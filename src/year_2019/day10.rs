@@ -1,312 +1,421 @@
 //! --- Day 10: Monitoring Station ---
 
-/// You fly into the asteroid belt and reach the Ceres monitoring station. The Elves here have an emergency: they're having trouble tracking all of the asteroids and can't be sure they're safe.
-///
-/// The Elves would like to build a new monitoring station in a nearby area of space; they hand you a map of all of the asteroids in that region (your puzzle input).
-///
-/// The map indicates whether each position is empty (.) or contains an asteroid (#). The asteroids are much smaller than they appear on the map, and every asteroid is exactly in the center of its marked position. The asteroids can be described with X,Y coordinates where X is the distance from the left edge and Y is the distance from the top edge (so the top-left corner is 0,0 and the position immediately to its right is 1,0).
-///
-/// Your job is to figure out which asteroid would be the best place to build a new monitoring station. A monitoring station can detect any asteroid to which it has direct line of sight - that is, there cannot be another asteroid exactly between them. This line of sight can be at any angle, not just lines aligned to the grid or diagonally. The best location is the asteroid that can detect the largest number of other asteroids.
-///
-/// For example, consider the following map:
-///
-/// .#..#
-/// .....
-/// #####
-/// ....#
-/// ...##
-///
-/// The best location for a new monitoring station on this map is the highlighted asteroid at 3,4 because it can detect 8 asteroids, more than any other location. (The only asteroid it cannot detect is the one at 1,0; its view of this asteroid is blocked by the asteroid at 2,2.) All other asteroids are worse locations; they can detect 7 or fewer other asteroids. Here is the number of other asteroids a monitoring station on each asteroid could detect:
-///
-/// .7..7
-/// .....
-/// 67775
-/// ....7
-/// ...87
-///
-/// Here is an asteroid (#) and some examples of the ways its line of sight might be blocked. If there were another asteroid at the location of a capital letter, the locations marked with the corresponding lowercase letter would be blocked and could not be detected:
-///
-/// #.........
-/// ...A......
-/// ...B..a...
-/// .EDCG....a
-/// ..F.c.b...
-/// .....c....
-/// ..efd.c.gb
-/// .......c..
-/// ....f...c.
-/// ...e..d..c
-///
-/// Here are some larger examples:
-///
-///     Best is 5,8 with 33 other asteroids detected:
-///
-///     ......#.#.
-///     #..#.#....
-///     ..#######.
-///     .#.#.###..
-///     .#..#.....
-///     ..#....#.#
-///     #..#....#.
-///     .##.#..###
-///     ##...#..#.
-///     .#....####
-///
-///     Best is 1,2 with 35 other asteroids detected:
-///
-///     #.#...#.#.
-///     .###....#.
-///     .#....#...
-///     ##.#.#.#.#
-///     ....#.#.#.
-///     .##..###.#
-///     ..#...##..
-///     ..##....##
-///     ......#...
-///     .####.###.
-///
-///     Best is 6,3 with 41 other asteroids detected:
-///
-///     .#..#..###
-///     ####.###.#
-///     ....###.#.
-///     ..###.##.#
-///     ##.##.#.#.
-///     ....###..#
-///     ..#.#..#.#
-///     #..#.#.###
-///     .##...##.#
-///     .....#.#..
-///
-///     Best is 11,13 with 210 other asteroids detected:
-///
-///     .#..##.###...#######
-///     ##.############..##.
-///     .#.######.########.#
-///     .###.#######.####.#.
-///     #####.##.#.##.###.##
-///     ..#####..#.#########
-///     ####################
-///     #.####....###.#.#.##
-///     ##.#################
-///     #####.##.###..####..
-///     ..######..##.#######
-///     ####.##.####...##..#
-///     .#####..#.######.###
-///     ##...#.##########...
-///     #.##########.#######
-///     .####.#.###.###.#.##
-///     ....##.##.###..#####
-///     .#.#.###########.###
-///     #.#.#.#####.####.###
-///     ###.##.####.##.#..##
-///
-/// Find the best location for a new monitoring station. How many other asteroids can be detected from that location?
-
-pub fn part1() {
-    let input = crate::common::read_stdin_to_string();
-
-    let map = AsteroidMap::from(input.as_str());
-    let (number_of_asteroids, _) = map.calculate_best_monitoring_station();
-
-    println!(
-        "The number of other asteroids that can be detected from the best location for a new monitoring station: {}",
-        number_of_asteroids
-    );
-}
+use crate::answer::Answer;
+use crate::common::geometry::Point;
+use crate::common::rng::Rng;
+use crate::error::Error;
+use crate::generate::Scale;
+use crate::params::{Param, Params};
+use crate::solutions::Solver;
+use std::cmp::Ordering;
 
-#[derive(Debug)]
-struct AsteroidMap(Vec<Point>);
+pub struct Day10;
 
-impl AsteroidMap {
-    fn calculate_best_monitoring_station(&self) -> (usize, Point) {
-        self.0
-            .iter()
-            .copied()
-            .map(|asteroid| (self.number_of_visible_asteroids(asteroid), asteroid))
-            .max_by(|(visible_asteroids_one, _), (visible_asteroids_two, _)| {
-                visible_asteroids_one.cmp(visible_asteroids_two)
-            })
-            .expect("Failed to find best monitoring station")
+impl Solver for Day10 {
+    type Parsed = AsteroidMap;
+
+    const PARAMS: &'static [Param] = &[VAPORIZED];
+
+    fn parse(input: &str) -> Result<AsteroidMap, Error> {
+        let mut asteroids = Vec::new();
+        let (mut width, mut height) = (0, 0);
+        for (y, line) in input.trim().lines().enumerate() {
+            for (x, c) in line.trim().chars().enumerate() {
+                match c {
+                    '#' => asteroids.push(Point::new(x as i64, y as i64)),
+                    '.' => {}
+                    _ => {
+                        return Err(Error::line(
+                            y,
+                            line,
+                            format!("has {:?}, which isn't an asteroid or empty space", c),
+                        ))
+                    }
+                }
+                width = width.max(x + 1);
+            }
+            height = y + 1;
+        }
+
+        if asteroids.is_empty() {
+            return Err(Error::Input("has no asteroids".to_string()));
+        }
+        Ok(AsteroidMap {
+            asteroids,
+            width,
+            height,
+        })
     }
 
-    fn number_of_visible_asteroids(&self, from: Point) -> usize {
-        let mut vectors: Vec<_> = self
-            .0
-            .iter()
-            .copied()
-            .filter(|asteroid| *asteroid != from)
-            .map(|asteroid| Vector::from_points(from, asteroid))
-            .map(|vector| vector.angle())
-            .collect();
+    /// You fly into the asteroid belt and reach the Ceres monitoring station. The Elves here have an emergency: they're having trouble tracking all of the asteroids and can't be sure they're safe.
+    ///
+    /// The Elves would like to build a new monitoring station in a nearby area of space; they hand you a map of all of the asteroids in that region (your puzzle input).
+    ///
+    /// The map indicates whether each position is empty (.) or contains an asteroid (#). The asteroids are much smaller than they appear on the map, and every asteroid is exactly in the center of its marked position. The asteroids can be described with X,Y coordinates where X is the distance from the left edge and Y is the distance from the top edge (so the top-left corner is 0,0 and the position immediately to its right is 1,0).
+    ///
+    /// Your job is to figure out which asteroid would be the best place to build a new monitoring station. A monitoring station can detect any asteroid to which it has direct line of sight - that is, there cannot be another asteroid exactly between them. This line of sight can be at any angle, not just lines aligned to the grid or diagonally. The best location is the asteroid that can detect the largest number of other asteroids.
+    ///
+    /// For example, consider the following map:
+    ///
+    /// .#..#
+    /// .....
+    /// #####
+    /// ....#
+    /// ...##
+    ///
+    /// The best location for a new monitoring station on this map is the highlighted asteroid at 3,4 because it can detect 8 asteroids, more than any other location. (The only asteroid it cannot detect is the one at 1,0; its view of this asteroid is blocked by the asteroid at 2,2.) All other asteroids are worse locations; they can detect 7 or fewer other asteroids. Here is the number of other asteroids a monitoring station on each asteroid could detect:
+    ///
+    /// .7..7
+    /// .....
+    /// 67775
+    /// ....7
+    /// ...87
+    ///
+    /// Here is an asteroid (#) and some examples of the ways its line of sight might be blocked. If there were another asteroid at the location of a capital letter, the locations marked with the corresponding lowercase letter would be blocked and could not be detected:
+    ///
+    /// #.........
+    /// ...A......
+    /// ...B..a...
+    /// .EDCG....a
+    /// ..F.c.b...
+    /// .....c....
+    /// ..efd.c.gb
+    /// .......c..
+    /// ....f...c.
+    /// ...e..d..c
+    ///
+    /// Here are some larger examples:
+    ///
+    ///     Best is 5,8 with 33 other asteroids detected:
+    ///
+    ///     ......#.#.
+    ///     #..#.#....
+    ///     ..#######.
+    ///     .#.#.###..
+    ///     .#..#.....
+    ///     ..#....#.#
+    ///     #..#....#.
+    ///     .##.#..###
+    ///     ##...#..#.
+    ///     .#....####
+    ///
+    ///     Best is 1,2 with 35 other asteroids detected:
+    ///
+    ///     #.#...#.#.
+    ///     .###....#.
+    ///     .#....#...
+    ///     ##.#.#.#.#
+    ///     ....#.#.#.
+    ///     .##..###.#
+    ///     ..#...##..
+    ///     ..##....##
+    ///     ......#...
+    ///     .####.###.
+    ///
+    ///     Best is 6,3 with 41 other asteroids detected:
+    ///
+    ///     .#..#..###
+    ///     ####.###.#
+    ///     ....###.#.
+    ///     ..###.##.#
+    ///     ##.##.#.#.
+    ///     ....###..#
+    ///     ..#.#..#.#
+    ///     #..#.#.###
+    ///     .##...##.#
+    ///     .....#.#..
+    ///
+    ///     Best is 11,13 with 210 other asteroids detected:
+    ///
+    ///     .#..##.###...#######
+    ///     ##.############..##.
+    ///     .#.######.########.#
+    ///     .###.#######.####.#.
+    ///     #####.##.#.##.###.##
+    ///     ..#####..#.#########
+    ///     ####################
+    ///     #.####....###.#.#.##
+    ///     ##.#################
+    ///     #####.##.###..####..
+    ///     ..######..##.#######
+    ///     ####.##.####...##..#
+    ///     .#####..#.######.###
+    ///     ##...#.##########...
+    ///     #.##########.#######
+    ///     .####.#.###.###.#.##
+    ///     ....##.##.###..#####
+    ///     .#.#.###########.###
+    ///     #.#.#.#####.####.###
+    ///     ###.##.####.##.#..##
+    ///
+    /// Find the best location for a new monitoring station. How many other asteroids can be detected from that location?
+    fn part1(map: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let (_, visible) = map.best_station();
 
-        vectors.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
-        vectors.dedup();
-        vectors.len()
+        Ok(Answer::from(visible))
     }
-}
 
-impl From<&str> for AsteroidMap {
-    fn from(string: &str) -> Self {
-        Self(
-            string
-                .trim()
-                .split('\n')
-                .enumerate()
-                .flat_map(|(y, line)| {
-                    line.chars()
-                        .enumerate()
-                        .filter(|(_, character)| *character == '#')
-                        .map(move |(x, _)| Point {
-                            x: x as i64,
-                            y: y as i64,
-                        })
-                })
-                .collect::<Vec<_>>(),
-        )
+    /// Once you give them the coordinates, the Elves quickly deploy an Instant Monitoring Station to the location and discover the worst: there are simply too many asteroids.
+    ///
+    /// The only solution is complete vaporization by giant laser.
+    ///
+    /// Fortunately, in addition to an asteroid scanner, the new monitoring station also comes equipped with a giant rotating laser perfect for vaporizing asteroids. The laser starts by pointing up and always rotates clockwise, vaporizing any asteroid it hits.
+    ///
+    /// If multiple asteroids are exactly in line with the station, the laser only has enough power to vaporize one of them before continuing its rotation. In other words, the same asteroids that can be detected can be vaporized, but if vaporizing one asteroid makes another one detectable, the newly-detected asteroid won't be vaporized until the laser has returned to the same position by rotating a full 360 degrees.
+    ///
+    /// The Elves are placing bets on which will be the 200th asteroid to be vaporized. Win the bet by determining which asteroid that will be; what do you get if you multiply its X coordinate by 100 and then add its Y coordinate? (For example, 8,2 becomes 802.)
+    fn part2(map: &Self::Parsed, params: &Params) -> Result<Answer, Error> {
+        let (station, _) = map.best_station();
+        let nth = params.get::<usize>(&VAPORIZED);
+
+        let asteroid = nth
+            .checked_sub(1)
+            .and_then(|i| map.vaporization_order(station).nth(i))
+            .ok_or_else(|| {
+                Error::NoAnswer(format!("fewer than {} asteroids are vaporized", nth))
+            })?;
+        Ok(Answer::from(asteroid.x * 100 + asteroid.y))
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Ord, PartialOrd)]
-struct Point {
-    x: i64,
-    y: i64,
+/// Which asteroid to be vaporized the bet is on, counting from 1
+pub const VAPORIZED: Param = Param {
+    name: "vaporized",
+    description: "which asteroid to be vaporized the bet is on, counting from 1",
+    default: 200,
+};
+
+/// Draws the laser sweeping around the best station, a frame to each asteroid it vaporizes, with
+/// the one bet on pointed out as it goes
+#[cfg(feature = "visualize")]
+pub fn part2_visualize(input: &str) -> Answer {
+    use crate::common::vis::{self, Renderer};
+
+    let map = Day10::parse(input).expect("Failed to parse the asteroid map");
+    let (station, _) = map.best_station();
+    let order: Vec<Point> = map.vaporization_order(station).collect();
+    let nth = Params::default().get::<usize>(&VAPORIZED);
+
+    {
+        let mut renderer = Renderer::new().expect("Failed to set up the terminal");
+
+        let mut sky = map.draw(station);
+        for (i, asteroid) in order.iter().enumerate() {
+            let mut frame = sky.clone();
+            for point in line_between(station, *asteroid) {
+                frame[(point.x as usize, point.y as usize)] = '·';
+            }
+            frame[(asteroid.x as usize, asteroid.y as usize)] = '*';
+
+            let bet = if i + 1 == nth {
+                " (the one bet on)"
+            } else {
+                ""
+            };
+            let header = format!(
+                "Vaporized {} of {}: {}{}",
+                i + 1,
+                order.len(),
+                asteroid,
+                bet
+            );
+            let hold = if i + 1 == nth {
+                vis::frames_per_second() as usize * 2
+            } else {
+                1
+            };
+            for _ in 0..hold {
+                renderer
+                    .render(&header, &frame)
+                    .expect("Failed to render frame");
+            }
+
+            sky[(asteroid.x as usize, asteroid.y as usize)] = ' ';
+        }
+    }
+
+    let asteroid = order[nth - 1];
+    Answer::from(asteroid.x * 100 + asteroid.y)
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Ord, PartialOrd)]
-struct Vector {
-    x: i64,
-    y: i64,
+/// Where each asteroid is, on a map `width` by `height`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsteroidMap {
+    asteroids: Vec<Point>,
+    width: usize,
+    height: usize,
 }
 
-impl Vector {
-    fn from_points(one: Point, two: Point) -> Self {
-        Self {
-            x: two.x - one.x,
-            y: two.y - one.y,
+impl AsteroidMap {
+    pub fn asteroids(&self) -> &[Point] {
+        &self.asteroids
+    }
+
+    /// The asteroid which can detect the most others, and how many it can detect
+    pub fn best_station(&self) -> (Point, usize) {
+        self.asteroids
+            .iter()
+            .map(|asteroid| (*asteroid, self.visible_from(*asteroid).len()))
+            .max_by_key(|(_, visible)| *visible)
+            .expect("an asteroid map has asteroids")
+    }
+
+    /// The asteroids which `station` has a direct line of sight to (the nearest in each
+    /// direction), in the order a laser sweeping clockwise from straight up meets them
+    pub fn visible_from(&self, station: Point) -> Vec<Point> {
+        self.lines_of_sight(station)
+            .into_iter()
+            .map(|line| line[0])
+            .collect()
+    }
+
+    /// Every asteroid other than `station` in the order its laser vaporizes them. It sweeps
+    /// clockwise from straight up, vaporizing the nearest asteroid in each direction it points,
+    /// so that those behind wait until it comes round again.
+    pub fn vaporization_order(&self, station: Point) -> impl Iterator<Item = Point> {
+        let mut order: Vec<(usize, usize, Point)> = self
+            .lines_of_sight(station)
+            .into_iter()
+            .enumerate()
+            .flat_map(|(direction, line)| {
+                line.into_iter()
+                    .enumerate()
+                    .map(move |(rotation, asteroid)| (rotation, direction, asteroid))
+            })
+            .collect();
+        order.sort_unstable_by_key(|(rotation, direction, _)| (*rotation, *direction));
+
+        order.into_iter().map(|(_, _, asteroid)| asteroid)
+    }
+
+    /// The asteroids other than `station` in each direction from it, nearest first, with the
+    /// directions in clockwise order from straight up
+    fn lines_of_sight(&self, station: Point) -> Vec<Vec<Point>> {
+        let mut others: Vec<Point> = self
+            .asteroids
+            .iter()
+            .copied()
+            .filter(|asteroid| *asteroid != station)
+            .collect();
+        // along a direction, the nearer asteroid is also the nearer one on the grid
+        others.sort_by(|a, b| {
+            (*a - station).cmp_clockwise(*b - station).then_with(|| {
+                station
+                    .manhattan_distance(*a)
+                    .cmp(&station.manhattan_distance(*b))
+            })
+        });
+
+        let mut lines: Vec<Vec<Point>> = Vec::new();
+        for asteroid in others {
+            match lines.last_mut() {
+                Some(line)
+                    if (line[0] - station).cmp_clockwise(asteroid - station) == Ordering::Equal =>
+                {
+                    line.push(asteroid)
+                }
+                _ => lines.push(vec![asteroid]),
+            }
         }
+
+        lines
     }
 
-    fn angle(&self) -> Angle {
-        Angle {
-            theta: (self.y as f64 / self.x as f64).atan(),
-            side: if self.x >= 0 { Side::Right } else { Side::Left },
+    /// The map as the puzzle draws it, with an `X` at `station`
+    #[cfg(feature = "visualize")]
+    fn draw(&self, station: Point) -> crate::common::grid::Grid2D<char> {
+        let mut sky = crate::common::grid::Grid2D::new(self.width, self.height, ' ');
+        for asteroid in &self.asteroids {
+            sky[(asteroid.x as usize, asteroid.y as usize)] = '#';
         }
+        sky[(station.x as usize, station.y as usize)] = 'X';
+
+        sky
     }
 }
 
-#[derive(Debug, PartialEq, Copy, Clone, PartialOrd)]
-struct Angle {
-    theta: f64,
+/// The cells between `from` and `to` (leaving out both) nearest to the straight line between them
+#[cfg(feature = "visualize")]
+fn line_between(from: Point, to: Point) -> impl Iterator<Item = Point> {
+    let offset = to - from;
+    let steps = offset.x.abs().max(offset.y.abs()).max(1);
+    // n / steps, rounded to the nearest whole number
+    let round = move |n: i64| (2 * n + steps).div_euclid(2 * steps);
 
-    // -0.0_f64 == 0.0_f64, but in this context we want to distinguish between them
-    side: Side,
+    (1..steps).map(move |step| from + Point::new(round(offset.x * step), round(offset.y * step)))
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Ord, PartialOrd)]
-enum Side {
-    Left,
-    Right,
+/// A square map as crowded with asteroids as a real one (which is 33 across, with over 300
+/// asteroids), whose area grows with the scale. Much smaller maps have too few asteroids for the
+/// 200th to be vaporized.
+pub fn generate(rng: &mut Rng, scale: Scale) -> String {
+    let side = scale.side(33);
+
+    (0..side)
+        .map(|_| {
+            let mut row: String = (0..side)
+                .map(|_| if rng.chance(0.3) { '#' } else { '.' })
+                .collect();
+            row.push('\n');
+            row
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::{examples, select};
+
+    examples!(2019, 10, Day10);
 
     #[test]
-    fn test_part1_examples() {
-        let examples = [
-            (
-                "
-.#..#
-.....
-#####
-....#
-...##
-",
-                Point { x: 3, y: 4 },
-                8,
-            ),
-            (
-                "
-......#.#.
-#..#.#....
-..#######.
-.#.#.###..
-.#..#.....
-..#....#.#
-#..#....#.
-.##.#..###
-##...#..#.
-.#....####
-            ",
-                Point { x: 5, y: 8 },
-                33,
-            ),
-            (
-                "
-#.#...#.#.
-.###....#.
-.#....#...
-##.#.#.#.#
-....#.#.#.
-.##..###.#
-..#...##..
-..##....##
-......#...
-.####.###.
-            ",
-                Point { x: 1, y: 2 },
-                35,
-            ),
-            (
-                "
-.#..#..###
-####.###.#
-....###.#.
-..###.##.#
-##.##.#.#.
-....###..#
-..#.#..#.#
-#..#.#.###
-.##...##.#
-.....#.#..
-            ",
-                Point { x: 6, y: 3 },
-                41,
-            ),
-            (
-                "
-.#..##.###...#######
-##.############..##.
-.#.######.########.#
-.###.#######.####.#.
-#####.##.#.##.###.##
-..#####..#.#########
-####################
-#.####....###.#.#.##
-##.#################
-#####.##.###..####..
-..######..##.#######
-####.##.####...##..#
-.#####..#.######.###
-##...#.##########...
-#.##########.#######
-.####.#.###.###.#.##
-....##.##.###..#####
-.#.#.###########.###
-#.#.#.#####.####.###
-###.##.####.##.#..##
-            ",
-                Point { x: 11, y: 13 },
-                210,
-            ),
-        ];
-
-        for example in &examples {
-            let best_station = AsteroidMap::from(example.0).calculate_best_monitoring_station();
-            assert_eq!(best_station.1, example.1);
-            assert_eq!(best_station.0, example.2);
-        }
+    fn test_best_station() {
+        let stations = select(2019, 10, 1)
+            .iter()
+            .map(|example| Day10::parse(example.input).unwrap().best_station().0)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stations,
+            [
+                Point::new(3, 4),
+                Point::new(5, 8),
+                Point::new(1, 2),
+                Point::new(6, 3),
+                Point::new(11, 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_vaporization_order() {
+        let map = Day10::parse(
+            ".#....#####...#..\n##...##.#####..##\n##...#...#.#####.\n..#.....#...###..\n..#.#.....#....##",
+        )
+        .unwrap();
+        let station = Point::new(8, 3);
+
+        let order: Vec<Point> = map.vaporization_order(station).collect();
+        assert_eq!(
+            order[..9],
+            [
+                Point::new(8, 1),
+                Point::new(9, 0),
+                Point::new(9, 1),
+                Point::new(10, 0),
+                Point::new(9, 2),
+                Point::new(11, 1),
+                Point::new(12, 1),
+                Point::new(11, 2),
+                Point::new(15, 1),
+            ]
+        );
+        assert_eq!(order.len(), map.asteroids().len() - 1);
+        assert_eq!(
+            map.visible_from(station),
+            order[..map.visible_from(station).len()]
+        );
+        // the asteroid straight up and behind 8,1 waits for the second time round
+        assert_eq!(order.iter().position(|a| *a == Point::new(8, 0)), Some(30));
     }
 }
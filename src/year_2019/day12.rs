@@ -0,0 +1,210 @@
+//! --- Day 12: The N-Body Problem ---
+
+use crate::answer::Answer;
+use crate::common::geometry::Point3;
+use crate::common::math::lcm_all;
+use crate::common::parse::ints_in;
+use crate::error::Error;
+use crate::params::{Param, Params};
+use crate::solutions::Solver;
+
+pub struct Day12;
+
+impl Solver for Day12 {
+    type Parsed = Vec<Point3>;
+
+    const PARAMS: &'static [Param] = &[STEPS];
+
+    /// The position of each moon
+    fn parse(input: &str) -> Result<Vec<Point3>, Error> {
+        let moons: Vec<Point3> = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| match ints_in(line)[..] {
+                [x, y, z] => Ok(Point3::new(x, y, z)),
+                _ => Err(Error::line(
+                    i,
+                    line,
+                    "isn't a position like <x=-1, y=0, z=2>",
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        if moons.is_empty() {
+            return Err(Error::Input("has no moons".to_string()));
+        }
+        Ok(moons)
+    }
+
+    /// The space near Jupiter is not a very safe place; you need to be careful of a big distracting red spot, extreme radiation, and a whole lot of moons swirling around. You decide to start by tracking the four largest moons: Io, Europa, Ganymede, and Callisto.
+    ///
+    /// After a brief scan, you calculate the position of each moon (your puzzle input). You just need to simulate their motion so you can avoid them.
+    ///
+    /// Each moon has a 3-dimensional position (x, y, and z) and a 3-dimensional velocity. The position of each moon is given in your scan; the x, y, and z velocity of each moon starts at 0.
+    ///
+    /// Simulate the motion of the moons in time steps. Within each time step, first update the velocity of every moon by applying gravity. Then, once all moons' velocities have been updated, update the position of every moon by applying velocity. Time progresses by one step once all of the positions are updated.
+    ///
+    /// To apply gravity, consider every pair of moons. On each axis (x, y, and z), the velocity of each moon changes by exactly +1 or -1 to pull the moons together. For example, if Ganymede has an x position of 3, and Callisto has a x position of 5, then Ganymede's x velocity changes by +1 (because 5 > 3) and Callisto's x velocity changes by -1 (because 3 < 5). However, if the positions on a given axis are the same, the velocity on that axis does not change for that pair of moons.
+    ///
+    /// Once all gravity has been applied, apply velocity: simply add the velocity of each moon to its own position. For example, if Europa has a position of x=1, y=2, z=3 and a velocity of x=-2, y=0,z=3, then its new position would be x=-1, y=2, z=6. This process does not modify the velocity of any moon.
+    ///
+    /// Then, it might help to calculate the total energy in the system. The total energy for a single moon is its potential energy multiplied by its kinetic energy. A moon's potential energy is the sum of the absolute values of its x, y, and z position coordinates. A moon's kinetic energy is the sum of the absolute values of its velocity coordinates.
+    ///
+    /// What is the total energy in the system after simulating the moons given in your scan for 1000 steps?
+    fn part1(moons: &Self::Parsed, params: &Params) -> Result<Answer, Error> {
+        let mut axes = axes(moons);
+        for _ in 0..params.get::<u64>(&STEPS) {
+            for axis in &mut axes {
+                axis.step();
+            }
+        }
+
+        Ok(Answer::from(total_energy(&axes)))
+    }
+
+    /// All this drifting around in space makes you wonder about the nature of the universe. Does history really repeat itself? You're curious whether the moons will ever return to a previous state.
+    ///
+    /// Determine the number of steps that must occur before all of the moons' positions and velocities exactly match a previous point in time.
+    ///
+    /// Of course, the universe might last for a very long time before repeating. Clearly, you might need to find a more efficient way to simulate the universe.
+    ///
+    /// How many steps does it take to reach the first state that exactly matches a previous state?
+    fn part2(moons: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        // the axes don't affect one another, so the moons are all back where they started once
+        // every axis is
+        Ok(Answer::from(lcm_all(axes(moons).iter().map(Axis::period))))
+    }
+}
+
+/// How many steps the moons are simulated for before their energy is measured
+pub const STEPS: Param = Param {
+    name: "steps",
+    description: "how many steps the moons move before their energy is measured",
+    default: 1000,
+};
+
+/// The positions and velocities of every moon along one axis, which move without regard to the
+/// other axes
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Axis {
+    positions: Vec<i64>,
+    velocities: Vec<i64>,
+}
+
+impl Axis {
+    fn new(positions: Vec<i64>) -> Self {
+        Self {
+            velocities: vec![0; positions.len()],
+            positions,
+        }
+    }
+
+    /// Moves the moons on a time step: pulling each pair of moons together, then moving each
+    /// moon by its velocity
+    fn step(&mut self) {
+        for (i, position) in self.positions.iter().enumerate() {
+            for other in &self.positions {
+                self.velocities[i] += (other - position).signum();
+            }
+        }
+
+        for (position, velocity) in self.positions.iter_mut().zip(&self.velocities) {
+            *position += velocity;
+        }
+    }
+
+    /// How many steps it takes the moons to be back where they started along this axis
+    ///
+    /// A step can be undone (by moving the moons back, then pushing them apart), so no two states
+    /// lead to the same one, and the first state to come round again is the one they start in.
+    fn period(&self) -> i64 {
+        let mut axis = self.clone();
+        let mut steps = 0;
+        loop {
+            axis.step();
+            steps += 1;
+            if axis == *self {
+                return steps;
+            }
+        }
+    }
+}
+
+/// The moons at `positions` and standing still, along each axis
+fn axes(positions: &[Point3]) -> [Axis; 3] {
+    [
+        Axis::new(positions.iter().map(|position| position.x).collect()),
+        Axis::new(positions.iter().map(|position| position.y).collect()),
+        Axis::new(positions.iter().map(|position| position.z).collect()),
+    ]
+}
+
+/// The sum over every moon of its potential energy (the size of its position) multiplied by its
+/// kinetic energy (the size of its velocity)
+fn total_energy(axes: &[Axis; 3]) -> i64 {
+    (0..axes[0].positions.len())
+        .map(|i| {
+            let potential: i64 = axes.iter().map(|axis| axis.positions[i].abs()).sum();
+            let kinetic: i64 = axes.iter().map(|axis| axis.velocities[i].abs()).sum();
+            potential * kinetic
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::examples;
+    use proptest::prelude::*;
+
+    examples!(2019, 12, Day12);
+
+    /// The total energy after `steps` steps, simulating the moons whole rather than an axis at a
+    /// time
+    fn energy_by_moon(positions: &[Point3], steps: usize) -> i64 {
+        let mut moons: Vec<(Point3, Point3)> = positions
+            .iter()
+            .map(|position| (*position, Point3::ORIGIN))
+            .collect();
+
+        for _ in 0..steps {
+            for i in 0..moons.len() {
+                for j in 0..moons.len() {
+                    let pull = (moons[j].0 - moons[i].0).signum();
+                    moons[i].1 += pull;
+                }
+            }
+            for (position, velocity) in &mut moons {
+                *position += *velocity;
+            }
+        }
+
+        let size = |point: Point3| point.manhattan_distance(Point3::ORIGIN);
+        moons
+            .iter()
+            .map(|(position, velocity)| size(*position) * size(*velocity))
+            .sum()
+    }
+
+    fn moons() -> impl Strategy<Value = Vec<Point3>> {
+        prop::collection::vec(
+            (-20..=20_i64, -20..=20_i64, -20..=20_i64).prop_map(Point3::from),
+            1..6,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn test_total_energy(moons in moons(), steps in 0..200_usize) {
+            let mut axes = axes(&moons);
+            for _ in 0..steps {
+                for axis in &mut axes {
+                    axis.step();
+                }
+            }
+
+            prop_assert_eq!(total_energy(&axes), energy_by_moon(&moons, steps));
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! --- Day 13: Care Package ---
+
+use super::arcade_cabinet::{self, Joystick, Tile};
+use super::IntcodeProgram;
+#[cfg(feature = "visualize")]
+use super::IntcodeWord;
+use crate::answer::Answer;
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day13;
+
+impl Solver for Day13 {
+    type Parsed = IntcodeProgram;
+
+    fn parse(input: &str) -> Result<IntcodeProgram, Error> {
+        IntcodeProgram::parse(input)
+    }
+
+    /// As you ponder the solitude of space and the ever-increasing three-hour roundtrip for messages between you and Earth, you notice that the Space Mail Indicator Light is blinking. To help keep you sane, the Elves have sent you a care package.
+    ///
+    /// It's a new game for the ship's arcade cabinet! Unfortunately, the arcade is all the way on the other end of the ship. Surely, it won't be hard to build your own - the care package even comes with schematics.
+    ///
+    /// The arcade cabinet runs Intcode software like the game the Elves sent (your puzzle input). It has a primitive screen capable of drawing square tiles on a grid. The software draws tiles to the screen with output instructions: every three output instructions specify the x position (distance from the left), y position (distance from the top), and tile id. The tile id is interpreted as follows:
+    ///
+    ///     0 is an empty tile. No game object appears in this tile.
+    ///     1 is a wall tile. Walls are indestructible barriers.
+    ///     2 is a block tile. Blocks can be broken by the ball.
+    ///     3 is a horizontal paddle tile. The paddle is indestructible.
+    ///     4 is a ball tile. The ball moves diagonally and bounces off objects.
+    ///
+    /// For example, a sequence of output values like 1,2,3,6,5,4 would draw a horizontal paddle tile (1 tile from the left and 2 tiles from the top) and a ball tile (6 tiles from the left and 5 tiles from the top).
+    ///
+    /// Start the game. How many block tiles are on the screen when the game exits?
+    fn part1(program: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let screen = arcade_cabinet::play(program, |_| Joystick::Neutral)?;
+
+        Ok(Answer::from(screen.count(Tile::Block)))
+    }
+
+    /// The game didn't run because you didn't put in any quarters. Unfortunately, you did not bring any quarters. Memory address 0 represents the number of quarters that have been inserted; set it to 2 to play for free.
+    ///
+    /// The arcade cabinet has a joystick that can move left and right. The software reads the position of the joystick with input instructions:
+    ///
+    ///     If the joystick is in the neutral position, provide 0.
+    ///     If the joystick is tilted to the left, provide -1.
+    ///     If the joystick is tilted to the right, provide 1.
+    ///
+    /// The arcade cabinet also has a segment display capable of showing a single number that represents the player's current score. When three output instructions specify X=-1, Y=0, the third output instruction is not a tile; the value instead specifies the new score to show in the segment display. For example, a sequence of output values like -1,0,12345 would show 12345 as the player's current score.
+    ///
+    /// Beat the game by breaking all the blocks. What is your score after the last block is broken?
+    fn part2(program: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let program = free_play(program.clone())?;
+
+        let screen = arcade_cabinet::play(&program, Joystick::track_ball)?;
+
+        Ok(Answer::from(screen.score()))
+    }
+}
+
+/// Plays part 2 in the terminal, a frame per game tick. The paddle-tracking AI plays until an arrow
+/// key is pressed, after which the arrow keys tilt the joystick (holding it neutral in any tick
+/// without one) until `a` hands the game back to the AI.
+#[cfg(feature = "visualize")]
+pub fn part2_visualize(input: &str) -> IntcodeWord {
+    use crate::common::vis::Renderer;
+    use crossterm::event::KeyCode;
+
+    let program = free_play(IntcodeProgram::from(input)).expect("Failed to insert quarters");
+
+    let screen = {
+        let mut renderer = Renderer::new().expect("Failed to set up the terminal");
+        let mut playing = false;
+
+        arcade_cabinet::play(&program, |screen| {
+            let player = if playing {
+                "you're playing: arrows move, a hands back to the AI"
+            } else {
+                "the AI is playing: arrows take over"
+            };
+            renderer
+                .render(&format!("Score: {}  ({})", screen.score(), player), screen)
+                .expect("Failed to render frame");
+
+            let mut joystick = Joystick::Neutral;
+            for key in renderer.take_pressed() {
+                match key {
+                    KeyCode::Left => (playing, joystick) = (true, Joystick::Left),
+                    KeyCode::Right => (playing, joystick) = (true, Joystick::Right),
+                    KeyCode::Down => (playing, joystick) = (true, Joystick::Neutral),
+                    KeyCode::Char('a') => playing = false,
+                    _ => {}
+                }
+            }
+
+            // nobody can play once the frames stop being drawn
+            if playing && !renderer.is_stopped() {
+                joystick
+            } else {
+                Joystick::track_ball(screen)
+            }
+        })
+        .expect("Failed to play the game")
+    };
+
+    screen.score()
+}
+
+/// Sets the number of quarters inserted (memory address 0) to 2
+fn free_play(mut program: IntcodeProgram) -> Result<IntcodeProgram, Error> {
+    program
+        .patch()
+        .set(0, 2)
+        .map_err(|_| Error::Input("is too short to be an arcade game".to_string()))?;
+    Ok(program)
+}
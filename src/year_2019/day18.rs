@@ -0,0 +1,327 @@
+//! --- Day 18: Many-Worlds Interpretation ---
+//!
+//! As you approach Neptune, a planetary security system detects you and activates a giant tractor beam on Triton! You have no choice but to land.
+
+use crate::answer::Answer;
+use crate::common::grid::Grid2D;
+use crate::common::pathfinding::{bfs, dijkstra};
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+use std::collections::HashMap;
+
+pub struct Day18;
+
+impl Solver for Day18 {
+    type Parsed = Vault;
+
+    fn parse(input: &str) -> Result<Vault, Error> {
+        let map = Grid2D::parse(input.trim_end(), |_, c| match c {
+            '#' => Ok(Cell::Wall),
+            '.' | '@' => Ok(Cell::Open),
+            'a'..='z' => Ok(Cell::Key(c as u8 - b'a')),
+            'A'..='Z' => Ok(Cell::Door(c as u8 - b'A')),
+            _ => Err(format!("has {:?}, which isn't part of a vault", c)),
+        })?;
+        let entrances: Vec<(usize, usize)> = input
+            .lines()
+            .enumerate()
+            .flat_map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .filter(|(_, c)| *c == '@')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+
+        if entrances.is_empty() {
+            return Err(Error::Input("has no entrance".to_string()));
+        }
+        Ok(Vault { map, entrances })
+    }
+
+    /// A scan of the local area reveals only one interesting feature: a massive underground vault. You generate a map of the tunnels (your puzzle input). The tunnels are too narrow to move diagonally.
+    ///
+    /// Only one entrance (marked @) is present among the open passages (marked .) and stone walls (#), but you also detect an assortment of keys (shown as lowercase letters) and doors (shown as uppercase letters). Keys of a given letter open the door of the same letter: a opens A, b opens B, and so on. You aren't sure which key you need to disable the tractor beam, so you'll need to collect all of them.
+    ///
+    /// How many steps is the shortest path that collects all of the keys?
+    fn part1(vault: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(vault.fewest_steps()?))
+    }
+
+    /// You arrive at the vault only to discover that there is not one vault, but four - each with its own entrance.
+    ///
+    /// On your map, find the area in the middle that looks like this:
+    ///
+    /// ```text
+    /// ...
+    /// .@.
+    /// ...
+    /// ```
+    ///
+    /// Update your map to instead use the correct data:
+    ///
+    /// ```text
+    /// @#@
+    /// ###
+    /// @#@
+    /// ```
+    ///
+    /// This change will split your map into four separate sections, each with its own entrance. Because some of the keys are for doors in other vaults, it would take much too long to collect all of the keys by yourself. Instead, you deploy four remote-controlled robots. Each starts at one of the entrances (@).
+    ///
+    /// Your goal is still to collect all of the keys in the fewest steps, but now, each robot has its own position and can move independently. You can only remotely control a single robot at a time. Collecting a key instantly unlocks any corresponding doors, regardless of the vault in which the key or door is found.
+    ///
+    /// After updating your map and using the remote-controlled robots, what is the fewest steps necessary to collect all of the keys?
+    fn part2(vault: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        Ok(Answer::from(vault.split()?.fewest_steps()?))
+    }
+}
+
+/// The map of the tunnels, and the entrance each robot starts at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Vault {
+    map: Grid2D<Cell>,
+    entrances: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Wall,
+    Open,
+    /// A key, numbered from 0 for `a`
+    Key(u8),
+    /// A door, numbered as the key which opens it is
+    Door(u8),
+}
+
+/// A set of keys, a bit to each. The searches go through a lot of these, so unlike a `BitSet` they
+/// are small enough to copy, with room for every letter.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Keys(u32);
+
+impl Keys {
+    fn with(self, key: u8) -> Self {
+        Self(self.0 | 1 << key)
+    }
+
+    fn union(self, other: Keys) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    pub fn contains(self, key: u8) -> bool {
+        self.0 & 1 << key != 0
+    }
+
+    pub fn contains_all(self, other: Keys) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+/// The way from an entrance or a key to another key, through the tunnels alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Route {
+    pub key: u8,
+    pub steps: usize,
+    /// The doors on the way, which can only be gone through with their keys
+    pub doors: Keys,
+    /// The keys picked up on the way, including the one at the end
+    pub keys: Keys,
+}
+
+/// Where every robot is, as the entrance it started at or the key it last picked up, and the keys
+/// picked up so far
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct State {
+    robots: Vec<Spot>,
+    keys: Keys,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Spot {
+    Entrance(usize),
+    Key(u8),
+}
+
+impl Vault {
+    /// The fewest steps for the robots to pick up every key between them, one moving at a time
+    ///
+    /// The tunnels between the keys are boiled down into the routes from each entrance and key to
+    /// every other key first, so that the search only has to pick which key a robot goes to next.
+    pub fn fewest_steps(&self) -> Result<usize, Error> {
+        let all_keys = self.keys();
+        let routes = self.routes();
+        let start = State {
+            robots: (0..self.entrances.len()).map(Spot::Entrance).collect(),
+            keys: Keys::default(),
+        };
+
+        let paths = dijkstra(start, |state| {
+            let mut next = Vec::new();
+            for (robot, spot) in state.robots.iter().enumerate() {
+                let open = routes[spot].iter().filter(|route| {
+                    !state.keys.contains(route.key) && state.keys.contains_all(route.doors)
+                });
+                for route in open {
+                    let mut robots = state.robots.clone();
+                    robots[robot] = Spot::Key(route.key);
+                    let keys = state.keys.union(route.keys);
+                    next.push((State { robots, keys }, route.steps));
+                }
+            }
+            next
+        });
+
+        paths
+            .iter()
+            .filter(|(state, _)| state.keys == all_keys)
+            .map(|(_, steps)| steps)
+            .min()
+            .ok_or_else(|| Error::NoAnswer("the keys can't all be picked up".to_string()))
+    }
+
+    /// The vault with its entrance walled off and a robot in each corner around it, as the
+    /// puzzle's updated map has it. A vault which already has more than one entrance is as it is.
+    pub fn split(&self) -> Result<Vault, Error> {
+        let (x, y) = match self.entrances[..] {
+            [entrance] => entrance,
+            _ => return Ok(self.clone()),
+        };
+        let around = |dx: usize, dy: usize| (x + dx - 1, y + dy - 1);
+        let open = (0..3)
+            .flat_map(|dy| (0..3).map(move |dx| (dx, dy)))
+            .all(|(dx, dy)| x > 0 && y > 0 && self.map.get(around(dx, dy)) == Some(&Cell::Open));
+        if !open {
+            return Err(Error::Input(
+                "has no open space around its entrance to split it with walls".to_string(),
+            ));
+        }
+
+        let mut map = self.map.clone();
+        for (dx, dy) in [(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)] {
+            map[around(dx, dy)] = Cell::Wall;
+        }
+        let entrances = [(0, 0), (2, 0), (0, 2), (2, 2)]
+            .iter()
+            .map(|(dx, dy)| around(*dx, *dy))
+            .collect();
+
+        Ok(Vault { map, entrances })
+    }
+
+    /// Every key in the vault
+    pub fn keys(&self) -> Keys {
+        self.map
+            .iter()
+            .fold(Keys::default(), |keys, (_, cell)| match cell {
+                Cell::Key(key) => keys.with(*key),
+                _ => keys,
+            })
+    }
+
+    /// The shortest way from each entrance and key to every key it can reach, whatever doors
+    /// are in the way
+    fn routes(&self) -> HashMap<Spot, Vec<Route>> {
+        let entrances = self
+            .entrances
+            .iter()
+            .enumerate()
+            .map(|(i, position)| (Spot::Entrance(i), *position));
+        let keys = self.map.iter().filter_map(|(position, cell)| match cell {
+            Cell::Key(key) => Some((Spot::Key(*key), position)),
+            _ => None,
+        });
+
+        entrances
+            .chain(keys)
+            .map(|(spot, position)| (spot, self.routes_from(position)))
+            .collect()
+    }
+
+    fn routes_from(&self, start: (usize, usize)) -> Vec<Route> {
+        let paths = bfs(start, |position| {
+            self.map
+                .neighbors4(*position)
+                .filter(|neighbor| self.map[*neighbor] != Cell::Wall)
+                .collect::<Vec<_>>()
+        });
+
+        let mut routes: Vec<Route> = paths
+            .iter()
+            .filter(|(position, _)| **position != start)
+            .filter_map(|(position, steps)| match self.map[*position] {
+                Cell::Key(key) => Some((position, key, steps)),
+                _ => None,
+            })
+            .map(|(position, key, steps)| {
+                let path = paths.path_to(position).unwrap_or_default();
+                let (doors, keys) = path.iter().skip(1).fold(
+                    (Keys::default(), Keys::default()),
+                    |(doors, keys), step| match self.map[*step] {
+                        Cell::Door(door) => (doors.with(door), keys),
+                        Cell::Key(key) => (doors, keys.with(key)),
+                        _ => (doors, keys),
+                    },
+                );
+                Route {
+                    key,
+                    steps,
+                    doors,
+                    keys,
+                }
+            })
+            .collect();
+        routes.sort_unstable_by_key(|route| route.key);
+
+        routes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::{examples, select};
+
+    examples!(2019, 18, Day18);
+
+    #[test]
+    fn test_routes() {
+        let vault = Day18::parse(select(2019, 18, 1)[1].input).unwrap();
+        assert_eq!(vault.keys().len(), 6);
+
+        // from the entrance, a is in the open, while the way to b goes through A
+        let routes = vault.routes();
+        let from_entrance = &routes[&Spot::Entrance(0)];
+        assert_eq!(from_entrance.len(), 6);
+        assert_eq!(
+            from_entrance[0],
+            Route {
+                key: 0,
+                steps: 2,
+                doors: Keys::default(),
+                keys: Keys::default().with(0),
+            }
+        );
+        assert_eq!(from_entrance[1].steps, 4);
+        assert!(from_entrance[1].doors.contains(0));
+        assert_eq!(from_entrance[1].keys, Keys::default().with(1));
+
+        let split = Day18::parse("#######\n#a.#Cd#\n##...##\n##.@.##\n##...##\n#cB#Ab#\n#######")
+            .unwrap()
+            .split()
+            .unwrap();
+        assert_eq!(split.entrances, [(2, 2), (4, 2), (2, 4), (4, 4)]);
+        assert_eq!(split.fewest_steps(), Ok(8));
+        assert!(Day18::parse(select(2019, 18, 1)[0].input)
+            .unwrap()
+            .split()
+            .is_err());
+    }
+}
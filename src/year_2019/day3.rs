@@ -1,135 +1,155 @@
 //! --- Day 3: Crossed Wires ---
 
-/// The gravity assist was successful, and you're well on your way to the Venus refuelling station. During the rush back on Earth, the fuel management system wasn't completely installed, so that's next on the priority list.
-///
-/// Opening the front panel reveals a jumble of wires. Specifically, two wires are connected to a central port and extend outward on a grid. You trace the path each wire takes as it leaves the central port, one wire per line of text (your puzzle input).
-///
-/// The wires twist and turn, but the two wires occasionally cross paths. To fix the circuit, you need to find the intersection point closest to the central port. Because the wires are on a grid, use the Manhattan distance for this measurement. While the wires do technically cross right at the central port where they both start, this point does not count, nor does a wire count as crossing with itself.
-///
-/// For example, if the first wire's path is R8,U5,L5,D3, then starting from the central port (o), it goes right 8, up 5, left 5, and finally down 3:
-///
-/// ...........
-/// ...........
-/// ...........
-/// ....+----+.
-/// ....|....|.
-/// ....|....|.
-/// ....|....|.
-/// .........|.
-/// .o-------+.
-/// ...........
-///
-/// Then, if the second wire's path is U7,R6,D4,L4, it goes up 7, right 6, down 4, and left 4:
-///
-/// ...........
-/// .+-----+...
-/// .|.....|...
-/// .|..+--X-+.
-/// .|..|..|.|.
-/// .|.-X--+.|.
-/// .|..|....|.
-/// .|.......|.
-/// .o-------+.
-/// ...........
-///
-/// These wires cross at two locations (marked X), but the lower-left one is closer to the central port: its distance is 3 + 3 = 6.
-///
-/// Here are a few more examples:
-///
-///     R75,D30,R83,U83,L12,D49,R71,U7,L72
-///     U62,R66,U55,R34,D71,R55,D58,R83 = distance 159
-///     R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
-///     U98,R91,D20,R16,D67,R40,U7,R15,U6,R7 = distance 135
-///
-/// What is the Manhattan distance from the central port to the closest intersection?
-pub fn part1() {
-    let input = crate::common::read_stdin_to_string();
-    let wires = Wire::parse_wires(input.as_str());
-
-    let first_wire = wires.get(0).expect("Missing first wire");
-    let second_wire = wires.get(1).expect("Missing second wire");
-
-    let origin = Point::zero();
-    let min_distance = first_wire
-        .intersections(second_wire)
-        .iter()
-        .map(|intersection| origin.manhattan_distance(intersection))
-        .min()
-        .expect("No intersections found!");
-
-    println!(
-        "The Manhattan distance from the central port to the closest intersection: {}",
-        min_distance
-    );
-}
+use crate::answer::Answer;
+use crate::common::geometry::{Direction, Point};
+use crate::error::Error;
+use crate::params::Params;
+use crate::solutions::Solver;
+
+pub struct Day3;
+
+impl Solver for Day3 {
+    type Parsed = Vec<Wire>;
 
-/// It turns out that this circuit is very timing-sensitive; you actually need to minimize the signal delay.
-///
-/// To do this, calculate the number of steps each wire takes to reach each intersection; choose the intersection where the sum of both wires' steps is lowest. If a wire visits a position on the grid multiple times, use the steps value from the first time it visits that position when calculating the total value of a specific intersection.
-///
-/// The number of steps a wire takes is the total number of grid squares the wire has entered to get to that location, including the intersection being considered. Again consider the example from above:
-///
-/// ...........
-/// .+-----+...
-/// .|.....|...
-/// .|..+--X-+.
-/// .|..|..|.|.
-/// .|.-X--+.|.
-/// .|..|....|.
-/// .|.......|.
-/// .o-------+.
-/// ...........
-///
-/// In the above example, the intersection closest to the central port is reached after 8+5+5+2 = 20 steps by the first wire and 7+6+4+3 = 20 steps by the second wire for a total of 20+20 = 40 steps.
-///
-/// However, the top-right intersection is better: the first wire takes only 8+5+2 = 15 and the second wire takes only 7+6+2 = 15, a total of 15+15 = 30 steps.
-///
-/// Here are the best steps for the extra examples from above:
-///
-///     R75,D30,R83,U83,L12,D49,R71,U7,L72
-///     U62,R66,U55,R34,D71,R55,D58,R83 = 610 steps
-///     R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
-///     U98,R91,D20,R16,D67,R40,U7,R15,U6,R7 = 410 steps
-///
-/// What is the fewest combined steps the wires must take to reach an intersection?
-pub fn part2() {
-    let input = crate::common::read_stdin_to_string();
-    let wires = Wire::parse_wires(input.as_str());
-
-    let first_wire = wires.get(0).expect("Missing first wire");
-    let second_wire = wires.get(1).expect("Missing second wire");
-
-    let min_distance = first_wire
-        .intersections(second_wire)
-        .iter()
-        .map(|intersection| {
-            first_wire.trace_distance(intersection) + second_wire.trace_distance(intersection)
-        })
-        .min()
-        .expect("No intersections found!");
-
-    println!(
-        "The fewest combined steps the wires must take to reach an intersection: {}",
-        min_distance
-    );
+    fn parse(input: &str) -> Result<Vec<Wire>, Error> {
+        Wire::parse_wires(input)
+    }
+
+    /// The gravity assist was successful, and you're well on your way to the Venus refuelling station. During the rush back on Earth, the fuel management system wasn't completely installed, so that's next on the priority list.
+    ///
+    /// Opening the front panel reveals a jumble of wires. Specifically, two wires are connected to a central port and extend outward on a grid. You trace the path each wire takes as it leaves the central port, one wire per line of text (your puzzle input).
+    ///
+    /// The wires twist and turn, but the two wires occasionally cross paths. To fix the circuit, you need to find the intersection point closest to the central port. Because the wires are on a grid, use the Manhattan distance for this measurement. While the wires do technically cross right at the central port where they both start, this point does not count, nor does a wire count as crossing with itself.
+    ///
+    /// For example, if the first wire's path is R8,U5,L5,D3, then starting from the central port (o), it goes right 8, up 5, left 5, and finally down 3:
+    ///
+    /// ...........
+    /// ...........
+    /// ...........
+    /// ....+----+.
+    /// ....|....|.
+    /// ....|....|.
+    /// ....|....|.
+    /// .........|.
+    /// .o-------+.
+    /// ...........
+    ///
+    /// Then, if the second wire's path is U7,R6,D4,L4, it goes up 7, right 6, down 4, and left 4:
+    ///
+    /// ...........
+    /// .+-----+...
+    /// .|.....|...
+    /// .|..+--X-+.
+    /// .|..|..|.|.
+    /// .|.-X--+.|.
+    /// .|..|....|.
+    /// .|.......|.
+    /// .o-------+.
+    /// ...........
+    ///
+    /// These wires cross at two locations (marked X), but the lower-left one is closer to the central port: its distance is 3 + 3 = 6.
+    ///
+    /// Here are a few more examples:
+    ///
+    ///     R75,D30,R83,U83,L12,D49,R71,U7,L72
+    ///     U62,R66,U55,R34,D71,R55,D58,R83 = distance 159
+    ///     R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
+    ///     U98,R91,D20,R16,D67,R40,U7,R15,U6,R7 = distance 135
+    ///
+    /// What is the Manhattan distance from the central port to the closest intersection?
+    fn part1(wires: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let (first_wire, second_wire) = (&wires[0], &wires[1]);
+
+        first_wire
+            .intersections(second_wire)
+            .iter()
+            .map(|intersection| intersection.manhattan_distance(Point::ORIGIN))
+            .min()
+            .map(Answer::from)
+            .ok_or_else(|| Error::NoAnswer("the wires never cross".to_string()))
+    }
+
+    /// It turns out that this circuit is very timing-sensitive; you actually need to minimize the signal delay.
+    ///
+    /// To do this, calculate the number of steps each wire takes to reach each intersection; choose the intersection where the sum of both wires' steps is lowest. If a wire visits a position on the grid multiple times, use the steps value from the first time it visits that position when calculating the total value of a specific intersection.
+    ///
+    /// The number of steps a wire takes is the total number of grid squares the wire has entered to get to that location, including the intersection being considered. Again consider the example from above:
+    ///
+    /// ...........
+    /// .+-----+...
+    /// .|.....|...
+    /// .|..+--X-+.
+    /// .|..|..|.|.
+    /// .|.-X--+.|.
+    /// .|..|....|.
+    /// .|.......|.
+    /// .o-------+.
+    /// ...........
+    ///
+    /// In the above example, the intersection closest to the central port is reached after 8+5+5+2 = 20 steps by the first wire and 7+6+4+3 = 20 steps by the second wire for a total of 20+20 = 40 steps.
+    ///
+    /// However, the top-right intersection is better: the first wire takes only 8+5+2 = 15 and the second wire takes only 7+6+2 = 15, a total of 15+15 = 30 steps.
+    ///
+    /// Here are the best steps for the extra examples from above:
+    ///
+    ///     R75,D30,R83,U83,L12,D49,R71,U7,L72
+    ///     U62,R66,U55,R34,D71,R55,D58,R83 = 610 steps
+    ///     R98,U47,R26,D63,R33,U87,L62,D20,R33,U53,R51
+    ///     U98,R91,D20,R16,D67,R40,U7,R15,U6,R7 = 410 steps
+    ///
+    /// What is the fewest combined steps the wires must take to reach an intersection?
+    fn part2(wires: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let (first_wire, second_wire) = (&wires[0], &wires[1]);
+
+        first_wire
+            .intersections(second_wire)
+            .iter()
+            .map(|intersection| {
+                first_wire.trace_distance(intersection) + second_wire.trace_distance(intersection)
+            })
+            .min()
+            .map(Answer::from)
+            .ok_or_else(|| Error::NoAnswer("the wires never cross".to_string()))
+    }
 }
 
 #[derive(Debug)]
-struct Wire {
+pub struct Wire {
     points: Vec<Point>,
 }
 
 impl Default for Wire {
     fn default() -> Self {
         Self {
-            points: vec![Point::zero()],
+            points: vec![Point::ORIGIN],
         }
     }
 }
 
 impl Wire {
-    fn parse_wires(serialized: &str) -> Vec<Self> {
-        serialized.trim().split('\n').map(Wire::from).collect()
+    /// Parses the two wires of the puzzle input, one to a line
+    fn parse_wires(serialized: &str) -> Result<Vec<Self>, Error> {
+        let wires = serialized
+            .trim()
+            .split('\n')
+            .enumerate()
+            .map(|(i, line)| Wire::parse(line).map_err(|reason| Error::line(i, line, reason)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        match wires.len() {
+            2 => Ok(wires),
+            count => Err(Error::Input(format!("has {} wires rather than 2", count))),
+        }
+    }
+
+    fn parse(wire_serialized: &str) -> Result<Self, String> {
+        let mut wire: Self = Default::default();
+
+        for wire_segment in wire_serialized.trim().split(',') {
+            wire.add_point_from_segment(wire_segment)?;
+        }
+
+        Ok(wire)
     }
 
     fn intersections(&self, target: &Wire) -> Vec<Point> {
@@ -170,7 +190,7 @@ impl Wire {
                         && target_y <= max_y
                     {
                         let intersection = Point::new(x, target_y);
-                        if intersection == Point::zero() {
+                        if intersection == Point::ORIGIN {
                             continue;
                         }
                         intersections.push(intersection);
@@ -187,7 +207,7 @@ impl Wire {
                         && target_x <= max_x
                     {
                         let intersection = Point::new(target_x, y);
-                        if intersection == Point::zero() {
+                        if intersection == Point::ORIGIN {
                             continue;
                         }
                         intersections.push(intersection);
@@ -236,94 +256,31 @@ impl Wire {
         distance
     }
 
-    fn add_point_from_segment(&mut self, wire_segment: &str) {
-        let last_point = self.points.last().cloned().unwrap_or_else(Point::zero);
+    fn add_point_from_segment(&mut self, wire_segment: &str) -> Result<(), String> {
+        let last_point = self.points.last().copied().unwrap_or(Point::ORIGIN);
 
+        let not_a_segment = || format!("has {:?}, which isn't a segment like U7", wire_segment);
         let mut chars = wire_segment.chars();
-        let direction: WireSegmentDirection = chars
+        let direction = chars
             .next()
-            .unwrap_or_else(|| panic!("Failed to read wire segment {}", wire_segment))
-            .into();
-        let distance: i64 = chars
-            .as_str()
-            .parse()
-            .expect("Failed to parse distance as u64");
-
-        match direction {
-            WireSegmentDirection::Up => self
-                .points
-                .push(Point::new(last_point.x, last_point.y + distance)),
-            WireSegmentDirection::Down => self
-                .points
-                .push(Point::new(last_point.x, last_point.y - distance)),
-            WireSegmentDirection::Left => self
-                .points
-                .push(Point::new(last_point.x - distance, last_point.y)),
-            WireSegmentDirection::Right => self
-                .points
-                .push(Point::new(last_point.x + distance, last_point.y)),
-        }
-    }
-}
-
-impl From<&str> for Wire {
-    fn from(wire_serialized: &str) -> Self {
-        let mut wire: Self = Default::default();
+            .filter(char::is_ascii_uppercase)
+            .and_then(Direction::from_char)
+            .ok_or_else(not_a_segment)?;
+        let distance: i64 = chars.as_str().parse().map_err(|_| not_a_segment())?;
 
-        wire_serialized
-            .trim()
-            .split(',')
-            .for_each(|wire_segment| wire.add_point_from_segment(wire_segment));
+        let point = last_point
+            .checked_steps(direction, distance)
+            .ok_or_else(|| format!("has {:?}, which takes the wire too far", wire_segment))?;
+        self.points.push(point);
 
-        wire
-    }
-}
-
-enum WireSegmentDirection {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl From<char> for WireSegmentDirection {
-    fn from(c: char) -> Self {
-        match c {
-            'U' => Self::Up,
-            'D' => Self::Down,
-            'L' => Self::Left,
-            'R' => Self::Right,
-            other => panic!(
-                "Invalid wire segment direction (must be U,D,L or R): {}",
-                other
-            ),
-        }
-    }
-}
-
-#[derive(Debug, Default, PartialEq, Copy, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
-}
-
-impl Point {
-    fn new(x: i64, y: i64) -> Self {
-        Self { x, y }
-    }
-
-    fn zero() -> Self {
-        Default::default()
-    }
-
-    fn manhattan_distance(&self, to: &Point) -> i64 {
-        (self.x - to.x).abs() + (self.y - to.y).abs()
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::examples;
 
     #[test]
     fn test_manhattan_distance() {
@@ -338,7 +295,7 @@ mod tests {
         ];
 
         for example in &examples {
-            assert_eq!(example.0.manhattan_distance(&example.1), example.2);
+            assert_eq!(example.0.manhattan_distance(example.1), example.2);
         }
     }
 
@@ -357,16 +314,15 @@ mod tests {
         ];
 
         for example in &examples {
-            let wires = Wire::parse_wires(example.0);
+            let wires = Wire::parse_wires(example.0).unwrap();
 
-            let first_wire = wires.get(0).expect("Missing first wire");
+            let first_wire = wires.first().expect("Missing first wire");
             let second_wire = wires.get(1).expect("Missing second wire");
 
-            let origin = Point::zero();
             let min_distance = first_wire
                 .intersections(second_wire)
                 .iter()
-                .map(|intersection| origin.manhattan_distance(intersection))
+                .map(|intersection| intersection.manhattan_distance(Point::ORIGIN))
                 .min()
                 .expect("No intersections found!");
 
@@ -389,9 +345,9 @@ mod tests {
         ];
 
         for example in &examples {
-            let wires = Wire::parse_wires(example.0);
+            let wires = Wire::parse_wires(example.0).unwrap();
 
-            let first_wire = wires.get(0).expect("Missing first wire");
+            let first_wire = wires.first().expect("Missing first wire");
             let second_wire = wires.get(1).expect("Missing second wire");
 
             let min_distance = first_wire
@@ -407,4 +363,14 @@ mod tests {
             assert_eq!(min_distance, example.1);
         }
     }
+
+    #[test]
+    fn test_parse_overflow() {
+        assert!(Wire::parse_wires("R9223372036854775807,R1\nU1").is_err());
+        // up is towards negative y, which goes one further than positive y
+        assert!(Wire::parse_wires("U9223372036854775807,U1\nD1").is_ok());
+        assert!(Wire::parse_wires("U9223372036854775807,U2\nD1").is_err());
+    }
+
+    examples!(2019, 3, Day3);
 }
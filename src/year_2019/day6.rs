@@ -1,128 +1,184 @@
 //! --- Day 6: Universal Orbit Map ---
 
-use std::collections::HashMap;
-
-/// You've landed at the Universal Orbit Map facility on Mercury. Because navigation in space often involves transferring between orbits, the orbit maps here are useful for finding efficient routes between, for example, you and Santa. You download a map of the local orbits (your puzzle input).
-///
-/// Except for the universal Center of Mass (COM), every object in space is in orbit around exactly one other object. An orbit looks roughly like this:
-///
-///                   \
-///                    \
-///                     |
-///                     |
-/// AAA--> o            o <--BBB
-///                     |
-///                     |
-///                    /
-///                   /
-///
-/// In this diagram, the object BBB is in orbit around AAA. The path that BBB takes around AAA (drawn with lines) is only partly shown. In the map data, this orbital relationship is written AAA)BBB, which means "BBB is in orbit around AAA".
-///
-/// Before you use your map data to plot a course, you need to make sure it wasn't corrupted during the download. To verify maps, the Universal Orbit Map facility uses orbit count checksums - the total number of direct orbits (like the one shown above) and indirect orbits.
-///
-/// Whenever A orbits B and B orbits C, then A indirectly orbits C. This chain can be any number of objects long: if A orbits B, B orbits C, and C orbits D, then A indirectly orbits D.
-///
-/// For example, suppose you have the following map:
-///
-/// COM)B
-/// B)C
-/// C)D
-/// D)E
-/// E)F
-/// B)G
-/// G)H
-/// D)I
-/// E)J
-/// J)K
-/// K)L
-///
-/// Visually, the above map of orbits looks like this:
-///
-///         G - H       J - K - L
-///        /           /
-/// COM - B - C - D - E - F
-///                \
-///                 I
-///
-/// In this visual representation, when two objects are connected by a line, the one on the right directly orbits the one on the left.
-///
-/// Here, we can count the total number of orbits as follows:
-///
-///     D directly orbits C and indirectly orbits B and COM, a total of 3 orbits.
-///     L directly orbits K and indirectly orbits J, E, D, C, B, and COM, a total of 7 orbits.
-///     COM orbits nothing.
-///
-/// The total number of direct and indirect orbits in this example is 42.
-///
-/// What is the total number of direct and indirect orbits in your map data?
-pub fn part1() {
-    let input = crate::common::read_stdin_to_string();
-    let orbit_map = OrbitMap::from(input.as_str());
-    let total_orbits = orbit_map.orbit_count_checksum();
-
-    println!(
-        "The total number of direct and indirect orbits in your map data: {}",
-        total_orbits
-    );
-}
+use crate::answer::Answer;
+use crate::common::rng::Rng;
+use crate::error::Error;
+use crate::generate::Scale;
+use crate::params::Params;
+use crate::solutions::Solver;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day6;
+
+impl Solver for Day6 {
+    type Parsed = Vec<(String, String)>;
+
+    /// The orbits of the map, as the body orbited and the body orbiting it
+    fn parse(input: &str) -> Result<Vec<(String, String)>, Error> {
+        let mut orbiting = HashSet::new();
+
+        let orbits: Vec<(String, String)> = input
+            .trim()
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                let (target, source) = line
+                    .split_once(')')
+                    .ok_or_else(|| Error::line(i, line, "isn't an orbit like COM)B"))?;
+                if !orbiting.insert(source) {
+                    return Err(Error::line(
+                        i,
+                        line,
+                        format!("has {} orbiting a second body", source),
+                    ));
+                }
+
+                Ok((target.to_string(), source.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        // a body which (by way of others) orbits itself never leads back to the center of mass,
+        // so it's turned away here rather than followed around forever
+        let orbited_by: HashMap<&str, usize> = orbits
+            .iter()
+            .enumerate()
+            .map(|(i, (_, source))| (source.as_str(), i))
+            .collect();
+        let mut settled = HashSet::new();
+        let mut path = HashSet::new();
+        for (_, body) in &orbits {
+            path.clear();
+            let mut next = Some(body.as_str());
+            while let Some(id) = next.filter(|id| !settled.contains(id)) {
+                if !path.insert(id) {
+                    let i = orbited_by[id];
+                    let (target, source) = &orbits[i];
+                    return Err(Error::line(
+                        i,
+                        &format!("{}){}", target, source),
+                        format!("has {} orbiting itself, by way of other bodies", source),
+                    ));
+                }
+                next = orbited_by.get(id).map(|&i| orbits[i].0.as_str());
+            }
+            settled.extend(path.drain());
+        }
+
+        Ok(orbits)
+    }
 
-/// Now, you just need to figure out how many orbital transfers you (YOU) need to take to get to Santa (SAN).
-///
-/// You start at the object YOU are orbiting; your destination is the object SAN is orbiting. An orbital transfer lets you move from any object to an object orbiting or orbited by that object.
-///
-/// For example, suppose you have the following map:
-///
-/// COM)B
-/// B)C
-/// C)D
-/// D)E
-/// E)F
-/// B)G
-/// G)H
-/// D)I
-/// E)J
-/// J)K
-/// K)L
-/// K)YOU
-/// I)SAN
-///
-/// Visually, the above map of orbits looks like this:
-///
-///                           YOU
-///                          /
-///         G - H       J - K - L
-///        /           /
-/// COM - B - C - D - E - F
-///                \
-///                 I - SAN
-///
-/// In this example, YOU are in orbit around K, and SAN is in orbit around I. To move from K to I, a minimum of 4 orbital transfers are required:
-///
-///     K to J
-///     J to E
-///     E to D
-///     D to I
-///
-/// Afterward, the map of orbits looks like this:
-///
-///         G - H       J - K - L
-///        /           /
-/// COM - B - C - D - E - F
-///                \
-///                 I - SAN
-///                  \
-///                   YOU
-///
-/// What is the minimum number of orbital transfers required to move from the object YOU are orbiting to the object SAN is orbiting? (Between the objects they are orbiting - not between YOU and SAN.libunwind
-pub fn part2() {
-    let input = crate::common::read_stdin_to_string();
-    let orbit_map = OrbitMap::from(input.as_str());
-    let minimum_transfers = orbit_map.minimum_transfers("SAN", "YOU");
-
-    println!(
-        "The minimum number of orbital transfers required to move from the object YOU are orbiting to the object SAN is orbiting: {}",
-        minimum_transfers
-    );
+    /// You've landed at the Universal Orbit Map facility on Mercury. Because navigation in space often involves transferring between orbits, the orbit maps here are useful for finding efficient routes between, for example, you and Santa. You download a map of the local orbits (your puzzle input).
+    ///
+    /// Except for the universal Center of Mass (COM), every object in space is in orbit around exactly one other object. An orbit looks roughly like this:
+    ///
+    ///                   \
+    ///                    \
+    ///                     |
+    ///                     |
+    /// AAA--> o            o <--BBB
+    ///                     |
+    ///                     |
+    ///                    /
+    ///                   /
+    ///
+    /// In this diagram, the object BBB is in orbit around AAA. The path that BBB takes around AAA (drawn with lines) is only partly shown. In the map data, this orbital relationship is written AAA)BBB, which means "BBB is in orbit around AAA".
+    ///
+    /// Before you use your map data to plot a course, you need to make sure it wasn't corrupted during the download. To verify maps, the Universal Orbit Map facility uses orbit count checksums - the total number of direct orbits (like the one shown above) and indirect orbits.
+    ///
+    /// Whenever A orbits B and B orbits C, then A indirectly orbits C. This chain can be any number of objects long: if A orbits B, B orbits C, and C orbits D, then A indirectly orbits D.
+    ///
+    /// For example, suppose you have the following map:
+    ///
+    /// COM)B
+    /// B)C
+    /// C)D
+    /// D)E
+    /// E)F
+    /// B)G
+    /// G)H
+    /// D)I
+    /// E)J
+    /// J)K
+    /// K)L
+    ///
+    /// Visually, the above map of orbits looks like this:
+    ///
+    ///         G - H       J - K - L
+    ///        /           /
+    /// COM - B - C - D - E - F
+    ///                \
+    ///                 I
+    ///
+    /// In this visual representation, when two objects are connected by a line, the one on the right directly orbits the one on the left.
+    ///
+    /// Here, we can count the total number of orbits as follows:
+    ///
+    ///     D directly orbits C and indirectly orbits B and COM, a total of 3 orbits.
+    ///     L directly orbits K and indirectly orbits J, E, D, C, B, and COM, a total of 7 orbits.
+    ///     COM orbits nothing.
+    ///
+    /// The total number of direct and indirect orbits in this example is 42.
+    ///
+    /// What is the total number of direct and indirect orbits in your map data?
+    fn part1(orbits: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let orbit_map = OrbitMap::from_orbits(orbits)?;
+
+        Ok(Answer::from(orbit_map.orbit_count_checksum()))
+    }
+
+    /// Now, you just need to figure out how many orbital transfers you (YOU) need to take to get to Santa (SAN).
+    ///
+    /// You start at the object YOU are orbiting; your destination is the object SAN is orbiting. An orbital transfer lets you move from any object to an object orbiting or orbited by that object.
+    ///
+    /// For example, suppose you have the following map:
+    ///
+    /// COM)B
+    /// B)C
+    /// C)D
+    /// D)E
+    /// E)F
+    /// B)G
+    /// G)H
+    /// D)I
+    /// E)J
+    /// J)K
+    /// K)L
+    /// K)YOU
+    /// I)SAN
+    ///
+    /// Visually, the above map of orbits looks like this:
+    ///
+    ///                           YOU
+    ///                          /
+    ///         G - H       J - K - L
+    ///        /           /
+    /// COM - B - C - D - E - F
+    ///                \
+    ///                 I - SAN
+    ///
+    /// In this example, YOU are in orbit around K, and SAN is in orbit around I. To move from K to I, a minimum of 4 orbital transfers are required:
+    ///
+    ///     K to J
+    ///     J to E
+    ///     E to D
+    ///     D to I
+    ///
+    /// Afterward, the map of orbits looks like this:
+    ///
+    ///         G - H       J - K - L
+    ///        /           /
+    /// COM - B - C - D - E - F
+    ///                \
+    ///                 I - SAN
+    ///                  \
+    ///                   YOU
+    ///
+    /// What is the minimum number of orbital transfers required to move from the object YOU are orbiting to the object SAN is orbiting? (Between the objects they are orbiting - not between YOU and SAN.libunwind
+    fn part2(orbits: &Self::Parsed, _: &Params) -> Result<Answer, Error> {
+        let orbit_map = OrbitMap::from_orbits(orbits)?;
+
+        orbit_map.minimum_transfers("SAN", "YOU").map(Answer::from)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -131,22 +187,31 @@ struct OrbitMap<'a> {
 }
 
 impl<'a> OrbitMap<'a> {
-    fn get_body(&self, id: &str) -> &OrbitMapBody {
+    fn from_orbits(orbits: &'a [(String, String)]) -> Result<Self, Error> {
+        let mut orbit_map: Self = Default::default();
+        for (target, source) in orbits {
+            orbit_map.add_orbit_relation(target, source)?;
+        }
+
+        Ok(orbit_map)
+    }
+
+    fn get_body(&self, id: &str) -> Result<&OrbitMapBody<'_>, Error> {
         self.bodies
             .get(id)
-            .unwrap_or_else(|| panic!("{} body not found in OrbitMap", id))
+            .ok_or_else(|| Error::NoAnswer(format!("{} isn't in the map", id)))
     }
 
-    fn get_body_parent(&self, id: &str) -> &OrbitMapBody {
+    fn get_body_parent(&self, id: &str) -> Result<&OrbitMapBody<'_>, Error> {
         let parent_id = self
-            .get_body(id)
+            .get_body(id)?
             .parent
-            .unwrap_or_else(|| panic!("{} body missing parent", id));
+            .ok_or_else(|| Error::NoAnswer(format!("{} orbits nothing", id)))?;
 
         self.get_body(parent_id)
     }
 
-    fn add_orbit_relation(&mut self, target_id: &'a str, source_id: &'a str) {
+    fn add_orbit_relation(&mut self, target_id: &'a str, source_id: &'a str) -> Result<(), Error> {
         // insert target if it doesn't exist
         self.bodies
             .entry(target_id)
@@ -160,12 +225,14 @@ impl<'a> OrbitMap<'a> {
 
         // set source.parent to target
         if let Some(parent_id) = &source.parent {
-            panic!(
-                "Failed adding orbit relation, source body {} already has a parent: {}",
-                source_id, parent_id
-            );
+            return Err(Error::Input(format!(
+                "has {} orbiting both {} and {}",
+                source_id, parent_id, target_id
+            )));
         }
         source.parent = Some(target_id);
+
+        Ok(())
     }
 
     fn orbit_count_checksum(&self) -> usize {
@@ -175,9 +242,9 @@ impl<'a> OrbitMap<'a> {
             .sum()
     }
 
-    fn minimum_transfers(&self, target_id: &'a str, source_id: &'a str) -> usize {
-        let source = self.get_body_parent(source_id);
-        let target = self.get_body_parent(target_id);
+    fn minimum_transfers(&self, target_id: &'a str, source_id: &'a str) -> Result<usize, Error> {
+        let source = self.get_body_parent(source_id)?;
+        let target = self.get_body_parent(target_id)?;
 
         let source_parents: Vec<_> = source.parents(self).collect();
         let target_parents: Vec<_> = target.parents(self).collect();
@@ -197,12 +264,12 @@ impl<'a> OrbitMap<'a> {
             }
         }
 
-        let common_parent_id = common_parent.unwrap_or_else(|| {
-            panic!(
-                "Failed to find common parent of {} and {}",
+        let common_parent_id = common_parent.ok_or_else(|| {
+            Error::NoAnswer(format!(
+                "{} and {} orbit nothing in common",
                 source_id, target_id
-            )
-        });
+            ))
+        })?;
 
         for parent in target_parents.iter() {
             minimum_transfers += 1;
@@ -212,28 +279,7 @@ impl<'a> OrbitMap<'a> {
             }
         }
 
-        minimum_transfers
-    }
-}
-
-impl<'a> From<&'a str> for OrbitMap<'a> {
-    fn from(string: &'a str) -> Self {
-        string
-            .trim()
-            .split('\n')
-            .map(|orbit| (orbit, orbit.split(')')))
-            .fold(Default::default(), |mut orbit_map, (orbit, mut bodies)| {
-                orbit_map.add_orbit_relation(
-                    bodies
-                        .next()
-                        .unwrap_or_else(|| panic!("Failed to parse orbit relation {}", orbit)),
-                    bodies
-                        .next()
-                        .unwrap_or_else(|| panic!("Failed to parse orbit relation {}", orbit)),
-                );
-
-                orbit_map
-            })
+        Ok(minimum_transfers)
     }
 }
 
@@ -266,10 +312,7 @@ impl<'a> Iterator for OrbitMapBodyParentIterator<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let next_parent = match self.next_parent_id {
-            Some(next_parent_id) => match self.map.bodies.get(next_parent_id) {
-                Some(next_parent) => next_parent,
-                None => return None,
-            },
+            Some(next_parent_id) => self.map.bodies.get(next_parent_id)?,
             None => return None,
         };
 
@@ -279,9 +322,47 @@ impl<'a> Iterator for OrbitMapBodyParentIterator<'a> {
     }
 }
 
+/// A map of as many objects as the 1,500 or so of a real one, with you and Santa orbiting two of
+/// them. Most objects orbit one of the last few objects made, so that the orbits form long
+/// branching chains as a real map's do, and the orbits are listed in no particular order.
+pub fn generate(rng: &mut Rng, scale: Scale) -> String {
+    let count = scale.of(1500);
+    let mut names = vec!["COM".to_string()];
+    let mut used: HashSet<String> = ["COM", "YOU", "SAN"]
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    let mut orbits = Vec::with_capacity(count + 2);
+    while names.len() <= count {
+        // three characters, and four once three run short
+        let length = if used.len() * 2 < 36 * 36 * 36 { 3 } else { 4 };
+        let name: String = (0..length)
+            .map(|_| *rng.pick(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789") as char)
+            .collect();
+        if !used.insert(name.clone()) {
+            continue;
+        }
+
+        let orbited = if rng.chance(0.9) {
+            names.len() - 1 - rng.below(names.len().min(3))
+        } else {
+            rng.below(names.len())
+        };
+        orbits.push(format!("{}){}", names[orbited], name));
+        names.push(name);
+    }
+    for traveller in ["YOU", "SAN"] {
+        orbits.push(format!("{}){}", rng.pick(&names[1..]), traveller));
+    }
+    rng.shuffle(&mut orbits);
+
+    orbits.iter().map(|orbit| format!("{}\n", orbit)).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::examples::examples;
 
     #[test]
     fn test_orbit_count_checksum() {
@@ -290,7 +371,13 @@ mod tests {
             42,
         );
 
-        assert_eq!(OrbitMap::from(example.0).orbit_count_checksum(), example.1);
+        let orbits = Day6::parse(example.0).unwrap();
+        assert_eq!(
+            OrbitMap::from_orbits(&orbits)
+                .unwrap()
+                .orbit_count_checksum(),
+            example.1
+        );
     }
 
     #[test]
@@ -300,9 +387,30 @@ mod tests {
             4,
         );
 
+        let orbits = Day6::parse(example.0).unwrap();
         assert_eq!(
-            OrbitMap::from(example.0).minimum_transfers("SAN", "YOU"),
-            example.1
+            OrbitMap::from_orbits(&orbits)
+                .unwrap()
+                .minimum_transfers("SAN", "YOU"),
+            Ok(example.1)
+        );
+    }
+
+    #[test]
+    fn test_invalid_maps() {
+        assert!(Day6::parse("COM)B\nB)C\nD)C").is_err());
+        assert_eq!(
+            Day6::parse("COM)B\nC)D\nD)E\nE)C").unwrap_err().to_string(),
+            "line 2 of the input (\"C)D\") has D orbiting itself, by way of other bodies"
         );
+        assert!(Day6::parse("A)A").is_err());
+
+        let orbits = [
+            ("COM".to_string(), "B".to_string()),
+            ("A".to_string(), "B".to_string()),
+        ];
+        assert!(OrbitMap::from_orbits(&orbits).is_err());
     }
+
+    examples!(2019, 6, Day6);
 }
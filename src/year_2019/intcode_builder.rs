@@ -0,0 +1,204 @@
+use super::{IntcodeProgram, IntcodeWord};
+
+/// A parameter of an instruction being built, along with its mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parameter {
+    /// PositionMode
+    Position(usize),
+
+    /// ImmediateMode
+    Immediate(IntcodeWord),
+
+    /// RelativeMode
+    Relative(IntcodeWord),
+}
+
+/// A parameter in position mode
+pub fn pos(address: usize) -> Parameter {
+    Parameter::Position(address)
+}
+
+/// A parameter in immediate mode
+pub fn imm(value: IntcodeWord) -> Parameter {
+    Parameter::Immediate(value)
+}
+
+/// A parameter in relative mode
+pub fn rel(offset: IntcodeWord) -> Parameter {
+    Parameter::Relative(offset)
+}
+
+impl Parameter {
+    fn mode(self) -> IntcodeWord {
+        match self {
+            Self::Position(_) => 0,
+            Self::Immediate(_) => 1,
+            Self::Relative(_) => 2,
+        }
+    }
+
+    fn integer(self) -> IntcodeWord {
+        match self {
+            Self::Position(address) => address as IntcodeWord,
+            Self::Immediate(value) => value,
+            Self::Relative(offset) => offset,
+        }
+    }
+}
+
+/// Builds an IntcodeProgram one instruction at a time, so that tests can say what a program does
+/// instead of spelling it out as a list of integers:
+///
+///     let program = IntcodeProgram::build()
+///         .add(pos(0), imm(5), pos(3))
+///         .halt()
+///         .finish();
+#[derive(Debug, Default, Clone)]
+pub struct IntcodeProgramBuilder {
+    data: Vec<IntcodeWord>,
+}
+
+impl IntcodeProgram {
+    pub fn build() -> IntcodeProgramBuilder {
+        IntcodeProgramBuilder::default()
+    }
+}
+
+impl IntcodeProgramBuilder {
+    /// The address the next instruction will be written to
+    pub fn here(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn add(self, one: Parameter, two: Parameter, output: Parameter) -> Self {
+        self.instruction(1, &[one, two], Some(output))
+    }
+
+    pub fn multiply(self, one: Parameter, two: Parameter, output: Parameter) -> Self {
+        self.instruction(2, &[one, two], Some(output))
+    }
+
+    pub fn input(self, to: Parameter) -> Self {
+        self.instruction(3, &[], Some(to))
+    }
+
+    pub fn output(self, from: Parameter) -> Self {
+        self.instruction(4, &[from], None)
+    }
+
+    pub fn jump_if_true(self, test: Parameter, jump_to: Parameter) -> Self {
+        self.instruction(5, &[test, jump_to], None)
+    }
+
+    pub fn jump_if_false(self, test: Parameter, jump_to: Parameter) -> Self {
+        self.instruction(6, &[test, jump_to], None)
+    }
+
+    pub fn less_than(self, one: Parameter, two: Parameter, output: Parameter) -> Self {
+        self.instruction(7, &[one, two], Some(output))
+    }
+
+    pub fn equals(self, one: Parameter, two: Parameter, output: Parameter) -> Self {
+        self.instruction(8, &[one, two], Some(output))
+    }
+
+    pub fn relative_base_offset(self, offset: Parameter) -> Self {
+        self.instruction(9, &[offset], None)
+    }
+
+    pub fn halt(self) -> Self {
+        self.instruction(99, &[], None)
+    }
+
+    /// Appends raw integers, such as the data a program works on
+    pub fn data(mut self, data: &[IntcodeWord]) -> Self {
+        self.data.extend_from_slice(data);
+        self
+    }
+
+    pub fn finish(self) -> IntcodeProgram {
+        IntcodeProgram::from(self.data)
+    }
+
+    fn instruction(
+        mut self,
+        opcode: IntcodeWord,
+        reads: &[Parameter],
+        write: Option<Parameter>,
+    ) -> Self {
+        if let Some(Parameter::Immediate(_)) = write {
+            panic!("ImmediateMode invalid for writeonly parameter");
+        }
+
+        let parameters: Vec<_> = reads.iter().chain(write.iter()).copied().collect();
+        let header = parameters
+            .iter()
+            .enumerate()
+            .map(|(index, parameter)| parameter.mode() * IntcodeWord::pow(10, 2 + index as u32))
+            .sum::<IntcodeWord>()
+            + opcode;
+
+        self.data.push(header);
+        self.data
+            .extend(parameters.iter().map(|parameter| parameter.integer()));
+        self
+    }
+}
+
+impl From<IntcodeProgramBuilder> for IntcodeProgram {
+    fn from(builder: IntcodeProgramBuilder) -> Self {
+        builder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year_2019::IntcodeComputer;
+
+    #[test]
+    fn test_build_encodes_parameter_modes() {
+        let program = IntcodeProgram::build()
+            .add(pos(0), imm(5), pos(3))
+            .multiply(rel(-1), imm(2), rel(4))
+            .input(pos(100))
+            .output(imm(7))
+            .relative_base_offset(imm(19))
+            .halt()
+            .data(&[1, 2, 3])
+            .finish();
+
+        assert_eq!(
+            program.data_serialized(),
+            "1001,0,5,3,21202,-1,2,4,3,100,104,7,109,19,99,1,2,3"
+        );
+    }
+
+    #[test]
+    fn test_build_runs() {
+        // counts down from the input, outputting each number
+        let counter = 100;
+        let program = IntcodeProgram::build()
+            .input(pos(counter))
+            .output(pos(counter))
+            .add(pos(counter), imm(-1), pos(counter))
+            .jump_if_true(pos(counter), imm(2))
+            .halt()
+            .finish();
+
+        let mut computer = IntcodeComputer::from(&program);
+        let input_tx = computer.create_input();
+        let output_rx = computer.create_output();
+
+        input_tx.send(3).unwrap();
+        computer.run().unwrap();
+
+        assert_eq!(output_rx.try_iter().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ImmediateMode invalid for writeonly parameter")]
+    fn test_build_immediate_write() {
+        IntcodeProgram::build().add(imm(1), imm(2), imm(3));
+    }
+}
@@ -1,14 +1,85 @@
-use std::convert::TryInto;
-use std::sync::mpsc::{self, Receiver, Sender};
+use super::intcode_heatmap::MemoryAccess;
+use crate::common::input;
+use crate::error;
+use rayon::prelude::*;
+use std::collections::VecDeque;
+use std::convert::{TryFrom, TryInto};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::mem;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
 use std::thread;
+use std::time::{Duration, Instant};
+
+/// The integer type of Intcode memory, inputs and outputs.
+///
+/// This is an i64, which is plenty for the puzzles themselves. Some community-written programs
+/// overflow an i64 in their intermediate values, and can be run by enabling the `wide-intcode`
+/// feature, which makes this an i128 at some cost to speed.
+#[cfg(not(feature = "wide-intcode"))]
+pub type IntcodeWord = i64;
+
+/// The integer type of Intcode memory, inputs and outputs (widened by the `wide-intcode`
+/// feature).
+#[cfg(feature = "wide-intcode")]
+pub type IntcodeWord = i128;
+
+/// Runs an Intcode program synchronously with the given inputs, and asserts that it halts within
+/// a bounded number of steps (100,000 unless given) having output exactly the expected outputs:
+///
+///     assert_intcode!("3,0,4,0,99", inputs: [5], outputs: [5]);
+///     assert_intcode!(program, inputs: [], outputs: [1, 2], max_steps: 10);
+#[cfg(test)]
+macro_rules! assert_intcode {
+    ($program:expr, inputs: [$($input:expr),* $(,)?], outputs: [$($output:expr),* $(,)?]) => {
+        assert_intcode!(
+            $program,
+            inputs: [$($input),*],
+            outputs: [$($output),*],
+            max_steps: 100_000
+        )
+    };
+    ($program:expr, inputs: [$($input:expr),* $(,)?], outputs: [$($output:expr),* $(,)?], max_steps: $max_steps:expr) => {{
+        let program = $crate::year_2019::IntcodeProgram::from($program);
+        let mut computer = $crate::year_2019::IntcodeComputer::from(&program);
+        let input_tx = computer.create_input();
+        let output_rx = computer.create_output();
+
+        $(input_tx.send($input).unwrap();)*
+        drop(input_tx);
+
+        let max_steps: usize = $max_steps;
+        assert_eq!(
+            computer.run_for(max_steps),
+            Ok($crate::year_2019::intcode_computer::IntcodeState::Halted),
+            "Intcode program did not halt within {} steps",
+            max_steps
+        );
+
+        let outputs: Vec<IntcodeWord> = output_rx.try_iter().collect();
+        let expected_outputs: Vec<IntcodeWord> = vec![$($output),*];
+        assert_eq!(outputs, expected_outputs, "Intcode program outputs");
+    }};
+}
 
 #[derive(Debug)]
 pub struct IntcodeComputer {
     pub memory: IntcodeProgram,
     instruction_pointer: usize,
-    relative_base: i64,
-    input: Option<Receiver<i64>>,
-    output: Option<Sender<i64>>,
+    relative_base: IntcodeWord,
+    input: Option<Receiver<IntcodeWord>>,
+    pending_input: VecDeque<IntcodeWord>,
+    input_default: Option<IntcodeWord>,
+    output: Option<Sender<IntcodeWord>>,
+    trace: Option<Sender<IntcodeTraceEvent>>,
+    executed_addresses: Option<Vec<bool>>,
+    self_modifications: Vec<SelfModification>,
+    history: Option<History>,
+    stats: IntcodeStats,
+    memory_access: Option<MemoryAccess>,
 }
 
 impl IntcodeComputer {
@@ -16,9 +87,19 @@ impl IntcodeComputer {
         self.memory = program.clone();
         self.instruction_pointer = 0;
         self.relative_base = 0;
+        if let Some(executed_addresses) = self.executed_addresses.as_mut() {
+            executed_addresses.clear();
+        }
+        self.self_modifications.clear();
+        self.stats = IntcodeStats::default();
+        if let Some(memory_access) = self.memory_access.as_mut() {
+            *memory_access = MemoryAccess::default();
+        }
     }
 
-    pub fn run_new_in_thread(program: IntcodeProgram) -> (Sender<i64>, Receiver<i64>) {
+    pub fn run_new_in_thread(
+        program: IntcodeProgram,
+    ) -> (Sender<IntcodeWord>, Receiver<IntcodeWord>) {
         let (input_tx, input_rx) = mpsc::channel();
         let (output_tx, output_rx) = mpsc::channel();
 
@@ -27,127 +108,555 @@ impl IntcodeComputer {
             computer.input = Some(input_rx);
             computer.output = Some(output_tx);
 
-            computer.run();
+            // a program which fails stops there, hanging up its output just as if it had halted
+            let _ = computer.run();
         });
 
         (input_tx, output_rx)
     }
 
-    pub fn create_input(&mut self) -> Sender<i64> {
+    /// Runs the program with every (noun, verb) pair between 0 and 99 inclusive, each on a fresh
+    /// computer, and returns the first pair which leaves `target` at address 0 (a pair the program
+    /// fails with, or which doesn't fit in it, never does)
+    pub fn search_noun_verb(
+        program: &IntcodeProgram,
+        target: IntcodeWord,
+    ) -> Option<(IntcodeWord, IntcodeWord)> {
+        (0..100)
+            .flat_map(|noun| (0..100).map(move |verb| (noun, verb)))
+            .find(|&(noun, verb)| {
+                let mut computer = IntcodeComputer::from(program);
+
+                computer.memory.patch().noun_verb(noun, verb).is_ok()
+                    && computer.run().is_ok()
+                    && computer.memory.get(0) == target
+            })
+    }
+
+    /// Runs the program once per set of inputs, each on a fresh computer, and returns the outputs
+    /// of each run in the same order as the inputs, or an error from a run which failed.
+    ///
+    /// The runs are spread across rayon's thread pool rather than spawning a thread per run, so
+    /// this scales to the thousands of short runs needed by probing puzzles (e.g. 2019 day 19).
+    pub fn run_many<I>(
+        program: &IntcodeProgram,
+        inputs: I,
+    ) -> Result<Vec<Vec<IntcodeWord>>, IntcodeError>
+    where
+        I: ParallelIterator<Item = Vec<IntcodeWord>>,
+    {
+        inputs
+            .map(|inputs| {
+                let mut computer = IntcodeComputer::from(program);
+                let input_tx = computer.create_input();
+                let output_rx = computer.create_output();
+
+                for input in inputs {
+                    input_tx.send(input).expect("Failed to send to input");
+                }
+                drop(input_tx);
+
+                computer.run()?;
+
+                Ok(output_rx.drain_outputs(None))
+            })
+            .collect()
+    }
+
+    pub fn create_input(&mut self) -> Sender<IntcodeWord> {
         let (input_tx, input_rx) = mpsc::channel();
         self.input = Some(input_rx);
         input_tx
     }
 
-    pub fn create_output(&mut self) -> Receiver<i64> {
+    pub fn create_output(&mut self) -> Receiver<IntcodeWord> {
         let (output_tx, output_rx) = mpsc::channel();
         self.output = Some(output_tx);
         output_rx
     }
 
-    pub fn run(&mut self) {
-        loop {
-            let next_instruction = IntcodeInstruction::from(&*self);
-            let instruction_pointer_before_instruction = self.instruction_pointer;
-            let instruction_length = next_instruction.length();
+    /// Queues an input directly, to be read before anything sent through the input channel.
+    ///
+    /// This lets a driver which steps the computer itself provide input without a channel.
+    pub fn push_input(&mut self, input: IntcodeWord) {
+        self.pending_input.push_back(input);
+    }
 
-            match next_instruction {
-                IntcodeInstruction::Add(one, two, output) => {
-                    let one = one.get_value(&self);
-                    let two = two.get_value(&self);
-                    let output_address = output
-                        .get_address(&self)
-                        .expect("Add 'output' parameter must be an address");
+    /// Whether the next instruction is an input instruction with no directly queued input (see
+    /// `push_input`) to read.
+    ///
+    /// Input waiting in the input channel isn't visible here, so stepping may still succeed when
+    /// a channel is connected.
+    pub fn needs_input(&self) -> bool {
+        self.pending_input.is_empty()
+            && Opcode::from(self.memory.get(self.instruction_pointer)) == Opcode(3)
+    }
 
-                    self.memory.replace(output_address, one + two)
-                }
+    /// Sets the value an input instruction receives when no input is waiting.
+    ///
+    /// By default (`None`) an input instruction blocks until input arrives. With a default set,
+    /// the computer never blocks on input, which suits drivers which poll the program (such as
+    /// the network of 2019 day 23, where -1 means "no packet").
+    pub fn set_input_default(&mut self, input_default: Option<IntcodeWord>) {
+        self.input_default = input_default;
+    }
 
-                IntcodeInstruction::Multiply(one, two, output) => {
-                    let one = one.get_value(&self);
-                    let two = two.get_value(&self);
-                    let output_address = output
-                        .get_address(&self)
-                        .expect("Multiply 'output' parameter must be an address");
+    /// Connects a trace sink, which receives an event for each notable thing the
+    /// computer notices while running (such as the program modifying its own code)
+    pub fn create_trace(&mut self) -> Receiver<IntcodeTraceEvent> {
+        let (trace_tx, trace_rx) = mpsc::channel();
+        self.trace = Some(trace_tx);
+        trace_rx
+    }
 
-                    self.memory.replace(output_address, one * two)
-                }
+    /// Starts tracking which addresses have been executed as instructions, so that any
+    /// later write to one of them is recorded as a self-modification
+    pub fn track_self_modifications(&mut self) {
+        if self.executed_addresses.is_none() {
+            self.executed_addresses = Some(Vec::new());
+        }
+    }
 
-                IntcodeInstruction::Input(to) => {
-                    let input_value = self
-                        .input
-                        .as_ref()
-                        .expect("Program requires input but no input was connected!")
-                        .recv()
-                        .expect("Failed to receive from input");
+    /// The writes recorded so far which landed on previously executed instructions
+    pub fn self_modifications(&self) -> &[SelfModification] {
+        &self.self_modifications
+    }
 
-                    let to_address = to
-                        .get_address(&self)
-                        .expect("Input 'to' parameter must be an address");
+    /// Starts counting the reads, writes and executions of each address, which can be exported as
+    /// a heatmap to see which parts of a program are code, data or scratch space
+    pub fn track_memory_access(&mut self) {
+        if self.memory_access.is_none() {
+            self.memory_access = Some(MemoryAccess::default());
+        }
+    }
 
-                    self.memory.replace(to_address, input_value);
-                }
+    /// The accesses counted since `track_memory_access` was called
+    pub fn memory_access(&self) -> Option<&MemoryAccess> {
+        self.memory_access.as_ref()
+    }
+
+    /// Writes the full state of the machine to a core dump file, which `load_core` can later
+    /// resume from.
+    ///
+    /// Any input which has been sent to the machine but not yet read is included in the dump. It
+    /// is moved out of the input channel and stays buffered in this machine, so it still reads
+    /// the same values if it continues to run.
+    pub fn save_core<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        if let Some(input) = self.input.as_ref() {
+            self.pending_input.extend(input.try_iter());
+        }
+
+        let mut core = Vec::new();
+        core.extend_from_slice(CORE_MAGIC);
+        core.extend_from_slice(&(self.instruction_pointer as u64).to_le_bytes());
+        core.extend_from_slice(&self.relative_base.to_le_bytes());
+        for section in &[
+            self.pending_input.iter().collect::<Vec<_>>(),
+            self.memory.data.iter().collect(),
+        ] {
+            core.extend_from_slice(&(section.len() as u64).to_le_bytes());
+            for integer in section {
+                core.extend_from_slice(&integer.to_le_bytes());
+            }
+        }
+
+        fs::write(path, core)
+    }
+
+    /// Restores a machine from a core dump written by `save_core`.
+    ///
+    /// The restored machine has no input or output connected yet, but will read any input which
+    /// was pending when the dump was written before reading from a newly connected input.
+    pub fn load_core<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let core = fs::read(path)?;
+        let mut reader = CoreReader(&core);
+
+        if reader.take(CORE_MAGIC.len())? != CORE_MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Not an Intcode core dump",
+            ));
+        }
+
+        let instruction_pointer = reader.read_u64()? as usize;
+        let relative_base = reader.read_word()?;
+        let pending_input = reader.read_section()?;
+        let memory = reader.read_section()?;
+
+        let mut computer = IntcodeComputer::from(&IntcodeProgram { data: memory });
+        computer.instruction_pointer = instruction_pointer;
+        computer.relative_base = relative_base;
+        computer.pending_input = pending_input.into();
+
+        Ok(computer)
+    }
+
+    /// Runs the program until it halts, or fails
+    pub fn run(&mut self) -> Result<(), IntcodeError> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("intcode_run").entered();
+
+        let started = now();
+        let mut state = Ok(IntcodeState::Running);
+        while state == Ok(IntcodeState::Running) {
+            state = self.step();
+        }
+        self.stats.running += elapsed_since(started);
+
+        state.map(drop)
+    }
+
+    /// Runs at most `max_steps` instructions (including the halt instruction), stopping early if
+    /// the program halts or fails
+    pub fn run_for(&mut self, max_steps: usize) -> Result<IntcodeState, IntcodeError> {
+        let started = now();
+        let mut state = Ok(IntcodeState::Running);
+        for _ in 0..max_steps {
+            state = self.step();
+            if state != Ok(IntcodeState::Running) {
+                break;
+            }
+        }
+        self.stats.running += elapsed_since(started);
+
+        state
+    }
 
-                IntcodeInstruction::Output(from) => {
-                    let output_value = from.get_value(&self);
+    /// How many instructions have been executed since the program was loaded, and how the time
+    /// spent in `run` and `run_for` splits between executing them and waiting on the input channel
+    pub fn stats(&self) -> &IntcodeStats {
+        &self.stats
+    }
+
+    /// Executes the next instruction, or fails without executing it (leaving the instruction
+    /// pointer on it)
+    pub fn step(&mut self) -> Result<IntcodeState, IntcodeError> {
+        let address = self.instruction_pointer;
+        self.try_step()
+            .map_err(|fault| IntcodeError { address, fault })
+    }
+
+    fn try_step(&mut self) -> Result<IntcodeState, IntcodeFault> {
+        let next_instruction = IntcodeInstruction::try_from(&*self)?;
+        let instruction_pointer_before_instruction = self.instruction_pointer;
+        let instruction_length = next_instruction.length();
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            address = instruction_pointer_before_instruction,
+            opcode = next_instruction.name(),
+            relative_base = ?self.relative_base,
+            "execute {:?}",
+            next_instruction
+        );
+
+        if let Some(history) = self.history.as_mut() {
+            history.begin_step(instruction_pointer_before_instruction, self.relative_base);
+        }
+
+        self.mark_executed(instruction_pointer_before_instruction, instruction_length);
+        if let Some(memory_access) = self.memory_access.as_mut() {
+            memory_access
+                .record_execute(instruction_pointer_before_instruction, instruction_length);
+        }
+        self.stats.instructions += 1;
+
+        match next_instruction {
+            IntcodeInstruction::Add(one, two, output) => {
+                let one = self.read(&one)?;
+                let two = self.read(&two)?;
+                let output_address = output.get_write_address(self)?;
+
+                self.write(
+                    output_address,
+                    one.checked_add(two).ok_or(IntcodeFault::Overflow)?,
+                )
+            }
+
+            IntcodeInstruction::Multiply(one, two, output) => {
+                let one = self.read(&one)?;
+                let two = self.read(&two)?;
+                let output_address = output.get_write_address(self)?;
+
+                self.write(
+                    output_address,
+                    one.checked_mul(two).ok_or(IntcodeFault::Overflow)?,
+                )
+            }
+
+            IntcodeInstruction::Input(to) => {
+                // worked out before the input is taken, so that a failure doesn't lose it
+                let to_address = to.get_write_address(self)?;
 
-                    self.output
-                        .as_ref()
-                        .expect("Program requires output but no output was connected!")
-                        .send(output_value)
-                        .expect("Failed to send to output");
+                let input_value = match self.pending_input.pop_front() {
+                    Some(input_value) => input_value,
+                    None => self.receive_input()?,
+                };
+                if let Some(history) = self.history.as_mut() {
+                    history.record_input(input_value);
                 }
 
-                IntcodeInstruction::JumpIfTrue(test, jump_to) => {
-                    if test.get_value(&self) != 0 {
-                        self.instruction_pointer = jump_to.get_value(&self).try_into().unwrap();
-                    }
+                #[cfg(feature = "tracing")]
+                tracing::debug!(value = ?input_value, "input");
+
+                self.write(to_address, input_value);
+            }
+
+            IntcodeInstruction::Output(from) => {
+                let output_value = self.read(&from)?;
+
+                #[cfg(feature = "tracing")]
+                tracing::debug!(value = ?output_value, "output");
+
+                self.output
+                    .as_ref()
+                    .ok_or(IntcodeFault::NoOutput)?
+                    .send(output_value)
+                    .map_err(|_| IntcodeFault::NoOutput)?;
+            }
+
+            IntcodeInstruction::JumpIfTrue(test, jump_to) => {
+                if self.read(&test)? != 0 {
+                    self.instruction_pointer = to_address(self.read(&jump_to)?)?;
                 }
+            }
 
-                IntcodeInstruction::JumpIfFalse(test, jump_to) => {
-                    if test.get_value(&self) == 0 {
-                        self.instruction_pointer = jump_to.get_value(&self).try_into().unwrap();
-                    }
+            IntcodeInstruction::JumpIfFalse(test, jump_to) => {
+                if self.read(&test)? == 0 {
+                    self.instruction_pointer = to_address(self.read(&jump_to)?)?;
                 }
+            }
 
-                IntcodeInstruction::LessThan(one, two, output) => {
-                    let one = one.get_value(&self);
-                    let two = two.get_value(&self);
+            IntcodeInstruction::LessThan(one, two, output) => {
+                let one = self.read(&one)?;
+                let two = self.read(&two)?;
 
-                    let output_value = if one < two { 1 } else { 0 };
+                let output_value = if one < two { 1 } else { 0 };
 
-                    let output_address = output
-                        .get_address(&self)
-                        .expect("LessThan 'output' parameter must be an address");
+                let output_address = output.get_write_address(self)?;
 
-                    self.memory.replace(output_address, output_value)
-                }
+                self.write(output_address, output_value)
+            }
 
-                IntcodeInstruction::Equals(one, two, output) => {
-                    let one = one.get_value(&self);
-                    let two = two.get_value(&self);
+            IntcodeInstruction::Equals(one, two, output) => {
+                let one = self.read(&one)?;
+                let two = self.read(&two)?;
 
-                    let output_value = if one == two { 1 } else { 0 };
+                let output_value = if one == two { 1 } else { 0 };
 
-                    let output_address = output
-                        .get_address(&self)
-                        .expect("LessThan 'output' parameter must be an address");
+                let output_address = output.get_write_address(self)?;
 
-                    self.memory.replace(output_address, output_value)
-                }
+                self.write(output_address, output_value)
+            }
 
-                IntcodeInstruction::RelativeBaseOffset(offset) => {
-                    let offset = offset.get_value(&self);
+            IntcodeInstruction::RelativeBaseOffset(offset) => {
+                let offset = self.read(&offset)?;
 
-                    self.relative_base = self.relative_base + offset;
-                }
+                self.relative_base = self
+                    .relative_base
+                    .checked_add(offset)
+                    .ok_or(IntcodeFault::Overflow)?;
+            }
+
+            IntcodeInstruction::Halt => return Ok(IntcodeState::Halted),
+        }
+
+        if instruction_pointer_before_instruction == self.instruction_pointer {
+            self.instruction_pointer += instruction_length;
+        }
+
+        if let Some(history) = self.history.as_mut() {
+            history.end_step();
+        }
+
+        Ok(IntcodeState::Running)
+    }
+
+    /// Starts recording the effects of each instruction executed from now on, keeping the most
+    /// recent `capacity` of them so that they can be undone with `step_back`
+    pub fn record_history(&mut self, capacity: usize) {
+        self.history = Some(History::new(capacity));
+    }
+
+    /// The number of recorded instructions which can currently be undone
+    pub fn history_len(&self) -> usize {
+        self.history
+            .as_ref()
+            .map_or(0, |history| history.steps.len())
+    }
+
+    /// Undoes the most recently executed instruction, restoring the instruction pointer, the
+    /// relative base and any memory it wrote. Returns false when there is nothing left to undo.
+    ///
+    /// An undone input instruction puts its input back, so stepping forward again reads the same
+    /// value. Outputs can't be taken back from whoever received them, so stepping forward again
+    /// over an output instruction sends it a second time.
+    pub fn step_back(&mut self) -> bool {
+        let step = match self
+            .history
+            .as_mut()
+            .and_then(|history| history.steps.pop_back())
+        {
+            Some(step) => step,
+            None => return false,
+        };
+
+        for (address, old_value) in step.writes.into_iter().rev() {
+            self.memory.replace(address, old_value);
+        }
+        if let Some(input) = step.input {
+            self.pending_input.push_front(input);
+        }
+        self.instruction_pointer = step.instruction_pointer;
+        self.relative_base = step.relative_base;
+
+        true
+    }
 
-                IntcodeInstruction::Halt => break,
+    fn receive_input(&mut self) -> Result<IntcodeWord, IntcodeFault> {
+        let input = self.input.as_ref().ok_or(IntcodeFault::NoInput)?;
+
+        let started = now();
+        let input_value = match self.input_default {
+            Some(input_default) => Ok(input.try_recv().unwrap_or(input_default)),
+            // the sender hung up without sending any more
+            None => input.recv().map_err(|_| IntcodeFault::NoInput),
+        };
+        self.stats.waiting += elapsed_since(started);
+        self.stats.inputs_received += 1;
+
+        input_value
+    }
+
+    fn mark_executed(&mut self, address: usize, length: usize) {
+        let executed_addresses = match self.executed_addresses.as_mut() {
+            Some(executed_addresses) => executed_addresses,
+            None => return,
+        };
+
+        if executed_addresses.len() < address + length {
+            executed_addresses.resize(address + length, false);
+        }
+        for executed in &mut executed_addresses[address..address + length] {
+            *executed = true;
+        }
+    }
+
+    fn read(&mut self, parameter: &IntcodeParameter) -> Result<IntcodeWord, IntcodeFault> {
+        if let Some(address) = parameter.get_address(self)? {
+            if let Some(memory_access) = self.memory_access.as_mut() {
+                memory_access.record_read(address);
             }
+        }
 
-            if instruction_pointer_before_instruction == self.instruction_pointer {
-                self.instruction_pointer += instruction_length;
+        parameter.get_value(self)
+    }
+
+    fn write(&mut self, address: usize, value: IntcodeWord) {
+        if let Some(memory_access) = self.memory_access.as_mut() {
+            memory_access.record_write(address);
+        }
+
+        let was_executed = self
+            .executed_addresses
+            .as_ref()
+            .and_then(|executed_addresses| executed_addresses.get(address))
+            .copied()
+            .unwrap_or(false);
+
+        if was_executed {
+            let self_modification = SelfModification {
+                instruction_pointer: self.instruction_pointer,
+                address,
+                old_value: self.memory.get(address),
+                new_value: value,
+            };
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(
+                address,
+                instruction_pointer = self.instruction_pointer,
+                "self-modification"
+            );
+
+            if let Some(trace) = self.trace.as_ref() {
+                // the trace is purely informational, a disconnected sink shouldn't stop the program
+                let _ = trace.send(IntcodeTraceEvent::SelfModification(
+                    self_modification.clone(),
+                ));
             }
+            self.self_modifications.push(self_modification);
+        }
+
+        if let Some(history) = self.history.as_mut() {
+            history.record_write(address, self.memory.get(address));
+        }
+
+        self.memory.replace(address, value);
+    }
+}
+
+/// A bounded record of executed instructions, which is enough to undo each of them
+#[derive(Debug)]
+struct History {
+    capacity: usize,
+    steps: VecDeque<HistoryStep>,
+    current: Option<HistoryStep>,
+}
+
+/// The state an instruction changed, as it was before the instruction executed
+#[derive(Debug)]
+struct HistoryStep {
+    instruction_pointer: usize,
+    relative_base: IntcodeWord,
+    /// (address, old value) for each write, in the order they happened
+    writes: Vec<(usize, IntcodeWord)>,
+    input: Option<IntcodeWord>,
+}
+
+impl History {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            steps: VecDeque::with_capacity(capacity),
+            current: None,
+        }
+    }
+
+    fn begin_step(&mut self, instruction_pointer: usize, relative_base: IntcodeWord) {
+        self.current = Some(HistoryStep {
+            instruction_pointer,
+            relative_base,
+            writes: Vec::new(),
+            input: None,
+        });
+    }
+
+    fn record_write(&mut self, address: usize, old_value: IntcodeWord) {
+        if let Some(current) = self.current.as_mut() {
+            current.writes.push((address, old_value));
+        }
+    }
+
+    fn record_input(&mut self, input: IntcodeWord) {
+        if let Some(current) = self.current.as_mut() {
+            current.input = Some(input);
+        }
+    }
+
+    fn end_step(&mut self) {
+        let current = match self.current.take() {
+            Some(current) => current,
+            None => return,
+        };
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.steps.len() == self.capacity {
+            self.steps.pop_front();
         }
+        self.steps.push_back(current);
     }
 }
 
@@ -158,23 +667,178 @@ impl From<&IntcodeProgram> for IntcodeComputer {
             instruction_pointer: 0,
             relative_base: 0,
             input: None,
+            pending_input: VecDeque::new(),
+            input_default: None,
             output: None,
+            trace: None,
+            executed_addresses: None,
+            self_modifications: Vec::new(),
+            history: None,
+            stats: IntcodeStats::default(),
+            memory_access: None,
         }
     }
 }
 
 impl From<&str> for IntcodeComputer {
     fn from(string: &str) -> Self {
-        Self {
-            memory: IntcodeProgram::from(string),
-            instruction_pointer: 0,
-            relative_base: 0,
-            input: None,
-            output: None,
+        Self::from(&IntcodeProgram::from(string))
+    }
+}
+
+/// Identifies an Intcode core dump, and the version of its format
+#[cfg(not(feature = "wide-intcode"))]
+const CORE_MAGIC: &[u8] = b"INTCORE1";
+
+/// Identifies an Intcode core dump with 128 bit words, which can't be loaded with 64 bit words
+#[cfg(feature = "wide-intcode")]
+const CORE_MAGIC: &[u8] = b"INTCW128";
+
+/// Reads the little-endian integers of a core dump
+struct CoreReader<'a>(&'a [u8]);
+
+impl<'a> CoreReader<'a> {
+    fn take(&mut self, length: usize) -> io::Result<&'a [u8]> {
+        if self.0.len() < length {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "Intcode core dump is truncated",
+            ));
+        }
+
+        let (taken, rest) = self.0.split_at(length);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn read_u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn read_word(&mut self) -> io::Result<IntcodeWord> {
+        Ok(IntcodeWord::from_le_bytes(
+            self.take(mem::size_of::<IntcodeWord>())?
+                .try_into()
+                .unwrap(),
+        ))
+    }
+
+    /// Reads a length followed by that many integers
+    fn read_section(&mut self) -> io::Result<Vec<IntcodeWord>> {
+        let length = self.read_u64()?;
+        (0..length).map(|_| self.read_word()).collect()
+    }
+}
+
+/// Helpers for collecting the output of an IntcodeComputer in batches
+pub trait IntcodeOutput {
+    /// Reads the next `n` outputs, for protocols which output values in groups (such as the
+    /// (x, y, tile id) triples of the arcade cabinet).
+    ///
+    /// With a timeout, gives up if any single output takes longer than that to arrive. Fails if
+    /// the computer stops before outputting `n` values. Either way, the values which were read
+    /// before failing are discarded.
+    fn read_outputs<T: Into<Option<Duration>>>(
+        &self,
+        n: usize,
+        timeout: T,
+    ) -> Result<Vec<IntcodeWord>, RecvTimeoutError>;
+
+    /// Reads every output which has already been emitted.
+    ///
+    /// With a timeout, keeps reading until no output arrives for that long (or the computer
+    /// stops).
+    fn drain_outputs<T: Into<Option<Duration>>>(&self, timeout: T) -> Vec<IntcodeWord>;
+}
+
+impl IntcodeOutput for Receiver<IntcodeWord> {
+    fn read_outputs<T: Into<Option<Duration>>>(
+        &self,
+        n: usize,
+        timeout: T,
+    ) -> Result<Vec<IntcodeWord>, RecvTimeoutError> {
+        let timeout = timeout.into();
+
+        (0..n)
+            .map(|_| match timeout {
+                Some(timeout) => self.recv_timeout(timeout),
+                None => self.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            })
+            .collect()
+    }
+
+    fn drain_outputs<T: Into<Option<Duration>>>(&self, timeout: T) -> Vec<IntcodeWord> {
+        match timeout.into() {
+            Some(timeout) => std::iter::from_fn(|| self.recv_timeout(timeout).ok()).collect(),
+            None => self.try_iter().collect(),
         }
     }
 }
 
+/// Whether an IntcodeComputer can continue to run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntcodeState {
+    Running,
+    Halted,
+}
+
+/// Execution statistics of an IntcodeComputer.
+///
+/// When several computers are chained together with channels, a computer which spends most of its
+/// time `waiting` is starved by the one feeding it, rather than being bound by its own execution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IntcodeStats {
+    /// Instructions executed, including the halt instruction
+    pub instructions: u64,
+
+    /// Inputs received from the input channel (inputs pushed with `push_input` aren't counted)
+    pub inputs_received: u64,
+
+    /// Time spent blocked on the input channel
+    pub waiting: Duration,
+
+    /// Total time spent in `run` and `run_for`
+    pub running: Duration,
+}
+
+impl IntcodeStats {
+    /// Time spent in `run` and `run_for` other than waiting on input
+    pub fn executing(&self) -> Duration {
+        self.running.saturating_sub(self.waiting)
+    }
+}
+
+impl fmt::Display for IntcodeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} instructions in {:?} ({:?} executing, {:?} waiting on {} inputs)",
+            self.instructions,
+            self.running,
+            self.executing(),
+            self.waiting,
+            self.inputs_received
+        )
+    }
+}
+
+/// An event reported to the trace sink of an IntcodeComputer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntcodeTraceEvent {
+    /// The program wrote to an address it had previously executed as an instruction
+    SelfModification(SelfModification),
+}
+
+/// A write which landed on an address previously executed as (part of) an instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelfModification {
+    /// The address of the instruction which performed the write
+    pub instruction_pointer: usize,
+    pub address: usize,
+    pub old_value: IntcodeWord,
+    pub new_value: IntcodeWord,
+}
+
 #[derive(Debug)]
 enum IntcodeInstruction {
     /// Adds the values from the first two parameters, writes the result to the third parameter
@@ -225,62 +889,81 @@ impl IntcodeInstruction {
             Self::Halt => 1,
         }
     }
+
+    /// The name of the instruction, for filtering traces by opcode
+    #[cfg(feature = "tracing")]
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Add(..) => "add",
+            Self::Multiply(..) => "multiply",
+            Self::Input(..) => "input",
+            Self::Output(..) => "output",
+            Self::JumpIfTrue(..) => "jump_if_true",
+            Self::JumpIfFalse(..) => "jump_if_false",
+            Self::LessThan(..) => "less_than",
+            Self::Equals(..) => "equals",
+            Self::RelativeBaseOffset(..) => "relative_base_offset",
+            Self::Halt => "halt",
+        }
+    }
 }
 
-impl From<&IntcodeComputer> for IntcodeInstruction {
-    fn from(state: &IntcodeComputer) -> Self {
+impl TryFrom<&IntcodeComputer> for IntcodeInstruction {
+    type Error = IntcodeFault;
+
+    fn try_from(state: &IntcodeComputer) -> Result<Self, IntcodeFault> {
         let instruction_header = state.memory.get(state.instruction_pointer);
         let opcode = Opcode::from(instruction_header);
         let mut parser = ParameterParser::from(instruction_header);
 
-        match opcode {
+        Ok(match opcode {
             Opcode(1) => Self::Add(
-                parser.parse_next(state.memory.get(state.instruction_pointer + 1)),
-                parser.parse_next(state.memory.get(state.instruction_pointer + 2)),
-                parser.parse_writeonly(state.memory.get(state.instruction_pointer + 3)),
+                parser.parse_next(state.memory.get(state.instruction_pointer + 1))?,
+                parser.parse_next(state.memory.get(state.instruction_pointer + 2))?,
+                parser.parse_writeonly(state.memory.get(state.instruction_pointer + 3))?,
             ),
             Opcode(2) => Self::Multiply(
-                parser.parse_next(state.memory.get(state.instruction_pointer + 1)),
-                parser.parse_next(state.memory.get(state.instruction_pointer + 2)),
-                parser.parse_writeonly(state.memory.get(state.instruction_pointer + 3)),
+                parser.parse_next(state.memory.get(state.instruction_pointer + 1))?,
+                parser.parse_next(state.memory.get(state.instruction_pointer + 2))?,
+                parser.parse_writeonly(state.memory.get(state.instruction_pointer + 3))?,
+            ),
+            Opcode(3) => Self::Input(
+                parser.parse_writeonly(state.memory.get(state.instruction_pointer + 1))?,
             ),
-            Opcode(3) => {
-                Self::Input(parser.parse_writeonly(state.memory.get(state.instruction_pointer + 1)))
-            }
             Opcode(4) => {
-                Self::Output(parser.parse_next(state.memory.get(state.instruction_pointer + 1)))
+                Self::Output(parser.parse_next(state.memory.get(state.instruction_pointer + 1))?)
             }
             Opcode(5) => Self::JumpIfTrue(
-                parser.parse_next(state.memory.get(state.instruction_pointer + 1)),
-                parser.parse_next(state.memory.get(state.instruction_pointer + 2)),
+                parser.parse_next(state.memory.get(state.instruction_pointer + 1))?,
+                parser.parse_next(state.memory.get(state.instruction_pointer + 2))?,
             ),
             Opcode(6) => Self::JumpIfFalse(
-                parser.parse_next(state.memory.get(state.instruction_pointer + 1)),
-                parser.parse_next(state.memory.get(state.instruction_pointer + 2)),
+                parser.parse_next(state.memory.get(state.instruction_pointer + 1))?,
+                parser.parse_next(state.memory.get(state.instruction_pointer + 2))?,
             ),
             Opcode(7) => Self::LessThan(
-                parser.parse_next(state.memory.get(state.instruction_pointer + 1)),
-                parser.parse_next(state.memory.get(state.instruction_pointer + 2)),
-                parser.parse_writeonly(state.memory.get(state.instruction_pointer + 3)),
+                parser.parse_next(state.memory.get(state.instruction_pointer + 1))?,
+                parser.parse_next(state.memory.get(state.instruction_pointer + 2))?,
+                parser.parse_writeonly(state.memory.get(state.instruction_pointer + 3))?,
             ),
             Opcode(8) => Self::Equals(
-                parser.parse_next(state.memory.get(state.instruction_pointer + 1)),
-                parser.parse_next(state.memory.get(state.instruction_pointer + 2)),
-                parser.parse_writeonly(state.memory.get(state.instruction_pointer + 3)),
+                parser.parse_next(state.memory.get(state.instruction_pointer + 1))?,
+                parser.parse_next(state.memory.get(state.instruction_pointer + 2))?,
+                parser.parse_writeonly(state.memory.get(state.instruction_pointer + 3))?,
             ),
             Opcode(9) => Self::RelativeBaseOffset(
-                parser.parse_next(state.memory.get(state.instruction_pointer + 1)),
+                parser.parse_next(state.memory.get(state.instruction_pointer + 1))?,
             ),
             Opcode(99) => Self::Halt,
-            Opcode(other) => panic!("Invalid Opcode encountered: {}", other),
-        }
+            Opcode(other) => return Err(IntcodeFault::UnknownOpcode(other)),
+        })
     }
 }
 
-#[derive(Debug)]
-struct Opcode(i64);
-impl From<i64> for Opcode {
-    fn from(instruction_header: i64) -> Self {
+#[derive(Debug, PartialEq, Eq)]
+struct Opcode(IntcodeWord);
+impl From<IntcodeWord> for Opcode {
+    fn from(instruction_header: IntcodeWord) -> Self {
         Self(get_digit(instruction_header, 1) * 10 + get_digit(instruction_header, 0))
     }
 }
@@ -291,40 +974,49 @@ enum IntcodeParameter {
     Position(usize),
 
     /// ImmediateMode
-    Value(i64),
+    Value(IntcodeWord),
 
     /// RelativeMode
-    Relative(i64),
+    Relative(IntcodeWord),
 }
 
 impl IntcodeParameter {
-    fn get_address(&self, computer: &IntcodeComputer) -> Option<usize> {
+    fn get_address(&self, computer: &IntcodeComputer) -> Result<Option<usize>, IntcodeFault> {
         match self {
-            Self::Position(address) => Some(*address),
-            Self::Value(_) => None,
-            Self::Relative(address) => Some((computer.relative_base + address).try_into().unwrap()),
+            Self::Position(address) => Ok(Some(*address)),
+            Self::Value(_) => Ok(None),
+            Self::Relative(address) => computer
+                .relative_base
+                .checked_add(*address)
+                .ok_or(IntcodeFault::Overflow)
+                .and_then(to_address)
+                .map(Some),
         }
     }
 
-    fn get_value(&self, computer: &IntcodeComputer) -> i64 {
-        match self {
-            Self::Position(address) => computer.memory.get(*address),
-            Self::Value(value) => *value,
-            Self::Relative(address) => computer
-                .memory
-                .get((computer.relative_base + address).try_into().unwrap()),
+    /// The address a parameter which is written to points at (immediate mode parameters are
+    /// turned away as the instruction is decoded, so this never fails for one)
+    fn get_write_address(&self, computer: &IntcodeComputer) -> Result<usize, IntcodeFault> {
+        self.get_address(computer)?
+            .ok_or(IntcodeFault::ImmediateWrite)
+    }
+
+    fn get_value(&self, computer: &IntcodeComputer) -> Result<IntcodeWord, IntcodeFault> {
+        match (self, self.get_address(computer)?) {
+            (Self::Value(value), _) => Ok(*value),
+            (_, address) => Ok(address.map_or(0, |address| computer.memory.get(address))),
         }
     }
 }
 
 #[derive(Debug)]
 struct ParameterParser {
-    instruction_header: i64,
+    instruction_header: IntcodeWord,
     parameters_read: u32,
 }
 
-impl From<i64> for ParameterParser {
-    fn from(instruction_header: i64) -> Self {
+impl From<IntcodeWord> for ParameterParser {
+    fn from(instruction_header: IntcodeWord) -> Self {
         Self {
             instruction_header,
             parameters_read: 0,
@@ -333,66 +1025,67 @@ impl From<i64> for ParameterParser {
 }
 
 impl ParameterParser {
-    fn parse_next(&mut self, parameter: i64) -> IntcodeParameter {
-        let mode = ParameterMode::from(&*self);
+    fn parse_next(&mut self, parameter: IntcodeWord) -> Result<IntcodeParameter, IntcodeFault> {
+        let mode = ParameterMode::try_from(&*self)?;
         let parameter = match mode {
-            ParameterMode::PositionMode => {
-                IntcodeParameter::Position(parameter.try_into().unwrap())
-            }
-            ParameterMode::ImmediateMode => IntcodeParameter::Value(parameter),
-            ParameterMode::RelativeMode => IntcodeParameter::Relative(parameter),
+            ParameterMode::Position => IntcodeParameter::Position(to_address(parameter)?),
+            ParameterMode::Immediate => IntcodeParameter::Value(parameter),
+            ParameterMode::Relative => IntcodeParameter::Relative(parameter),
         };
 
         self.parameters_read += 1;
 
-        parameter
+        Ok(parameter)
     }
 
-    fn parse_writeonly(&mut self, parameter: i64) -> IntcodeParameter {
-        let mode = ParameterMode::from(&*self);
+    fn parse_writeonly(
+        &mut self,
+        parameter: IntcodeWord,
+    ) -> Result<IntcodeParameter, IntcodeFault> {
+        let mode = ParameterMode::try_from(&*self)?;
         let parameter = match mode {
-            ParameterMode::PositionMode => {
-                IntcodeParameter::Position(parameter.try_into().unwrap())
-            }
-            ParameterMode::ImmediateMode => panic!("ImmediateMode invalid for writeonly parameter"),
-            ParameterMode::RelativeMode => IntcodeParameter::Relative(parameter),
+            ParameterMode::Position => IntcodeParameter::Position(to_address(parameter)?),
+            ParameterMode::Immediate => return Err(IntcodeFault::ImmediateWrite),
+            ParameterMode::Relative => IntcodeParameter::Relative(parameter),
         };
 
         self.parameters_read += 1;
 
-        parameter
+        Ok(parameter)
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 enum ParameterMode {
-    PositionMode,
-    ImmediateMode,
-    RelativeMode,
+    Position,
+    Immediate,
+    Relative,
 }
 
-impl From<&ParameterParser> for ParameterMode {
-    fn from(state: &ParameterParser) -> Self {
+impl TryFrom<&ParameterParser> for ParameterMode {
+    type Error = IntcodeFault;
+
+    fn try_from(state: &ParameterParser) -> Result<Self, IntcodeFault> {
         match get_digit(state.instruction_header, 2 + state.parameters_read) {
-            0 => Self::PositionMode,
-            1 => Self::ImmediateMode,
-            2 => Self::RelativeMode,
-            other => panic!("Invalid ParameterMode: {}", other),
+            0 => Ok(Self::Position),
+            1 => Ok(Self::Immediate),
+            2 => Ok(Self::Relative),
+            other => Err(IntcodeFault::UnknownParameterMode(other)),
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct IntcodeProgram {
-    data: Vec<i64>,
+    data: Vec<IntcodeWord>,
 }
 
 impl IntcodeProgram {
-    pub fn get(&self, address: usize) -> i64 {
+    pub fn get(&self, address: usize) -> IntcodeWord {
         *self.data.get(address).unwrap_or(&0)
     }
 
-    pub fn replace(&mut self, address: usize, replacement: i64) {
+    pub fn replace(&mut self, address: usize, replacement: IntcodeWord) {
         if self.data.len() <= address {
             self.data.resize(address + 1, 0);
         }
@@ -405,10 +1098,28 @@ impl IntcodeProgram {
         *integer = replacement;
     }
 
-    pub fn data(&self) -> &Vec<i64> {
+    /// Starts a bounds-checked patch of this program's initial state
+    pub fn patch(&mut self) -> IntcodePatch<'_> {
+        IntcodePatch { program: self }
+    }
+
+    pub fn data(&self) -> &Vec<IntcodeWord> {
         &self.data
     }
 
+    /// Lists every address whose value differs between this program and `other`, as
+    /// `(address, value in self, value in other)`.
+    ///
+    /// Addresses beyond the end of either program are treated as 0, the same as `get`.
+    pub fn diff(&self, other: &IntcodeProgram) -> Vec<(usize, IntcodeWord, IntcodeWord)> {
+        let length = self.data.len().max(other.data.len());
+
+        (0..length)
+            .map(|address| (address, self.get(address), other.get(address)))
+            .filter(|(_, ours, theirs)| ours != theirs)
+            .collect()
+    }
+
     pub fn data_serialized(&self) -> String {
         self.data
             .iter()
@@ -418,20 +1129,652 @@ impl IntcodeProgram {
     }
 }
 
+impl From<Vec<IntcodeWord>> for IntcodeProgram {
+    fn from(data: Vec<IntcodeWord>) -> Self {
+        Self { data }
+    }
+}
+
+impl IntcodeProgram {
+    /// Parses a program of comma separated integers, such as a puzzle input
+    pub fn parse(string: &str) -> Result<Self, error::Error> {
+        let data = input::comma_separated(&mut string.as_bytes(), "an Intcode integer")?;
+
+        Ok(Self { data })
+    }
+}
+
 impl From<&str> for IntcodeProgram {
     fn from(string: &str) -> Self {
-        Self {
-            data: string
-                .trim()
-                .split(',')
-                .map(|integer| integer.parse::<i64>())
-                .map(|parse_result| parse_result.expect("Failed to parse Intcode integer as i64"))
-                .collect(),
+        Self::parse(string).unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+/// Bounds-checked modifications to an IntcodeProgram, for setting up a program's inputs before
+/// it is run.
+///
+/// Unlike `IntcodeProgram::replace`, which grows memory as needed, a patch refuses to write
+/// outside of the program, as that almost always means the wrong address was used.
+#[derive(Debug)]
+pub struct IntcodePatch<'a> {
+    program: &'a mut IntcodeProgram,
+}
+
+impl<'a> IntcodePatch<'a> {
+    /// The address of the noun, as used by the gravity assist program (2019 day 2)
+    pub const NOUN_ADDRESS: usize = 1;
+
+    /// The address of the verb, as used by the gravity assist program (2019 day 2)
+    pub const VERB_ADDRESS: usize = 2;
+
+    /// Writes the noun and verb into addresses 1 and 2
+    pub fn noun_verb(
+        &mut self,
+        noun: IntcodeWord,
+        verb: IntcodeWord,
+    ) -> Result<&mut Self, IntcodePatchError> {
+        self.set(Self::NOUN_ADDRESS, noun)?
+            .set(Self::VERB_ADDRESS, verb)
+    }
+
+    /// Writes a single value into an existing address
+    pub fn set(
+        &mut self,
+        address: usize,
+        value: IntcodeWord,
+    ) -> Result<&mut Self, IntcodePatchError> {
+        self.overwrite(address, &[value])
+    }
+
+    /// Writes a run of values into existing addresses, starting at `start`
+    pub fn overwrite(
+        &mut self,
+        start: usize,
+        values: &[IntcodeWord],
+    ) -> Result<&mut Self, IntcodePatchError> {
+        let length = self.program.data.len();
+        let end = start + values.len();
+        if end > length {
+            return Err(IntcodePatchError::OutOfBounds { start, end, length });
+        }
+
+        self.program.data[start..end].copy_from_slice(values);
+
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntcodePatchError {
+    /// The patch covered addresses `start..end`, but the program is only `length` integers long
+    OutOfBounds {
+        start: usize,
+        end: usize,
+        length: usize,
+    },
+}
+
+impl fmt::Display for IntcodePatchError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { start, end, length } => write!(
+                f,
+                "Patch of addresses {}..{} is outside of the program (length {})",
+                start, end, length
+            ),
         }
     }
 }
 
+impl Error for IntcodePatchError {}
+
+/// The furthest address a program can use, which keeps a program from growing its memory past
+/// what can be allocated by writing somewhere far off
+const MAX_ADDRESS: usize = 1 << 24;
+
+/// Why an IntcodeComputer couldn't execute the instruction at `address`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntcodeError {
+    pub address: usize,
+    pub fault: IntcodeFault,
+}
+
+/// What was wrong with an instruction which couldn't be executed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntcodeFault {
+    UnknownOpcode(IntcodeWord),
+    UnknownParameterMode(IntcodeWord),
+
+    /// A parameter which is written to is in immediate mode
+    ImmediateWrite,
+
+    /// An address is negative, or further out than `MAX_ADDRESS`
+    InvalidAddress(IntcodeWord),
+
+    /// A result doesn't fit in an IntcodeWord
+    Overflow,
+
+    /// An input instruction has no input connected, or the input was disconnected
+    NoInput,
+
+    /// An output instruction has no output connected, or the output was disconnected
+    NoOutput,
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.fault {
+            IntcodeFault::UnknownOpcode(opcode) => write!(f, "unknown opcode {}", opcode),
+            IntcodeFault::UnknownParameterMode(mode) => {
+                write!(f, "unknown parameter mode {}", mode)
+            }
+            IntcodeFault::ImmediateWrite => write!(f, "write to an immediate mode parameter"),
+            IntcodeFault::InvalidAddress(address) => write!(f, "invalid address {}", address),
+            IntcodeFault::Overflow => write!(
+                f,
+                "overflow of a {} bit word (see the wide-intcode feature)",
+                IntcodeWord::BITS
+            ),
+            IntcodeFault::NoInput => write!(f, "input with none left to read"),
+            IntcodeFault::NoOutput => write!(f, "output with nothing receiving it"),
+        }?;
+
+        write!(f, " at address {}", self.address)
+    }
+}
+
+impl Error for IntcodeError {}
+
+impl From<IntcodeError> for error::Error {
+    fn from(error: IntcodeError) -> Self {
+        Self::Input(format!("fails as an Intcode program: {}", error))
+    }
+}
+
+/// The time now, for the stats of how long a computer spends running. wasm32-unknown-unknown has
+/// no clock (`Instant::now` panics there), so the time isn't measured in a browser.
+fn now() -> Option<Instant> {
+    if cfg!(target_arch = "wasm32") {
+        None
+    } else {
+        Some(Instant::now())
+    }
+}
+
+fn elapsed_since(started: Option<Instant>) -> Duration {
+    started.map_or(Duration::ZERO, |started| started.elapsed())
+}
+
+/// `word` as an address, which is never negative, and at most `MAX_ADDRESS`
+fn to_address(word: IntcodeWord) -> Result<usize, IntcodeFault> {
+    usize::try_from(word)
+        .ok()
+        .filter(|&address| address <= MAX_ADDRESS)
+        .ok_or(IntcodeFault::InvalidAddress(word))
+}
+
 /// Gets the digit from number at a zero-indexed position from the right (in base 10)
-fn get_digit(number: i64, position: u32) -> i64 {
-    (number / (10_i64.pow(position))) % 10
+fn get_digit(number: IntcodeWord, position: u32) -> IntcodeWord {
+    (number / (IntcodeWord::pow(10, position))) % 10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year_2019::intcode_builder::{imm, pos};
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_add_and_multiply() {
+        assert_intcode!("1,5,6,7,99,20,22,0,4,7,99", inputs: [], outputs: []);
+        assert_intcode!("1,9,10,11,4,11,99,0,0,20,22,0", inputs: [], outputs: [42]);
+        assert_intcode!("1101,20,22,7,4,7,99,0", inputs: [], outputs: [42]);
+        assert_intcode!("1102,6,7,7,4,7,99,0", inputs: [], outputs: [42]);
+        assert_intcode!("1002,7,-1,7,4,7,99,-42", inputs: [], outputs: [42]);
+    }
+
+    #[test]
+    fn test_input_and_output() {
+        assert_intcode!("3,0,4,0,99", inputs: [42], outputs: [42]);
+        assert_intcode!("3,0,3,1,4,1,4,0,99", inputs: [1, 2], outputs: [2, 1]);
+        assert_intcode!("104,-7,99", inputs: [], outputs: [-7]);
+    }
+
+    #[test]
+    fn test_comparisons() {
+        for (input, output) in &[(7, 0), (8, 1), (9, 0)] {
+            assert_intcode!("3,9,8,9,10,9,4,9,99,-1,8", inputs: [*input], outputs: [*output]);
+            assert_intcode!("3,3,1108,-1,8,3,4,3,99", inputs: [*input], outputs: [*output]);
+        }
+        for (input, output) in &[(7, 1), (8, 0), (9, 0)] {
+            assert_intcode!("3,9,7,9,10,9,4,9,99,-1,8", inputs: [*input], outputs: [*output]);
+            assert_intcode!("3,3,1107,-1,8,3,4,3,99", inputs: [*input], outputs: [*output]);
+        }
+    }
+
+    #[test]
+    fn test_jumps() {
+        for (input, output) in &[(-1, 1), (0, 0), (1, 1)] {
+            assert_intcode!(
+                "3,12,6,12,15,1,13,14,13,4,13,99,-1,0,1,9",
+                inputs: [*input],
+                outputs: [*output]
+            );
+            assert_intcode!(
+                "3,3,1105,-1,9,1101,0,0,12,4,12,99,1",
+                inputs: [*input],
+                outputs: [*output]
+            );
+        }
+
+        let larger_example = "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99";
+        assert_intcode!(larger_example, inputs: [7], outputs: [999]);
+        assert_intcode!(larger_example, inputs: [8], outputs: [1000]);
+        assert_intcode!(larger_example, inputs: [9], outputs: [1001]);
+    }
+
+    #[test]
+    fn test_relative_mode() {
+        assert_intcode!(
+            "109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99",
+            inputs: [],
+            outputs: [109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99]
+        );
+        assert_intcode!("109,10,203,-3,204,-3,99", inputs: [5], outputs: [5]);
+        assert_intcode!("109,-1,109,21,21101,4,5,-1,204,-1,99", inputs: [], outputs: [9]);
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        assert_intcode!("1102,34915192,34915192,7,4,7,99,0", inputs: [], outputs: [1_219_070_632_396_864]);
+        assert_intcode!("104,1125899906842624,99", inputs: [], outputs: [1_125_899_906_842_624]);
+    }
+
+    #[test]
+    #[cfg(feature = "wide-intcode")]
+    fn test_wide_words() {
+        // squares 2^40, which overflows an i64
+        assert_intcode!(
+            "1102,1099511627776,1099511627776,7,4,7,99,0",
+            inputs: [],
+            outputs: [1_208_925_819_614_629_174_706_176]
+        );
+    }
+
+    #[test]
+    fn test_memory_beyond_program() {
+        assert_intcode!("1101,20,22,1000,4,1000,4,2000,99", inputs: [], outputs: [42, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "did not halt within 100 steps")]
+    fn test_assert_intcode_bounds_steps() {
+        // two jumps to each other, forever
+        assert_intcode!("1105,1,3,1105,1,0", inputs: [], outputs: [], max_steps: 100);
+    }
+
+    #[test]
+    fn test_input_default() {
+        // reads and outputs three inputs
+        let mut computer = IntcodeComputer::from("3,0,4,0,3,0,4,0,3,0,4,0,99");
+        computer.set_input_default(Some(-1));
+        let input_tx = computer.create_input();
+        let output_rx = computer.create_output();
+
+        input_tx.send(7).unwrap();
+        assert_eq!(computer.run_for(4), Ok(IntcodeState::Running));
+        input_tx.send(8).unwrap();
+        drop(input_tx);
+        computer.run().unwrap();
+
+        assert_eq!(output_rx.try_iter().collect::<Vec<_>>(), vec![7, -1, 8]);
+    }
+
+    #[test]
+    fn test_read_outputs() {
+        let (input_tx, output_rx) = IntcodeComputer::run_new_in_thread(IntcodeProgram::from(
+            "104,1,104,2,104,3,3,0,104,4,99",
+        ));
+
+        assert_eq!(output_rx.read_outputs(2, None), Ok(vec![1, 2]));
+        // only one more value is output before the program waits for input
+        assert_eq!(
+            output_rx.read_outputs(2, Duration::from_millis(50)),
+            Err(RecvTimeoutError::Timeout)
+        );
+
+        input_tx.send(0).unwrap();
+        assert_eq!(
+            output_rx.read_outputs(2, None),
+            Err(RecvTimeoutError::Disconnected)
+        );
+    }
+
+    #[test]
+    fn test_drain_outputs() {
+        let (input_tx, output_rx) =
+            IntcodeComputer::run_new_in_thread(IntcodeProgram::from("104,1,104,2,3,0,104,3,99"));
+
+        assert_eq!(
+            output_rx.drain_outputs(Duration::from_millis(50)),
+            vec![1, 2]
+        );
+        assert_eq!(output_rx.drain_outputs(None), Vec::<IntcodeWord>::new());
+
+        input_tx.send(0).unwrap();
+        assert_eq!(output_rx.drain_outputs(Duration::from_secs(5)), vec![3]);
+    }
+
+    #[test]
+    fn test_step_back() {
+        // reads a number, then counts it down to zero, outputting each step
+        let program = IntcodeProgram::from("3,100,4,100,1001,100,-1,100,1005,100,2,99");
+        let mut computer = IntcodeComputer::from(&program);
+        computer.record_history(1000);
+        let input_tx = computer.create_input();
+        let output_rx = computer.create_output();
+        input_tx.send(3).unwrap();
+
+        let snapshots: Vec<_> = (0..8)
+            .map(|_| {
+                let snapshot = (computer.memory.clone(), computer.instruction_pointer);
+                computer.step().unwrap();
+                snapshot
+            })
+            .collect();
+        assert_eq!(computer.history_len(), 8);
+
+        for (memory, instruction_pointer) in snapshots.iter().rev() {
+            assert!(computer.step_back());
+            assert!(computer.memory.diff(memory).is_empty());
+            assert_eq!(computer.instruction_pointer, *instruction_pointer);
+        }
+        assert!(!computer.step_back());
+
+        // the input is read again when replaying
+        computer.run().unwrap();
+        assert_eq!(
+            output_rx.try_iter().collect::<Vec<_>>(),
+            vec![3, 2, 1, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn test_step_back_is_bounded() {
+        let mut computer = IntcodeComputer::from("1105,1,3,1105,1,0");
+        computer.record_history(10);
+
+        computer.run_for(100).unwrap();
+
+        assert_eq!(computer.history_len(), 10);
+        for _ in 0..10 {
+            assert!(computer.step_back());
+        }
+        assert!(!computer.step_back());
+    }
+
+    #[test]
+    fn test_push_input() {
+        let mut computer = IntcodeComputer::from("3,0,4,0,99");
+        let output_rx = computer.create_output();
+
+        assert!(computer.needs_input());
+        computer.push_input(42);
+        assert!(!computer.needs_input());
+
+        computer.run().unwrap();
+        assert_eq!(output_rx.recv().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_step() {
+        let mut computer = IntcodeComputer::from("1101,1,1,5,99,0");
+
+        assert_eq!(computer.step(), Ok(IntcodeState::Running));
+        assert_eq!(computer.memory.get(5), 2);
+        assert_eq!(computer.step(), Ok(IntcodeState::Halted));
+        assert_eq!(computer.step(), Ok(IntcodeState::Halted));
+        assert_eq!(computer.run_for(10), Ok(IntcodeState::Halted));
+    }
+
+    #[test]
+    fn test_stats() {
+        let mut computer = IntcodeComputer::from("3,0,4,0,3,0,4,0,99");
+        let input_tx = computer.create_input();
+        let output_rx = computer.create_output();
+
+        computer.push_input(1);
+        let sender = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            input_tx.send(2).unwrap();
+        });
+        computer.run().unwrap();
+        sender.join().unwrap();
+
+        assert_eq!(output_rx.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+        let stats = computer.stats();
+        assert_eq!(stats.instructions, 5);
+        assert_eq!(stats.inputs_received, 1);
+        assert!(stats.waiting >= Duration::from_millis(50));
+        assert!(stats.running >= stats.waiting);
+        assert_eq!(stats.executing(), stats.running - stats.waiting);
+    }
+
+    #[test]
+    fn test_sum_of_primes() {
+        // the same program as the sum of primes benchmark
+        let program = IntcodeProgram::from("3,78,1101,0,0,81,1101,2,0,79,7,79,78,82,1006,82,75,1101,2,0,80,2,80,80,82,7,79,82,82,1005,82,64,1001,79,0,83,7,83,80,82,1005,82,54,1002,80,-1,82,1,83,82,83,1105,1,36,1006,83,68,1001,80,1,80,1105,1,21,1,81,79,81,1001,79,1,79,1105,1,10,4,81,99,0,0,0,0,0,0");
+
+        for (below, sum) in &[(10, 17), (100, 1060), (1000, 76127)] {
+            let mut computer = IntcodeComputer::from(&program);
+            let input_tx = computer.create_input();
+            let output_rx = computer.create_output();
+
+            input_tx.send(*below).unwrap();
+            computer.run().unwrap();
+
+            assert_eq!(output_rx.recv().unwrap(), *sum);
+        }
+    }
+
+    #[test]
+    fn test_run_many() {
+        // outputs the input multiplied by itself
+        let program = IntcodeProgram::from("3,9,2,9,9,10,4,10,99,0,0");
+
+        let outputs =
+            IntcodeComputer::run_many(&program, (0..1000).into_par_iter().map(|input| vec![input]))
+                .unwrap();
+
+        assert_eq!(outputs.len(), 1000);
+        for (input, output) in outputs.iter().enumerate() {
+            assert_eq!(output, &[(input * input) as IntcodeWord]);
+        }
+    }
+
+    #[test]
+    fn test_core_dump() {
+        // reads two inputs, then outputs their sum
+        let program = IntcodeProgram::from("3,11,3,12,1,11,12,13,4,13,99,0,0,0");
+        let path = std::env::temp_dir().join(format!("intcode-core-{}", std::process::id()));
+
+        let mut computer = IntcodeComputer::from(&program);
+        computer.instruction_pointer = 2;
+        computer.relative_base = -5;
+        computer.memory.replace(11, 40);
+        let input_tx = computer.create_input();
+        input_tx.send(2).unwrap();
+
+        computer.save_core(&path).unwrap();
+        let mut restored = IntcodeComputer::load_core(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(restored.instruction_pointer, 2);
+        assert_eq!(restored.relative_base, -5);
+        assert!(restored.memory.diff(&computer.memory).is_empty());
+
+        let output_rx = restored.create_output();
+        restored.run().unwrap();
+        assert_eq!(output_rx.recv().unwrap(), 42);
+
+        // the original machine still sees its pending input
+        let output_rx = computer.create_output();
+        computer.run().unwrap();
+        assert_eq!(output_rx.recv().unwrap(), 42);
+    }
+
+    #[test]
+    fn test_core_dump_invalid() {
+        let path = std::env::temp_dir().join(format!("intcode-bad-core-{}", std::process::id()));
+
+        fs::write(&path, [CORE_MAGIC, &[1]].concat()).unwrap();
+        let truncated = IntcodeComputer::load_core(&path).unwrap_err();
+        fs::write(&path, b"not a core dump").unwrap();
+        let not_a_core = IntcodeComputer::load_core(&path).unwrap_err();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(truncated.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(not_a_core.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_program_diff() {
+        let before = IntcodeProgram::from("1,9,10,3,2,3,11,0,99,30,40,50");
+        let mut computer = IntcodeComputer::from(&before);
+
+        computer.run().unwrap();
+
+        assert_eq!(
+            before.diff(&computer.memory),
+            vec![(0, 1, 3500), (3, 3, 70)]
+        );
+        assert_eq!(computer.memory.diff(&computer.memory), vec![]);
+    }
+
+    #[test]
+    fn test_program_diff_different_lengths() {
+        let short = IntcodeProgram::from("1,2");
+        let long = IntcodeProgram::from("1,2,0,4");
+
+        assert_eq!(short.diff(&long), vec![(3, 0, 4)]);
+        assert_eq!(long.diff(&short), vec![(3, 4, 0)]);
+    }
+
+    #[test]
+    fn test_program_patch() {
+        let mut program = IntcodeProgram::from("1,0,0,3,99,0,0");
+
+        program
+            .patch()
+            .noun_verb(5, 6)
+            .unwrap()
+            .overwrite(5, &[7, 8])
+            .unwrap();
+
+        assert_eq!(program.data_serialized(), "1,5,6,3,99,7,8");
+    }
+
+    #[test]
+    fn test_program_patch_out_of_bounds() {
+        let mut program = IntcodeProgram::from("1,0,0,3,99");
+
+        assert_eq!(
+            program.patch().overwrite(4, &[1, 2]).unwrap_err(),
+            IntcodePatchError::OutOfBounds {
+                start: 4,
+                end: 6,
+                length: 5
+            }
+        );
+        assert!(program.patch().set(5, 1).is_err());
+        assert_eq!(program.data_serialized(), "1,0,0,3,99");
+    }
+
+    #[test]
+    fn test_search_noun_verb() {
+        let program = IntcodeProgram::from("1,0,0,0,99");
+
+        // only address 4 holds 99, so 99 + 99 needs both the noun and verb to point there
+        assert_eq!(
+            IntcodeComputer::search_noun_verb(&program, 198),
+            Some((4, 4))
+        );
+        assert_eq!(IntcodeComputer::search_noun_verb(&program, -1), None);
+    }
+
+    #[test]
+    fn test_self_modification_detection() {
+        // overwrites its own first instruction's opcode (address 0) and then halts
+        let program = IntcodeProgram::build()
+            .add(imm(1), imm(1), pos(0))
+            .halt()
+            .finish();
+        let mut computer = IntcodeComputer::from(&program);
+        computer.track_self_modifications();
+        let trace_rx = computer.create_trace();
+
+        computer.run().unwrap();
+
+        let expected = SelfModification {
+            instruction_pointer: 0,
+            address: 0,
+            old_value: 1101,
+            new_value: 2,
+        };
+        assert_eq!(
+            trace_rx.try_iter().collect::<Vec<_>>(),
+            vec![IntcodeTraceEvent::SelfModification(expected.clone())]
+        );
+        assert_eq!(computer.self_modifications(), &[expected]);
+    }
+
+    #[test]
+    fn test_self_modification_ignores_data_writes() {
+        let mut computer = IntcodeComputer::from("1,9,10,3,2,3,11,0,99,30,40,50");
+        computer.track_self_modifications();
+
+        computer.run().unwrap();
+
+        // both writes land on the first instruction, which had already executed
+        assert_eq!(computer.self_modifications().len(), 2);
+
+        let mut computer = IntcodeComputer::from("1101,1,1,7,99,0,0,0");
+        computer.track_self_modifications();
+
+        computer.run().unwrap();
+
+        assert_eq!(computer.self_modifications(), &[]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_get_digit(number in 0..IntcodeWord::MAX, position in 0..19_u32) {
+            let digits = number.to_string();
+            let expected = digits
+                .chars()
+                .rev()
+                .nth(position as usize)
+                .map_or(0, |digit| IntcodeWord::from(digit.to_digit(10).unwrap()));
+
+            prop_assert_eq!(get_digit(number, position), expected);
+        }
+
+        #[test]
+        fn test_parameter_modes(opcode in 1..100 as IntcodeWord, modes in prop::collection::vec(0..3 as IntcodeWord, 3)) {
+            let header = opcode + 100 * modes[0] + 1000 * modes[1] + 10000 * modes[2];
+            prop_assert_eq!(Opcode::from(header), Opcode(opcode));
+
+            let mut parser = ParameterParser::from(header);
+            for mode in modes {
+                let expected = match mode {
+                    0 => ParameterMode::Position,
+                    1 => ParameterMode::Immediate,
+                    _ => ParameterMode::Relative,
+                };
+                prop_assert_eq!(ParameterMode::try_from(&parser), Ok(expected));
+                parser.parse_next(0).unwrap();
+            }
+        }
+    }
 }
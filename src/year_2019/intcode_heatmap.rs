@@ -0,0 +1,285 @@
+//! Counts of how often a program reads, writes and executes each address of memory, exported as
+//! heatmaps to tell apart the code, data and scratch space of a program which needs decompiling.
+
+use std::fmt;
+use std::ops::Range;
+#[cfg(feature = "image")]
+use std::path::Path;
+
+/// How many times each address has been read, written and executed, counted by an
+/// IntcodeComputer after `track_memory_access`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemoryAccess {
+    reads: Vec<u64>,
+    writes: Vec<u64>,
+    executes: Vec<u64>,
+}
+
+/// What an address is used for, judging by how it was accessed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Region {
+    /// Never accessed
+    Untouched,
+
+    /// Executed as (part of) an instruction
+    Code,
+
+    /// Read, but never written
+    Data,
+
+    /// Written while running
+    Scratch,
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Untouched => "untouched",
+            Self::Code => "code",
+            Self::Data => "data",
+            Self::Scratch => "scratch",
+        };
+
+        f.pad(name)
+    }
+}
+
+/// Characters of increasing intensity for the text heatmap
+const INTENSITY: &[char] = &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+
+impl MemoryAccess {
+    pub(super) fn record_read(&mut self, address: usize) {
+        increment(&mut self.reads, address..address + 1);
+    }
+
+    pub(super) fn record_write(&mut self, address: usize) {
+        increment(&mut self.writes, address..address + 1);
+    }
+
+    pub(super) fn record_execute(&mut self, address: usize, length: usize) {
+        increment(&mut self.executes, address..address + length);
+    }
+
+    pub fn reads(&self, address: usize) -> u64 {
+        self.reads.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn writes(&self, address: usize) -> u64 {
+        self.writes.get(address).copied().unwrap_or(0)
+    }
+
+    pub fn executes(&self, address: usize) -> u64 {
+        self.executes.get(address).copied().unwrap_or(0)
+    }
+
+    /// One past the highest address accessed
+    pub fn len(&self) -> usize {
+        self.reads
+            .len()
+            .max(self.writes.len())
+            .max(self.executes.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Classifies an address, preferring code over scratch over data when an address was
+    /// accessed in more than one way
+    pub fn region(&self, address: usize) -> Region {
+        if self.executes(address) > 0 {
+            Region::Code
+        } else if self.writes(address) > 0 {
+            Region::Scratch
+        } else if self.reads(address) > 0 {
+            Region::Data
+        } else {
+            Region::Untouched
+        }
+    }
+
+    /// Splits memory into runs of consecutive addresses in the same region
+    pub fn regions(&self) -> Vec<(Range<usize>, Region)> {
+        let mut regions: Vec<(Range<usize>, Region)> = Vec::new();
+
+        for address in 0..self.len() {
+            let region = self.region(address);
+            match regions.last_mut() {
+                Some((range, last_region)) if *last_region == region => range.end = address + 1,
+                _ => regions.push((address..address + 1, region)),
+            }
+        }
+
+        regions
+    }
+
+    /// Draws the total accesses of each address as a grid of characters, `columns` addresses to
+    /// a row, with each row labelled by its first address. Intensity is on a log scale, so that
+    /// rarely touched addresses still show up next to a hot loop.
+    pub fn heatmap(&self, columns: usize) -> String {
+        let columns = columns.max(1);
+        let totals: Vec<u64> = (0..self.len())
+            .map(|address| self.reads(address) + self.writes(address) + self.executes(address))
+            .collect();
+        let max = totals.iter().copied().max().unwrap_or(0);
+
+        let mut heatmap = String::new();
+        for (row, chunk) in totals.chunks(columns).enumerate() {
+            heatmap.push_str(&format!("{:>6} ", row * columns));
+            heatmap.extend(chunk.iter().map(|&total| {
+                let level = (intensity(total, max) * (INTENSITY.len() - 1) as f64).ceil();
+                INTENSITY[level as usize]
+            }));
+            heatmap.push('\n');
+        }
+
+        heatmap
+    }
+
+    /// Saves a heatmap as a PNG, `columns` addresses to a row and each address drawn as a
+    /// `scale` pixel square. Writes are drawn in red, reads in green and executions in blue, each
+    /// on a log scale, so code shows up blue, data green and scratch space red or yellow.
+    #[cfg(feature = "image")]
+    pub fn save_png<P: AsRef<Path>>(
+        &self,
+        path: P,
+        columns: usize,
+        scale: u32,
+    ) -> image::ImageResult<()> {
+        let columns = columns.max(1);
+        let scale = scale.max(1);
+        let rows = self.len().div_ceil(columns);
+
+        let max_reads = self.reads.iter().copied().max().unwrap_or(0);
+        let max_writes = self.writes.iter().copied().max().unwrap_or(0);
+        let max_executes = self.executes.iter().copied().max().unwrap_or(0);
+        let channel = |count, max| (intensity(count, max) * 255.0).round() as u8;
+
+        let image = image::RgbImage::from_fn(
+            columns as u32 * scale,
+            rows.max(1) as u32 * scale,
+            |x, y| {
+                let address = (y / scale) as usize * columns + (x / scale) as usize;
+
+                image::Rgb([
+                    channel(self.writes(address), max_writes),
+                    channel(self.reads(address), max_reads),
+                    channel(self.executes(address), max_executes),
+                ])
+            },
+        );
+
+        image.save_with_format(path, image::ImageFormat::Png)
+    }
+}
+
+/// A table of the regions of memory and how they were accessed
+impl fmt::Display for MemoryAccess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>15}  {:<9}  {:>10}  {:>10}  {:>10}",
+            "addresses", "region", "reads", "writes", "executes"
+        )?;
+
+        for (range, region) in self.regions() {
+            let sum = |counts: fn(&Self, usize) -> u64| -> u64 {
+                range.clone().map(|address| counts(self, address)).sum()
+            };
+
+            writeln!(
+                f,
+                "{:>15}  {:<9}  {:>10}  {:>10}  {:>10}",
+                format!("{}..{}", range.start, range.end),
+                region,
+                sum(Self::reads),
+                sum(Self::writes),
+                sum(Self::executes)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn increment(counts: &mut Vec<u64>, addresses: Range<usize>) {
+    if counts.len() < addresses.end {
+        counts.resize(addresses.end, 0);
+    }
+    for count in &mut counts[addresses] {
+        *count += 1;
+    }
+}
+
+/// Scales a count to between 0 and 1 on a log scale, relative to the highest count
+fn intensity(count: u64, max: u64) -> f64 {
+    if count == 0 {
+        return 0.0;
+    }
+
+    (count as f64).ln_1p() / (max as f64).ln_1p()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::year_2019::IntcodeComputer;
+
+    #[test]
+    fn test_memory_access() {
+        // loops twice, counting down the value at address 13 and writing the counter to 14
+        let mut computer = IntcodeComputer::from("1001,13,-1,13,1001,13,0,14,1005,13,0,99,0,2,0");
+        computer.track_memory_access();
+        computer.run().unwrap();
+
+        let memory_access = computer.memory_access().unwrap();
+        assert_eq!(memory_access.executes(0), 2);
+        assert_eq!(memory_access.executes(11), 1);
+        assert_eq!(memory_access.reads(13), 6);
+        assert_eq!(memory_access.writes(13), 2);
+        assert_eq!(memory_access.writes(14), 2);
+        assert_eq!(memory_access.len(), 15);
+
+        assert_eq!(
+            memory_access.regions(),
+            vec![
+                (0..12, Region::Code),
+                (12..13, Region::Untouched),
+                (13..15, Region::Scratch),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_region_data() {
+        let mut computer = IntcodeComputer::from("1,5,5,6,99,7,0");
+        computer.track_memory_access();
+        computer.run().unwrap();
+
+        let memory_access = computer.memory_access().unwrap();
+        assert_eq!(memory_access.region(5), Region::Data);
+        assert_eq!(memory_access.region(6), Region::Scratch);
+        assert_eq!(memory_access.heatmap(4), "     0 ****\n     4 *@*\n");
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_save_png() {
+        let mut computer = IntcodeComputer::from("1,5,5,6,99,7,0");
+        computer.track_memory_access();
+        computer.run().unwrap();
+
+        let path = std::env::temp_dir().join(format!("heatmap-{}.png", std::process::id()));
+        let memory_access = computer.memory_access().unwrap();
+        memory_access.save_png(&path, 4, 2).unwrap();
+
+        let image = image::open(&path).unwrap().to_rgb8();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(image.dimensions(), (8, 4));
+        // address 5 was only read, address 6 only written, and address 0 only executed
+        assert_eq!(image.get_pixel(2, 2).0, [0, 255, 0]);
+        assert_eq!(image.get_pixel(4, 2).0, [255, 0, 0]);
+        assert_eq!(image.get_pixel(0, 0).0, [0, 0, 255]);
+    }
+}
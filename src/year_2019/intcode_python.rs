@@ -0,0 +1,106 @@
+//! Python bindings for the IntcodeComputer, so that programs can be driven from a notebook.
+//!
+//! These are part of the module built with the `python` feature (see `python`). As with the
+//! JavaScript bindings, input is pushed in ahead of time and outputs are polled, so nothing here
+//! blocks.
+
+use super::intcode_computer::{IntcodeOutput, IntcodeState};
+use super::{IntcodeComputer, IntcodeProgram, IntcodeWord};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use std::sync::mpsc::Receiver;
+
+#[pyclass(eq, eq_int, skip_from_py_object)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntcodeStatus {
+    /// The computer can execute its next instruction
+    Running,
+
+    /// The next instruction is an input instruction, but no input has been pushed
+    AwaitingInput,
+
+    Halted,
+}
+
+#[pyclass(name = "IntcodeComputer", unsendable)]
+pub struct PyIntcodeComputer {
+    computer: IntcodeComputer,
+    output: Receiver<IntcodeWord>,
+    halted: bool,
+}
+
+#[pymethods]
+impl PyIntcodeComputer {
+    /// Loads a program from its comma separated text, raising ValueError when it isn't one
+    #[new]
+    fn new(program: &str) -> PyResult<Self> {
+        let program = IntcodeProgram::parse(program)
+            .map_err(|error| PyValueError::new_err(error.to_string()))?;
+        let mut computer = IntcodeComputer::from(&program);
+        let output = computer.create_output();
+
+        Ok(Self {
+            computer,
+            output,
+            halted: false,
+        })
+    }
+
+    fn push_input(&mut self, input: IntcodeWord) {
+        self.computer.push_input(input);
+    }
+
+    /// Takes every output the program has produced since the last poll
+    fn poll_outputs(&mut self) -> Vec<IntcodeWord> {
+        self.output.drain_outputs(None)
+    }
+
+    fn status(&self) -> IntcodeStatus {
+        if self.halted {
+            IntcodeStatus::Halted
+        } else if self.computer.needs_input() {
+            IntcodeStatus::AwaitingInput
+        } else {
+            IntcodeStatus::Running
+        }
+    }
+
+    /// Executes the next instruction, unless the computer is halted or awaiting input, raising
+    /// RuntimeError when the instruction can't be executed
+    fn step(&mut self) -> PyResult<IntcodeStatus> {
+        if self.status() == IntcodeStatus::Running {
+            let state = self
+                .computer
+                .step()
+                .map_err(|error| PyRuntimeError::new_err(error.to_string()))?;
+            self.halted = state == IntcodeState::Halted;
+        }
+
+        Ok(self.status())
+    }
+
+    /// Executes instructions until the computer halts or awaits input, or until `max_steps` have
+    /// been executed when it's given
+    #[pyo3(signature = (max_steps=None))]
+    fn run(&mut self, max_steps: Option<u64>) -> PyResult<IntcodeStatus> {
+        let mut steps = 0;
+        while max_steps.is_none_or(|max_steps| steps < max_steps) {
+            if self.step()? != IntcodeStatus::Running {
+                break;
+            }
+            steps += 1;
+        }
+
+        Ok(self.status())
+    }
+
+    /// Reads a single address of memory
+    fn peek(&self, address: usize) -> IntcodeWord {
+        self.computer.memory.get(address)
+    }
+
+    /// The whole of memory
+    fn memory(&self) -> Vec<IntcodeWord> {
+        self.computer.memory.data().clone()
+    }
+}
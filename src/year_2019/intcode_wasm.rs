@@ -0,0 +1,120 @@
+//! JavaScript bindings for the IntcodeComputer, so that it can run in a browser.
+//!
+//! Build with the `wasm` feature for the `wasm32-unknown-unknown` target:
+//!
+//!     cargo rustc --lib --release --target wasm32-unknown-unknown --no-default-features --features year-2019,wasm --crate-type cdylib
+//!
+//! and then generate the JavaScript glue with `wasm-bindgen`. Everything here runs on the
+//! calling thread and never blocks: input is pushed in ahead of time, and outputs are polled.
+
+use super::intcode_computer::{IntcodeOutput, IntcodeState};
+use super::{IntcodeComputer, IntcodeProgram, IntcodeWord};
+use std::sync::mpsc::Receiver;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntcodeStatus {
+    /// The computer can execute its next instruction
+    Running,
+
+    /// The next instruction is an input instruction, but no input has been pushed
+    AwaitingInput,
+
+    Halted,
+}
+
+#[wasm_bindgen(js_name = IntcodeComputer)]
+pub struct WasmIntcodeComputer {
+    computer: IntcodeComputer,
+    output: Receiver<IntcodeWord>,
+    halted: bool,
+}
+
+#[wasm_bindgen(js_class = IntcodeComputer)]
+impl WasmIntcodeComputer {
+    /// Loads a program from its comma separated text
+    #[wasm_bindgen(constructor)]
+    pub fn new(program: &str) -> Result<WasmIntcodeComputer, JsValue> {
+        let program = parse_program(program)?;
+        let mut computer = IntcodeComputer::from(&program);
+        let output = computer.create_output();
+
+        Ok(Self {
+            computer,
+            output,
+            halted: false,
+        })
+    }
+
+    #[wasm_bindgen(js_name = pushInput)]
+    pub fn push_input(&mut self, input: IntcodeWord) {
+        self.computer.push_input(input);
+    }
+
+    /// Takes every output the program has produced since the last poll
+    #[wasm_bindgen(js_name = pollOutputs)]
+    pub fn poll_outputs(&mut self) -> Vec<IntcodeWord> {
+        self.output.drain_outputs(None)
+    }
+
+    pub fn status(&self) -> IntcodeStatus {
+        if self.halted {
+            IntcodeStatus::Halted
+        } else if self.computer.needs_input() {
+            IntcodeStatus::AwaitingInput
+        } else {
+            IntcodeStatus::Running
+        }
+    }
+
+    /// Executes the next instruction, unless the computer is halted or awaiting input, throwing
+    /// when the instruction can't be executed
+    pub fn step(&mut self) -> Result<IntcodeStatus, JsValue> {
+        if self.status() == IntcodeStatus::Running {
+            let state = self
+                .computer
+                .step()
+                .map_err(|error| JsValue::from_str(&error.to_string()))?;
+            self.halted = state == IntcodeState::Halted;
+        }
+
+        Ok(self.status())
+    }
+
+    /// Executes up to `max_steps` instructions, stopping early when the computer halts or awaits
+    /// input
+    pub fn run(&mut self, max_steps: u32) -> Result<IntcodeStatus, JsValue> {
+        for _ in 0..max_steps {
+            if self.step()? != IntcodeStatus::Running {
+                break;
+            }
+        }
+
+        Ok(self.status())
+    }
+
+    /// Reads a single address of memory
+    pub fn peek(&self, address: usize) -> IntcodeWord {
+        self.computer.memory.get(address)
+    }
+
+    /// The whole of memory as comma separated text
+    pub fn memory(&self) -> String {
+        self.computer.memory.data_serialized()
+    }
+}
+
+/// Parses a program without panicking, as a panic in wasm can't be caught by the page
+fn parse_program(program: &str) -> Result<IntcodeProgram, JsValue> {
+    program
+        .trim()
+        .split(',')
+        .map(|integer| {
+            integer.trim().parse::<IntcodeWord>().map_err(|error| {
+                JsValue::from_str(&format!("Invalid Intcode integer '{}': {}", integer, error))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(IntcodeProgram::from)
+}
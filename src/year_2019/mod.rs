@@ -0,0 +1,79 @@
+//! Santa has become stranded at the edge of the Solar System while delivering presents to other planets! To accurately calculate his position in space, safely align his warp drive, and return to Earth in time to save Christmas, he needs you to bring him measurements from fifty stars.
+//!
+//! Collect stars by solving puzzles. Two puzzles will be made available on each day in the Advent calendar; the second puzzle is unlocked when you complete the first. Each puzzle grants one star. Good luck!
+
+use crate::generate::{generator, Generator};
+#[cfg(feature = "visualize")]
+use crate::solutions::solution;
+use crate::solutions::{solver, Solution};
+
+pub mod intcode_computer;
+pub use intcode_computer::{IntcodeComputer, IntcodeProgram, IntcodeWord};
+pub mod intcode_builder;
+pub mod intcode_heatmap;
+#[cfg(feature = "python")]
+pub mod intcode_python;
+#[cfg(feature = "wasm")]
+pub mod intcode_wasm;
+
+pub mod arcade_cabinet;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub mod day10;
+pub mod day12;
+pub mod day13;
+pub mod day18;
+
+/// Every solution of the year, in order of day and part
+pub const SOLUTIONS: &[Solution] = &[
+    solver!(2019, 1, 1, day1::Day1),
+    solver!(2019, 1, 2, day1::Day1),
+    solver!(2019, 2, 1, day2::Day2),
+    solver!(2019, 2, 2, day2::Day2),
+    solver!(2019, 3, 1, day3::Day3),
+    solver!(2019, 3, 2, day3::Day3),
+    solver!(2019, 4, 1, day4::Day4),
+    solver!(2019, 4, 2, day4::Day4),
+    solver!(2019, 5, 1, day5::Day5),
+    solver!(2019, 5, 2, day5::Day5),
+    solver!(2019, 6, 1, day6::Day6),
+    solver!(2019, 6, 2, day6::Day6),
+    solver!(2019, 7, 1, day7::Day7),
+    solver!(2019, 7, 2, day7::Day7),
+    solver!(2019, 8, 1, day8::Day8),
+    solver!(2019, 8, 2, day8::Day8),
+    solver!(2019, 9, 1, day9::Day9),
+    solver!(2019, 9, 2, day9::Day9),
+    solver!(2019, 10, 1, day10::Day10),
+    solver!(2019, 10, 2, day10::Day10),
+    solver!(2019, 12, 1, day12::Day12),
+    solver!(2019, 12, 2, day12::Day12),
+    solver!(2019, 13, 1, day13::Day13),
+    solver!(2019, 13, 2, day13::Day13),
+    solver!(2019, 18, 1, day18::Day18),
+    solver!(2019, 18, 2, day18::Day18),
+];
+
+/// Days which can make up inputs of any size, with `aoc gen`
+pub const GENERATORS: &[Generator] = &[
+    generator!(2019, 1, day1::generate),
+    generator!(2019, 6, day6::generate),
+    generator!(2019, 10, day10::generate),
+];
+
+/// Solutions which draw the puzzle as they solve it, run in place of the plain solution of the
+/// same part with `--visualize`
+#[cfg(feature = "visualize")]
+pub const VISUALIZATIONS: &[Solution] = &[
+    solution!(2019, 10, 2, day10::part2_visualize),
+    solution!(2019, 13, 2, day13::part2_visualize),
+];
@@ -0,0 +1,46 @@
+//! Golden tests for every solution.
+//!
+//! Solves each part which has both a stored input under `inputs/` and a recorded answer in
+//! `answers.toml`, and checks that it still gives that answer, so that changes to the code shared
+//! between days can't quietly break an old solution. Parts without either are skipped, so the
+//! tests pass on a checkout without anyone's puzzle inputs.
+
+use advent_of_code::answers::{Answers, ANSWERS_FILE};
+use advent_of_code::params::Params;
+use advent_of_code::solutions::{INPUTS_DIRECTORY, SOLUTIONS};
+use rayon::prelude::*;
+use std::path::Path;
+
+#[test]
+fn test_recorded_answers() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let answers = Answers::load(&root.join(ANSWERS_FILE)).expect("Failed to load the answers");
+    let inputs = root.join(INPUTS_DIRECTORY);
+
+    let checked: Vec<Result<(), String>> = SOLUTIONS
+        .par_iter()
+        .filter_map(|solution| {
+            let expected = answers.get(solution)?;
+            let input = solution.stored_input(&inputs)?;
+
+            Some(match solution.solve(&input, &Params::default()) {
+                Ok(answer) if answer.is(expected) => Ok(()),
+                Ok(answer) => Err(format!(
+                    "{}: expected {:?}, got {:?}",
+                    solution,
+                    expected,
+                    answer.to_string()
+                )),
+                Err(error) => Err(format!("{}: {}", solution, error)),
+            })
+        })
+        .collect();
+
+    let failures: Vec<&String> = checked.iter().filter_map(|c| c.as_ref().err()).collect();
+    println!(
+        "checked {} of {} parts against their recorded answers",
+        checked.len(),
+        SOLUTIONS.len()
+    );
+    assert!(failures.is_empty(), "wrong answers:\n{:#?}", failures);
+}
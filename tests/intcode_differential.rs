@@ -0,0 +1,363 @@
+//! Differential tests for the Intcode computer.
+//!
+//! Generates random (but always valid and always halting) Intcode programs, runs each of them
+//! through every way the IntcodeComputer can be driven, and checks that they all agree on the
+//! outputs and the final state of memory.
+//!
+//! The number of generated programs defaults to 500, and can be raised with the
+//! `INTCODE_FUZZ_ITERATIONS` environment variable. A failing program is reported with its seed,
+//! which can be replayed with `INTCODE_FUZZ_SEED`.
+
+use advent_of_code::common::rng::Rng;
+use advent_of_code::year_2019::intcode_builder::{imm, pos, rel, Parameter};
+use advent_of_code::year_2019::intcode_computer::IntcodeState;
+use advent_of_code::year_2019::{IntcodeComputer, IntcodeProgram, IntcodeWord};
+use rayon::prelude::*;
+use std::env;
+use std::fs;
+use std::process;
+use std::thread;
+
+const DEFAULT_ITERATIONS: u64 = 500;
+
+/// Upper bound on the instructions in a program, not counting the prologue and the halt
+const MAX_INSTRUCTIONS: usize = 30;
+
+/// Cells of scratch memory after the code, which every read and write is aimed at
+const DATA_CELLS: usize = 8;
+
+/// Every multiply can grow a value by up to a factor of 9, and every add by up to a factor of 2,
+/// so capping the multiplies keeps every value well inside 64 bits
+const MAX_MULTIPLIES: usize = 8;
+
+#[test]
+fn test_engines_agree() {
+    let seeds = match env::var("INTCODE_FUZZ_SEED") {
+        Ok(seed) => {
+            let seed = seed.parse().expect("Failed to parse INTCODE_FUZZ_SEED");
+            seed..seed + 1
+        }
+        Err(_) => {
+            let iterations = env::var("INTCODE_FUZZ_ITERATIONS")
+                .map(|iterations| {
+                    iterations
+                        .parse()
+                        .expect("Failed to parse INTCODE_FUZZ_ITERATIONS")
+                })
+                .unwrap_or(DEFAULT_ITERATIONS);
+            0..iterations
+        }
+    };
+
+    for seed in seeds {
+        let (program, inputs) = generate_program(seed);
+        let expected = run_blocking(&program, &inputs);
+
+        for (engine, run) in ENGINES {
+            let actual = run(&program, &inputs, seed);
+            assert_eq!(
+                actual,
+                expected,
+                "{} disagrees with run on seed {}: {}",
+                engine,
+                seed,
+                program.data_serialized()
+            );
+        }
+
+        assert_eq!(
+            run_then_step_back(&program, &inputs),
+            program.data().clone(),
+            "step_back didn't restore the program on seed {}: {}",
+            seed,
+            program.data_serialized()
+        );
+    }
+}
+
+#[test]
+fn test_run_many_agrees() {
+    let programs: Vec<_> = (0..50).map(generate_program).collect();
+
+    for (seed, (program, inputs)) in programs.iter().enumerate() {
+        let expected = run_blocking(program, inputs).outputs;
+        let inputs = vec![inputs.clone(); 4];
+
+        for outputs in IntcodeComputer::run_many(program, inputs.into_par_iter()).unwrap() {
+            assert_eq!(outputs, expected, "run_many disagrees on seed {}", seed);
+        }
+    }
+}
+
+/// What a run leaves behind
+#[derive(Debug, PartialEq, Eq)]
+struct RunResult {
+    outputs: Vec<IntcodeWord>,
+    memory: Vec<IntcodeWord>,
+}
+
+type Engine = fn(&IntcodeProgram, &[IntcodeWord], u64) -> RunResult;
+
+const ENGINES: [(&str, Engine); 4] = [
+    ("step", |program, inputs, _| run_stepped(program, inputs)),
+    ("run_for", |program, inputs, _| {
+        run_for_chunks(program, inputs)
+    }),
+    ("threaded", |program, inputs, _| {
+        run_threaded(program, inputs)
+    }),
+    ("core dump", run_through_core_dump),
+];
+
+/// The reference engine: all input queued up on the channel, then `run`
+fn run_blocking(program: &IntcodeProgram, inputs: &[IntcodeWord]) -> RunResult {
+    let mut computer = IntcodeComputer::from(program);
+    let input_tx = computer.create_input();
+    let output_rx = computer.create_output();
+
+    for input in inputs {
+        input_tx.send(*input).unwrap();
+    }
+    computer.run().unwrap();
+
+    RunResult {
+        outputs: output_rx.try_iter().collect(),
+        memory: computer.memory.data().clone(),
+    }
+}
+
+/// All input pushed without a channel, then one `step` at a time
+fn run_stepped(program: &IntcodeProgram, inputs: &[IntcodeWord]) -> RunResult {
+    let mut computer = IntcodeComputer::from(program);
+    let output_rx = computer.create_output();
+
+    for input in inputs {
+        computer.push_input(*input);
+    }
+    while computer.step().unwrap() == IntcodeState::Running {}
+
+    RunResult {
+        outputs: output_rx.try_iter().collect(),
+        memory: computer.memory.data().clone(),
+    }
+}
+
+/// Runs a few instructions at a time with `run_for`
+fn run_for_chunks(program: &IntcodeProgram, inputs: &[IntcodeWord]) -> RunResult {
+    let mut computer = IntcodeComputer::from(program);
+    let input_tx = computer.create_input();
+    let output_rx = computer.create_output();
+
+    for input in inputs {
+        input_tx.send(*input).unwrap();
+    }
+    while computer.run_for(3).unwrap() == IntcodeState::Running {}
+
+    RunResult {
+        outputs: output_rx.try_iter().collect(),
+        memory: computer.memory.data().clone(),
+    }
+}
+
+/// Runs the computer on another thread, racing it with the input being sent
+fn run_threaded(program: &IntcodeProgram, inputs: &[IntcodeWord]) -> RunResult {
+    let mut computer = IntcodeComputer::from(program);
+    let input_tx = computer.create_input();
+    let output_rx = computer.create_output();
+
+    let runner = thread::spawn(move || {
+        computer.run().unwrap();
+        computer
+    });
+
+    for input in inputs {
+        thread::yield_now();
+        input_tx.send(*input).unwrap();
+    }
+
+    let computer = runner.join().expect("Intcode thread panicked");
+
+    RunResult {
+        outputs: output_rx.try_iter().collect(),
+        memory: computer.memory.data().clone(),
+    }
+}
+
+/// Stops halfway, saves a core dump and finishes the run on the machine restored from it
+fn run_through_core_dump(program: &IntcodeProgram, inputs: &[IntcodeWord], seed: u64) -> RunResult {
+    let path = env::temp_dir().join(format!(
+        "intcode-differential-{}-{}.core",
+        process::id(),
+        seed
+    ));
+
+    let mut computer = IntcodeComputer::from(program);
+    let input_tx = computer.create_input();
+    let output_rx = computer.create_output();
+
+    for input in inputs {
+        input_tx.send(*input).unwrap();
+    }
+    let mut outputs = Vec::new();
+    if computer.run_for(MAX_INSTRUCTIONS / 2).unwrap() == IntcodeState::Running {
+        computer.save_core(&path).expect("Failed to save core");
+        outputs.extend(output_rx.try_iter());
+
+        computer = IntcodeComputer::load_core(&path).expect("Failed to load core");
+        fs::remove_file(&path).expect("Failed to remove core");
+
+        let output_rx = computer.create_output();
+        computer.run().unwrap();
+        outputs.extend(output_rx.try_iter());
+    } else {
+        outputs.extend(output_rx.try_iter());
+    }
+
+    RunResult {
+        outputs,
+        memory: computer.memory.data().clone(),
+    }
+}
+
+/// Runs while recording history, then undoes every instruction, which should leave memory as it
+/// was loaded
+fn run_then_step_back(program: &IntcodeProgram, inputs: &[IntcodeWord]) -> Vec<IntcodeWord> {
+    let mut computer = IntcodeComputer::from(program);
+    let output_rx = computer.create_output();
+
+    computer.record_history(MAX_INSTRUCTIONS + 2);
+    for input in inputs {
+        computer.push_input(*input);
+    }
+    computer.run().unwrap();
+    drop(output_rx);
+
+    while computer.step_back() {}
+
+    computer.memory.data().clone()
+}
+
+/// An instruction of a generated program, before it is laid out in memory
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Add(Parameter, Parameter, Parameter),
+    Multiply(Parameter, Parameter, Parameter),
+    LessThan(Parameter, Parameter, Parameter),
+    Equals(Parameter, Parameter, Parameter),
+    Input(Parameter),
+    Output(Parameter),
+    /// Jumps forward to the start of the op with this index (or the halt, past the last op)
+    JumpIfTrue(Parameter, usize),
+    JumpIfFalse(Parameter, usize),
+}
+
+impl Op {
+    fn length(self) -> usize {
+        match self {
+            Op::Add(..) | Op::Multiply(..) | Op::LessThan(..) | Op::Equals(..) => 4,
+            Op::JumpIfTrue(..) | Op::JumpIfFalse(..) => 3,
+            Op::Input(_) | Op::Output(_) => 2,
+        }
+    }
+}
+
+/// Generates a program along with enough input for every input instruction in it.
+///
+/// The grammar is:
+///
+///     program  := prologue op* halt data
+///     prologue := relative_base_offset(imm data_start)
+///     op       := arith(read, read, write) | input(write) | output(read)
+///               | jump(read, imm later_op)
+///     read     := pos(data cell) | imm(small) | rel(data cell)
+///     write    := pos(data cell) | rel(data cell)
+///
+/// Jumps only go forwards, so every program halts, and reads and writes only go to the data
+/// cells, so the code is never modified.
+fn generate_program(seed: u64) -> (IntcodeProgram, Vec<IntcodeWord>) {
+    // spreads neighbouring seeds apart, as xorshift's first numbers from small seeds barely differ
+    let mut rng = Rng::new(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    let count = 1 + rng.below(MAX_INSTRUCTIONS);
+
+    // the data cells come after the prologue (2), the ops and the halt (1), so their address is
+    // only known once the ops are chosen; addresses are stored as cell indices until then
+    let mut multiplies = 0;
+    let mut ops = Vec::with_capacity(count);
+    for index in 0..count {
+        let op = match rng.below(8) {
+            0 => Op::Add(read(&mut rng), read(&mut rng), write(&mut rng)),
+            1 if multiplies < MAX_MULTIPLIES => {
+                multiplies += 1;
+                Op::Multiply(
+                    read(&mut rng),
+                    imm(IntcodeWord::from(rng.between(-9, 9))),
+                    write(&mut rng),
+                )
+            }
+            2 => Op::LessThan(read(&mut rng), read(&mut rng), write(&mut rng)),
+            3 => Op::Equals(read(&mut rng), read(&mut rng), write(&mut rng)),
+            4 => Op::Input(write(&mut rng)),
+            5 => Op::JumpIfTrue(read(&mut rng), index + 1 + rng.below(count - index)),
+            6 => Op::JumpIfFalse(read(&mut rng), index + 1 + rng.below(count - index)),
+            _ => Op::Output(read(&mut rng)),
+        };
+        ops.push(op);
+    }
+
+    let mut addresses = Vec::with_capacity(count + 1);
+    let mut address = 2;
+    for op in &ops {
+        addresses.push(address);
+        address += op.length();
+    }
+    addresses.push(address);
+    let data_start = address + 1;
+
+    let locate = |parameter: Parameter| match parameter {
+        Parameter::Position(cell) => pos(data_start + cell),
+        other => other,
+    };
+    let jump_to = |index: usize| imm(addresses[index] as IntcodeWord);
+
+    let mut builder = IntcodeProgram::build().relative_base_offset(imm(data_start as IntcodeWord));
+    let mut inputs = Vec::new();
+    for op in ops {
+        builder = match op {
+            Op::Add(one, two, out) => builder.add(locate(one), locate(two), locate(out)),
+            Op::Multiply(one, two, out) => builder.multiply(locate(one), locate(two), locate(out)),
+            Op::LessThan(one, two, out) => builder.less_than(locate(one), locate(two), locate(out)),
+            Op::Equals(one, two, out) => builder.equals(locate(one), locate(two), locate(out)),
+            Op::Input(to) => {
+                inputs.push(IntcodeWord::from(rng.between(-1000, 1000)));
+                builder.input(locate(to))
+            }
+            Op::Output(from) => builder.output(locate(from)),
+            Op::JumpIfTrue(test, to) => builder.jump_if_true(locate(test), jump_to(to)),
+            Op::JumpIfFalse(test, to) => builder.jump_if_false(locate(test), jump_to(to)),
+        };
+    }
+
+    let data: Vec<_> = (0..DATA_CELLS)
+        .map(|_| IntcodeWord::from(rng.between(-1000, 1000)))
+        .collect();
+    let program = builder.halt().data(&data).finish();
+
+    (program, inputs)
+}
+
+/// A data cell is read through position or relative mode (relative to the start of the data),
+/// and a small constant through immediate mode
+fn read(rng: &mut Rng) -> Parameter {
+    match rng.below(3) {
+        0 => imm(IntcodeWord::from(rng.between(-100, 100))),
+        _ => write(rng),
+    }
+}
+
+fn write(rng: &mut Rng) -> Parameter {
+    let cell = rng.below(DATA_CELLS);
+    match rng.below(2) {
+        0 => pos(cell),
+        _ => rel(cell as IntcodeWord),
+    }
+}